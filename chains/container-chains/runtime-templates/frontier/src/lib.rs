@@ -61,6 +61,7 @@ use {
             ConstantMultiplier, Weight, WeightToFee as _, WeightToFeeCoefficient,
             WeightToFeeCoefficients, WeightToFeePolynomial,
         },
+        PalletId,
     },
     frame_system::{
         limits::{BlockLength, BlockWeights},
@@ -73,10 +74,14 @@ use {
         EnsureCreateOrigin, FeeCalculator, FrameSystemAccountProvider, GasWeightMapping,
         IdentityAddressMapping, OnChargeEVMTransaction as OnChargeEVMTransactionT, Runner,
     },
+    pallet_stream_payment_runtime_api::{
+        StreamPaymentApiError, StreamPaymentApiEvent, StreamPaymentApiParty, StreamPaymentApiStatus,
+    },
     pallet_transaction_payment::FungibleAdapter,
     parity_scale_codec::{Decode, Encode},
     polkadot_runtime_common::SlowAdjustingFeeUpdate,
-    scale_info::TypeInfo,
+    scale_info::{prelude::format, TypeInfo},
+    serde::{Deserialize, Serialize},
     smallvec::smallvec,
     sp_api::impl_runtime_apis,
     sp_consensus_slots::{Slot, SlotDuration},
@@ -518,8 +523,13 @@ parameter_types! {
 }
 
 pub const RELAY_CHAIN_SLOT_DURATION_MILLIS: u32 = 6000;
-pub const UNINCLUDED_SEGMENT_CAPACITY: u32 = 3;
+/// Max number of blocks that can be included in each relay chain block. Chains built with the
+/// `elastic-scaling` feature can use up to the 4 cores that a parachain can request in Dancelight.
+#[cfg(not(feature = "elastic-scaling"))]
 pub const BLOCK_PROCESSING_VELOCITY: u32 = 1;
+#[cfg(feature = "elastic-scaling")]
+pub const BLOCK_PROCESSING_VELOCITY: u32 = 4;
+pub const UNINCLUDED_SEGMENT_CAPACITY: u32 = 2 * BLOCK_PROCESSING_VELOCITY + 1;
 
 type ConsensusHook = pallet_async_backing::consensus_hook::FixedVelocityConsensusHook<
     Runtime,
@@ -958,6 +968,90 @@ impl pallet_multisig::Config for Runtime {
     type WeightInfo = weights::pallet_multisig::SubstrateWeight<Runtime>;
 }
 
+pub type StreamPaymentAssetId =
+    tanssi_runtime_common::stream_payment::StreamPaymentAssetId<xcm_config::AssetId>;
+
+parameter_types! {
+    pub const StreamPaymentPalletId: PalletId = PalletId(*b"tsp/strm");
+}
+
+pub type StreamPaymentAssets = tanssi_runtime_common::stream_payment::StreamPaymentAssets<
+    Runtime,
+    Balances,
+    ForeignAssets,
+    StreamPaymentPalletId,
+>;
+
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Encode,
+    Decode,
+    Debug,
+    MaxEncodedLen,
+    TypeInfo,
+    Serialize,
+    Deserialize,
+)]
+pub enum TimeUnit {
+    BlockNumber,
+    Timestamp,
+}
+
+pub struct TimeProvider;
+impl pallet_stream_payment::TimeProvider<TimeUnit, Balance> for TimeProvider {
+    fn now(unit: &TimeUnit) -> Option<Balance> {
+        match *unit {
+            TimeUnit::BlockNumber => Some(System::block_number().into()),
+            TimeUnit::Timestamp => Some(Timestamp::get().into()),
+        }
+    }
+
+    /// Benchmarks: should return the time unit which has the worst performance calling
+    /// `TimeProvider::now(unit)` with.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_worst_case_time_unit() -> TimeUnit {
+        // Both BlockNumber and Timestamp cost the same (1 db read), but overriding timestamp
+        // doesn't work well in benches, while block number works fine.
+        TimeUnit::BlockNumber
+    }
+
+    /// Benchmarks: sets the "now" time for time unit returned by `worst_case_time_unit`.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_set_now(instant: Balance) {
+        System::set_block_number(instant as u32)
+    }
+}
+
+type StreamId = u64;
+
+parameter_types! {
+    // 1 entry, storing 207 bytes on-chain
+    pub const OpenStreamHoldAmount: Balance = currency::deposit(1, 207);
+    // 1 target lookup entry and 1 split, storing 108 bytes on-chain
+    pub const OpenStreamSplitHoldAmount: Balance = currency::deposit(1, 108);
+    pub const MaxStreamSplits: u32 = 4;
+}
+
+impl pallet_stream_payment::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type StreamId = StreamId;
+    type TimeUnit = TimeUnit;
+    type Balance = Balance;
+    type AssetId = StreamPaymentAssetId;
+    type Assets = StreamPaymentAssets;
+    type Currency = Balances;
+    type OpenStreamHoldAmount = OpenStreamHoldAmount;
+    type OpenStreamSplitHoldAmount = OpenStreamSplitHoldAmount;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type TimeProvider = TimeProvider;
+    type MaxStreamSplits = MaxStreamSplits;
+    type AutoSettlementScanLimit = ConstU32<20>;
+    type WeightInfo = weights::pallet_stream_payment::SubstrateWeight<Runtime>;
+}
+
 impl_tanssi_pallets_config!(Runtime);
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -978,6 +1072,7 @@ construct_runtime!(
 
         // Monetary stuff.
         Balances: pallet_balances = 10,
+        StreamPayment: pallet_stream_payment = 12,
 
         // Other utilities
         Multisig: pallet_multisig = 16,
@@ -1021,6 +1116,7 @@ mod benches {
         [pallet_tx_pause, TxPause]
         [pallet_balances, Balances]
         [pallet_multisig, Multisig]
+        [pallet_stream_payment, StreamPayment]
         [pallet_parameters, Parameters]
         [pallet_cc_authorities_noting, AuthoritiesNoting]
         [pallet_author_inherent, AuthorInherent]
@@ -1701,6 +1797,43 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_stream_payment_runtime_api::StreamPaymentApi<Block, StreamId, Balance, Balance, AccountId>
+    for Runtime {
+        fn stream_payment_status(
+            stream_id: StreamId,
+            now: Option<Balance>,
+        ) -> Result<StreamPaymentApiStatus<Balance>, StreamPaymentApiError> {
+            match StreamPayment::stream_payment_status(stream_id, now) {
+                Ok(pallet_stream_payment::StreamPaymentStatus {
+                    payment, deposit_left, stalled, started, ended
+                }) => Ok(StreamPaymentApiStatus {
+                    payment, deposit_left, stalled, started, ended
+                }),
+                Err(pallet_stream_payment::Error::<Runtime>::UnknownStreamId)
+                => Err(StreamPaymentApiError::UnknownStreamId),
+                Err(e) => Err(StreamPaymentApiError::Other(format!("{e:?}")))
+            }
+        }
+
+        fn account_streams(
+            account: AccountId,
+            party: StreamPaymentApiParty,
+            start_after: Option<StreamId>,
+            limit: u32,
+        ) -> Vec<StreamId> {
+            let party = match party {
+                StreamPaymentApiParty::Source => pallet_stream_payment::Party::Source,
+                StreamPaymentApiParty::Target => pallet_stream_payment::Party::Target,
+            };
+
+            StreamPayment::account_streams(&account, party, start_after, limit)
+        }
+
+        fn stream_events() -> Vec<StreamPaymentApiEvent<AccountId, StreamId, Balance>> {
+            StreamPayment::api_events()
+        }
+    }
+
     impl xcm_runtime_apis::fees::XcmPaymentApi<Block> for Runtime {
         fn query_acceptable_payment_assets(xcm_version: staging_xcm::Version) -> Result<Vec<VersionedAssetId>, XcmPaymentApiError> {
             if !matches!(xcm_version, 3 | 4) {
@@ -17,7 +17,7 @@
 use {
     crate::{
         xcm_config::{AssetId, ForeignAssetsInstance, XcmConfig},
-        AccountId, Balances, ForeignAssetsCreator, Runtime,
+        AccountId, Balances, ForeignAssetsCreator, Runtime, StreamPaymentAssetId, TimeUnit,
     },
     frame_support::parameter_types,
     pallet_evm_precompile_balances_erc20::{Erc20BalancesPrecompile, Erc20Metadata},
@@ -27,6 +27,7 @@ use {
     pallet_evm_precompile_proxy::{OnlyIsProxyAndProxy, ProxyPrecompile},
     pallet_evm_precompile_sha3fips::Sha3FIPS256,
     pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256},
+    pallet_evm_precompile_stream_payment::{StreamPaymentPrecompile, StreamPaymentTypesConverter},
     pallet_evm_precompile_xcm::PalletXcmPrecompile,
     pallet_evm_precompile_xcm_utils::{AllExceptXcmExecute, XcmUtilsPrecompile},
     pallet_evm_precompileset_assets_erc20::Erc20AssetsPrecompileSet,
@@ -35,7 +36,11 @@ use {
         PrecompileAt, PrecompileSetBuilder, PrecompileSetStartingWith, PrecompilesInRangeInclusive,
         SubcallWithMaxNesting,
     },
-    xcm_primitives::location_matcher::{ForeignAssetMatcher, SingleAddressMatcher},
+    sp_core::H160,
+    xcm_primitives::{
+        location_matcher::{ForeignAssetMatcher, SingleAddressMatcher},
+        AccountIdAssetIdConversion,
+    },
 };
 
 /// ERC20 metadata for the native token.
@@ -71,6 +76,9 @@ pub const FOREIGN_ASSET_PRECOMPILE_ADDRESS_PREFIX: &[u8] = &[255u8; 18];
 /// Const to identify ERC20_BALANCES_PRECOMPILE address
 pub const ERC20_BALANCES_PRECOMPILE: u64 = 2048;
 
+/// Const to identify STREAM_PAYMENT_PRECOMPILE address
+pub const STREAM_PAYMENT_PRECOMPILE: u64 = 2054;
+
 parameter_types! {
     pub ForeignAssetPrefix: &'static [u8] = FOREIGN_ASSET_PRECOMPILE_ADDRESS_PREFIX;
 }
@@ -84,6 +92,30 @@ type BalancesPrecompileMatch = SingleAddressMatcher<AccountId, ERC20_BALANCES_PR
 // Type that matches an AccountId with a foreign asset address (if any)
 type ForeignAssetMatch = ForeignAssetMatcher<AccountId, AssetId, Runtime, ForeignAssetsCreator>;
 
+/// Maps the stream payment precompile arguments to runtime types. The native token is
+/// identified by the ERC20 balances precompile address, and foreign assets by their XC20
+/// address.
+pub struct StreamPaymentConverter;
+
+impl StreamPaymentTypesConverter<TimeUnit, StreamPaymentAssetId> for StreamPaymentConverter {
+    fn time_unit(unit: u8) -> Option<TimeUnit> {
+        match unit {
+            0 => Some(TimeUnit::BlockNumber),
+            1 => Some(TimeUnit::Timestamp),
+            _ => None,
+        }
+    }
+
+    fn asset_id(address: H160) -> Option<StreamPaymentAssetId> {
+        if address == H160::from_low_u64_be(ERC20_BALANCES_PRECOMPILE) {
+            return Some(StreamPaymentAssetId::Native);
+        }
+
+        Runtime::account_to_asset_id(address.into())
+            .map(|(_prefix, asset_id)| StreamPaymentAssetId::Foreign(asset_id))
+    }
+}
+
 #[precompile_utils::precompile_name_from_address]
 type TemplatePrecompilesAt<R> = (
     // Ethereum precompiles:
@@ -128,6 +160,11 @@ type TemplatePrecompilesAt<R> = (
             CallableByPrecompile<OnlyFrom<AddressU64<2049>>>,
         ),
     >,
+    PrecompileAt<
+        AddressU64<STREAM_PAYMENT_PRECOMPILE>,
+        StreamPaymentPrecompile<R, StreamPaymentConverter>,
+        (CallableByContract, CallableByPrecompile),
+    >,
 );
 
 pub type TemplatePrecompiles<R> = PrecompileSetBuilder<
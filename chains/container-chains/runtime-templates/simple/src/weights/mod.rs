@@ -28,6 +28,7 @@ pub mod pallet_foreign_asset_creator;
 pub mod pallet_message_queue;
 pub mod pallet_multisig;
 pub mod pallet_proxy;
+pub mod pallet_stream_payment;
 
 pub mod pallet_sudo;
 pub mod pallet_timestamp;
@@ -27,7 +27,8 @@ use {
     manual_randomness_rpc::{ManualRandomness, ManualRandomnessApiServer},
     manual_xcm_rpc::{ManualXcm, ManualXcmApiServer},
     polkadot_primitives::Hash,
-    sc_client_api::{AuxStore, UsageProvider},
+    pooled_staking_rpc::{PooledStaking, PooledStakingApiServer as _, PooledStakingRuntimeApi},
+    sc_client_api::{AuxStore, BlockchainEvents, UsageProvider},
     sc_consensus_manual_seal::{
         rpc::{ManualSeal, ManualSealApiServer},
         EngineCommand,
@@ -72,11 +73,13 @@ where
         + Send
         + Sync
         + UsageProvider<Block>
+        + BlockchainEvents<Block>
         + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: BlockBuilder<Block>,
-    C::Api: StreamPaymentRuntimeApi<Block, u64, u128, u128>,
-    C::Api: ServicesPaymentRuntimeApi<Block, u128, ParaId>,
+    C::Api: StreamPaymentRuntimeApi<Block, u64, u128, u128, AccountId>,
+    C::Api: ServicesPaymentRuntimeApi<Block, u128, ParaId, u16>,
+    C::Api: PooledStakingRuntimeApi<Block, AccountId, u128, u32>,
     P: TransactionPool + Sync + Send + 'static,
 {
     use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -92,7 +95,8 @@ where
 
     module.merge(System::new(client.clone(), pool).into_rpc())?;
     module.merge(StreamPayment::<_, Block>::new(client.clone()).into_rpc())?;
-    module.merge(ServicesPayment::<_, Block>::new(client).into_rpc())?;
+    module.merge(ServicesPayment::<_, Block>::new(client.clone()).into_rpc())?;
+    module.merge(PooledStaking::<_, Block>::new(client).into_rpc())?;
 
     if let Some(command_sink) = command_sink {
         module.merge(
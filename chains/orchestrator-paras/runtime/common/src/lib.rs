@@ -17,3 +17,5 @@
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 pub mod migrations;
+pub mod services_payment;
+pub mod stream_payment;
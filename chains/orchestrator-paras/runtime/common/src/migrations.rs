@@ -837,6 +837,99 @@ where
     }
 }
 
+#[derive(
+    Clone,
+    parity_scale_codec::Encode,
+    parity_scale_codec::Decode,
+    PartialEq,
+    sp_core::RuntimeDebug,
+    scale_info::TypeInfo,
+)]
+pub struct StreamV1<AccountId, Unit, AssetId, Balance> {
+    pub source: AccountId,
+    pub target: AccountId,
+    pub config: pallet_stream_payment::StreamConfig<Unit, AssetId, Balance>,
+    pub deposit: Balance,
+    pub last_time_updated: Balance,
+    pub request_nonce: pallet_stream_payment::RequestNonce,
+    pub pending_request: Option<pallet_stream_payment::ChangeRequest<Unit, AssetId, Balance>>,
+    pub opening_deposit: Balance,
+}
+
+pub type StreamV1Of<T> = StreamV1<
+    <T as frame_system::Config>::AccountId,
+    <T as pallet_stream_payment::Config>::TimeUnit,
+    <T as pallet_stream_payment::Config>::AssetId,
+    <T as pallet_stream_payment::Config>::Balance,
+>;
+
+pub struct MigrateStreamPaymentAddSchedule<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateStreamPaymentAddSchedule<T>
+where
+    T: pallet_stream_payment::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateStreamPaymentAddSchedule"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        let mut count = 0u64;
+
+        // Existing streams are unbounded: they started when opened and never end.
+        pallet_stream_payment::Streams::<T>::translate::<StreamV1Of<T>, _>(|_, old| {
+            count += 1;
+            Some(pallet_stream_payment::Stream {
+                source: old.source,
+                target: old.target,
+                config: old.config,
+                deposit: old.deposit,
+                last_time_updated: old.last_time_updated,
+                request_nonce: old.request_nonce,
+                pending_request: old.pending_request,
+                opening_deposit: old.opening_deposit,
+                schedule: Default::default(),
+            })
+        });
+
+        T::DbWeight::get().reads_writes(count, count)
+    }
+
+    /// Run a standard pre-runtime test. This works the same way as in a normal runtime upgrade.
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade(&self) -> Result<Vec<u8>, sp_runtime::DispatchError> {
+        use parity_scale_codec::Encode;
+
+        let count = storage_key_iter::<_, StreamV1Of<T>, Blake2_128Concat>(
+            pallet_stream_payment::Streams::<T>::pallet_prefix(),
+            pallet_stream_payment::Streams::<T>::storage_prefix(),
+        )
+        .count() as u32;
+
+        Ok(count.encode())
+    }
+
+    /// Run a standard post-runtime test. This works the same way as in a normal runtime upgrade.
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(&self, state: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+        use parity_scale_codec::Decode;
+
+        let old_count = u32::decode(&mut state.as_ref()).map_err(|_| "invalid state")?;
+        let mut new_count = 0u32;
+
+        for (_, stream) in pallet_stream_payment::Streams::<T>::iter() {
+            ensure!(
+                stream.schedule.is_unbounded(),
+                "migrated streams should be unbounded"
+            );
+            new_count += 1;
+        }
+
+        ensure!(old_count == new_count, "streams count should not change");
+
+        Ok(())
+    }
+}
+
 pub struct FlashboxMigrations<Runtime>(PhantomData<Runtime>);
 
 impl<Runtime> GetMigrations for FlashboxMigrations<Runtime>
@@ -845,6 +938,7 @@ where
     Runtime: pallet_configuration::Config,
     Runtime: pallet_registrar::Config,
     Runtime: pallet_data_preservers::Config,
+    Runtime: pallet_stream_payment::Config,
     Runtime: pallet_services_payment::Config,
     Runtime: pallet_data_preservers::Config,
     Runtime::AccountId: From<[u8; 32]>,
@@ -870,6 +964,8 @@ where
         //let migrate_registrar_reserves = RegistrarReserveToHoldMigration::<Runtime>(Default::default());
         //let migrate_config_max_parachain_percentage = MigrateConfigurationAddParachainPercentage::<Runtime>(Default::default());
         let migrate_config_full_rotation_mode = MigrateConfigurationAddFullRotationMode::<Runtime>(Default::default());
+        let migrate_stream_payment_add_schedule =
+            MigrateStreamPaymentAddSchedule::<Runtime>(Default::default());
 
         vec![
             // Applied in runtime 400
@@ -891,6 +987,7 @@ where
             // Applied in runtime 900
             //Box::new(migrate_config_max_parachain_percentage),
             Box::new(migrate_config_full_rotation_mode),
+            Box::new(migrate_stream_payment_add_schedule),
         ]
     }
 }
@@ -906,6 +1003,7 @@ where
     Runtime: pallet_services_payment::Config,
     Runtime: cumulus_pallet_xcmp_queue::Config,
     Runtime: pallet_data_preservers::Config,
+    Runtime: pallet_stream_payment::Config,
     Runtime: pallet_xcm::Config,
     <Runtime as pallet_balances::Config>::RuntimeHoldReason:
         From<pallet_pooled_staking::HoldReason>,
@@ -947,6 +1045,8 @@ where
         //    ForeignAssetCreatorMigration::<Runtime>(Default::default());
         //let migrate_registrar_reserves = RegistrarReserveToHoldMigration::<Runtime>(Default::default());
         let migrate_config_full_rotation_mode = MigrateConfigurationAddFullRotationMode::<Runtime>(Default::default());
+        let migrate_stream_payment_add_schedule =
+            MigrateStreamPaymentAddSchedule::<Runtime>(Default::default());
 
         vec![
             // Applied in runtime 200
@@ -986,6 +1086,7 @@ where
             // Applied in runtime 900
             //Box::new(migrate_config_max_parachain_percentage),
             Box::new(migrate_config_full_rotation_mode),
+            Box::new(migrate_stream_payment_add_schedule),
         ]
     }
 }
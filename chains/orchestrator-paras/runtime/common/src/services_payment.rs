@@ -0,0 +1,164 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Services payment configuration shared by the runtimes that assign collators to container
+//! chains.
+
+use {
+    core::marker::PhantomData,
+    cumulus_primitives_core::ParaId,
+    frame_support::{
+        traits::{Contains, Get},
+        weights::Weight,
+    },
+    pallet_collator_assignment::AssignedCollators,
+    pallet_services_payment::{BalanceOf, ProvideCollatorAssignmentCost},
+    sp_runtime::FixedPointNumber,
+    tp_traits::GetHostConfiguration,
+};
+
+type Configuration<Runtime> = pallet_configuration::Pallet<Runtime>;
+
+/// Paras that are paused, or have no collators assigned in the current and next sessions,
+/// so they cannot be charged anymore.
+pub struct PausedOrWithoutCollators<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Contains<ParaId> for PausedOrWithoutCollators<Runtime>
+where
+    Runtime: pallet_registrar::Config + pallet_collator_assignment::Config,
+{
+    fn contains(para_id: &ParaId) -> bool {
+        if pallet_registrar::Paused::<Runtime>::get().contains(para_id) {
+            return true;
+        }
+
+        let has_collators =
+            |assignment: AssignedCollators<<Runtime as frame_system::Config>::AccountId>| {
+                assignment
+                    .container_chains
+                    .get(para_id)
+                    .is_some_and(|collators| !collators.is_empty())
+            };
+
+        !has_collators(pallet_collator_assignment::Pallet::<Runtime>::collator_container_chain())
+            && !pallet_collator_assignment::Pallet::<Runtime>::pending_collator_container_chain()
+                .is_some_and(has_collators)
+    }
+}
+
+/// Collator assignment cost: `FixedCost` for each relay chain core of the para, and
+/// `ExtraCollatorCost` for each collator above the default of the session. Parachains only use
+/// more than one core if `ParachainsUseCores` is true.
+pub struct CollatorAssignmentCost<Runtime, FixedCost, ExtraCollatorCost, ParachainsUseCores>(
+    PhantomData<(Runtime, FixedCost, ExtraCollatorCost, ParachainsUseCores)>,
+);
+
+impl<Runtime, FixedCost, ExtraCollatorCost, ParachainsUseCores>
+    CollatorAssignmentCost<Runtime, FixedCost, ExtraCollatorCost, ParachainsUseCores>
+where
+    Runtime: pallet_registrar::Config
+        + pallet_configuration::Config<SessionIndex = u32>
+        + pallet_services_payment::Config
+        + pallet_session::Config,
+    FixedCost: Get<u128>,
+    ExtraCollatorCost: Get<u128>,
+    ParachainsUseCores: Get<bool>,
+{
+    /// Cost of assigning `num_collators` collators to `para_id` for the next session, which is
+    /// the one collators are assigned for. Chains assigned more collators than the default of
+    /// that session also pay for the extra ones, and parachains using `num_cores` relay chain
+    /// cores pay the fixed cost for each of them.
+    pub fn cost_for_collators(
+        para_id: &ParaId,
+        num_collators: u32,
+        num_cores: u32,
+    ) -> (u128, Weight) {
+        let target_session_index =
+            pallet_session::Pallet::<Runtime>::current_index().saturating_add(1);
+        let (default_collators, num_cores) =
+            if pallet_registrar::Pallet::<Runtime>::parathread_params(*para_id).is_some() {
+                (
+                    <Configuration<Runtime> as GetHostConfiguration<u32>>::collators_per_parathread(
+                        target_session_index,
+                    ),
+                    // Parathreads don't use more than one core
+                    1,
+                )
+            } else {
+                (
+                    <Configuration<Runtime> as GetHostConfiguration<u32>>::collators_per_container(
+                        target_session_index,
+                    ),
+                    if ParachainsUseCores::get() {
+                        num_cores.max(1)
+                    } else {
+                        1
+                    },
+                )
+            };
+        let extra_collators = num_collators.saturating_sub(default_collators);
+
+        (
+            pallet_services_payment::Pallet::<Runtime>::price_multiplier().saturating_mul_int(
+                FixedCost::get()
+                    .saturating_mul(num_cores.into())
+                    .saturating_add(
+                        ExtraCollatorCost::get().saturating_mul(extra_collators.into()),
+                    ),
+            ),
+            // Session index, parathread params, active and pending configs, pending config
+            // patches and price multiplier
+            <Runtime as frame_system::Config>::DbWeight::get().reads(6),
+        )
+    }
+}
+
+impl<Runtime, FixedCost, ExtraCollatorCost, ParachainsUseCores>
+    ProvideCollatorAssignmentCost<Runtime>
+    for CollatorAssignmentCost<Runtime, FixedCost, ExtraCollatorCost, ParachainsUseCores>
+where
+    Runtime: pallet_registrar::Config
+        + pallet_configuration::Config<SessionIndex = u32>
+        + pallet_services_payment::Config
+        + pallet_session::Config,
+    BalanceOf<Runtime>: From<u128>,
+    FixedCost: Get<u128>,
+    ExtraCollatorCost: Get<u128>,
+    ParachainsUseCores: Get<bool>,
+{
+    /// Estimated with the max collators and the cores requested by the para manager. Chains
+    /// without a collator count request are assigned the default collators, which are covered by
+    /// the fixed cost.
+    fn collator_assignment_cost(para_id: &ParaId) -> (BalanceOf<Runtime>, Weight) {
+        let num_collators = pallet_registrar::Pallet::<Runtime>::collator_count_request(*para_id)
+            .map(|request| request.max)
+            .unwrap_or_default();
+        let (num_cores, reads) = if ParachainsUseCores::get() {
+            (
+                pallet_registrar::Pallet::<Runtime>::core_count_request(*para_id).unwrap_or(1),
+                2,
+            )
+        } else {
+            (1, 1)
+        };
+        let (cost, weight) = Self::cost_for_collators(para_id, num_collators, num_cores);
+
+        (
+            cost.into(),
+            weight.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads(reads)),
+        )
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Stream payment assets for container chains supporting both the native currency and
+//! foreign assets.
+
+use {
+    core::marker::PhantomData,
+    frame_support::{
+        traits::{
+            fungible, fungibles,
+            tokens::{Precision, Preservation},
+            Get,
+        },
+        PalletId,
+    },
+    parity_scale_codec::{Decode, Encode, MaxEncodedLen},
+    scale_info::TypeInfo,
+    serde::{Deserialize, Serialize},
+    sp_core::hashing::blake2_256,
+    sp_runtime::{
+        traits::{TrailingZeroInput, Zero},
+        DispatchResult,
+    },
+};
+
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Encode,
+    Decode,
+    Debug,
+    MaxEncodedLen,
+    TypeInfo,
+    Serialize,
+    Deserialize,
+)]
+pub enum StreamPaymentAssetId<ForeignAssetId> {
+    Native,
+    Foreign(ForeignAssetId),
+}
+
+/// Native deposits are held in the account of the depositor. Foreign assets can't be held,
+/// so deposits in those assets are moved to an account dedicated to each depositor.
+pub struct StreamPaymentAssets<Runtime, Balances, ForeignAssets, DepositPalletId>(
+    PhantomData<(Runtime, Balances, ForeignAssets, DepositPalletId)>,
+);
+
+impl<Runtime, Balances, ForeignAssets, DepositPalletId>
+    StreamPaymentAssets<Runtime, Balances, ForeignAssets, DepositPalletId>
+where
+    Runtime: frame_system::Config,
+    Balances: fungible::Inspect<Runtime::AccountId>,
+    ForeignAssets: fungibles::Mutate<Runtime::AccountId>,
+    DepositPalletId: Get<PalletId>,
+{
+    /// Account holding the foreign deposits of `account`. It is derived from a hash of the
+    /// full account, as truncating would make short accounts such as `AccountId20` collide.
+    pub fn foreign_deposit_account(account: &Runtime::AccountId) -> Runtime::AccountId {
+        let entropy = (b"modl", DepositPalletId::get(), account).using_encoded(blake2_256);
+        Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+            .expect("infinite length input; no invalid inputs for type; qed")
+    }
+
+    /// Moves foreign assets between a deposit account and another account. The deposit
+    /// account is allowed to be reaped, in which case the dust below the minimum balance of
+    /// the asset is moved too and later transfers are capped to what is left.
+    fn transfer_foreign_deposit(
+        asset_id: ForeignAssets::AssetId,
+        deposit_account: &Runtime::AccountId,
+        to: &Runtime::AccountId,
+        amount: ForeignAssets::Balance,
+    ) -> DispatchResult {
+        let amount = amount.min(ForeignAssets::balance(asset_id.clone(), deposit_account));
+
+        if !amount.is_zero() {
+            ForeignAssets::transfer(
+                asset_id,
+                deposit_account,
+                to,
+                amount,
+                Preservation::Expendable,
+            )?;
+        }
+
+        // The provider added when the deposit account was created is removed once it
+        // no longer holds any asset. Native balance has its own provider which must be kept.
+        if frame_system::Pallet::<Runtime>::consumers(deposit_account) == 0
+            && frame_system::Pallet::<Runtime>::providers(deposit_account) > 0
+            && Balances::total_balance(deposit_account).is_zero()
+        {
+            let _ = frame_system::Pallet::<Runtime>::dec_providers(deposit_account);
+        }
+
+        Ok(())
+    }
+}
+
+impl<Runtime, Balances, ForeignAssets, DepositPalletId, Balance>
+    pallet_stream_payment::Assets<
+        Runtime::AccountId,
+        StreamPaymentAssetId<ForeignAssets::AssetId>,
+        Balance,
+    > for StreamPaymentAssets<Runtime, Balances, ForeignAssets, DepositPalletId>
+where
+    Runtime: frame_system::Config,
+    Balances: fungible::Mutate<Runtime::AccountId, Balance = Balance>
+        + fungible::MutateHold<Runtime::AccountId>,
+    Balances::Reason: From<pallet_stream_payment::HoldReason>,
+    ForeignAssets: fungibles::Mutate<Runtime::AccountId, Balance = Balance>,
+    DepositPalletId: Get<PalletId>,
+{
+    fn transfer_deposit(
+        asset_id: &StreamPaymentAssetId<ForeignAssets::AssetId>,
+        from: &Runtime::AccountId,
+        to: &Runtime::AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        match asset_id {
+            StreamPaymentAssetId::Native => {
+                // We remove the hold before transfering.
+                Self::decrease_deposit(asset_id, from, amount)?;
+                Balances::transfer(from, to, amount, Preservation::Preserve).map(|_| ())
+            }
+            StreamPaymentAssetId::Foreign(asset_id) => Self::transfer_foreign_deposit(
+                asset_id.clone(),
+                &Self::foreign_deposit_account(from),
+                to,
+                amount,
+            ),
+        }
+    }
+
+    fn increase_deposit(
+        asset_id: &StreamPaymentAssetId<ForeignAssets::AssetId>,
+        account: &Runtime::AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        match asset_id {
+            StreamPaymentAssetId::Native => Balances::hold(
+                &pallet_stream_payment::HoldReason::StreamPayment.into(),
+                account,
+                amount,
+            ),
+            StreamPaymentAssetId::Foreign(asset_id) => {
+                let deposit_account = Self::foreign_deposit_account(account);
+
+                // Deposit accounts don't hold any native balance, and need a provider
+                // to receive non-sufficient assets. It is removed once the account
+                // no longer holds any asset.
+                if !frame_system::Pallet::<Runtime>::account_exists(&deposit_account) {
+                    frame_system::Pallet::<Runtime>::inc_providers(&deposit_account);
+                }
+
+                ForeignAssets::transfer(
+                    asset_id.clone(),
+                    account,
+                    &deposit_account,
+                    amount,
+                    Preservation::Preserve,
+                )
+                .map(|_| ())
+            }
+        }
+    }
+
+    fn decrease_deposit(
+        asset_id: &StreamPaymentAssetId<ForeignAssets::AssetId>,
+        account: &Runtime::AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        match asset_id {
+            StreamPaymentAssetId::Native => Balances::release(
+                &pallet_stream_payment::HoldReason::StreamPayment.into(),
+                account,
+                amount,
+                Precision::Exact,
+            )
+            .map(|_| ()),
+            StreamPaymentAssetId::Foreign(asset_id) => Self::transfer_foreign_deposit(
+                asset_id.clone(),
+                &Self::foreign_deposit_account(account),
+                account,
+                amount,
+            ),
+        }
+    }
+
+    fn get_deposit(
+        asset_id: &StreamPaymentAssetId<ForeignAssets::AssetId>,
+        account: &Runtime::AccountId,
+    ) -> Balance {
+        match asset_id {
+            StreamPaymentAssetId::Native => Balances::balance_on_hold(
+                &pallet_stream_payment::HoldReason::StreamPayment.into(),
+                account,
+            ),
+            StreamPaymentAssetId::Foreign(asset_id) => {
+                ForeignAssets::balance(asset_id.clone(), &Self::foreign_deposit_account(account))
+            }
+        }
+    }
+
+    /// Benchmarks: should return the asset id which has the worst performance when interacting
+    /// with it.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_worst_case_asset_id() -> StreamPaymentAssetId<ForeignAssets::AssetId> {
+        StreamPaymentAssetId::Native
+    }
+
+    /// Benchmarks: should return the another asset id which has the worst performance when interacting
+    /// with it afther `bench_worst_case_asset_id`. This is to benchmark the worst case when changing config
+    /// from one asset to another.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_worst_case_asset_id2() -> StreamPaymentAssetId<ForeignAssets::AssetId> {
+        StreamPaymentAssetId::Native
+    }
+
+    /// Benchmarks: should set the balance for the asset id returned by `bench_worst_case_asset_id`.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_set_balance(
+        asset_id: &StreamPaymentAssetId<ForeignAssets::AssetId>,
+        account: &Runtime::AccountId,
+        amount: Balance,
+    ) {
+        // only the native asset is used in benchmarks
+        let StreamPaymentAssetId::Native = asset_id else {
+            unreachable!("benchmarks only use the native asset");
+        };
+
+        Balances::set_balance(account, amount);
+    }
+}
@@ -25,7 +25,6 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 pub mod xcm_config;
 
 use frame_support::storage::{with_storage_layer, with_transaction};
-use frame_support::traits::{ExistenceRequirement, WithdrawReasons};
 use polkadot_runtime_common::SlowAdjustingFeeUpdate;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
@@ -58,8 +57,8 @@ use {
         traits::{
             fungible::{Balanced, Credit, Inspect, InspectHold, Mutate, MutateHold},
             tokens::{
-                imbalance::ResolveTo, ConversionToAssetBalance, PayFromAccount, Precision,
-                Preservation, UnityAssetBalanceConversion,
+                imbalance::ResolveTo, ConversionToAssetBalance, Fortitude, PayFromAccount,
+                Precision, Preservation, UnityAssetBalanceConversion,
             },
             ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, Contains, EitherOfDiverse,
             Imbalance, InsideBoth, InstanceFilter, OnUnbalanced, ValidatorRegistration,
@@ -87,7 +86,9 @@ use {
     pallet_registrar_runtime_api::ContainerChainGenesisData,
     pallet_services_payment::{ProvideBlockProductionCost, ProvideCollatorAssignmentCost},
     pallet_session::{SessionManager, ShouldEndSession},
-    pallet_stream_payment_runtime_api::{StreamPaymentApiError, StreamPaymentApiStatus},
+    pallet_stream_payment_runtime_api::{
+        StreamPaymentApiError, StreamPaymentApiEvent, StreamPaymentApiParty, StreamPaymentApiStatus,
+    },
     pallet_transaction_payment::FungibleAdapter,
     pallet_xcm_core_buyer::BuyingError,
     polkadot_runtime_common::BlockHashCount,
@@ -115,8 +116,8 @@ use {
     },
     tp_traits::{
         apply, derive_storage_traits, GetContainerChainAuthor, GetHostConfiguration,
-        GetSessionContainerChains, MaybeSelfChainBlockAuthor, ParaIdAssignmentHooks,
-        RelayStorageRootProvider, RemoveInvulnerables, SlotFrequency,
+        GetSessionContainerChains, IsCollatorRegistered, MaybeSelfChainBlockAuthor,
+        ParaIdAssignmentHooks, RelayStorageRootProvider, RemoveInvulnerables, SlotFrequency,
     },
     tp_xcm_core_buyer::BuyCoreCollatorProof,
     xcm_runtime_apis::{
@@ -636,6 +637,13 @@ impl pallet_initializer::ApplyNewSession<Runtime> for OwnApplySession {
         // Next: AuthorityMapping
         AuthorityMapping::initializer_on_new_session(&session_index, &all_validators);
 
+        // Next: ServicesPayment, priced by the share of collators assigned in the previous
+        // session. It must run before the collator assignment, which charges for the new one.
+        ServicesPayment::initializer_on_new_session(
+            pallet_collator_assignment::CollatorFullnessRatio::<Runtime>::get().unwrap_or_default(),
+            Configuration::config().target_container_chain_fullness,
+        );
+
         let next_collators = queued.iter().map(|(k, _)| k.clone()).collect();
 
         // Next: CollatorAssignment
@@ -820,21 +828,29 @@ impl ParaIdAssignmentHooksImpl {
         para_id: ParaId,
         currently_assigned: &BTreeSet<ParaId>,
         maybe_tip: &Option<BalanceOf<Runtime>>,
+        maybe_num_collators: Option<u32>,
     ) -> Result<Weight, DispatchError> {
         use frame_support::traits::Currency;
-        type ServicePaymentCurrency = <Runtime as pallet_services_payment::Config>::Currency;
+
+        let mut weight = Weight::zero();
 
         // Check if the container chain has enough credits for a session assignments
         let maybe_assignment_imbalance =
             if  pallet_services_payment::Pallet::<Runtime>::burn_collator_assignment_free_credit_for_para(&para_id).is_err() {
-                let (amount_to_charge, _weight) =
-                    <Runtime as pallet_services_payment::Config>::ProvideCollatorAssignmentCost::collator_assignment_cost(&para_id);
-                Some(<ServicePaymentCurrency as Currency<AccountId>>::withdraw(
-                    &pallet_services_payment::Pallet::<Runtime>::parachain_tank(para_id),
+                // Chains are charged for the collators actually assigned, or estimated before
+                // the assignment is known
+                let (amount_to_charge, cost_weight) = match maybe_num_collators {
+                    Some(num_collators) => {
+                        CollatorAssignmentCost::cost_for_collators(&para_id, num_collators, 1)
+                    }
+                    None => <Runtime as pallet_services_payment::Config>::ProvideCollatorAssignmentCost::collator_assignment_cost(&para_id),
+                };
+                weight.saturating_accrue(cost_weight);
+                pallet_services_payment::Pallet::<Runtime>::charge_fee(
+                    &para_id,
                     amount_to_charge,
-                    WithdrawReasons::FEE,
-                    ExistenceRequirement::KeepAlive,
-                )?)
+                    pallet_services_payment::PaidService::CollatorAssignment,
+                )?
             } else {
                 None
             };
@@ -876,15 +892,20 @@ impl ParaIdAssignmentHooksImpl {
         let remaining_block_credits_to_pay =
             u128::from(remaining_block_credits).saturating_mul(block_production_costs);
         let remaining_to_pay = remaining_block_credits_to_pay;
-        // This should take into account whether we tank goes below ED
-        // The true refers to keepAlive
-        Balances::can_withdraw(
-            &pallet_services_payment::Pallet::<Runtime>::parachain_tank(para_id),
-            remaining_to_pay,
-        )
-        .into_result(true)?;
+        let parachain_tank = pallet_services_payment::Pallet::<Runtime>::parachain_tank(para_id);
+        if let Some(asset_id) = pallet_services_payment::PaymentAsset::<Runtime>::get(para_id) {
+            pallet_services_payment::Pallet::<Runtime>::ensure_can_pay_in_asset(
+                &asset_id,
+                &parachain_tank,
+                remaining_to_pay,
+            )?;
+        } else {
+            // This should take into account whether we tank goes below ED
+            // The true refers to keepAlive
+            Balances::can_withdraw(&parachain_tank, remaining_to_pay).into_result(true)?;
+        }
         // TODO: Have proper weight
-        Ok(Weight::zero())
+        Ok(weight)
     }
 }
 
@@ -900,6 +921,7 @@ impl<AC> ParaIdAssignmentHooks<BalanceOf<Runtime>, AC> for ParaIdAssignmentHooks
                     *para_id,
                     currently_assigned,
                     &Some(max_tip),
+                    None,
                 ))
             })
             .is_ok()
@@ -909,6 +931,7 @@ impl<AC> ParaIdAssignmentHooks<BalanceOf<Runtime>, AC> for ParaIdAssignmentHooks
     fn post_assignment(
         current_assigned: &BTreeSet<ParaId>,
         new_assigned: &mut BTreeMap<ParaId, Vec<AC>>,
+        _parachain_cores: &BTreeMap<ParaId, u32>,
         maybe_tip: &Option<BalanceOf<Runtime>>,
     ) -> Weight {
         let blocks_per_session = Period::get();
@@ -924,6 +947,7 @@ impl<AC> ParaIdAssignmentHooks<BalanceOf<Runtime>, AC> for ParaIdAssignmentHooks
                     para_id,
                     current_assigned,
                     maybe_tip,
+                    Some(collators.len() as u32),
                 )
             })
             .inspect(|weight| {
@@ -959,6 +983,17 @@ impl<AC> ParaIdAssignmentHooks<BalanceOf<Runtime>, AC> for ParaIdAssignmentHooks
     }
 }
 
+parameter_types! {
+    /// Collators producing less than half of the blocks they were expected to produce are
+    /// assigned to new chains last.
+    pub const MinLivenessScore: Perbill = Perbill::from_percent(50);
+    /// Container chains have the same slot duration as this chain, so a session has as many
+    /// container chain slots as blocks.
+    pub const ContainerSlotsPerSession: u32 = Period::get();
+    /// Number of past sessions whose collator assignment can be queried.
+    pub const AssignmentHistoryDepth: u32 = 168;
+}
+
 impl pallet_collator_assignment::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type HostConfiguration = Configuration;
@@ -974,6 +1009,10 @@ impl pallet_collator_assignment::Config for Runtime {
     type Currency = Balances;
     type ForceEmptyOrchestrator = ConstBool<false>;
     type CoreAllocationConfiguration = ();
+    type CollatorRegistration = CandidateHasRegisteredKeys;
+    type MinLivenessScore = MinLivenessScore;
+    type ContainerSlotsPerSession = ContainerSlotsPerSession;
+    type AssignmentHistoryDepth = AssignmentHistoryDepth;
     type WeightInfo = weights::pallet_collator_assignment::SubstrateWeight<Runtime>;
 }
 
@@ -984,21 +1023,33 @@ impl pallet_authority_assignment::Config for Runtime {
 
 pub const FIXED_BLOCK_PRODUCTION_COST: u128 = 1 * currency::MICRODANCE;
 pub const FIXED_COLLATOR_ASSIGNMENT_COST: u128 = 100 * currency::MICRODANCE;
+pub const EXTRA_COLLATOR_ASSIGNMENT_COST: u128 = 50 * currency::MICRODANCE;
+
+parameter_types! {
+    /// Services get at most 10% more expensive, or cheaper, each session
+    pub const ServicesPriceAdjustmentRate: Perbill = Perbill::from_percent(10);
+}
 
 pub struct BlockProductionCost<Runtime>(PhantomData<Runtime>);
 impl ProvideBlockProductionCost<Runtime> for BlockProductionCost<Runtime> {
     fn block_cost(_para_id: &ParaId) -> (u128, Weight) {
-        (FIXED_BLOCK_PRODUCTION_COST, Weight::zero())
-    }
-}
+        use sp_runtime::FixedPointNumber;
 
-pub struct CollatorAssignmentCost<Runtime>(PhantomData<Runtime>);
-impl ProvideCollatorAssignmentCost<Runtime> for CollatorAssignmentCost<Runtime> {
-    fn collator_assignment_cost(_para_id: &ParaId) -> (u128, Weight) {
-        (FIXED_COLLATOR_ASSIGNMENT_COST, Weight::zero())
+        (
+            ServicesPayment::price_multiplier().saturating_mul_int(FIXED_BLOCK_PRODUCTION_COST),
+            // Price multiplier
+            <Runtime as frame_system::Config>::DbWeight::get().reads(1),
+        )
     }
 }
 
+pub type CollatorAssignmentCost = tanssi_runtime_common::services_payment::CollatorAssignmentCost<
+    Runtime,
+    ConstU128<FIXED_COLLATOR_ASSIGNMENT_COST>,
+    ConstU128<EXTRA_COLLATOR_ASSIGNMENT_COST>,
+    ConstBool<false>,
+>;
+
 parameter_types! {
     // 60 days worth of blocks
     pub const FreeBlockProductionCredits: BlockNumber = 60 * DAYS;
@@ -1017,16 +1068,106 @@ impl pallet_services_payment::Config for Runtime {
     /// Provider of a block cost which can adjust from block to block
     type ProvideBlockProductionCost = BlockProductionCost<Runtime>;
     /// Provider of a block cost which can adjust from block to block
-    type ProvideCollatorAssignmentCost = CollatorAssignmentCost<Runtime>;
+    type ProvideCollatorAssignmentCost = CollatorAssignmentCost;
     /// The maximum number of block credits that can be accumulated
     type FreeBlockProductionCredits = FreeBlockProductionCredits;
     /// The maximum number of session credits that can be accumulated
     type FreeCollatorAssignmentCredits = FreeCollatorAssignmentCredits;
     type ManagerOrigin =
         EitherOfDiverse<pallet_registrar::EnsureSignedByManager<Runtime>, EnsureRoot<AccountId>>;
+    type AssetId = xcm_config::AssetId;
+    type PaymentAssets = ServicesPaymentAssets;
+    type CurrentSessionIndex = CurrentSessionIndexGetter;
+    type SpendHistoryLength = ConstU32<30>;
+    type PriceAdjustmentRate = ServicesPriceAdjustmentRate;
+    type InactiveParas = tanssi_runtime_common::services_payment::PausedOrWithoutCollators<Runtime>;
+    type TankReserveBlocks = ConstU32<10>;
     type WeightInfo = weights::pallet_services_payment::SubstrateWeight<Runtime>;
 }
 
+/// Foreign assets with a rate in `AssetRate` can be used to pay for services.
+pub struct ServicesPaymentAssets;
+impl pallet_services_payment::PaymentAssets<AccountId, xcm_config::AssetId, Balance>
+    for ServicesPaymentAssets
+{
+    fn to_asset_balance(
+        asset_id: &xcm_config::AssetId,
+        amount: Balance,
+    ) -> Result<Balance, DispatchError> {
+        AssetRate::to_asset_balance(amount, *asset_id).map_err(Into::into)
+    }
+
+    fn transfer(
+        asset_id: &xcm_config::AssetId,
+        from: &AccountId,
+        to: &AccountId,
+        amount: Balance,
+        preservation: Preservation,
+    ) -> DispatchResult {
+        <ForeignAssets as frame_support::traits::fungibles::Mutate<AccountId>>::transfer(
+            *asset_id,
+            from,
+            to,
+            amount,
+            preservation,
+        )
+        .map(|_| ())
+    }
+
+    fn withdraw_fee(
+        asset_id: &xcm_config::AssetId,
+        who: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        // The credit is dropped, so fees paid in assets are burnt like the native ones
+        <ForeignAssets as frame_support::traits::fungibles::Balanced<AccountId>>::withdraw(
+            *asset_id,
+            who,
+            amount,
+            Precision::Exact,
+            Preservation::Preserve,
+            Fortitude::Polite,
+        )
+        .map(|_| ())
+    }
+
+    fn reducible_balance(asset_id: &xcm_config::AssetId, who: &AccountId) -> Balance {
+        <ForeignAssets as frame_support::traits::fungibles::Inspect<AccountId>>::reducible_balance(
+            *asset_id,
+            who,
+            Preservation::Preserve,
+            Fortitude::Polite,
+        )
+    }
+
+    fn balance(asset_id: &xcm_config::AssetId, who: &AccountId) -> Balance {
+        ForeignAssets::balance(*asset_id, who)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_worst_case_asset_id() -> xcm_config::AssetId {
+        1
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_set_balance(asset_id: &xcm_config::AssetId, account: &AccountId, amount: Balance) {
+        use frame_support::traits::fungibles::{Create, Inspect, Mutate};
+
+        if !<ForeignAssets as Inspect<AccountId>>::asset_exists(*asset_id) {
+            <ForeignAssets as Create<AccountId>>::create(*asset_id, account.clone(), true, 1)
+                .expect("failed to create asset");
+            AssetRate::create(
+                RuntimeOrigin::root(),
+                Box::new(*asset_id),
+                sp_runtime::FixedU128::from_u32(1),
+            )
+            .expect("failed to create asset rate");
+        }
+        <ForeignAssets as Mutate<AccountId>>::mint_into(*asset_id, account, amount)
+            .expect("failed to mint asset");
+    }
+}
+
 parameter_types! {
     pub const ProfileDepositBaseFee: Balance = currency::STORAGE_ITEM_FEE;
     pub const ProfileDepositByteFee: Balance = currency::STORAGE_BYTE_FEE;
@@ -1109,7 +1250,11 @@ impl pallet_data_preservers::AssignmentPayment<AccountId> for PreserversAssignem
         match witness {
             Self::AssignmentWitness::Free => (),
             Self::AssignmentWitness::StreamPayment { stream_id } => {
-                StreamPayment::close_stream(RuntimeOrigin::signed(provider), stream_id)?;
+                // The stream is closed automatically once it can no longer pay, in which
+                // case there is nothing left to close.
+                if pallet_stream_payment::Streams::<Runtime>::contains_key(stream_id) {
+                    StreamPayment::close_stream(RuntimeOrigin::signed(provider), stream_id)?;
+                }
             }
         }
 
@@ -1172,7 +1317,12 @@ impl pallet_author_noting::Config for Runtime {
     type ContainerChains = Registrar;
     type SlotBeacon = dp_consensus::AuraDigestSlotBeacon<Runtime>;
     type ContainerChainAuthor = CollatorAssignment;
-    type AuthorNotingHook = (XcmCoreBuyer, InflationRewards, ServicesPayment);
+    type AuthorNotingHook = (
+        XcmCoreBuyer,
+        InflationRewards,
+        ServicesPayment,
+        CollatorAssignment,
+    );
     type RelayOrPara = pallet_author_noting::ParaMode<
         cumulus_pallet_parachain_system::RelaychainDataProvider<Self>,
     >;
@@ -1216,10 +1366,12 @@ impl tp_traits::GetSessionIndex<u32> for CurrentSessionIndexGetter {
 }
 
 impl pallet_configuration::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
     type SessionDelay = ConstU32<2>;
     type SessionIndex = u32;
     type CurrentSessionIndex = CurrentSessionIndexGetter;
     type ForceEmptyOrchestrator = ConstBool<false>;
+    type MaxPendingConfigPatches = ConstU32<10>;
     type WeightInfo = weights::pallet_configuration::SubstrateWeight<Runtime>;
 }
 
@@ -1355,6 +1507,8 @@ impl pallet_registrar::Config for Runtime {
     type RegistrarHooks = DanceboxRegistrarHooks;
     type RuntimeHoldReason = RuntimeHoldReason;
     type InnerRegistrar = ();
+    type MaxRequestedCollators = ConstU32<10>;
+    type MaxRequestedCores = ConstU32<4>;
     type WeightInfo = weights::pallet_registrar::SubstrateWeight<Runtime>;
 }
 
@@ -1560,7 +1714,9 @@ parameter_types! {
     pub const InitialManualClaimShareValue: u128 = currency::MILLIDANCE;
     pub const InitialAutoCompoundingShareValue: u128 = currency::MILLIDANCE;
     pub const MinimumSelfDelegation: u128 = 10 * currency::KILODANCE;
+    pub const MinimumRedelegation: u128 = currency::KILODANCE;
     pub const RewardsCollatorCommission: Perbill = Perbill::from_percent(20);
+    pub const MaxCommission: Perbill = Perbill::from_percent(50);
     // Need to wait 2 sessions before being able to join or leave staking pools
     pub const StakingSessionDelay: u32 = 2;
 }
@@ -1585,6 +1741,10 @@ where
         end <= Self::now()
     }
 
+    fn elapsed_at(instant: &Self::Instant) -> Option<Self::Instant> {
+        instant.checked_add(Delay::get())
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn elapsed_instant() -> Self::Instant {
         let delay = Delay::get();
@@ -1625,6 +1785,16 @@ impl IsCandidateEligible<AccountId> for CandidateHasRegisteredKeys {
     }
 }
 
+impl IsCollatorRegistered<AccountId> for CandidateHasRegisteredKeys {
+    fn is_collator_registered(a: &AccountId) -> bool {
+        Self::is_candidate_eligible(a)
+    }
+    #[cfg(feature = "runtime-benchmarks")]
+    fn register_collator(a: &AccountId) {
+        Self::make_candidate_eligible(a, true)
+    }
+}
+
 impl pallet_pooled_staking::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type Currency = Balances;
@@ -1635,10 +1805,19 @@ impl pallet_pooled_staking::Config for Runtime {
     type MinimumSelfDelegation = MinimumSelfDelegation;
     type RuntimeHoldReason = RuntimeHoldReason;
     type RewardsCollatorCommission = RewardsCollatorCommission;
+    type MaxCommission = MaxCommission;
     type JoiningRequestTimer = SessionTimer<StakingSessionDelay>;
     type LeavingRequestTimer = SessionTimer<StakingSessionDelay>;
+    type CommissionChangeTimer = SessionTimer<StakingSessionDelay>;
+    type RedelegationTimer = SessionTimer<StakingSessionDelay>;
+    type MaxRedelegationsPerCandidate = ConstU32<100>;
+    type MinimumRedelegation = MinimumRedelegation;
     type EligibleCandidatesBufferSize = ConstU32<100>;
     type EligibleCandidatesFilter = CandidateHasRegisteredKeys;
+    type SlashedFundsHandler = ResolveTo<pallet_treasury::TreasuryAccountId<Runtime>, Balances>;
+    type AutoExecutionScanLimit = ConstU32<20>;
+    type AutoExecutionBounty = ConstU128<0>;
+    type AutoExecutionKeeper = BlockAuthorAsKeeper;
     type WeightInfo = weights::pallet_pooled_staking::SubstrateWeight<Runtime>;
 }
 
@@ -1669,6 +1848,14 @@ impl MaybeSelfChainBlockAuthor<AccountId32> for GetSelfChainBlockAuthor {
     }
 }
 
+/// Block author executes pending operations in `on_idle`, so it receives the bounties.
+pub struct BlockAuthorAsKeeper;
+impl Get<Option<AccountId32>> for BlockAuthorAsKeeper {
+    fn get() -> Option<AccountId32> {
+        GetSelfChainBlockAuthor::get_block_author()
+    }
+}
+
 pub struct OnUnbalancedInflation;
 impl frame_support::traits::OnUnbalanced<Credit<AccountId, Balances>> for OnUnbalancedInflation {
     fn on_nonzero_unbalanced(credit: Credit<AccountId, Balances>) {
@@ -1823,8 +2010,11 @@ impl pallet_stream_payment::TimeProvider<TimeUnit, Balance> for TimeProvider {
 type StreamId = u64;
 
 parameter_types! {
-    // 1 entry, storing 173 bytes on-chain
-    pub const OpenStreamHoldAmount: Balance = currency::deposit(1, 173);
+    // 1 entry, storing 207 bytes on-chain
+    pub const OpenStreamHoldAmount: Balance = currency::deposit(1, 207);
+    // 1 target lookup entry and 1 split, storing 108 bytes on-chain
+    pub const OpenStreamSplitHoldAmount: Balance = currency::deposit(1, 108);
+    pub const MaxStreamSplits: u32 = 4;
 }
 
 impl pallet_stream_payment::Config for Runtime {
@@ -1836,8 +2026,11 @@ impl pallet_stream_payment::Config for Runtime {
     type Assets = StreamPaymentAssets;
     type Currency = Balances;
     type OpenStreamHoldAmount = OpenStreamHoldAmount;
+    type OpenStreamSplitHoldAmount = OpenStreamSplitHoldAmount;
     type RuntimeHoldReason = RuntimeHoldReason;
     type TimeProvider = TimeProvider;
+    type MaxStreamSplits = MaxStreamSplits;
+    type AutoSettlementScanLimit = ConstU32<20>;
     type WeightInfo = weights::pallet_stream_payment::SubstrateWeight<Runtime>;
 }
 
@@ -2470,7 +2663,8 @@ impl_runtime_apis! {
         }
     }
 
-    impl pallet_collator_assignment_runtime_api::CollatorAssignmentApi<Block, AccountId, ParaId> for Runtime {
+    impl pallet_collator_assignment_runtime_api::CollatorAssignmentApi<Block, AccountId, ParaId, pallet_configuration::HostConfiguration>
+    for Runtime {
         /// Return the parachain that the given `AccountId` is collating for.
         /// Returns `None` if the `AccountId` is not collating.
         fn current_collator_parachain_assignment(account: AccountId) -> Option<ParaId> {
@@ -2511,6 +2705,76 @@ impl_runtime_apis! {
                 assigned_collators.container_chains.get(&para_id).cloned()
             }
         }
+
+        /// Simulate the assignment of the next session change as if the given host configuration
+        /// and collators were used, without modifying the storage.
+        fn simulate_collator_assignment(
+            config: pallet_configuration::HostConfiguration,
+            collators: Vec<AccountId>,
+        ) -> Option<pallet_collator_assignment_runtime_api::CollatorAssignmentSimulation<AccountId, ParaId>> {
+            let config = pallet_collator_assignment::AssignmentHostConfiguration {
+                max_collators: config.max_collators,
+                min_orchestrator_collators: config.min_orchestrator_collators,
+                max_orchestrator_collators: config.max_orchestrator_collators,
+                collators_per_container: config.collators_per_container,
+                collators_per_parathread: config.collators_per_parathread,
+                max_parachain_cores_percentage: config.max_parachain_cores_percentage,
+                full_rotation_mode: config.full_rotation_mode,
+            };
+            let simulation = CollatorAssignment::simulate_assignment(
+                Session::current_index() + 1,
+                config,
+                collators,
+            )
+            .ok()?;
+
+            Some(pallet_collator_assignment_runtime_api::CollatorAssignmentSimulation {
+                assignment: simulation.assignment,
+                dropped_chains: simulation.dropped_chains,
+                tips_charged: simulation.tips_charged,
+            })
+        }
+
+        /// Return the liveness score of the given `AccountId` in the last session.
+        fn collator_liveness(
+            account: AccountId,
+        ) -> Option<pallet_collator_assignment_runtime_api::CollatorLiveness> {
+            pallet_collator_assignment::LivenessScores::<Runtime>::get(account).map(|score| {
+                pallet_collator_assignment_runtime_api::CollatorLiveness {
+                    blocks_produced: score.blocks_produced,
+                    slots_expected: score.slots_expected,
+                }
+            })
+        }
+
+        /// Return the collators assigned to the given `ParaId` in the given session.
+        fn chain_assignment_at_session(
+            session_index: u32,
+            para_id: ParaId,
+        ) -> Option<pallet_collator_assignment_runtime_api::ChainAssignmentRecord<AccountId>> {
+            use pallet_collator_assignment_runtime_api::DroppedChainReason;
+
+            let (collators, dropped_reason) =
+                CollatorAssignment::chain_assignment_at_session(session_index, para_id)?;
+
+            Some(pallet_collator_assignment_runtime_api::ChainAssignmentRecord {
+                collators,
+                dropped_reason: dropped_reason.map(|reason| match reason {
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughCredits => {
+                        DroppedChainReason::NotEnoughCredits
+                    }
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughCores => {
+                        DroppedChainReason::NotEnoughCores
+                    }
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughCollators => {
+                        DroppedChainReason::NotEnoughCollators
+                    }
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughEligibleCollators => {
+                        DroppedChainReason::NotEnoughEligibleCollators
+                    }
+                }),
+            })
+        }
     }
 
     impl pallet_registrar_runtime_api::RegistrarApi<Block, ParaId> for Runtime {
@@ -2642,7 +2906,7 @@ impl_runtime_apis! {
         }
     }
 
-    impl pallet_stream_payment_runtime_api::StreamPaymentApi<Block, StreamId, Balance, Balance>
+    impl pallet_stream_payment_runtime_api::StreamPaymentApi<Block, StreamId, Balance, Balance, AccountId>
     for Runtime {
         fn stream_payment_status(
             stream_id: StreamId,
@@ -2650,15 +2914,33 @@ impl_runtime_apis! {
         ) -> Result<StreamPaymentApiStatus<Balance>, StreamPaymentApiError> {
             match StreamPayment::stream_payment_status(stream_id, now) {
                 Ok(pallet_stream_payment::StreamPaymentStatus {
-                    payment, deposit_left, stalled
+                    payment, deposit_left, stalled, started, ended
                 }) => Ok(StreamPaymentApiStatus {
-                    payment, deposit_left, stalled
+                    payment, deposit_left, stalled, started, ended
                 }),
                 Err(pallet_stream_payment::Error::<Runtime>::UnknownStreamId)
                 => Err(StreamPaymentApiError::UnknownStreamId),
                 Err(e) => Err(StreamPaymentApiError::Other(format!("{e:?}")))
             }
         }
+
+        fn account_streams(
+            account: AccountId,
+            party: StreamPaymentApiParty,
+            start_after: Option<StreamId>,
+            limit: u32,
+        ) -> Vec<StreamId> {
+            let party = match party {
+                StreamPaymentApiParty::Source => pallet_stream_payment::Party::Source,
+                StreamPaymentApiParty::Target => pallet_stream_payment::Party::Target,
+            };
+
+            StreamPayment::account_streams(&account, party, start_after, limit)
+        }
+
+        fn stream_events() -> Vec<StreamPaymentApiEvent<AccountId, StreamId, Balance>> {
+            StreamPayment::api_events()
+        }
     }
 
     impl pallet_data_preservers_runtime_api::DataPreserversApi<Block, DataPreserversProfileId, ParaId> for Runtime {
@@ -2699,7 +2981,7 @@ impl_runtime_apis! {
         }
     }
 
-    impl pallet_services_payment_runtime_api::ServicesPaymentApi<Block, Balance, ParaId> for Runtime {
+    impl pallet_services_payment_runtime_api::ServicesPaymentApi<Block, Balance, ParaId, xcm_config::AssetId> for Runtime {
         fn block_cost(para_id: ParaId) -> Balance {
             let (block_production_costs, _) = <Runtime as pallet_services_payment::Config>::ProvideBlockProductionCost::block_cost(&para_id);
             block_production_costs
@@ -2709,6 +2991,135 @@ impl_runtime_apis! {
             let (collator_assignment_costs, _) = <Runtime as pallet_services_payment::Config>::ProvideCollatorAssignmentCost::collator_assignment_cost(&para_id);
             collator_assignment_costs
         }
+
+        fn block_cost_in_asset(para_id: ParaId, asset_id: xcm_config::AssetId) -> Option<Balance> {
+            ServicesPayment::block_cost_in_asset(&para_id, &asset_id)
+        }
+
+        fn collator_assignment_cost_in_asset(para_id: ParaId, asset_id: xcm_config::AssetId) -> Option<Balance> {
+            ServicesPayment::collator_assignment_cost_in_asset(&para_id, &asset_id)
+        }
+
+        fn runway(para_id: ParaId) -> pallet_services_payment_runtime_api::TankRunway {
+            let pallet_services_payment::Runway { blocks, sessions } =
+                ServicesPayment::runway(&para_id, Period::get());
+
+            pallet_services_payment_runtime_api::TankRunway { blocks, sessions }
+        }
+
+        fn spend_history(para_id: ParaId) -> Vec<pallet_services_payment_runtime_api::SessionSpend<Balance>> {
+            ServicesPayment::spend_history(para_id)
+                .into_iter()
+                .map(|(session, spend)| pallet_services_payment_runtime_api::SessionSpend {
+                    session,
+                    block_production: spend.block_production,
+                    collator_assignment: spend.collator_assignment,
+                    tips: spend.tips,
+                    cores_purchased: spend.cores_purchased,
+                })
+                .collect()
+        }
+    }
+
+    impl pallet_pooled_staking_runtime_api::PooledStakingApi<Block, AccountId, Balance, SessionIndex>
+    for Runtime {
+        fn candidate_commission(candidate: AccountId) -> Perbill {
+            PooledStaking::candidate_commission(&candidate)
+        }
+
+        fn delegator_candidates(delegator: AccountId) -> Vec<AccountId> {
+            PooledStaking::delegator_candidates(&delegator)
+        }
+
+        fn delegator_stake(
+            candidate: AccountId,
+            delegator: AccountId,
+        ) -> pallet_pooled_staking_runtime_api::DelegatorStake<Balance> {
+            use {
+                pallet_pooled_staking::AllTargetPool,
+                pallet_pooled_staking_runtime_api::{DelegatorStake, PoolStake},
+            };
+
+            let pool_stake = |pool| PoolStake {
+                shares: PooledStaking::shares(candidate.clone(), delegator.clone(), pool),
+                stake: PooledStaking::computed_stake(candidate.clone(), delegator.clone(), pool)
+                    .unwrap_or_default(),
+            };
+
+            DelegatorStake {
+                joining: pool_stake(AllTargetPool::Joining),
+                auto_compounding: pool_stake(AllTargetPool::AutoCompounding),
+                manual_rewards: pool_stake(AllTargetPool::ManualRewards),
+                leaving: pool_stake(AllTargetPool::Leaving),
+                claimable_manual_rewards: PooledStaking::claimable_manual_rewards(
+                    &candidate,
+                    &delegator,
+                )
+                .unwrap_or_default(),
+            }
+        }
+
+        fn delegator_pending_operations(
+            delegator: AccountId,
+        ) -> Vec<pallet_pooled_staking_runtime_api::PendingOperation<AccountId, SessionIndex, Balance>> {
+            use {
+                pallet_pooled_staking::{traits::Timer, PendingOperationKey},
+                pallet_pooled_staking_runtime_api::{PendingOperation, PendingOperationKind},
+            };
+
+            type JoiningTimer = <Runtime as pallet_pooled_staking::Config>::JoiningRequestTimer;
+            type LeavingTimer = <Runtime as pallet_pooled_staking::Config>::LeavingRequestTimer;
+
+            pallet_pooled_staking::PendingOperations::<Runtime>::iter_prefix(&delegator)
+                .map(|(operation, shares)| {
+                    let stake = PooledStaking::pending_operation_stake(&operation, shares)
+                        .unwrap_or_default();
+
+                    let (candidate, kind, at, unlock_at, executable) = match operation {
+                        PendingOperationKey::JoiningAutoCompounding { candidate, at } => (
+                            candidate,
+                            PendingOperationKind::JoiningAutoCompounding,
+                            at,
+                            JoiningTimer::elapsed_at(&at),
+                            JoiningTimer::is_elapsed(&at),
+                        ),
+                        PendingOperationKey::JoiningManualRewards { candidate, at } => (
+                            candidate,
+                            PendingOperationKind::JoiningManualRewards,
+                            at,
+                            JoiningTimer::elapsed_at(&at),
+                            JoiningTimer::is_elapsed(&at),
+                        ),
+                        PendingOperationKey::Leaving { candidate, at } => (
+                            candidate,
+                            PendingOperationKind::Leaving,
+                            at,
+                            LeavingTimer::elapsed_at(&at),
+                            LeavingTimer::is_elapsed(&at),
+                        ),
+                    };
+
+                    PendingOperation {
+                        candidate,
+                        kind,
+                        at,
+                        unlock_at,
+                        executable,
+                        shares,
+                        stake,
+                    }
+                })
+                .collect()
+        }
+
+        fn candidate_stake(
+            candidate: AccountId,
+        ) -> pallet_pooled_staking_runtime_api::CandidateStake<Balance> {
+            pallet_pooled_staking_runtime_api::CandidateStake {
+                total_stake: PooledStaking::candidate_total_stake(&candidate),
+                rank: PooledStaking::candidate_rank(&candidate),
+            }
+        }
     }
 
     impl pallet_xcm_core_buyer_runtime_api::XCMCoreBuyerApi<Block, BlockNumber, ParaId, NimbusId> for Runtime {
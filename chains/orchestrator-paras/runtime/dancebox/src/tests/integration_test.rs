@@ -61,7 +61,8 @@ use {
     std::marker::PhantomData,
     tanssi_runtime_common::migrations::{
         ForeignAssetCreatorMigration, HostConfigurationV3, MigrateConfigurationAddFullRotationMode,
-        MigrateServicesPaymentAddCollatorAssignmentCredits, RegistrarPendingVerificationValueToMap,
+        MigrateServicesPaymentAddCollatorAssignmentCredits, MigrateStreamPaymentAddSchedule,
+        RegistrarPendingVerificationValueToMap, StreamV1,
     },
     test_relay_sproof_builder::{HeaderAs, ParaHeaderSproofBuilder, ParaHeaderSproofBuilderItem},
     tp_traits::{ContainerChainBlockInfo, SlotFrequency},
@@ -4009,6 +4010,52 @@ fn test_migration_config_add_full_rotation_mode() {
     });
 }
 
+#[test]
+fn test_migration_stream_payment_add_schedule() {
+    ExtBuilder::default().build().execute_with(|| {
+        let config = pallet_stream_payment::StreamConfig {
+            time_unit: TimeUnit::BlockNumber,
+            asset_id: StreamPaymentAssetId::Native,
+            rate: 2 * UNIT,
+        };
+        let old_stream = StreamV1 {
+            source: AccountId::from(ALICE),
+            target: AccountId::from(BOB),
+            config: config.clone(),
+            deposit: 1_000 * UNIT,
+            last_time_updated: 10,
+            request_nonce: 3,
+            pending_request: None,
+            opening_deposit: 5 * UNIT,
+        };
+        frame_support::storage::unhashed::put_raw(
+            &pallet_stream_payment::Streams::<Runtime>::hashed_key_for(7u64),
+            &old_stream.encode(),
+        );
+
+        let migration = MigrateStreamPaymentAddSchedule::<Runtime>(Default::default());
+        migration.migrate(Default::default());
+
+        let stream = pallet_stream_payment::Streams::<Runtime>::get(7u64)
+            .expect("migrated stream should decode");
+        assert!(stream.schedule.is_unbounded());
+        assert_eq!(
+            stream,
+            pallet_stream_payment::Stream {
+                source: AccountId::from(ALICE),
+                target: AccountId::from(BOB),
+                config,
+                deposit: 1_000 * UNIT,
+                last_time_updated: 10,
+                request_nonce: 3,
+                pending_request: None,
+                opening_deposit: 5 * UNIT,
+                schedule: Default::default(),
+            }
+        );
+    });
+}
+
 #[test]
 fn test_migration_registrar_pending_verification() {
     ExtBuilder::default().build().execute_with(|| {
@@ -5444,6 +5491,116 @@ fn test_block_credits_and_collator_assignation_credits_through_tank() {
         });
 }
 
+#[test]
+fn test_block_credits_and_collator_assignation_credits_through_tank_in_foreign_asset() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            // Alice gets 10k extra tokens for her mapping deposit
+            (AccountId::from(ALICE), 210_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+            (AccountId::from(CHARLIE), 100_000 * UNIT),
+            (AccountId::from(DAVE), 100_000 * UNIT),
+        ])
+        .with_collators(vec![
+            (AccountId::from(ALICE), 210 * UNIT),
+            (AccountId::from(BOB), 100 * UNIT),
+            (AccountId::from(CHARLIE), 100 * UNIT),
+            (AccountId::from(DAVE), 100 * UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+
+            assert_ok!(Registrar::register(
+                origin_of(ALICE.into()),
+                1001.into(),
+                empty_genesis_data(),
+                None
+            ));
+            set_dummy_boot_node(origin_of(ALICE.into()), 1001.into());
+            assert_ok!(Registrar::mark_valid_for_collating(
+                root_origin(),
+                1001.into()
+            ));
+
+            // We make all free credits 0
+            assert_ok!(ServicesPayment::set_collator_assignment_credits(
+                root_origin(),
+                1001.into(),
+                0
+            ));
+            assert_ok!(ServicesPayment::set_block_production_credits(
+                root_origin(),
+                1001.into(),
+                0
+            ));
+
+            // A foreign asset worth half the native token
+            assert_ok!(ForeignAssetsCreator::create_foreign_asset(
+                root_origin(),
+                Location::parent(),
+                1,
+                AccountId::from(ALICE),
+                true,
+                1
+            ));
+            assert_ok!(AssetRate::create(
+                root_origin(),
+                Box::new(1),
+                FixedU128::from_u32(2)
+            ));
+            assert_ok!(ServicesPayment::set_payment_asset(
+                root_origin(),
+                1001.into(),
+                Some(1)
+            ));
+
+            // We buy 2 sessions through tank
+            let collator_assignation_credits =
+                collator_assignment_credits_to_required_balance(2, 1001.into());
+            let block_production_credits =
+                block_credits_to_required_balance(crate::Period::get() * 2, 1001.into());
+            let asset_credits = (collator_assignation_credits + block_production_credits) * 2;
+            assert_ok!(ForeignAssets::mint(
+                origin_of(ALICE.into()),
+                1,
+                AccountId::from(ALICE).into(),
+                asset_credits * 2
+            ));
+
+            // Fill the tank, keeping the min balance of the asset
+            assert_ok!(ServicesPayment::purchase_credits_in_asset(
+                origin_of(ALICE.into()),
+                1001.into(),
+                1,
+                asset_credits + 1
+            ));
+
+            // Assignment should happen after 2 sessions
+            run_to_session(1u32);
+            let assignment = CollatorAssignment::collator_container_chain();
+            assert!(assignment.container_chains.is_empty());
+            run_to_session(2u32);
+            // Charlie and Dave should be assigned to para 1001
+            let assignment = CollatorAssignment::collator_container_chain();
+            assert_eq!(
+                assignment.container_chains[&1001u32.into()],
+                vec![CHARLIE.into(), DAVE.into()]
+            );
+            // Nothing was paid in the native currency
+            assert_eq!(
+                Balances::free_balance(ServicesPayment::parachain_tank(1001.into())),
+                0
+            );
+
+            // After this it should not be assigned anymore, since credits are not payable
+            run_to_session(4u32);
+            // Nobody should be assigned to para 1001
+            let assignment = CollatorAssignment::collator_container_chain();
+            assert_eq!(assignment.container_chains.get(&1001u32.into()), None,);
+        });
+}
+
 #[test]
 fn test_migration_services_collator_assignment_payment() {
     ExtBuilder::default().build().execute_with(|| {
@@ -176,4 +176,52 @@ impl<T: frame_system::Config> pallet_stream_payment::WeightInfo for SubstrateWei
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `StreamPayment::NextStreamId` (r:1 w:1)
+	/// Proof: `StreamPayment::NextStreamId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(229), added: 2704, mode: `MaxEncodedLen`)
+	/// Storage: `StreamPayment::LookupStreamsWithTarget` (r:0 w:5)
+	/// Proof: `StreamPayment::LookupStreamsWithTarget` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StreamPayment::StreamSplits` (r:0 w:1)
+	/// Proof: `StreamPayment::StreamSplits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StreamPayment::LookupStreamsWithSource` (r:0 w:1)
+	/// Proof: `StreamPayment::LookupStreamsWithSource` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StreamPayment::Streams` (r:0 w:1)
+	/// Proof: `StreamPayment::Streams` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `s` is `[0, 4]`.
+	fn open_scheduled_stream(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `107`
+		//  Estimated: `3694`
+		// Minimum execution time: 50_127_000 picoseconds.
+		Weight::from_parts(57_316_442, 3694)
+			// Standard Error: 9_807
+			.saturating_add(Weight::from_parts(1_802_517, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+	}
+	/// Storage: `StreamPayment::StreamSplits` (r:1 w:0)
+	/// Proof: `StreamPayment::StreamSplits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:4 w:4)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(229), added: 2704, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 4]`.
+	fn pay_splits(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `423 + s * (71 ±0)`
+		//  Estimated: `3694 + s * (2603 ±0)`
+		// Minimum execution time: 9_581_000 picoseconds.
+		Weight::from_parts(11_205_870, 3694)
+			// Standard Error: 21_332
+			.saturating_add(Weight::from_parts(62_450_314, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(s.into()))
+	}
 }
\ No newline at end of file
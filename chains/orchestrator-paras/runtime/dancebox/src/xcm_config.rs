@@ -42,7 +42,7 @@ use {
     pallet_xcm::XcmPassthrough,
     pallet_xcm_core_buyer::{
         CheckCollatorValidity, GetParathreadMaxCorePrice, GetParathreadParams, GetPurchaseCoreCall,
-        ParaIdIntoAccountTruncating, XCMNotifier,
+        OnCorePurchased, ParaIdIntoAccountTruncating, XCMNotifier,
     },
     parachains_common::message_queue::{NarrowOriginToSibling, ParaIdToSibling},
     parity_scale_codec::{Decode, Encode},
@@ -456,6 +456,7 @@ impl pallet_xcm_core_buyer::Config for Runtime {
     type GetPurchaseCoreCall = EncodedCallToBuyCore;
     type GetParathreadAccountId = ParaIdIntoAccountTruncating;
     type GetParathreadMaxCorePrice = GetMaxCorePriceFromServicesPayment;
+    type OnCorePurchased = RecordCorePurchaseInServicesPayment;
     type SelfParaId = parachain_info::Pallet<Runtime>;
     type RelayChain = RelayChain;
     type GetParathreadParams = GetParathreadParamsImpl;
@@ -500,6 +501,10 @@ impl CheckCollatorValidity<AccountId, NimbusId> for CheckCollatorValidityImpl {
         maybe_public_keys.is_some_and(|public_keys| public_keys.contains(&public_key))
     }
 
+    fn num_collators(para_id: ParaId) -> u32 {
+        get_para_id_authorities(para_id).map_or(0, |public_keys| public_keys.len() as u32)
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn set_valid_collator(para_id: ParaId, account_id: AccountId, public_key: NimbusId) {
         let parent_number = System::block_number();
@@ -568,3 +573,11 @@ impl GetParathreadMaxCorePrice for GetMaxCorePriceFromServicesPayment {
         pallet_services_payment::MaxCorePrice::<Runtime>::get(para_id)
     }
 }
+
+pub struct RecordCorePurchaseInServicesPayment;
+
+impl OnCorePurchased for RecordCorePurchaseInServicesPayment {
+    fn on_core_purchased(para_id: ParaId) {
+        pallet_services_payment::Pallet::<Runtime>::record_core_purchase(&para_id);
+    }
+}
@@ -23,7 +23,6 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use frame_support::storage::{with_storage_layer, with_transaction};
-use frame_support::traits::{ExistenceRequirement, WithdrawReasons};
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
 use {
@@ -80,7 +79,9 @@ use {
     pallet_registrar_runtime_api::ContainerChainGenesisData,
     pallet_services_payment::ProvideBlockProductionCost,
     pallet_session::{SessionManager, ShouldEndSession},
-    pallet_stream_payment_runtime_api::{StreamPaymentApiError, StreamPaymentApiStatus},
+    pallet_stream_payment_runtime_api::{
+        StreamPaymentApiError, StreamPaymentApiEvent, StreamPaymentApiParty, StreamPaymentApiStatus,
+    },
     pallet_transaction_payment::FungibleAdapter,
     polkadot_runtime_common::BlockHashCount,
     scale_info::prelude::format,
@@ -103,8 +104,9 @@ use {
     sp_version::RuntimeVersion,
     tp_traits::{
         apply, derive_storage_traits, GetContainerChainAuthor, GetHostConfiguration,
-        GetSessionContainerChains, MaybeSelfChainBlockAuthor, ParaIdAssignmentHooks,
-        RelayStorageRootProvider, RemoveInvulnerables, ShouldRotateAllCollators,
+        GetSessionContainerChains, IsCollatorRegistered, MaybeSelfChainBlockAuthor,
+        ParaIdAssignmentHooks, RelayStorageRootProvider, RemoveInvulnerables,
+        ShouldRotateAllCollators,
     },
 };
 pub use {
@@ -547,6 +549,13 @@ impl pallet_initializer::ApplyNewSession<Runtime> for OwnApplySession {
         // Next: AuthorityMapping
         AuthorityMapping::initializer_on_new_session(&session_index, &all_validators);
 
+        // Next: ServicesPayment, priced by the share of collators assigned in the previous
+        // session. It must run before the collator assignment, which charges for the new one.
+        ServicesPayment::initializer_on_new_session(
+            pallet_collator_assignment::CollatorFullnessRatio::<Runtime>::get().unwrap_or_default(),
+            Configuration::config().target_container_chain_fullness,
+        );
+
         let next_collators = queued.iter().map(|(k, _)| k.clone()).collect();
 
         // Next: CollatorAssignment
@@ -661,21 +670,29 @@ impl ParaIdAssignmentHooksImpl {
         para_id: ParaId,
         currently_assigned: &BTreeSet<ParaId>,
         maybe_tip: &Option<BalanceOf<Runtime>>,
+        maybe_num_collators: Option<u32>,
     ) -> Result<Weight, DispatchError> {
         use frame_support::traits::Currency;
-        type ServicePaymentCurrency = <Runtime as pallet_services_payment::Config>::Currency;
+
+        let mut weight = Weight::zero();
 
         // Check if the container chain has enough credits for a session assignments
         let maybe_assignment_imbalance =
             if  pallet_services_payment::Pallet::<Runtime>::burn_collator_assignment_free_credit_for_para(&para_id).is_err() {
-                let (amount_to_charge, _weight) =
-                    <Runtime as pallet_services_payment::Config>::ProvideCollatorAssignmentCost::collator_assignment_cost(&para_id);
-                Some(<ServicePaymentCurrency as Currency<AccountId>>::withdraw(
-                    &pallet_services_payment::Pallet::<Runtime>::parachain_tank(para_id),
+                // Chains are charged for the collators actually assigned, or estimated before
+                // the assignment is known
+                let (amount_to_charge, cost_weight) = match maybe_num_collators {
+                    Some(num_collators) => {
+                        CollatorAssignmentCost::cost_for_collators(&para_id, num_collators, 1)
+                    }
+                    None => <Runtime as pallet_services_payment::Config>::ProvideCollatorAssignmentCost::collator_assignment_cost(&para_id),
+                };
+                weight.saturating_accrue(cost_weight);
+                pallet_services_payment::Pallet::<Runtime>::charge_fee(
+                    &para_id,
                     amount_to_charge,
-                    WithdrawReasons::FEE,
-                    ExistenceRequirement::KeepAlive,
-                )?)
+                    pallet_services_payment::PaidService::CollatorAssignment,
+                )?
             } else {
                 None
             };
@@ -725,7 +742,7 @@ impl ParaIdAssignmentHooksImpl {
         )
         .into_result(true)?;
         // TODO: Have proper weight
-        Ok(Weight::zero())
+        Ok(weight)
     }
 }
 
@@ -741,6 +758,7 @@ impl<AC> ParaIdAssignmentHooks<BalanceOf<Runtime>, AC> for ParaIdAssignmentHooks
                     *para_id,
                     currently_assigned,
                     &Some(max_tip),
+                    None,
                 ))
             })
             .is_ok()
@@ -750,6 +768,7 @@ impl<AC> ParaIdAssignmentHooks<BalanceOf<Runtime>, AC> for ParaIdAssignmentHooks
     fn post_assignment(
         current_assigned: &BTreeSet<ParaId>,
         new_assigned: &mut BTreeMap<ParaId, Vec<AC>>,
+        _parachain_cores: &BTreeMap<ParaId, u32>,
         maybe_tip: &Option<BalanceOf<Runtime>>,
     ) -> Weight {
         let blocks_per_session = Period::get();
@@ -765,6 +784,7 @@ impl<AC> ParaIdAssignmentHooks<BalanceOf<Runtime>, AC> for ParaIdAssignmentHooks
                     para_id,
                     current_assigned,
                     maybe_tip,
+                    Some(collators.len() as u32),
                 )
             })
             .inspect(|weight| {
@@ -800,6 +820,25 @@ impl<AC> ParaIdAssignmentHooks<BalanceOf<Runtime>, AC> for ParaIdAssignmentHooks
     }
 }
 
+pub struct CollatorHasRegisteredKeys;
+impl IsCollatorRegistered<AccountId> for CollatorHasRegisteredKeys {
+    fn is_collator_registered(a: &AccountId) -> bool {
+        <Session as frame_support::traits::ValidatorRegistration<AccountId>>::is_registered(a)
+    }
+    #[cfg(feature = "runtime-benchmarks")]
+    fn register_collator(a: &AccountId) {
+        use sp_core::crypto::UncheckedFrom;
+        let account_slice: &[u8; 32] = a.as_ref();
+        let _ = Session::set_keys(
+            RuntimeOrigin::signed(a.clone()),
+            SessionKeys {
+                nimbus: NimbusId::unchecked_from(*account_slice),
+            },
+            vec![],
+        );
+    }
+}
+
 pub struct NeverRotateCollators;
 
 impl ShouldRotateAllCollators<u32> for NeverRotateCollators {
@@ -808,6 +847,17 @@ impl ShouldRotateAllCollators<u32> for NeverRotateCollators {
     }
 }
 
+parameter_types! {
+    /// Collators producing less than half of the blocks they were expected to produce are
+    /// assigned to new chains last.
+    pub const MinLivenessScore: Perbill = Perbill::from_percent(50);
+    /// Container chains have the same slot duration as this chain, so a session has as many
+    /// container chain slots as blocks.
+    pub const ContainerSlotsPerSession: u32 = Period::get();
+    /// Number of past sessions whose collator assignment can be queried.
+    pub const AssignmentHistoryDepth: u32 = 168;
+}
+
 impl pallet_collator_assignment::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type HostConfiguration = Configuration;
@@ -822,6 +872,10 @@ impl pallet_collator_assignment::Config for Runtime {
     type Currency = Balances;
     type ForceEmptyOrchestrator = ConstBool<false>;
     type CoreAllocationConfiguration = ();
+    type CollatorRegistration = CollatorHasRegisteredKeys;
+    type MinLivenessScore = MinLivenessScore;
+    type ContainerSlotsPerSession = ContainerSlotsPerSession;
+    type AssignmentHistoryDepth = AssignmentHistoryDepth;
     type WeightInfo = weights::pallet_collator_assignment::SubstrateWeight<Runtime>;
 }
 
@@ -832,21 +886,33 @@ impl pallet_authority_assignment::Config for Runtime {
 
 pub const FIXED_BLOCK_PRODUCTION_COST: u128 = 1 * currency::MICRODANCE;
 pub const FIXED_COLLATOR_ASSIGNMENT_COST: u128 = 100 * currency::MICRODANCE;
+pub const EXTRA_COLLATOR_ASSIGNMENT_COST: u128 = 50 * currency::MICRODANCE;
+
+parameter_types! {
+    /// Services get at most 10% more expensive, or cheaper, each session
+    pub const ServicesPriceAdjustmentRate: Perbill = Perbill::from_percent(10);
+}
 
 pub struct BlockProductionCost<Runtime>(PhantomData<Runtime>);
 impl ProvideBlockProductionCost<Runtime> for BlockProductionCost<Runtime> {
     fn block_cost(_para_id: &ParaId) -> (u128, Weight) {
-        (FIXED_BLOCK_PRODUCTION_COST, Weight::zero())
-    }
-}
+        use sp_runtime::FixedPointNumber;
 
-pub struct CollatorAssignmentCost<Runtime>(PhantomData<Runtime>);
-impl ProvideCollatorAssignmentCost<Runtime> for CollatorAssignmentCost<Runtime> {
-    fn collator_assignment_cost(_para_id: &ParaId) -> (u128, Weight) {
-        (FIXED_COLLATOR_ASSIGNMENT_COST, Weight::zero())
+        (
+            ServicesPayment::price_multiplier().saturating_mul_int(FIXED_BLOCK_PRODUCTION_COST),
+            // Price multiplier
+            <Runtime as frame_system::Config>::DbWeight::get().reads(1),
+        )
     }
 }
 
+pub type CollatorAssignmentCost = tanssi_runtime_common::services_payment::CollatorAssignmentCost<
+    Runtime,
+    ConstU128<FIXED_COLLATOR_ASSIGNMENT_COST>,
+    ConstU128<EXTRA_COLLATOR_ASSIGNMENT_COST>,
+    ConstBool<false>,
+>;
+
 parameter_types! {
     // 60 days worth of blocks
     pub const FreeBlockProductionCredits: BlockNumber = 60 * DAYS;
@@ -865,13 +931,21 @@ impl pallet_services_payment::Config for Runtime {
     /// Provider of a block cost which can adjust from block to block
     type ProvideBlockProductionCost = BlockProductionCost<Runtime>;
     /// Provider of a block cost which can adjust from block to block
-    type ProvideCollatorAssignmentCost = CollatorAssignmentCost<Runtime>;
+    type ProvideCollatorAssignmentCost = CollatorAssignmentCost;
     /// The maximum number of block credits that can be accumulated
     type FreeBlockProductionCredits = FreeBlockProductionCredits;
     /// The maximum number of session credits that can be accumulated
     type FreeCollatorAssignmentCredits = FreeCollatorAssignmentCredits;
     type ManagerOrigin =
         EitherOfDiverse<pallet_registrar::EnsureSignedByManager<Runtime>, EnsureRoot<AccountId>>;
+    /// Services can only be paid in the native currency
+    type AssetId = u16;
+    type PaymentAssets = ();
+    type CurrentSessionIndex = CurrentSessionIndexGetter;
+    type SpendHistoryLength = ConstU32<30>;
+    type PriceAdjustmentRate = ServicesPriceAdjustmentRate;
+    type InactiveParas = tanssi_runtime_common::services_payment::PausedOrWithoutCollators<Runtime>;
+    type TankReserveBlocks = ConstU32<10>;
     type WeightInfo = weights::pallet_services_payment::SubstrateWeight<Runtime>;
 }
 
@@ -957,7 +1031,11 @@ impl pallet_data_preservers::AssignmentPayment<AccountId> for PreserversAssignem
         match witness {
             Self::AssignmentWitness::Free => (),
             Self::AssignmentWitness::StreamPayment { stream_id } => {
-                StreamPayment::close_stream(RuntimeOrigin::signed(provider), stream_id)?;
+                // The stream is closed automatically once it can no longer pay, in which
+                // case there is nothing left to close.
+                if pallet_stream_payment::Streams::<Runtime>::contains_key(stream_id) {
+                    StreamPayment::close_stream(RuntimeOrigin::signed(provider), stream_id)?;
+                }
             }
         }
 
@@ -1020,7 +1098,7 @@ impl pallet_author_noting::Config for Runtime {
     type ContainerChains = Registrar;
     type SlotBeacon = dp_consensus::AuraDigestSlotBeacon<Runtime>;
     type ContainerChainAuthor = CollatorAssignment;
-    type AuthorNotingHook = (InflationRewards, ServicesPayment);
+    type AuthorNotingHook = (InflationRewards, ServicesPayment, CollatorAssignment);
     type RelayOrPara = pallet_author_noting::ParaMode<
         cumulus_pallet_parachain_system::RelaychainDataProvider<Self>,
     >;
@@ -1064,10 +1142,12 @@ impl tp_traits::GetSessionIndex<u32> for CurrentSessionIndexGetter {
 }
 
 impl pallet_configuration::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
     type SessionDelay = ConstU32<2>;
     type SessionIndex = u32;
     type CurrentSessionIndex = CurrentSessionIndexGetter;
     type ForceEmptyOrchestrator = ConstBool<false>;
+    type MaxPendingConfigPatches = ConstU32<10>;
     type WeightInfo = weights::pallet_configuration::SubstrateWeight<Runtime>;
 }
 
@@ -1200,6 +1280,8 @@ impl pallet_registrar::Config for Runtime {
     type RegistrarHooks = FlashboxRegistrarHooks;
     type RuntimeHoldReason = RuntimeHoldReason;
     type InnerRegistrar = ();
+    type MaxRequestedCollators = ConstU32<10>;
+    type MaxRequestedCores = ConstU32<4>;
     type WeightInfo = weights::pallet_registrar::SubstrateWeight<Runtime>;
 }
 
@@ -1575,8 +1657,11 @@ impl pallet_stream_payment::TimeProvider<TimeUnit, Balance> for TimeProvider {
 type StreamId = u64;
 
 parameter_types! {
-    // 1 entry, storing 173 bytes on-chain
-    pub const OpenStreamHoldAmount: Balance = currency::deposit(1, 173);
+    // 1 entry, storing 207 bytes on-chain
+    pub const OpenStreamHoldAmount: Balance = currency::deposit(1, 207);
+    // 1 target lookup entry and 1 split, storing 108 bytes on-chain
+    pub const OpenStreamSplitHoldAmount: Balance = currency::deposit(1, 108);
+    pub const MaxStreamSplits: u32 = 4;
 }
 
 impl pallet_stream_payment::Config for Runtime {
@@ -1588,8 +1673,11 @@ impl pallet_stream_payment::Config for Runtime {
     type Assets = StreamPaymentAssets;
     type Currency = Balances;
     type OpenStreamHoldAmount = OpenStreamHoldAmount;
+    type OpenStreamSplitHoldAmount = OpenStreamSplitHoldAmount;
     type RuntimeHoldReason = RuntimeHoldReason;
     type TimeProvider = TimeProvider;
+    type MaxStreamSplits = MaxStreamSplits;
+    type AutoSettlementScanLimit = ConstU32<20>;
     type WeightInfo = weights::pallet_stream_payment::SubstrateWeight<Runtime>;
 }
 
@@ -1986,7 +2074,8 @@ impl_runtime_apis! {
         }
     }
 
-    impl pallet_collator_assignment_runtime_api::CollatorAssignmentApi<Block, AccountId, ParaId> for Runtime {
+    impl pallet_collator_assignment_runtime_api::CollatorAssignmentApi<Block, AccountId, ParaId, pallet_configuration::HostConfiguration>
+    for Runtime {
         /// Return the parachain that the given `AccountId` is collating for.
         /// Returns `None` if the `AccountId` is not collating.
         fn current_collator_parachain_assignment(account: AccountId) -> Option<ParaId> {
@@ -2027,6 +2116,76 @@ impl_runtime_apis! {
                 assigned_collators.container_chains.get(&para_id).cloned()
             }
         }
+
+        /// Simulate the assignment of the next session change as if the given host configuration
+        /// and collators were used, without modifying the storage.
+        fn simulate_collator_assignment(
+            config: pallet_configuration::HostConfiguration,
+            collators: Vec<AccountId>,
+        ) -> Option<pallet_collator_assignment_runtime_api::CollatorAssignmentSimulation<AccountId, ParaId>> {
+            let config = pallet_collator_assignment::AssignmentHostConfiguration {
+                max_collators: config.max_collators,
+                min_orchestrator_collators: config.min_orchestrator_collators,
+                max_orchestrator_collators: config.max_orchestrator_collators,
+                collators_per_container: config.collators_per_container,
+                collators_per_parathread: config.collators_per_parathread,
+                max_parachain_cores_percentage: config.max_parachain_cores_percentage,
+                full_rotation_mode: config.full_rotation_mode,
+            };
+            let simulation = CollatorAssignment::simulate_assignment(
+                Session::current_index() + 1,
+                config,
+                collators,
+            )
+            .ok()?;
+
+            Some(pallet_collator_assignment_runtime_api::CollatorAssignmentSimulation {
+                assignment: simulation.assignment,
+                dropped_chains: simulation.dropped_chains,
+                tips_charged: simulation.tips_charged,
+            })
+        }
+
+        /// Return the liveness score of the given `AccountId` in the last session.
+        fn collator_liveness(
+            account: AccountId,
+        ) -> Option<pallet_collator_assignment_runtime_api::CollatorLiveness> {
+            pallet_collator_assignment::LivenessScores::<Runtime>::get(account).map(|score| {
+                pallet_collator_assignment_runtime_api::CollatorLiveness {
+                    blocks_produced: score.blocks_produced,
+                    slots_expected: score.slots_expected,
+                }
+            })
+        }
+
+        /// Return the collators assigned to the given `ParaId` in the given session.
+        fn chain_assignment_at_session(
+            session_index: u32,
+            para_id: ParaId,
+        ) -> Option<pallet_collator_assignment_runtime_api::ChainAssignmentRecord<AccountId>> {
+            use pallet_collator_assignment_runtime_api::DroppedChainReason;
+
+            let (collators, dropped_reason) =
+                CollatorAssignment::chain_assignment_at_session(session_index, para_id)?;
+
+            Some(pallet_collator_assignment_runtime_api::ChainAssignmentRecord {
+                collators,
+                dropped_reason: dropped_reason.map(|reason| match reason {
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughCredits => {
+                        DroppedChainReason::NotEnoughCredits
+                    }
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughCores => {
+                        DroppedChainReason::NotEnoughCores
+                    }
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughCollators => {
+                        DroppedChainReason::NotEnoughCollators
+                    }
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughEligibleCollators => {
+                        DroppedChainReason::NotEnoughEligibleCollators
+                    }
+                }),
+            })
+        }
     }
 
     impl pallet_registrar_runtime_api::RegistrarApi<Block, ParaId> for Runtime {
@@ -2158,7 +2317,7 @@ impl_runtime_apis! {
         }
     }
 
-    impl pallet_stream_payment_runtime_api::StreamPaymentApi<Block, StreamId, Balance, Balance>
+    impl pallet_stream_payment_runtime_api::StreamPaymentApi<Block, StreamId, Balance, Balance, AccountId>
     for Runtime {
         fn stream_payment_status(
             stream_id: StreamId,
@@ -2166,15 +2325,33 @@ impl_runtime_apis! {
         ) -> Result<StreamPaymentApiStatus<Balance>, StreamPaymentApiError> {
             match StreamPayment::stream_payment_status(stream_id, now) {
                 Ok(pallet_stream_payment::StreamPaymentStatus {
-                    payment, deposit_left, stalled
+                    payment, deposit_left, stalled, started, ended
                 }) => Ok(StreamPaymentApiStatus {
-                    payment, deposit_left, stalled
+                    payment, deposit_left, stalled, started, ended
                 }),
                 Err(pallet_stream_payment::Error::<Runtime>::UnknownStreamId)
                 => Err(StreamPaymentApiError::UnknownStreamId),
                 Err(e) => Err(StreamPaymentApiError::Other(format!("{e:?}")))
             }
         }
+
+        fn account_streams(
+            account: AccountId,
+            party: StreamPaymentApiParty,
+            start_after: Option<StreamId>,
+            limit: u32,
+        ) -> Vec<StreamId> {
+            let party = match party {
+                StreamPaymentApiParty::Source => pallet_stream_payment::Party::Source,
+                StreamPaymentApiParty::Target => pallet_stream_payment::Party::Target,
+            };
+
+            StreamPayment::account_streams(&account, party, start_after, limit)
+        }
+
+        fn stream_events() -> Vec<StreamPaymentApiEvent<AccountId, StreamId, Balance>> {
+            StreamPayment::api_events()
+        }
     }
 
     impl pallet_data_preservers_runtime_api::DataPreserversApi<Block, DataPreserversProfileId, ParaId> for Runtime {
@@ -2224,7 +2401,7 @@ impl_runtime_apis! {
         }
     }
 
-    impl pallet_services_payment_runtime_api::ServicesPaymentApi<Block, Balance, ParaId> for Runtime {
+    impl pallet_services_payment_runtime_api::ServicesPaymentApi<Block, Balance, ParaId, u16> for Runtime {
         fn block_cost(para_id: ParaId) -> Balance {
             let (block_production_costs, _) = <Runtime as pallet_services_payment::Config>::ProvideBlockProductionCost::block_cost(&para_id);
             block_production_costs
@@ -2234,6 +2411,34 @@ impl_runtime_apis! {
             let (collator_assignment_costs, _) = <Runtime as pallet_services_payment::Config>::ProvideCollatorAssignmentCost::collator_assignment_cost(&para_id);
             collator_assignment_costs
         }
+
+        fn block_cost_in_asset(para_id: ParaId, asset_id: u16) -> Option<Balance> {
+            ServicesPayment::block_cost_in_asset(&para_id, &asset_id)
+        }
+
+        fn collator_assignment_cost_in_asset(para_id: ParaId, asset_id: u16) -> Option<Balance> {
+            ServicesPayment::collator_assignment_cost_in_asset(&para_id, &asset_id)
+        }
+
+        fn runway(para_id: ParaId) -> pallet_services_payment_runtime_api::TankRunway {
+            let pallet_services_payment::Runway { blocks, sessions } =
+                ServicesPayment::runway(&para_id, Period::get());
+
+            pallet_services_payment_runtime_api::TankRunway { blocks, sessions }
+        }
+
+        fn spend_history(para_id: ParaId) -> Vec<pallet_services_payment_runtime_api::SessionSpend<Balance>> {
+            ServicesPayment::spend_history(para_id)
+                .into_iter()
+                .map(|(session, spend)| pallet_services_payment_runtime_api::SessionSpend {
+                    session,
+                    block_production: spend.block_production,
+                    collator_assignment: spend.collator_assignment,
+                    tips: spend.tips,
+                    cores_purchased: spend.cores_purchased,
+                })
+                .collect()
+        }
     }
 }
 
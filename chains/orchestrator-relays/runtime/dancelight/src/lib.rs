@@ -34,8 +34,11 @@ use {
         dispatch::{DispatchErrorWithPostInfo, DispatchResult},
         dynamic_params::{dynamic_pallet_params, dynamic_params},
         traits::{
-            fungible::Inspect,
-            tokens::{PayFromAccount, UnityAssetBalanceConversion},
+            fungible::{Inspect, InspectHold, Mutate, MutateHold},
+            tokens::{
+                imbalance::ResolveTo, PayFromAccount, Precision, Preservation,
+                UnityAssetBalanceConversion,
+            },
             ConstBool, Contains, EverythingBut,
         },
     },
@@ -47,6 +50,9 @@ use {
     pallet_registrar::Error as ContainerRegistrarError,
     pallet_registrar_runtime_api::ContainerChainGenesisData,
     pallet_services_payment::{ProvideBlockProductionCost, ProvideCollatorAssignmentCost},
+    pallet_stream_payment_runtime_api::{
+        StreamPaymentApiError, StreamPaymentApiEvent, StreamPaymentApiParty, StreamPaymentApiStatus,
+    },
     parachains_scheduler::common::Assignment,
     parity_scale_codec::{Decode, Encode, MaxEncodedLen},
     primitives::{
@@ -76,7 +82,7 @@ use {
         scheduler as parachains_scheduler, session_info as parachains_session_info,
         shared as parachains_shared,
     },
-    scale_info::TypeInfo,
+    scale_info::{prelude::format, TypeInfo},
     serde::{Deserialize, Serialize},
     snowbridge_core::{
         outbound::{Command, Fee},
@@ -97,7 +103,7 @@ use {
     },
     tp_traits::{
         apply, derive_storage_traits, EraIndex, GetHostConfiguration, GetSessionContainerChains,
-        ParaIdAssignmentHooks, RegistrarHandler, Slot, SlotFrequency,
+        IsCollatorRegistered, ParaIdAssignmentHooks, RegistrarHandler, Slot, SlotFrequency,
     },
 };
 
@@ -125,7 +131,7 @@ use {
     sp_runtime::{
         create_runtime_str, generic, impl_opaque_keys,
         traits::{
-            AccountIdConversion, BlakeTwo256, Block as BlockT, ConstU32, Convert,
+            AccountIdConversion, BlakeTwo256, Block as BlockT, ConstU128, ConstU32, Convert,
             Extrinsic as ExtrinsicT, Hash as HashT, IdentityLookup, Keccak256, OpaqueKeys,
             SaturatedConversion, Verify, Zero,
         },
@@ -618,9 +624,7 @@ pub struct TreasuryBenchmarkHelper<T>(PhantomData<T>);
 
 #[cfg(feature = "runtime-benchmarks")]
 use frame_support::traits::Currency;
-use frame_support::traits::{
-    ExistenceRequirement, OnUnbalanced, ValidatorRegistration, WithdrawReasons,
-};
+use frame_support::traits::{OnUnbalanced, ValidatorRegistration};
 use pallet_services_payment::BalanceOf;
 #[cfg(feature = "runtime-benchmarks")]
 use pallet_treasury::ArgumentsFactory;
@@ -1110,15 +1114,15 @@ impl parachains_scheduler::common::AssignmentProvider<BlockNumberFor<Runtime>>
 {
     fn pop_assignment_for_core(core_idx: CoreIndex) -> Option<Assignment> {
         let assigned_collators = TanssiCollatorAssignment::collator_container_chain();
+        let session_index = Session::current_index();
+        // Parachains with elastic scaling take as many consecutive cores as they were assigned
         let assigned_paras: Vec<ParaId> = assigned_collators
             .container_chains
             .iter()
-            .filter_map(|(&para_id, _)| {
-                if Paras::is_parachain(para_id) {
-                    Some(para_id)
-                } else {
-                    None
-                }
+            .filter(|(&para_id, _)| Paras::is_parachain(para_id))
+            .flat_map(|(&para_id, _)| {
+                let cores = TanssiCollatorAssignment::parachain_cores(session_index, para_id);
+                sp_std::iter::repeat(para_id).take(cores as usize)
             })
             .collect();
         log::debug!("pop assigned collators {:?}", assigned_paras);
@@ -1152,9 +1156,13 @@ impl parachains_scheduler::common::AssignmentProvider<BlockNumberFor<Runtime>>
             Assignment::Pool {
                 para_id,
                 core_index,
-            } => parachains_assigner_on_demand::Pallet::<Runtime>::report_processed(
-                para_id, core_index,
-            ),
+            } => {
+                parachains_assigner_on_demand::Pallet::<Runtime>::report_processed(
+                    para_id, core_index,
+                );
+                // The parathread used a core bought with an on-demand order
+                ServicesPayment::record_core_purchase(&para_id);
+            }
             Assignment::Bulk(_) => {}
         }
     }
@@ -1444,6 +1452,8 @@ impl pallet_external_validator_slashes::Config for Runtime {
     type OutboundQueue = tp_bridge::CustomSendMessage<Runtime, GetAggregateMessageOriginTanssi>;
     type TimestampProvider = TimestampProvider;
     type QueuedSlashesProcessedPerBlock = ConstU32<10>;
+    // Offenders that are also pooled staking candidates get their pools slashed
+    type OnSlashApplied = PooledStaking;
     type WeightInfo = weights::pallet_external_validator_slashes::SubstrateWeight<Runtime>;
 }
 
@@ -1497,10 +1507,12 @@ impl tp_traits::GetSessionIndex<SessionIndex> for CurrentSessionIndexGetter {
 }
 
 impl pallet_configuration::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
     type SessionDelay = ConstU32<2>;
     type SessionIndex = SessionIndex;
     type CurrentSessionIndex = CurrentSessionIndexGetter;
     type ForceEmptyOrchestrator = ConstBool<true>;
+    type MaxPendingConfigPatches = ConstU32<10>;
     type WeightInfo = ();
 }
 
@@ -1531,21 +1543,33 @@ impl pallet_multiblock_migrations::Config for Runtime {
 
 pub const FIXED_BLOCK_PRODUCTION_COST: u128 = 1 * MICROUNITS;
 pub const FIXED_COLLATOR_ASSIGNMENT_COST: u128 = 100 * MICROUNITS;
+pub const EXTRA_COLLATOR_ASSIGNMENT_COST: u128 = 50 * MICROUNITS;
+
+parameter_types! {
+    /// Services get at most 10% more expensive, or cheaper, each session
+    pub const ServicesPriceAdjustmentRate: Perbill = Perbill::from_percent(10);
+}
 
 pub struct BlockProductionCost<Runtime>(PhantomData<Runtime>);
 impl ProvideBlockProductionCost<Runtime> for BlockProductionCost<Runtime> {
     fn block_cost(_para_id: &ParaId) -> (u128, Weight) {
-        (FIXED_BLOCK_PRODUCTION_COST, Weight::zero())
-    }
-}
+        use sp_runtime::FixedPointNumber;
 
-pub struct CollatorAssignmentCost<Runtime>(PhantomData<Runtime>);
-impl ProvideCollatorAssignmentCost<Runtime> for CollatorAssignmentCost<Runtime> {
-    fn collator_assignment_cost(_para_id: &ParaId) -> (u128, Weight) {
-        (FIXED_COLLATOR_ASSIGNMENT_COST, Weight::zero())
+        (
+            ServicesPayment::price_multiplier().saturating_mul_int(FIXED_BLOCK_PRODUCTION_COST),
+            // Price multiplier
+            <Runtime as frame_system::Config>::DbWeight::get().reads(1),
+        )
     }
 }
 
+pub type CollatorAssignmentCost = tanssi_runtime_common::services_payment::CollatorAssignmentCost<
+    Runtime,
+    ConstU128<FIXED_COLLATOR_ASSIGNMENT_COST>,
+    ConstU128<EXTRA_COLLATOR_ASSIGNMENT_COST>,
+    ConstBool<true>,
+>;
+
 parameter_types! {
     // 60 days worth of blocks
     pub const FreeBlockProductionCredits: BlockNumber = 60 * DAYS;
@@ -1564,15 +1588,172 @@ impl pallet_services_payment::Config for Runtime {
     /// Provider of a block cost which can adjust from block to block
     type ProvideBlockProductionCost = BlockProductionCost<Runtime>;
     /// Provider of a block cost which can adjust from block to block
-    type ProvideCollatorAssignmentCost = CollatorAssignmentCost<Runtime>;
+    type ProvideCollatorAssignmentCost = CollatorAssignmentCost;
     /// The maximum number of block credits that can be accumulated
     type FreeBlockProductionCredits = FreeBlockProductionCredits;
     /// The maximum number of session credits that can be accumulated
     type FreeCollatorAssignmentCredits = FreeCollatorAssignmentCredits;
     type ManagerOrigin = EnsureRoot<AccountId>;
+    /// Services can only be paid in the native currency
+    type AssetId = u16;
+    type PaymentAssets = ();
+    type CurrentSessionIndex = CurrentSessionIndexGetter;
+    type SpendHistoryLength = ConstU32<30>;
+    type PriceAdjustmentRate = ServicesPriceAdjustmentRate;
+    type InactiveParas = tanssi_runtime_common::services_payment::PausedOrWithoutCollators<Runtime>;
+    type TankReserveBlocks = ConstU32<10>;
     type WeightInfo = weights::pallet_services_payment::SubstrateWeight<Runtime>;
 }
 
+#[apply(derive_storage_traits)]
+#[derive(Copy, Serialize, Deserialize, MaxEncodedLen)]
+pub enum StreamPaymentAssetId {
+    Native,
+}
+
+pub struct StreamPaymentAssets;
+impl pallet_stream_payment::Assets<AccountId, StreamPaymentAssetId, Balance>
+    for StreamPaymentAssets
+{
+    fn transfer_deposit(
+        asset_id: &StreamPaymentAssetId,
+        from: &AccountId,
+        to: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        match asset_id {
+            StreamPaymentAssetId::Native => {
+                // We remove the hold before transfering.
+                Self::decrease_deposit(asset_id, from, amount)?;
+                <Balances as Mutate<AccountId>>::transfer(from, to, amount, Preservation::Preserve)
+                    .map(|_| ())
+            }
+        }
+    }
+
+    fn increase_deposit(
+        asset_id: &StreamPaymentAssetId,
+        account: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        match asset_id {
+            StreamPaymentAssetId::Native => Balances::hold(
+                &pallet_stream_payment::HoldReason::StreamPayment.into(),
+                account,
+                amount,
+            ),
+        }
+    }
+
+    fn decrease_deposit(
+        asset_id: &StreamPaymentAssetId,
+        account: &AccountId,
+        amount: Balance,
+    ) -> DispatchResult {
+        match asset_id {
+            StreamPaymentAssetId::Native => Balances::release(
+                &pallet_stream_payment::HoldReason::StreamPayment.into(),
+                account,
+                amount,
+                Precision::Exact,
+            )
+            .map(|_| ()),
+        }
+    }
+
+    fn get_deposit(asset_id: &StreamPaymentAssetId, account: &AccountId) -> Balance {
+        match asset_id {
+            StreamPaymentAssetId::Native => Balances::balance_on_hold(
+                &pallet_stream_payment::HoldReason::StreamPayment.into(),
+                account,
+            ),
+        }
+    }
+
+    /// Benchmarks: should return the asset id which has the worst performance when interacting
+    /// with it.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_worst_case_asset_id() -> StreamPaymentAssetId {
+        StreamPaymentAssetId::Native
+    }
+
+    /// Benchmarks: should return the another asset id which has the worst performance when interacting
+    /// with it afther `bench_worst_case_asset_id`. This is to benchmark the worst case when changing config
+    /// from one asset to another.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_worst_case_asset_id2() -> StreamPaymentAssetId {
+        StreamPaymentAssetId::Native
+    }
+
+    /// Benchmarks: should set the balance for the asset id returned by `bench_worst_case_asset_id`.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_set_balance(asset_id: &StreamPaymentAssetId, account: &AccountId, amount: Balance) {
+        // only one asset id
+        let StreamPaymentAssetId::Native = asset_id;
+
+        <Balances as Mutate<AccountId>>::set_balance(account, amount);
+    }
+}
+
+#[apply(derive_storage_traits)]
+#[derive(Copy, Serialize, Deserialize, MaxEncodedLen)]
+pub enum TimeUnit {
+    BlockNumber,
+    Timestamp,
+}
+
+pub struct TimeProvider;
+impl pallet_stream_payment::TimeProvider<TimeUnit, Balance> for TimeProvider {
+    fn now(unit: &TimeUnit) -> Option<Balance> {
+        match *unit {
+            TimeUnit::BlockNumber => Some(System::block_number().into()),
+            TimeUnit::Timestamp => Some(Timestamp::get().into()),
+        }
+    }
+
+    /// Benchmarks: should return the time unit which has the worst performance calling
+    /// `TimeProvider::now(unit)` with.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_worst_case_time_unit() -> TimeUnit {
+        // Both BlockNumber and Timestamp cost the same (1 db read), but overriding timestamp
+        // doesn't work well in benches, while block number works fine.
+        TimeUnit::BlockNumber
+    }
+
+    /// Benchmarks: sets the "now" time for time unit returned by `worst_case_time_unit`.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_set_now(instant: Balance) {
+        System::set_block_number(instant as u32)
+    }
+}
+
+type StreamId = u64;
+
+parameter_types! {
+    // 1 entry, storing 207 bytes on-chain
+    pub const OpenStreamHoldAmount: Balance = deposit(1, 207);
+    // 1 target lookup entry and 1 split, storing 108 bytes on-chain
+    pub const OpenStreamSplitHoldAmount: Balance = deposit(1, 108);
+    pub const MaxStreamSplits: u32 = 4;
+}
+
+impl pallet_stream_payment::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type StreamId = StreamId;
+    type TimeUnit = TimeUnit;
+    type Balance = Balance;
+    type AssetId = StreamPaymentAssetId;
+    type Assets = StreamPaymentAssets;
+    type Currency = Balances;
+    type OpenStreamHoldAmount = OpenStreamHoldAmount;
+    type OpenStreamSplitHoldAmount = OpenStreamSplitHoldAmount;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type TimeProvider = TimeProvider;
+    type MaxStreamSplits = MaxStreamSplits;
+    type AutoSettlementScanLimit = ConstU32<20>;
+    type WeightInfo = weights::pallet_stream_payment::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
     pub const ProfileDepositBaseFee: Balance = STORAGE_ITEM_FEE;
     pub const ProfileDepositByteFee: Balance = STORAGE_BYTE_FEE;
@@ -1586,21 +1767,25 @@ parameter_types! {
 #[derive(Copy, Serialize, Deserialize, MaxEncodedLen)]
 pub enum PreserversAssignmentPaymentRequest {
     Free,
-    // TODO: Add Stream Payment (with config)
+    StreamPayment {
+        config: pallet_stream_payment::StreamConfigOf<Runtime>,
+    },
 }
 
 #[apply(derive_storage_traits)]
 #[derive(Copy, Serialize, Deserialize)]
 pub enum PreserversAssignmentPaymentExtra {
     Free,
-    // TODO: Add Stream Payment (with deposit)
+    StreamPayment { initial_deposit: Balance },
 }
 
 #[apply(derive_storage_traits)]
 #[derive(Copy, Serialize, Deserialize, MaxEncodedLen)]
 pub enum PreserversAssignmentPaymentWitness {
     Free,
-    // TODO: Add Stream Payment (with stream id)
+    StreamPayment {
+        stream_id: <Runtime as pallet_stream_payment::Config>::StreamId,
+    },
 }
 
 pub struct PreserversAssignmentPayment;
@@ -1614,8 +1799,8 @@ impl pallet_data_preservers::AssignmentPayment<AccountId> for PreserversAssignme
     type AssignmentWitness = PreserversAssignmentPaymentWitness;
 
     fn try_start_assignment(
-        _assigner: AccountId,
-        _provider: AccountId,
+        assigner: AccountId,
+        provider: AccountId,
         request: &Self::ProviderRequest,
         extra: Self::AssignerParameter,
     ) -> Result<Self::AssignmentWitness, DispatchErrorWithPostInfo> {
@@ -1623,17 +1808,40 @@ impl pallet_data_preservers::AssignmentPayment<AccountId> for PreserversAssignme
             (Self::ProviderRequest::Free, Self::AssignerParameter::Free) => {
                 Self::AssignmentWitness::Free
             }
+            (
+                Self::ProviderRequest::StreamPayment { config },
+                Self::AssignerParameter::StreamPayment { initial_deposit },
+            ) => {
+                let stream_id = StreamPayment::open_stream_returns_id(
+                    assigner,
+                    provider,
+                    *config,
+                    initial_deposit,
+                )?;
+
+                Self::AssignmentWitness::StreamPayment { stream_id }
+            }
+            _ => Err(
+                pallet_data_preservers::Error::<Runtime>::AssignmentPaymentRequestParameterMismatch,
+            )?,
         };
 
         Ok(witness)
     }
 
     fn try_stop_assignment(
-        _provider: AccountId,
+        provider: AccountId,
         witness: Self::AssignmentWitness,
     ) -> Result<(), DispatchErrorWithPostInfo> {
         match witness {
             Self::AssignmentWitness::Free => (),
+            Self::AssignmentWitness::StreamPayment { stream_id } => {
+                // The stream is closed automatically once it can no longer pay, in which
+                // case there is nothing left to close.
+                if pallet_stream_payment::Streams::<Runtime>::contains_key(stream_id) {
+                    StreamPayment::close_stream(RuntimeOrigin::signed(provider), stream_id)?;
+                }
+            }
         }
 
         Ok(())
@@ -1727,9 +1935,14 @@ parameter_types! {
     pub const InitialManualClaimShareValue: u128 = MILLIUNITS;
     pub const InitialAutoCompoundingShareValue: u128 = MILLIUNITS;
     pub const MinimumSelfDelegation: u128 = 10_000 * UNITS;
+    pub const MinimumRedelegation: u128 = 1_000 * UNITS;
     pub const RewardsCollatorCommission: Perbill = Perbill::from_percent(20);
+    pub const MaxCommission: Perbill = Perbill::from_percent(50);
     // Need to wait 2 sessions before being able to join or leave staking pools
     pub const StakingSessionDelay: u32 = 2;
+    // Redelegated stake stays slashable for offences of the previous candidate
+    // as long as those offences can still be reported
+    pub const RedelegationSessionDelay: u32 = BondingDuration::get() * SessionsPerEra::get();
 }
 
 pub struct SessionTimer<Delay>(PhantomData<Delay>);
@@ -1752,6 +1965,10 @@ where
         end <= Self::now()
     }
 
+    fn elapsed_at(instant: &Self::Instant) -> Option<Self::Instant> {
+        instant.checked_add(Delay::get())
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn elapsed_instant() -> Self::Instant {
         let delay = Delay::get();
@@ -1801,6 +2018,16 @@ impl IsCandidateEligible<AccountId> for CandidateHasRegisteredKeys {
     }
 }
 
+impl IsCollatorRegistered<AccountId> for CandidateHasRegisteredKeys {
+    fn is_collator_registered(a: &AccountId) -> bool {
+        Self::is_candidate_eligible(a)
+    }
+    #[cfg(feature = "runtime-benchmarks")]
+    fn register_collator(a: &AccountId) {
+        Self::make_candidate_eligible(a, true)
+    }
+}
+
 impl pallet_pooled_staking::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type Currency = Balances;
@@ -1811,13 +2038,30 @@ impl pallet_pooled_staking::Config for Runtime {
     type MinimumSelfDelegation = MinimumSelfDelegation;
     type RuntimeHoldReason = RuntimeHoldReason;
     type RewardsCollatorCommission = RewardsCollatorCommission;
+    type MaxCommission = MaxCommission;
     type JoiningRequestTimer = SessionTimer<StakingSessionDelay>;
     type LeavingRequestTimer = SessionTimer<StakingSessionDelay>;
+    type CommissionChangeTimer = SessionTimer<StakingSessionDelay>;
+    type RedelegationTimer = SessionTimer<RedelegationSessionDelay>;
+    type MaxRedelegationsPerCandidate = ConstU32<100>;
+    type MinimumRedelegation = MinimumRedelegation;
     type EligibleCandidatesBufferSize = ConstU32<100>;
     type EligibleCandidatesFilter = CandidateHasRegisteredKeys;
+    type SlashedFundsHandler = ResolveTo<pallet_treasury::TreasuryAccountId<Runtime>, Balances>;
+    type AutoExecutionScanLimit = ConstU32<20>;
+    type AutoExecutionBounty = ConstU128<0>;
+    type AutoExecutionKeeper = BlockAuthorAsKeeper;
     type WeightInfo = weights::pallet_pooled_staking::SubstrateWeight<Runtime>;
 }
 
+/// Block author executes pending operations in `on_idle`, so it receives the bounties.
+pub struct BlockAuthorAsKeeper;
+impl Get<Option<AccountId>> for BlockAuthorAsKeeper {
+    fn get() -> Option<AccountId> {
+        Authorship::author()
+    }
+}
+
 construct_runtime! {
     pub enum Runtime
     {
@@ -1870,6 +2114,7 @@ construct_runtime! {
         // InflationRewards must be after Session
         InflationRewards: pallet_inflation_rewards = 33,
         PooledStaking: pallet_pooled_staking = 34,
+        StreamPayment: pallet_stream_payment = 35,
 
         // Governance stuff; uncallable initially.
         Treasury: pallet_treasury = 40,
@@ -2120,6 +2365,8 @@ impl pallet_registrar::Config for Runtime {
         Registrar,
         weights::runtime_common_paras_registrar::SubstrateWeight<Runtime>,
     >;
+    type MaxRequestedCollators = ConstU32<10>;
+    type MaxRequestedCores = ConstU32<4>;
     type WeightInfo = weights::pallet_registrar::SubstrateWeight<Runtime>;
 }
 
@@ -2218,7 +2465,7 @@ impl pallet_author_noting::Config for Runtime {
     type ContainerChains = ContainerRegistrar;
     type SlotBeacon = BabeSlotBeacon;
     type ContainerChainAuthor = TanssiCollatorAssignment;
-    type AuthorNotingHook = (InflationRewards, ServicesPayment);
+    type AuthorNotingHook = (InflationRewards, ServicesPayment, TanssiCollatorAssignment);
     type RelayOrPara = pallet_author_noting::RelayMode;
     type WeightInfo = weights::pallet_author_noting::SubstrateWeight<Runtime>;
 }
@@ -2263,6 +2510,7 @@ mod benches {
         [pallet_asset_rate, AssetRate]
         [pallet_whitelist, Whitelist]
         [pallet_services_payment, ServicesPayment]
+        [pallet_stream_payment, StreamPayment]
         // Tanssi
         [pallet_author_noting, AuthorNoting]
         [pallet_registrar, ContainerRegistrar]
@@ -2837,6 +3085,109 @@ sp_api::impl_runtime_apis! {
         }
     }
 
+    impl pallet_collator_assignment_runtime_api::CollatorAssignmentApi<Block, AccountId, ParaId, pallet_configuration::HostConfiguration>
+    for Runtime {
+        /// Return the parachain that the given `AccountId` is collating for.
+        /// Returns `None` if the `AccountId` is not collating.
+        fn current_collator_parachain_assignment(account: AccountId) -> Option<ParaId> {
+            let assigned_collators = TanssiCollatorAssignment::collator_container_chain();
+
+            assigned_collators.para_id_of(&account, MockParaId::get())
+        }
+
+        /// Return the parachain that the given `AccountId` will be collating for
+        /// in the next session change.
+        /// Returns `None` if the `AccountId` will not be collating.
+        fn future_collator_parachain_assignment(account: AccountId) -> Option<ParaId> {
+            match TanssiCollatorAssignment::pending_collator_container_chain() {
+                Some(assigned_collators) => {
+                    assigned_collators.para_id_of(&account, MockParaId::get())
+                }
+                None => {
+                    Self::current_collator_parachain_assignment(account)
+                }
+            }
+        }
+
+        /// Return the list of collators of the given `ParaId`.
+        /// Returns `None` if the `ParaId` is not in the registrar.
+        fn parachain_collators(para_id: ParaId) -> Option<Vec<AccountId>> {
+            let assigned_collators = TanssiCollatorAssignment::collator_container_chain();
+
+            assigned_collators.container_chains.get(&para_id).cloned()
+        }
+
+        /// Simulate the assignment of the next session change as if the given host configuration
+        /// and collators were used, without modifying the storage.
+        fn simulate_collator_assignment(
+            config: pallet_configuration::HostConfiguration,
+            collators: Vec<AccountId>,
+        ) -> Option<pallet_collator_assignment_runtime_api::CollatorAssignmentSimulation<AccountId, ParaId>> {
+            let config = pallet_collator_assignment::AssignmentHostConfiguration {
+                max_collators: config.max_collators,
+                min_orchestrator_collators: config.min_orchestrator_collators,
+                max_orchestrator_collators: config.max_orchestrator_collators,
+                collators_per_container: config.collators_per_container,
+                collators_per_parathread: config.collators_per_parathread,
+                max_parachain_cores_percentage: config.max_parachain_cores_percentage,
+                full_rotation_mode: config.full_rotation_mode,
+            };
+            let simulation = TanssiCollatorAssignment::simulate_assignment(
+                Session::current_index() + 1,
+                config,
+                collators,
+            )
+            .ok()?;
+
+            Some(pallet_collator_assignment_runtime_api::CollatorAssignmentSimulation {
+                assignment: simulation.assignment,
+                dropped_chains: simulation.dropped_chains,
+                tips_charged: simulation.tips_charged,
+            })
+        }
+
+        /// Return the liveness score of the given `AccountId` in the last session.
+        fn collator_liveness(
+            account: AccountId,
+        ) -> Option<pallet_collator_assignment_runtime_api::CollatorLiveness> {
+            pallet_collator_assignment::LivenessScores::<Runtime>::get(account).map(|score| {
+                pallet_collator_assignment_runtime_api::CollatorLiveness {
+                    blocks_produced: score.blocks_produced,
+                    slots_expected: score.slots_expected,
+                }
+            })
+        }
+
+        /// Return the collators assigned to the given `ParaId` in the given session.
+        fn chain_assignment_at_session(
+            session_index: u32,
+            para_id: ParaId,
+        ) -> Option<pallet_collator_assignment_runtime_api::ChainAssignmentRecord<AccountId>> {
+            use pallet_collator_assignment_runtime_api::DroppedChainReason;
+
+            let (collators, dropped_reason) =
+                TanssiCollatorAssignment::chain_assignment_at_session(session_index, para_id)?;
+
+            Some(pallet_collator_assignment_runtime_api::ChainAssignmentRecord {
+                collators,
+                dropped_reason: dropped_reason.map(|reason| match reason {
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughCredits => {
+                        DroppedChainReason::NotEnoughCredits
+                    }
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughCores => {
+                        DroppedChainReason::NotEnoughCores
+                    }
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughCollators => {
+                        DroppedChainReason::NotEnoughCollators
+                    }
+                    pallet_collator_assignment::DroppedChainReason::NotEnoughEligibleCollators => {
+                        DroppedChainReason::NotEnoughEligibleCollators
+                    }
+                }),
+            })
+        }
+    }
+
     impl pallet_registrar_runtime_api::RegistrarApi<Block, ParaId> for Runtime {
         /// Return the registered para ids
         fn registered_paras() -> Vec<ParaId> {
@@ -2948,7 +3299,7 @@ sp_api::impl_runtime_apis! {
         }
     }
 
-    impl pallet_services_payment_runtime_api::ServicesPaymentApi<Block, Balance, ParaId> for Runtime {
+    impl pallet_services_payment_runtime_api::ServicesPaymentApi<Block, Balance, ParaId, u16> for Runtime {
         fn block_cost(para_id: ParaId) -> Balance {
             let (block_production_costs, _) = <Runtime as pallet_services_payment::Config>::ProvideBlockProductionCost::block_cost(&para_id);
             block_production_costs
@@ -2958,6 +3309,172 @@ sp_api::impl_runtime_apis! {
             let (collator_assignment_costs, _) = <Runtime as pallet_services_payment::Config>::ProvideCollatorAssignmentCost::collator_assignment_cost(&para_id);
             collator_assignment_costs
         }
+
+        fn block_cost_in_asset(para_id: ParaId, asset_id: u16) -> Option<Balance> {
+            ServicesPayment::block_cost_in_asset(&para_id, &asset_id)
+        }
+
+        fn collator_assignment_cost_in_asset(para_id: ParaId, asset_id: u16) -> Option<Balance> {
+            ServicesPayment::collator_assignment_cost_in_asset(&para_id, &asset_id)
+        }
+
+        fn runway(para_id: ParaId) -> pallet_services_payment_runtime_api::TankRunway {
+            let pallet_services_payment::Runway { blocks, sessions } =
+                ServicesPayment::runway(&para_id, EpochDurationInBlocks::get());
+
+            pallet_services_payment_runtime_api::TankRunway { blocks, sessions }
+        }
+
+        fn spend_history(para_id: ParaId) -> Vec<pallet_services_payment_runtime_api::SessionSpend<Balance>> {
+            ServicesPayment::spend_history(para_id)
+                .into_iter()
+                .map(|(session, spend)| pallet_services_payment_runtime_api::SessionSpend {
+                    session,
+                    block_production: spend.block_production,
+                    collator_assignment: spend.collator_assignment,
+                    tips: spend.tips,
+                    cores_purchased: spend.cores_purchased,
+                })
+                .collect()
+        }
+    }
+
+    impl pallet_stream_payment_runtime_api::StreamPaymentApi<Block, StreamId, Balance, Balance, AccountId>
+    for Runtime {
+        fn stream_payment_status(
+            stream_id: StreamId,
+            now: Option<Balance>,
+        ) -> Result<StreamPaymentApiStatus<Balance>, StreamPaymentApiError> {
+            match StreamPayment::stream_payment_status(stream_id, now) {
+                Ok(pallet_stream_payment::StreamPaymentStatus {
+                    payment, deposit_left, stalled, started, ended
+                }) => Ok(StreamPaymentApiStatus {
+                    payment, deposit_left, stalled, started, ended
+                }),
+                Err(pallet_stream_payment::Error::<Runtime>::UnknownStreamId)
+                => Err(StreamPaymentApiError::UnknownStreamId),
+                Err(e) => Err(StreamPaymentApiError::Other(format!("{e:?}")))
+            }
+        }
+
+        fn account_streams(
+            account: AccountId,
+            party: StreamPaymentApiParty,
+            start_after: Option<StreamId>,
+            limit: u32,
+        ) -> Vec<StreamId> {
+            let party = match party {
+                StreamPaymentApiParty::Source => pallet_stream_payment::Party::Source,
+                StreamPaymentApiParty::Target => pallet_stream_payment::Party::Target,
+            };
+
+            StreamPayment::account_streams(&account, party, start_after, limit)
+        }
+
+        fn stream_events() -> Vec<StreamPaymentApiEvent<AccountId, StreamId, Balance>> {
+            StreamPayment::api_events()
+        }
+    }
+
+    impl pallet_pooled_staking_runtime_api::PooledStakingApi<Block, AccountId, Balance, SessionIndex>
+    for Runtime {
+        fn candidate_commission(candidate: AccountId) -> Perbill {
+            PooledStaking::candidate_commission(&candidate)
+        }
+
+        fn delegator_candidates(delegator: AccountId) -> Vec<AccountId> {
+            PooledStaking::delegator_candidates(&delegator)
+        }
+
+        fn delegator_stake(
+            candidate: AccountId,
+            delegator: AccountId,
+        ) -> pallet_pooled_staking_runtime_api::DelegatorStake<Balance> {
+            use {
+                pallet_pooled_staking::AllTargetPool,
+                pallet_pooled_staking_runtime_api::{DelegatorStake, PoolStake},
+            };
+
+            let pool_stake = |pool| PoolStake {
+                shares: PooledStaking::shares(candidate.clone(), delegator.clone(), pool),
+                stake: PooledStaking::computed_stake(candidate.clone(), delegator.clone(), pool)
+                    .unwrap_or_default(),
+            };
+
+            DelegatorStake {
+                joining: pool_stake(AllTargetPool::Joining),
+                auto_compounding: pool_stake(AllTargetPool::AutoCompounding),
+                manual_rewards: pool_stake(AllTargetPool::ManualRewards),
+                leaving: pool_stake(AllTargetPool::Leaving),
+                claimable_manual_rewards: PooledStaking::claimable_manual_rewards(
+                    &candidate,
+                    &delegator,
+                )
+                .unwrap_or_default(),
+            }
+        }
+
+        fn delegator_pending_operations(
+            delegator: AccountId,
+        ) -> Vec<pallet_pooled_staking_runtime_api::PendingOperation<AccountId, SessionIndex, Balance>> {
+            use {
+                pallet_pooled_staking::{traits::Timer, PendingOperationKey},
+                pallet_pooled_staking_runtime_api::{PendingOperation, PendingOperationKind},
+            };
+
+            type JoiningTimer = <Runtime as pallet_pooled_staking::Config>::JoiningRequestTimer;
+            type LeavingTimer = <Runtime as pallet_pooled_staking::Config>::LeavingRequestTimer;
+
+            pallet_pooled_staking::PendingOperations::<Runtime>::iter_prefix(&delegator)
+                .map(|(operation, shares)| {
+                    let stake = PooledStaking::pending_operation_stake(&operation, shares)
+                        .unwrap_or_default();
+
+                    let (candidate, kind, at, unlock_at, executable) = match operation {
+                        PendingOperationKey::JoiningAutoCompounding { candidate, at } => (
+                            candidate,
+                            PendingOperationKind::JoiningAutoCompounding,
+                            at,
+                            JoiningTimer::elapsed_at(&at),
+                            JoiningTimer::is_elapsed(&at),
+                        ),
+                        PendingOperationKey::JoiningManualRewards { candidate, at } => (
+                            candidate,
+                            PendingOperationKind::JoiningManualRewards,
+                            at,
+                            JoiningTimer::elapsed_at(&at),
+                            JoiningTimer::is_elapsed(&at),
+                        ),
+                        PendingOperationKey::Leaving { candidate, at } => (
+                            candidate,
+                            PendingOperationKind::Leaving,
+                            at,
+                            LeavingTimer::elapsed_at(&at),
+                            LeavingTimer::is_elapsed(&at),
+                        ),
+                    };
+
+                    PendingOperation {
+                        candidate,
+                        kind,
+                        at,
+                        unlock_at,
+                        executable,
+                        shares,
+                        stake,
+                    }
+                })
+                .collect()
+        }
+
+        fn candidate_stake(
+            candidate: AccountId,
+        ) -> pallet_pooled_staking_runtime_api::CandidateStake<Balance> {
+            pallet_pooled_staking_runtime_api::CandidateStake {
+                total_stake: PooledStaking::candidate_total_stake(&candidate),
+                rank: PooledStaking::candidate_rank(&candidate),
+            }
+        }
     }
 
     impl snowbridge_outbound_queue_runtime_api::OutboundQueueApi<Block, Balance> for Runtime {
@@ -3284,6 +3801,13 @@ impl tanssi_initializer::ApplyNewSession<Runtime> for OwnApplySession {
             &queued_amalgamated,
         );
 
+        // ServicesPayment, priced by the share of collators assigned in the previous session.
+        // It must run before the collator assignment, which charges for the new one.
+        ServicesPayment::initializer_on_new_session(
+            pallet_collator_assignment::CollatorFullnessRatio::<Runtime>::get().unwrap_or_default(),
+            CollatorConfiguration::config().target_container_chain_fullness,
+        );
+
         // 4. CollatorAssignment
         // Unlike in tanssi, where the input to this function are the correct
         // queued keys & collators, here we get the input refers to the validators
@@ -3410,21 +3934,33 @@ impl ParaIdAssignmentHooksImpl {
         para_id: ParaId,
         currently_assigned: &BTreeSet<ParaId>,
         maybe_tip: &Option<BalanceOf<Runtime>>,
+        maybe_num_collators_and_cores: Option<(u32, u32)>,
     ) -> Result<Weight, DispatchError> {
         use frame_support::traits::Currency;
-        type ServicePaymentCurrency = <Runtime as pallet_services_payment::Config>::Currency;
+
+        let mut weight = Weight::zero();
 
         // Check if the container chain has enough credits for a session assignments
         let maybe_assignment_imbalance =
             if  pallet_services_payment::Pallet::<Runtime>::burn_collator_assignment_free_credit_for_para(&para_id).is_err() {
-                let (amount_to_charge, _weight) =
-                    <Runtime as pallet_services_payment::Config>::ProvideCollatorAssignmentCost::collator_assignment_cost(&para_id);
-                Some(<ServicePaymentCurrency as Currency<AccountId>>::withdraw(
-                    &pallet_services_payment::Pallet::<Runtime>::parachain_tank(para_id),
+                // Chains are charged for the collators and cores actually assigned, or estimated
+                // before the assignment is known
+                let (amount_to_charge, cost_weight) = match maybe_num_collators_and_cores {
+                    Some((num_collators, num_cores)) => {
+                        CollatorAssignmentCost::cost_for_collators(
+                            &para_id,
+                            num_collators,
+                            num_cores,
+                        )
+                    }
+                    None => <Runtime as pallet_services_payment::Config>::ProvideCollatorAssignmentCost::collator_assignment_cost(&para_id),
+                };
+                weight.saturating_accrue(cost_weight);
+                pallet_services_payment::Pallet::<Runtime>::charge_fee(
+                    &para_id,
                     amount_to_charge,
-                    WithdrawReasons::FEE,
-                    ExistenceRequirement::KeepAlive,
-                )?)
+                    pallet_services_payment::PaidService::CollatorAssignment,
+                )?
             } else {
                 None
             };
@@ -3474,7 +4010,7 @@ impl ParaIdAssignmentHooksImpl {
         )
         .into_result(true)?;
         // TODO: Have proper weight
-        Ok(Weight::zero())
+        Ok(weight)
     }
 }
 
@@ -3490,6 +4026,7 @@ impl<AC> ParaIdAssignmentHooks<BalanceOf<Runtime>, AC> for ParaIdAssignmentHooks
                     *para_id,
                     currently_assigned,
                     &Some(max_tip),
+                    None,
                 ))
             })
             .is_ok()
@@ -3499,6 +4036,7 @@ impl<AC> ParaIdAssignmentHooks<BalanceOf<Runtime>, AC> for ParaIdAssignmentHooks
     fn post_assignment(
         current_assigned: &BTreeSet<ParaId>,
         new_assigned: &mut BTreeMap<ParaId, Vec<AC>>,
+        parachain_cores: &BTreeMap<ParaId, u32>,
         maybe_tip: &Option<BalanceOf<Runtime>>,
     ) -> Weight {
         let blocks_per_session = EpochDurationInBlocks::get();
@@ -3514,6 +4052,10 @@ impl<AC> ParaIdAssignmentHooks<BalanceOf<Runtime>, AC> for ParaIdAssignmentHooks
                     para_id,
                     current_assigned,
                     maybe_tip,
+                    Some((
+                        collators.len() as u32,
+                        parachain_cores.get(&para_id).copied().unwrap_or(1),
+                    )),
                 )
             })
             .inspect(|weight| {
@@ -3600,6 +4142,17 @@ impl Get<Option<CoreAllocationConfiguration>> for GetCoreAllocationConfiguration
     }
 }
 
+parameter_types! {
+    /// Collators producing less than half of the blocks they were expected to produce are
+    /// assigned to new chains last.
+    pub const MinLivenessScore: Perbill = Perbill::from_percent(50);
+    /// Container chains have the same slot duration as this chain, so a session has as many
+    /// container chain slots as blocks.
+    pub const ContainerSlotsPerSession: u32 = EpochDurationInBlocks::get();
+    /// Number of past sessions whose collator assignment can be queried.
+    pub const AssignmentHistoryDepth: u32 = 168;
+}
+
 impl pallet_collator_assignment::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type HostConfiguration = CollatorConfiguration;
@@ -3615,6 +4168,10 @@ impl pallet_collator_assignment::Config for Runtime {
     type Currency = Balances;
     type ForceEmptyOrchestrator = ConstBool<true>;
     type CoreAllocationConfiguration = GetCoreAllocationConfigurationImpl;
+    type CollatorRegistration = CandidateHasRegisteredKeys;
+    type MinLivenessScore = MinLivenessScore;
+    type ContainerSlotsPerSession = ContainerSlotsPerSession;
+    type AssignmentHistoryDepth = AssignmentHistoryDepth;
     type WeightInfo = weights::pallet_collator_assignment::SubstrateWeight<Runtime>;
 }
 
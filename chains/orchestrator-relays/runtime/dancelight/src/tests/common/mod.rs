@@ -821,6 +821,9 @@ pub(crate) struct ParasInherentTestBuilder<T: runtime_parachains::paras_inherent
 
     /// Paras which don't yet occupy a core, but will after the inherent has been processed.
     backed_in_inherent_paras: BTreeMap<u32, u32>,
+
+    /// Map from para id seed to the number of cores it uses, and candidates it backs.
+    elastic_paras: BTreeMap<u32, u8>,
     _phantom: core::marker::PhantomData<T>,
 }
 
@@ -837,6 +840,7 @@ impl<T: runtime_parachains::paras_inherent::Config> ParasInherentTestBuilder<T>
             block_number: Zero::zero(),
             backed_and_concluding_paras: Default::default(),
             backed_in_inherent_paras: Default::default(),
+            elastic_paras: Default::default(),
             _phantom: core::marker::PhantomData::<T>,
         }
     }
@@ -856,6 +860,18 @@ impl<T: runtime_parachains::paras_inherent::Config> ParasInherentTestBuilder<T>
         self
     }
 
+    /// Set a map from para id seed to number of cores assigned to it, which back a chain of
+    /// candidates of that para.
+    pub(crate) fn set_elastic_paras(mut self, elastic_paras: BTreeMap<u32, u8>) -> Self {
+        self.elastic_paras = elastic_paras;
+        self
+    }
+
+    /// Number of cores used by the para id `seed`.
+    fn cores_of(&self, seed: &u32) -> u8 {
+        self.elastic_paras.get(seed).copied().unwrap_or(1)
+    }
+
     /// Mock header.
     pub(crate) fn header(block_number: BlockNumberFor<T>) -> HeaderFor<T> {
         HeaderFor::<T>::new(
@@ -964,8 +980,10 @@ impl<T: runtime_parachains::paras_inherent::Config> ParasInherentTestBuilder<T>
                 let para_id = ParaId::from(*seed);
                 let prev_head_non_mut = runtime_parachains::paras::Heads::<T>::get(para_id);
                 let prev_head = prev_head_non_mut.unwrap_or(Self::mock_head_data());
+                // Head data produced by the previous candidate of the chain
+                let mut prev_candidate_head: Option<HeadData> = None;
                 // How many chained candidates we want to build ?
-                (0..1)
+                (0..self.cores_of(seed))
                     .map(|chain_idx| {
                         let core_idx = CoreIndex::from(current_core_idx);
                         // Advance core index.
@@ -986,15 +1004,20 @@ impl<T: runtime_parachains::paras_inherent::Config> ParasInherentTestBuilder<T>
                         if chain_idx == 0 {
                             // Only first parahead of the chain needs to be set in storage.
                             Self::heads_insert(&para_id, prev_head.clone());
+                            // The first candidate keeps the head data in storage
+                            head_data = prev_head.clone();
                         } else {
                             // Make each candidate head data unique to avoid cycles.
                             head_data.0[0] = chain_idx;
                         }
+                        let parent_head = prev_candidate_head
+                            .replace(head_data.clone())
+                            .unwrap_or(prev_head.clone());
 
                         let persisted_validation_data = PersistedValidationData::<T::Hash> {
                             // To form a chain we set parent head to previous block if any, or
                             // default to what is in storage already setup.
-                            parent_head: prev_head.clone(),
+                            parent_head,
                             relay_parent_number: self.relay_parent_number() + 1,
                             relay_parent_storage_root: Default::default(),
                             max_pov_size: config.max_pov_size,
@@ -1028,14 +1051,14 @@ impl<T: runtime_parachains::paras_inherent::Config> ParasInherentTestBuilder<T>
                                 pov_hash,
                                 erasure_root: Default::default(),
                                 signature,
-                                para_head: prev_head.hash(),
+                                para_head: head_data.hash(),
                                 validation_code_hash,
                             },
                             commitments: CandidateCommitments::<u32> {
                                 upward_messages: Default::default(),
                                 horizontal_messages: Default::default(),
                                 new_validation_code: None,
-                                head_data: prev_head.clone(),
+                                head_data,
                                 processed_downward_messages: 0,
                                 hrmp_watermark: self.relay_parent_number() + 1,
                             },
@@ -1144,8 +1167,12 @@ impl<T: runtime_parachains::paras_inherent::Config> ParasInherentTestBuilder<T>
 
         let max_cores = self.max_cores() as usize;
 
-        let used_cores =
-            self.backed_and_concluding_paras.len() + self.backed_in_inherent_paras.len();
+        let used_cores = self
+            .backed_and_concluding_paras
+            .keys()
+            .chain(self.backed_in_inherent_paras.keys())
+            .map(|seed| self.cores_of(seed) as usize)
+            .sum();
         assert!(used_cores <= max_cores);
         let mut backed_in_inherent = BTreeMap::new();
         backed_in_inherent.append(&mut self.backed_and_concluding_paras.clone());
@@ -18,10 +18,12 @@
 
 use {
     crate::{
-        tests::common::*, ContainerRegistrar, OnDemandAssignmentProvider, Paras, Registrar, Session,
+        tests::common::*, ContainerRegistrar, OnDemandAssignmentProvider, Paras, Registrar,
+        ServicesPayment, Session, TanssiCollatorAssignment,
     },
     cumulus_primitives_core::relay_chain::{
-        AsyncBackingParams, CoreIndex, HeadData, SchedulerParams,
+        node_features::FeatureIndex, AsyncBackingParams, CoreIndex, HeadData, NodeFeatures,
+        SchedulerParams,
     },
     dancelight_runtime_constants::time::EpochDurationInBlocks,
     frame_support::assert_ok,
@@ -336,6 +338,22 @@ fn test_parathread_that_buys_core_has_affinity_and_can_produce() {
             let availability_after = Runtime::candidates_pending_availability(1000u32.into());
             // After the availability length is 1 as we have one candidate succesfully backed
             assert_eq!(availability_after.len(), 1);
+
+            // Once the candidate is included the bought core is recorded in the spend history
+            let cores_with_backed: BTreeMap<_, _> =
+                vec![(1000u32, Session::validators().len() as u32)]
+                    .into_iter()
+                    .collect();
+            let inherent_data = ParasInherentTestBuilder::<Runtime>::new()
+                .set_backed_and_concluding_paras(cores_with_backed)
+                .build();
+            set_new_inherent_data(inherent_data);
+            run_block();
+            let cores_purchased: u32 = ServicesPayment::spend_history(1000u32.into())
+                .iter()
+                .map(|(_, spend)| spend.cores_purchased)
+                .sum();
+            assert_eq!(cores_purchased, 1);
         })
 }
 
@@ -731,6 +749,179 @@ fn test_should_have_availability_for_registered_parachain() {
         })
 }
 
+#[test]
+fn test_parachain_gets_requested_cores() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            // Alice gets 10k extra tokens for her mapping deposit
+            (AccountId::from(ALICE), 210_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+            (AccountId::from(CHARLIE), 100_000 * UNIT),
+            (AccountId::from(DAVE), 100_000 * UNIT),
+        ])
+        .with_collators(vec![
+            (AccountId::from(ALICE), 210 * UNIT),
+            (AccountId::from(BOB), 100 * UNIT),
+        ])
+        .with_config(pallet_configuration::HostConfiguration {
+            max_collators: 2,
+            min_orchestrator_collators: 0,
+            max_orchestrator_collators: 0,
+            collators_per_container: 2,
+            ..Default::default()
+        })
+        .with_relay_config(runtime_parachains::configuration::HostConfiguration::<
+            BlockNumberFor<Runtime>,
+        > {
+            scheduler_params: SchedulerParams {
+                num_cores: 4,
+                // A very high number to avoid group rotation in tests
+                // Otherwise we get a 1 by default, which changes groups every block
+                group_rotation_frequency: 10000000,
+                ..Default::default()
+            },
+            async_backing_params: AsyncBackingParams {
+                allowed_ancestry_len: 1,
+                max_candidate_depth: 0,
+            },
+            minimum_backing_votes: 1,
+            max_head_data_size: 5,
+            ..Default::default()
+        })
+        .with_para_ids(vec![ParaRegistrationParams {
+            para_id: 1000,
+            genesis_data: empty_genesis_data(),
+            block_production_credits: u32::MAX,
+            collator_assignment_credits: u32::MAX,
+            parathread_params: None,
+        }])
+        .with_keystore(Arc::new(MemoryKeystore::new()))
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+
+            // Before the request, the parachain only has the first core
+            let claim_queue = Runtime::claim_queue();
+            assert_eq!(
+                claim_queue.get(&CoreIndex(0)).and_then(|q| q.front()),
+                Some(&1000u32.into())
+            );
+            assert_eq!(claim_queue.get(&CoreIndex(1)).and_then(|q| q.front()), None);
+
+            // Half of the 4 cores are for parachains
+            assert_ok!(ContainerRegistrar::set_core_count_request(
+                root_origin(),
+                1000u32.into(),
+                Some(2)
+            ));
+
+            // The request is used in the assignment of session 2
+            run_to_session(2);
+            run_block();
+
+            assert_eq!(
+                TanssiCollatorAssignment::parachain_cores(Session::current_index(), 1000u32.into()),
+                2
+            );
+            let claim_queue = Runtime::claim_queue();
+            for core in [0, 1] {
+                assert_eq!(
+                    claim_queue.get(&CoreIndex(core)).and_then(|q| q.front()),
+                    Some(&1000u32.into())
+                );
+            }
+        })
+}
+
+#[test]
+fn test_parachain_with_two_cores_backs_two_candidates_per_block() {
+    // Candidates are injected with their core index
+    let mut node_features =
+        NodeFeatures::repeat(false, FeatureIndex::ElasticScalingMVP as usize + 1);
+    node_features.set(FeatureIndex::ElasticScalingMVP as usize, true);
+
+    ExtBuilder::default()
+        .with_balances(vec![
+            // Alice gets 10k extra tokens for her mapping deposit
+            (AccountId::from(ALICE), 210_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+            (AccountId::from(CHARLIE), 100_000 * UNIT),
+            (AccountId::from(DAVE), 100_000 * UNIT),
+        ])
+        .with_collators(vec![
+            (AccountId::from(ALICE), 210 * UNIT),
+            (AccountId::from(BOB), 100 * UNIT),
+        ])
+        .with_config(pallet_configuration::HostConfiguration {
+            max_collators: 2,
+            min_orchestrator_collators: 0,
+            max_orchestrator_collators: 0,
+            collators_per_container: 2,
+            ..Default::default()
+        })
+        .with_relay_config(runtime_parachains::configuration::HostConfiguration::<
+            BlockNumberFor<Runtime>,
+        > {
+            scheduler_params: SchedulerParams {
+                num_cores: 4,
+                // A very high number to avoid group rotation in tests
+                // Otherwise we get a 1 by default, which changes groups every block
+                group_rotation_frequency: 10000000,
+                ..Default::default()
+            },
+            async_backing_params: AsyncBackingParams {
+                allowed_ancestry_len: 1,
+                max_candidate_depth: 0,
+            },
+            minimum_backing_votes: 1,
+            max_head_data_size: 5,
+            node_features,
+            ..Default::default()
+        })
+        .with_para_ids(vec![ParaRegistrationParams {
+            para_id: 1000,
+            genesis_data: empty_genesis_data(),
+            block_production_credits: u32::MAX,
+            collator_assignment_credits: u32::MAX,
+            parathread_params: None,
+        }])
+        .with_keystore(Arc::new(MemoryKeystore::new()))
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+
+            assert_ok!(ContainerRegistrar::set_core_count_request(
+                root_origin(),
+                1000u32.into(),
+                Some(2)
+            ));
+
+            // The request is used in the assignment of session 2
+            run_to_session(2);
+            run_block();
+
+            // One chained candidate is backed in each of the 2 cores of the parachain
+            let cores_with_backed: BTreeMap<_, _> =
+                vec![(1000u32, Session::validators().len() as u32)]
+                    .into_iter()
+                    .collect();
+            let inherent_data = ParasInherentTestBuilder::<Runtime>::new()
+                .set_backed_and_concluding_paras(cores_with_backed)
+                .set_elastic_paras(vec![(1000u32, 2u8)].into_iter().collect())
+                .build();
+            set_new_inherent_data(inherent_data);
+            assert_eq!(
+                Runtime::candidates_pending_availability(1000u32.into()).len(),
+                0
+            );
+            run_block();
+            assert_eq!(
+                Runtime::candidates_pending_availability(1000u32.into()).len(),
+                2
+            );
+        })
+}
+
 // we dont have access to the type so this is the only thing we can do
 #[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, scale_info::TypeInfo)]
 pub struct CoreAffinityCount {
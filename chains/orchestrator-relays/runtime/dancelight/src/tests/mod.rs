@@ -39,6 +39,7 @@ mod services_payment;
 mod session_keys;
 mod slashes;
 mod staking;
+mod stream_payment;
 mod sudo;
 
 #[test]
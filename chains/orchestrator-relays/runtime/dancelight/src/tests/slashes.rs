@@ -18,9 +18,10 @@ use {
     crate::tests::common::*,
     crate::{
         BondingDuration, ExternalValidatorSlashes, ExternalValidators, Grandpa, Historical,
-        RuntimeEvent, SessionsPerEra, SlashDeferDuration,
+        MinimumSelfDelegation, PooledStaking, RuntimeEvent, SessionsPerEra, SlashDeferDuration,
     },
     frame_support::{assert_noop, assert_ok, traits::KeyOwnerProofSystem},
+    pallet_pooled_staking::{PoolsKey, TargetPool},
     parity_scale_codec::Encode,
     sp_core::{Pair, H256},
     sp_runtime::Perbill,
@@ -717,6 +718,66 @@ fn test_slashes_are_sent_to_ethereum_accumulate_until_next_era() {
         });
 }
 
+#[test]
+fn test_slashes_are_applied_to_pooled_staking_pools() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            // Alice gets 10k extra tokens for her mapping deposit
+            (AccountId::from(ALICE), 210_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+            (AccountId::from(CHARLIE), 100_000 * UNIT),
+            (AccountId::from(DAVE), 100_000 * UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            assert_ok!(ExternalValidators::remove_whitelisted(
+                RuntimeOrigin::root(),
+                AccountId::from(ALICE)
+            ));
+
+            let stake = MinimumSelfDelegation::get() * 10;
+            assert_ok!(PooledStaking::request_delegate(
+                origin_of(ALICE.into()),
+                ALICE.into(),
+                TargetPool::ManualRewards,
+                stake
+            ));
+
+            inject_babe_slash(&AccountId::from(ALICE).to_string());
+
+            let slashes = ExternalValidatorSlashes::slashes(
+                ExternalValidators::current_era().unwrap() + SlashDeferDuration::get() + 1,
+            );
+            assert_eq!(slashes.len(), 1);
+            assert_eq!(slashes[0].percentage, Perbill::from_percent(100));
+
+            let session_in_which_slashes_are_sent =
+                (ExternalValidators::current_era().unwrap() + SlashDeferDuration::get() + 1)
+                    * SessionsPerEra::get();
+            run_to_session(session_in_which_slashes_are_sent);
+            // Slashes are processed in the block after the era starts
+            run_block();
+
+            // A full slash keeps one unit of stake in the pool
+            let candidate: AccountId = ALICE.into();
+            assert_eq!(
+                pallet_pooled_staking::Pools::<Runtime>::get(
+                    &candidate,
+                    &PoolsKey::JoiningSharesTotalStaked
+                ),
+                1
+            );
+            assert_eq!(
+                pallet_pooled_staking::Pools::<Runtime>::get(
+                    &candidate,
+                    &PoolsKey::CandidateTotalStake
+                ),
+                1
+            );
+        });
+}
+
 fn inject_babe_slash(seed: &str) {
     let babe_key = get_pair_from_seed::<babe_primitives::AuthorityId>(seed);
     let equivocation_proof = generate_babe_equivocation_proof(&babe_key);
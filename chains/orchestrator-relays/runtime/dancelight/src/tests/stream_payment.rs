@@ -0,0 +1,269 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+#![cfg(test)]
+
+use {
+    crate::{
+        tests::common::*, ContainerRegistrar, PreserversAssignmentPaymentExtra,
+        PreserversAssignmentPaymentRequest, PreserversAssignmentPaymentWitness, Registrar,
+        StreamPayment, StreamPaymentAssetId, TimeUnit,
+    },
+    cumulus_primitives_core::{relay_chain::HeadData, ParaId},
+    frame_support::{assert_noop, assert_ok, traits::fungible::InspectHold, weights::Weight},
+    pallet_data_preservers::{ParaIdsFilter, Profile, ProfileMode},
+    pallet_stream_payment::{ChangeKind, StreamConfig},
+    sp_std::vec,
+};
+
+fn register_para(manager: AccountId, para_id: ParaId) {
+    assert_ok!(Registrar::reserve(origin_of(manager.clone())));
+    assert_ok!(ContainerRegistrar::register(
+        origin_of(manager),
+        para_id,
+        get_genesis_data_with_validation_code().0,
+        Some(HeadData(vec![1u8, 1u8, 1u8]))
+    ));
+}
+
+fn stream_payment_profile(rate: Balance) -> Profile<Runtime> {
+    Profile {
+        url: b"test".to_vec().try_into().unwrap(),
+        para_ids: ParaIdsFilter::AnyParaId,
+        mode: ProfileMode::Bootnode,
+        assignment_request: PreserversAssignmentPaymentRequest::StreamPayment {
+            config: StreamConfig {
+                time_unit: TimeUnit::BlockNumber,
+                asset_id: StreamPaymentAssetId::Native,
+                rate,
+            },
+        },
+    }
+}
+
+#[test]
+fn stream_payment_works() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            (AccountId::from(ALICE), 100_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+            (AccountId::from(CHARLIE), 100_000 * UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            assert_ok!(StreamPayment::open_stream(
+                origin_of(ALICE.into()),
+                BOB.into(),
+                StreamConfig {
+                    rate: 2 * UNIT,
+                    asset_id: StreamPaymentAssetId::Native,
+                    time_unit: TimeUnit::BlockNumber,
+                },
+                1_000 * UNIT,
+            ));
+
+            run_block();
+
+            assert_ok!(StreamPayment::perform_payment(origin_of(CHARLIE.into()), 0));
+            assert_eq!(
+                Balances::free_balance(AccountId::from(BOB)),
+                100_000 * UNIT + 2 * UNIT
+            );
+
+            assert_ok!(StreamPayment::request_change(
+                origin_of(ALICE.into()),
+                0,
+                ChangeKind::Suggestion,
+                StreamConfig {
+                    rate: 1 * UNIT,
+                    asset_id: StreamPaymentAssetId::Native,
+                    time_unit: TimeUnit::BlockNumber,
+                },
+                None,
+            ));
+
+            assert_ok!(StreamPayment::accept_requested_change(
+                origin_of(BOB.into()),
+                0,
+                1, // nonce
+                None,
+            ));
+
+            run_block();
+
+            assert_ok!(StreamPayment::close_stream(origin_of(BOB.into()), 0));
+
+            assert_eq!(
+                Balances::free_balance(AccountId::from(BOB)),
+                100_000 * UNIT + 3 * UNIT
+            );
+            assert_eq!(
+                Balances::free_balance(AccountId::from(ALICE)),
+                100_000 * UNIT - 3 * UNIT
+            );
+        });
+}
+
+#[test]
+fn data_preserver_assignment_with_stream_payment() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            (AccountId::from(ALICE), 210_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            let para_id = ParaId::from(2000);
+            let profile_id = 0u64;
+            let stream_id = pallet_stream_payment::NextStreamId::<Runtime>::get();
+
+            register_para(ALICE.into(), para_id);
+
+            assert_ok!(DataPreservers::create_profile(
+                origin_of(BOB.into()),
+                stream_payment_profile(2 * UNIT),
+            ));
+
+            assert_ok!(DataPreservers::start_assignment(
+                origin_of(ALICE.into()),
+                profile_id,
+                para_id,
+                PreserversAssignmentPaymentExtra::StreamPayment {
+                    initial_deposit: 1_000 * UNIT
+                }
+            ));
+
+            // The assignment opened a stream from the para manager to the preserver
+            let profile = pallet_data_preservers::Profiles::<Runtime>::get(profile_id)
+                .expect("profile to exist");
+            assert_eq!(
+                profile.assignment,
+                Some((
+                    para_id,
+                    PreserversAssignmentPaymentWitness::StreamPayment { stream_id }
+                ))
+            );
+            let stream =
+                pallet_stream_payment::Streams::<Runtime>::get(stream_id).expect("stream to exist");
+            assert_eq!(stream.source, AccountId::from(ALICE));
+            assert_eq!(stream.target, AccountId::from(BOB));
+            assert_eq!(stream.deposit, 1_000 * UNIT);
+
+            run_block();
+
+            // Stopping the assignment closes the stream and pays the preserver
+            let bob_balance_before = Balances::free_balance(AccountId::from(BOB));
+            assert_ok!(DataPreservers::stop_assignment(
+                origin_of(BOB.into()),
+                profile_id,
+                para_id,
+            ));
+
+            assert!(pallet_stream_payment::Streams::<Runtime>::get(stream_id).is_none());
+            assert!(pallet_data_preservers::Assignments::<Runtime>::get(para_id).is_empty());
+            assert_eq!(
+                Balances::free_balance(AccountId::from(BOB)),
+                bob_balance_before + 2 * UNIT
+            );
+            assert_eq!(
+                Balances::balance_on_hold(
+                    &pallet_stream_payment::HoldReason::StreamPayment.into(),
+                    &AccountId::from(ALICE)
+                ),
+                0
+            );
+        });
+}
+
+#[test]
+fn data_preserver_assignment_can_be_stopped_after_stream_is_closed_automatically() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            (AccountId::from(ALICE), 210_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            let para_id = ParaId::from(2000);
+            let profile_id = 0u64;
+            let stream_id = pallet_stream_payment::NextStreamId::<Runtime>::get();
+
+            register_para(ALICE.into(), para_id);
+
+            assert_ok!(DataPreservers::create_profile(
+                origin_of(BOB.into()),
+                stream_payment_profile(2 * UNIT),
+            ));
+
+            assert_ok!(DataPreservers::start_assignment(
+                origin_of(ALICE.into()),
+                profile_id,
+                para_id,
+                PreserversAssignmentPaymentExtra::StreamPayment {
+                    initial_deposit: 2 * UNIT
+                }
+            ));
+
+            run_block();
+            run_block();
+
+            // The drained stream is closed while the assignment still references it
+            StreamPayment::auto_settle_streams(Weight::MAX);
+            assert!(pallet_stream_payment::Streams::<Runtime>::get(stream_id).is_none());
+
+            assert_ok!(DataPreservers::stop_assignment(
+                origin_of(BOB.into()),
+                profile_id,
+                para_id,
+            ));
+
+            let profile = pallet_data_preservers::Profiles::<Runtime>::get(profile_id)
+                .expect("profile to exist");
+            assert_eq!(profile.assignment, None);
+            assert!(pallet_data_preservers::Assignments::<Runtime>::get(para_id).is_empty());
+        });
+}
+
+#[test]
+fn data_preserver_assignment_payment_mismatch() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            (AccountId::from(ALICE), 210_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            let para_id = ParaId::from(2000);
+            let profile_id = 0u64;
+
+            register_para(ALICE.into(), para_id);
+
+            assert_ok!(DataPreservers::create_profile(
+                origin_of(BOB.into()),
+                stream_payment_profile(2 * UNIT),
+            ));
+
+            assert_noop!(
+                DataPreservers::start_assignment(
+                    origin_of(ALICE.into()),
+                    profile_id,
+                    para_id,
+                    PreserversAssignmentPaymentExtra::Free
+                ),
+                pallet_data_preservers::Error::<Runtime>::AssignmentPaymentRequestParameterMismatch
+            );
+        });
+}
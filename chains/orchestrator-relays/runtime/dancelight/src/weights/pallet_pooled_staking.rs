@@ -140,18 +140,20 @@ impl<T: frame_system::Config> pallet_pooled_staking::WeightInfo for SubstrateWei
 	}
 	/// Storage: `PooledStaking::Pools` (r:4 w:1)
 	/// Proof: `PooledStaking::Pools` (`max_values`: None, `max_size`: Some(113), added: 2588, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `System::Account` (r:3 w:3)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `Balances::Holds` (r:1 w:1)
 	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(121), added: 2596, mode: `MaxEncodedLen`)
+	/// Storage: `PooledStaking::PendingSlashedFunds` (r:1 w:1)
+	/// Proof: `PooledStaking::PendingSlashedFunds` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
 	fn rebalance_hold() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `981`
-		//  Estimated: `11342`
+		//  Measured:  `1045`
+		//  Estimated: `14456`
 		// Minimum execution time: 132_493_000 picoseconds.
-		Weight::from_parts(132_493_000, 11342)
-			.saturating_add(T::DbWeight::get().reads(7_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+		Weight::from_parts(132_493_000, 14456)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
 	/// Storage: `PooledStaking::Pools` (r:600 w:100)
 	/// Proof: `PooledStaking::Pools` (`max_values`: None, `max_size`: Some(113), added: 2588, mode: `MaxEncodedLen`)
@@ -202,4 +204,103 @@ impl<T: frame_system::Config> pallet_pooled_staking::WeightInfo for SubstrateWei
 			.saturating_add(T::DbWeight::get().reads(13_u64))
 			.saturating_add(T::DbWeight::get().writes(8_u64))
 	}
+	/// Storage: `PooledStaking::Pools` (r:1 w:1)
+	/// Proof: `PooledStaking::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `PooledStaking::SortedEligibleCandidates` (r:1 w:1)
+	/// Proof: `PooledStaking::SortedEligibleCandidates` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `PooledStaking::PendingSlashedFunds` (r:1 w:1)
+	/// Proof: `PooledStaking::PendingSlashedFunds` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `PooledStaking::Redelegations` (r:1 w:1)
+	/// Proof: `PooledStaking::Redelegations` (`max_values`: None, `max_size`: Some(8502), added: 10977, mode: `MaxEncodedLen`)
+	/// The range of component `r` is `[0, 100]`.
+	fn slash_candidate(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `13696`
+		//  Estimated: `41088`
+		// Minimum execution time: 151_883_000 picoseconds.
+		Weight::from_parts(151_883_000, 41088)
+			// Standard Error: 41_205
+			.saturating_add(Weight::from_parts(38_913_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(21_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 7761).saturating_mul(r.into()))
+	}
+	/// Storage: `PooledStaking::Pools` (r:6 w:0)
+	/// Proof: `PooledStaking::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `PooledStaking::PendingCommissionChanges` (r:1 w:1)
+	/// Proof: `PooledStaking::PendingCommissionChanges` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `PooledStaking::LastCommissionChange` (r:1 w:0)
+	/// Proof: `PooledStaking::LastCommissionChange` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn request_commission_change() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1202`
+		//  Estimated: `28502`
+		// Minimum execution time: 14_873_000 picoseconds.
+		Weight::from_parts(14_873_000, 28502)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PooledStaking::PendingCommissionChanges` (r:1 w:1)
+	/// Proof: `PooledStaking::PendingCommissionChanges` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `PooledStaking::CandidateCommission` (r:0 w:1)
+	/// Proof: `PooledStaking::CandidateCommission` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `PooledStaking::LastCommissionChange` (r:0 w:1)
+	/// Proof: `PooledStaking::LastCommissionChange` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn execute_commission_change() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1242`
+		//  Estimated: `3727`
+		// Minimum execution time: 18_311_000 picoseconds.
+		Weight::from_parts(18_311_000, 3727)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `PooledStaking::PendingCommissionChanges` (r:1 w:1)
+	/// Proof: `PooledStaking::PendingCommissionChanges` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn cancel_commission_change() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `262`
+		//  Estimated: `3727`
+		// Minimum execution time: 12_904_000 picoseconds.
+		Weight::from_parts(13_402_000, 3727)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PooledStaking::AutoExecutionOptIn` (r:0 w:1)
+	/// Proof: `PooledStaking::AutoExecutionOptIn` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_auto_execution() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_112_000 picoseconds.
+		Weight::from_parts(8_461_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `PooledStaking::LastRedelegationTo` (r:1 w:1)
+	/// Proof: `PooledStaking::LastRedelegationTo` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `PooledStaking::Pools` (r:8 w:8)
+	/// Proof: `PooledStaking::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `PooledStaking::SortedEligibleCandidates` (r:1 w:1)
+	/// Proof: `PooledStaking::SortedEligibleCandidates` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `PooledStaking::Redelegations` (r:1 w:1)
+	/// Proof: `PooledStaking::Redelegations` (`max_values`: None, `max_size`: Some(8502), added: 10977, mode: `MaxEncodedLen`)
+	fn redelegate(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `10430`
+		//  Estimated: `31290`
+		// The range of component `r` is `[0, 100]`.
+		// Minimum execution time: 165_337_000 picoseconds.
+		Weight::from_parts(165_337_000, 31290)
+			// Standard Error: 2_417
+			.saturating_add(Weight::from_parts(3_312_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2530).saturating_mul(r.into()))
+	}
 }
\ No newline at end of file
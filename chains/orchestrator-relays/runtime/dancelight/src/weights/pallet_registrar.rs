@@ -328,4 +328,34 @@ impl<T: frame_system::Config> pallet_registrar::WeightInfo for SubstrateWeight<T
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Registrar::ParaManager` (r:1 w:0)
+	/// Proof: `Registrar::ParaManager` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::ParaGenesisData` (r:1 w:0)
+	/// Proof: `Registrar::ParaGenesisData` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Registrar::CollatorCountRequests` (r:0 w:1)
+	/// Proof: `Registrar::CollatorCountRequests` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	fn set_collator_count_request() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `298`
+		//  Estimated: `3763`
+		// Minimum execution time: 17_512_000 picoseconds.
+		Weight::from_parts(18_204_000, 3763)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Registrar::ParaManager` (r:1 w:0)
+	/// Proof: `Registrar::ParaManager` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::ParaGenesisData` (r:1 w:0)
+	/// Proof: `Registrar::ParaGenesisData` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Registrar::CoreCountRequests` (r:0 w:1)
+	/// Proof: `Registrar::CoreCountRequests` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	fn set_core_count_request() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `298`
+		//  Estimated: `3763`
+		// Minimum execution time: 17_301_000 picoseconds.
+		Weight::from_parts(17_958_000, 3763)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
\ No newline at end of file
@@ -107,35 +107,61 @@ impl<T: frame_system::Config> pallet_services_payment::WeightInfo for SubstrateW
 	}
 	/// Storage: `ServicesPayment::BlockProductionCredits` (r:50 w:0)
 	/// Proof: `ServicesPayment::BlockProductionCredits` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:50 w:50)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:51 w:51)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:50 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `Session::CurrentIndex` (r:1 w:0)
+	/// Proof: `Session::CurrentIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ServicesPayment::SpendHistory` (r:50 w:50)
+	/// Proof: `ServicesPayment::SpendHistory` (`max_values`: None, `max_size`: Some(1701), added: 4176, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::LowBalanceThreshold` (r:50 w:0)
+	/// Proof: `ServicesPayment::LowBalanceThreshold` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::AutoTopUp` (r:50 w:50)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
 	/// The range of component `n` is `[1, 50]`.
 	fn on_container_authors_noted(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `177 + n * (132 ±0)`
-		//  Estimated: `990 + n * (2603 ±0)`
-		// Minimum execution time: 17_484_000 picoseconds.
-		Weight::from_parts(3_972_613, 990)
+		//  Measured:  `589 + n * (263 ±0)`
+		//  Estimated: `3593 + n * (4176 ±0)`
+		// Minimum execution time: 48_688_000 picoseconds.
+		Weight::from_parts(21_914_731, 3593)
 			// Standard Error: 305_529
-			.saturating_add(Weight::from_parts(14_021_908, 0).saturating_mul(n.into()))
-			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
-			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(33_338_310, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 4176).saturating_mul(n.into()))
 	}
 	/// Storage: `ServicesPayment::CollatorAssignmentCredits` (r:1 w:0)
 	/// Proof: `ServicesPayment::CollatorAssignmentCredits` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Session::CurrentIndex` (r:1 w:0)
+	/// Proof: `Session::CurrentIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ServicesPayment::SpendHistory` (r:1 w:1)
+	/// Proof: `ServicesPayment::SpendHistory` (`max_values`: None, `max_size`: Some(1701), added: 4176, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::LowBalanceThreshold` (r:1 w:0)
+	/// Proof: `ServicesPayment::LowBalanceThreshold` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::AutoTopUp` (r:1 w:1)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
 	/// Storage: `ServicesPayment::MaxTip` (r:1 w:0)
 	/// Proof: `ServicesPayment::MaxTip` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
 	fn on_collators_assigned() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `205`
-		//  Estimated: `3593`
-		// Minimum execution time: 34_564_000 picoseconds.
-		Weight::from_parts(34_564_000, 3593)
-			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+		//  Measured:  `691`
+		//  Estimated: `6196`
+		// Minimum execution time: 92_981_000 picoseconds.
+		Weight::from_parts(92_981_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: `ServicesPayment::MaxTip` (r:0 w:1)
 	/// Proof: `ServicesPayment::MaxTip` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
@@ -147,4 +173,106 @@ impl<T: frame_system::Config> pallet_services_payment::WeightInfo for SubstrateW
 		Weight::from_parts(5_781_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRate::ConversionRateToNative` (r:1 w:0)
+	/// Proof: `AssetRate::ConversionRateToNative` (`max_values`: None, `max_size`: Some(34), added: 2509, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(208), added: 2683, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:2 w:2)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn purchase_credits_in_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `560`
+		//  Estimated: `6208`
+		// Minimum execution time: 64_102_000 picoseconds.
+		Weight::from_parts(65_487_000, 6208)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:1)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:1 w:0)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRate::ConversionRateToNative` (r:1 w:0)
+	/// Proof: `AssetRate::ConversionRateToNative` (`max_values`: None, `max_size`: Some(34), added: 2509, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::AutoTopUp` (r:1 w:1)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
+	fn set_payment_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3599`
+		// Minimum execution time: 19_203_000 picoseconds.
+		Weight::from_parts(19_841_000, 3599)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `ServicesPayment::LowBalanceThreshold` (r:0 w:1)
+	/// Proof: `ServicesPayment::LowBalanceThreshold` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	fn set_low_balance_threshold() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 5_914_000 picoseconds.
+		Weight::from_parts(6_102_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ServicesPayment::AutoTopUp` (r:1 w:1)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	fn set_auto_top_up() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `3533`
+		// Minimum execution time: 9_835_000 picoseconds.
+		Weight::from_parts(10_211_000, 3533)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:1)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PriceMultiplierBounds` (r:0 w:1)
+	/// Proof: `ServicesPayment::PriceMultiplierBounds` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	fn set_price_multiplier_bounds() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `1501`
+		// Minimum execution time: 7_012_000 picoseconds.
+		Weight::from_parts(7_341_000, 1501)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `ServicesPayment::PriceMultiplierBounds` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplierBounds` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:1)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn update_price_multiplier() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `48`
+		//  Estimated: `1517`
+		// Minimum execution time: 6_528_000 picoseconds.
+		Weight::from_parts(6_703_000, 1517)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ServicesPayment::RefundAddress` (r:1 w:0)
+	/// Proof: `ServicesPayment::RefundAddress` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn withdraw_from_tank() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `310`
+		//  Estimated: `6196`
+		// Minimum execution time: 58_211_000 picoseconds.
+		Weight::from_parts(59_847_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
\ No newline at end of file
@@ -355,12 +355,13 @@ where
             // TODO: this doesn't take into account force authoring.
             // So a node with `force_authoring = true` will not propose a block for a parathread until the
             // `min_slot_freq` has elapsed.
+            // With several collators, all of them must agree on which one authors the next block
+            let next_block_slot = slot_freq
+                .next_parathread_block_slot(chain_head_slot, aux_data.authorities.len() as u32);
             match claim_mode {
-                ClaimMode::NormalAuthoring | ClaimMode::ForceAuthoring => {
-                    !slot_freq.should_parathread_author_block(slot, chain_head_slot)
-                }
+                ClaimMode::NormalAuthoring | ClaimMode::ForceAuthoring => slot < next_block_slot,
                 ClaimMode::ParathreadCoreBuying { drift_permitted } => {
-                    !slot_freq.should_parathread_buy_core(slot, drift_permitted, chain_head_slot)
+                    slot.saturating_add(drift_permitted) < next_block_slot
                 }
             }
         } else {
@@ -385,7 +386,12 @@ where
     P::Public: Codec + std::fmt::Debug,
     P::Signature: Codec,
 {
-    let expected_author = crate::slot_author::<P>(slot, authorities.as_slice());
+    // A parathread core is bought for a later slot, so the author of that slot buys it
+    let author_slot = match claim_mode {
+        ClaimMode::ParathreadCoreBuying { drift_permitted } => slot.saturating_add(drift_permitted),
+        ClaimMode::ForceAuthoring | ClaimMode::NormalAuthoring => slot,
+    };
+    let expected_author = crate::slot_author::<P>(author_slot, authorities.as_slice());
     // if running with force-authoring, as long as you are in the authority set, propose
     if claim_mode == ClaimMode::ForceAuthoring {
         authorities
@@ -602,9 +602,11 @@ where
 
                     let overseer_handle = &mut params.overseer_handle;
 
-                    // This needs to change to support elastic scaling, but for continuously
-                    // scheduled chains this ensures that the backlog will grow steadily.
-                    for n_built in 0..2 {
+                    // Build one block per core scheduled for the para (elastic scaling). With a
+                    // single core, build 2 blocks so that the backlog grows steadily for
+                    // continuously scheduled chains.
+                    let blocks_to_build = core_indices.len().max(2);
+                    for n_built in 0..blocks_to_build {
                         let validation_data = PersistedValidationData {
                             parent_head: parent_header.encode().into(),
                             relay_parent_number: *relay_parent_header.number(),
@@ -643,10 +645,9 @@ where
                             Ok(h) => h,
                         };
 
-                        // TODO: Currently we use just the first core here, but for elastic scaling
-                        // we iterate and build on all of the cores returned.
-                        // More info: https://github.com/paritytech/polkadot-sdk/issues/1829
-                        let (is_parachain, core_index) = match (&aux_data.slot_freq, core_indices.first()) {
+                        // Each block is submitted to the next core, parathreads only use the first one.
+                        let next_core_index = core_indices.get(n_built % core_indices.len().max(1));
+                        let (is_parachain, core_index) = match (&aux_data.slot_freq, next_core_index) {
                             (None, None) => {
                                 tracing::warn!(target: crate::LOG_TARGET, para_id = ?params.para_id, "We are parachain and we do not have core allocated, nothing to do");
                                 break;
@@ -150,6 +150,128 @@ async fn claim_slot_respects_min_slot_freq() {
     assert_eq!(claimed_slots, vec![0, 4, 8]);
 }
 
+#[tokio::test]
+async fn claim_slot_rotates_parathread_authors() {
+    // There are 2 authorities and blocks can only be authored every 2 slots, so the authorities
+    // take turns instead of the author of the last block always claiming the next one
+    let mut authorities: Vec<NimbusId> = vec![];
+    let mut keystores: Vec<KeystorePtr> = vec![];
+    let min_slot_freq = 2u32;
+
+    let mut keystore_paths = vec![];
+    for _ in 0..2 {
+        let keystore_path = tempfile::tempdir().expect("Creates keystore path");
+        let keystore = LocalKeystore::open(keystore_path.path(), None).expect("Creates keystore.");
+        let public = keystore
+            .sr25519_generate_new(NIMBUS_KEY_ID, None)
+            .expect("Key should be created");
+        authorities.push(public.into());
+        keystores.push(keystore.into());
+        keystore_paths.push(keystore_path);
+    }
+
+    // The last block was authored in slot 0, by authority 0
+    let mut claimed_slots: Vec<(u64, usize)> = vec![(0, 0)];
+
+    for slot in 1..=12 {
+        let parent_slot: Slot = claimed_slots.last().unwrap().0.into();
+        let head = TestHeader {
+            parent_hash: Default::default(),
+            number: claimed_slots.len() as u64,
+            state_root: Default::default(),
+            extrinsics_root: Default::default(),
+            digest: Digest {
+                logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, parent_slot.encode())],
+            },
+        };
+
+        let claims: Vec<usize> = keystores
+            .iter()
+            .enumerate()
+            .filter_map(|(i, keystore_ptr)| {
+                let aux_data = OrchestratorAuraWorkerAuxData {
+                    authorities: authorities.clone(),
+                    slot_freq: Some(SlotFrequency {
+                        min: min_slot_freq,
+                        max: 0u32,
+                    }),
+                };
+                tanssi_claim_slot::<NimbusPair, TestBlock>(
+                    aux_data,
+                    &head,
+                    slot.into(),
+                    ClaimMode::NormalAuthoring,
+                    keystore_ptr,
+                )
+                .map(|_| i)
+            })
+            .collect();
+
+        assert!(claims.len() <= 1, "competing claims in slot {}", slot);
+        if let Some(author) = claims.first() {
+            claimed_slots.push((slot, *author));
+        }
+    }
+
+    assert_eq!(claimed_slots, vec![(0, 0), (3, 1), (6, 0), (9, 1), (12, 0)]);
+}
+
+#[tokio::test]
+async fn parathread_core_is_bought_by_next_author() {
+    // With 2 authorities, the core for the next block is bought by the author of that block
+    let mut authorities: Vec<NimbusId> = vec![];
+    let mut keystores: Vec<KeystorePtr> = vec![];
+
+    let mut keystore_paths = vec![];
+    for _ in 0..2 {
+        let keystore_path = tempfile::tempdir().expect("Creates keystore path");
+        let keystore = LocalKeystore::open(keystore_path.path(), None).expect("Creates keystore.");
+        let public = keystore
+            .sr25519_generate_new(NIMBUS_KEY_ID, None)
+            .expect("Key should be created");
+        authorities.push(public.into());
+        keystores.push(keystore.into());
+        keystore_paths.push(keystore_path);
+    }
+
+    // The last block was authored in slot 0, by authority 0
+    let parent_slot: Slot = 0u64.into();
+    let head = TestHeader {
+        parent_hash: Default::default(),
+        number: 1,
+        state_root: Default::default(),
+        extrinsics_root: Default::default(),
+        digest: Digest {
+            logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, parent_slot.encode())],
+        },
+    };
+
+    let mut buys = vec![];
+    for slot in 1..=2u64 {
+        for (i, keystore_ptr) in keystores.iter().enumerate() {
+            let aux_data = OrchestratorAuraWorkerAuxData {
+                authorities: authorities.clone(),
+                slot_freq: Some(SlotFrequency { min: 2, max: 0 }),
+            };
+            let claim = tanssi_claim_slot::<NimbusPair, TestBlock>(
+                aux_data,
+                &head,
+                slot.into(),
+                ClaimMode::ParathreadCoreBuying {
+                    drift_permitted: 1u64.into(),
+                },
+                keystore_ptr,
+            );
+            if claim.is_some() {
+                buys.push((slot, i));
+            }
+        }
+    }
+
+    // Next block goes in slot 3, authored by authority 1
+    assert_eq!(buys, vec![(2, 1)]);
+}
+
 #[tokio::test]
 async fn collate_returns_correct_block() {
     let net = AuraTestNet::new(4);
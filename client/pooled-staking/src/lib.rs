@@ -0,0 +1,162 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! RPC client for Pooled Staking pallet
+
+pub use pallet_pooled_staking_runtime_api::PooledStakingApi as PooledStakingRuntimeApi;
+use {
+    core::marker::PhantomData,
+    jsonrpsee::{
+        core::{async_trait, RpcResult},
+        proc_macros::rpc,
+    },
+    pallet_pooled_staking_runtime_api::{CandidateStake, DelegatorStake, PendingOperation},
+    sp_api::ProvideRuntimeApi,
+    sp_arithmetic::Perbill,
+    sp_runtime::traits::Block as BlockT,
+    std::sync::Arc,
+};
+
+/// Top-level error type for the RPC handler.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Failed to fetch API
+    #[error("Failed to fetch API: {0}")]
+    ApiError(sp_api::ApiError),
+}
+
+#[rpc(client, server)]
+pub trait PooledStakingApi<Hash, AccountId, Balance, Instant> {
+    #[method(name = "tanssi_pooledStakingCandidateCommission")]
+    async fn candidate_commission(&self, block: Hash, candidate: AccountId) -> RpcResult<Perbill>;
+
+    #[method(name = "tanssi_pooledStakingDelegatorCandidates")]
+    async fn delegator_candidates(
+        &self,
+        block: Hash,
+        delegator: AccountId,
+    ) -> RpcResult<Vec<AccountId>>;
+
+    #[method(name = "tanssi_pooledStakingDelegatorStake")]
+    async fn delegator_stake(
+        &self,
+        block: Hash,
+        candidate: AccountId,
+        delegator: AccountId,
+    ) -> RpcResult<DelegatorStake<Balance>>;
+
+    #[method(name = "tanssi_pooledStakingDelegatorPendingOperations")]
+    async fn delegator_pending_operations(
+        &self,
+        block: Hash,
+        delegator: AccountId,
+    ) -> RpcResult<Vec<PendingOperation<AccountId, Instant, Balance>>>;
+
+    #[method(name = "tanssi_pooledStakingCandidateStake")]
+    async fn candidate_stake(
+        &self,
+        block: Hash,
+        candidate: AccountId,
+    ) -> RpcResult<CandidateStake<Balance>>;
+}
+
+pub struct PooledStaking<Client, Block> {
+    client: Arc<Client>,
+    _phantom: PhantomData<Block>,
+}
+
+impl<Client, Block> PooledStaking<Client, Block> {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<Block, Hash, Client, AccountId, Balance, Instant>
+    PooledStakingApiServer<Hash, AccountId, Balance, Instant> for PooledStaking<Client, Block>
+where
+    Hash: Send + 'static,
+    Block: BlockT<Hash = Hash>,
+    Client: ProvideRuntimeApi<Block> + Sync + Send + 'static,
+    Client::Api: PooledStakingRuntimeApi<Block, AccountId, Balance, Instant>,
+    AccountId: parity_scale_codec::Codec + Send + 'static,
+    Balance: parity_scale_codec::Codec + Send + 'static,
+    Instant: parity_scale_codec::Codec + Send + 'static,
+{
+    async fn candidate_commission(&self, block: Hash, candidate: AccountId) -> RpcResult<Perbill> {
+        self.client
+            .runtime_api()
+            .candidate_commission(block, candidate)
+            .map_err(|e| internal_err(Error::ApiError(e)))
+    }
+
+    async fn delegator_candidates(
+        &self,
+        block: Hash,
+        delegator: AccountId,
+    ) -> RpcResult<Vec<AccountId>> {
+        self.client
+            .runtime_api()
+            .delegator_candidates(block, delegator)
+            .map_err(|e| internal_err(Error::ApiError(e)))
+    }
+
+    async fn delegator_stake(
+        &self,
+        block: Hash,
+        candidate: AccountId,
+        delegator: AccountId,
+    ) -> RpcResult<DelegatorStake<Balance>> {
+        self.client
+            .runtime_api()
+            .delegator_stake(block, candidate, delegator)
+            .map_err(|e| internal_err(Error::ApiError(e)))
+    }
+
+    async fn delegator_pending_operations(
+        &self,
+        block: Hash,
+        delegator: AccountId,
+    ) -> RpcResult<Vec<PendingOperation<AccountId, Instant, Balance>>> {
+        self.client
+            .runtime_api()
+            .delegator_pending_operations(block, delegator)
+            .map_err(|e| internal_err(Error::ApiError(e)))
+    }
+
+    async fn candidate_stake(
+        &self,
+        block: Hash,
+        candidate: AccountId,
+    ) -> RpcResult<CandidateStake<Balance>> {
+        self.client
+            .runtime_api()
+            .candidate_stake(block, candidate)
+            .map_err(|e| internal_err(Error::ApiError(e)))
+    }
+}
+
+pub fn internal_err<T: ToString>(message: T) -> jsonrpsee::types::ErrorObjectOwned {
+    jsonrpsee::types::error::ErrorObject::borrowed(
+        jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+        &message.to_string(),
+        None,
+    )
+    .into_owned()
+}
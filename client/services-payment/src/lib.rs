@@ -23,6 +23,7 @@ use {
         core::{async_trait, RpcResult},
         proc_macros::rpc,
     },
+    pallet_services_payment_runtime_api::SessionSpend,
     sc_client_api::UsageProvider,
     sp_api::ProvideRuntimeApi,
     sp_runtime::traits::Block as BlockT,
@@ -30,12 +31,29 @@ use {
 };
 
 #[rpc(server)]
-pub trait ServicesPaymentApi<Balance, ParaId> {
+pub trait ServicesPaymentApi<Balance, ParaId, AssetId> {
     #[method(name = "tanssi_servicesPaymentBlockCost")]
     async fn block_cost(&self, para_id: ParaId) -> RpcResult<Balance>;
 
     #[method(name = "tanssi_servicesPaymentCollatorAssignmentCost")]
     async fn collator_assignment_cost(&self, para_id: ParaId) -> RpcResult<Balance>;
+
+    #[method(name = "tanssi_servicesPaymentBlockCostInAsset")]
+    async fn block_cost_in_asset(
+        &self,
+        para_id: ParaId,
+        asset_id: AssetId,
+    ) -> RpcResult<Option<Balance>>;
+
+    #[method(name = "tanssi_servicesPaymentCollatorAssignmentCostInAsset")]
+    async fn collator_assignment_cost_in_asset(
+        &self,
+        para_id: ParaId,
+        asset_id: AssetId,
+    ) -> RpcResult<Option<Balance>>;
+
+    #[method(name = "tanssi_servicesPaymentSpendHistory")]
+    async fn spend_history(&self, para_id: ParaId) -> RpcResult<Vec<SessionSpend<Balance>>>;
 }
 
 pub struct ServicesPayment<Client, Block> {
@@ -53,15 +71,16 @@ impl<Client, Block> ServicesPayment<Client, Block> {
 }
 
 #[async_trait]
-impl<Client, Hash, Block, Balance, ParaId> ServicesPaymentApiServer<Balance, ParaId>
-    for ServicesPayment<Client, Block>
+impl<Client, Hash, Block, Balance, ParaId, AssetId>
+    ServicesPaymentApiServer<Balance, ParaId, AssetId> for ServicesPayment<Client, Block>
 where
     Hash: Send + 'static,
     Block: BlockT<Hash = Hash>,
     Client: ProvideRuntimeApi<Block> + Sync + Send + UsageProvider<Block> + 'static,
-    Client::Api: ServicesPaymentRuntimeApi<Block, Balance, ParaId>,
+    Client::Api: ServicesPaymentRuntimeApi<Block, Balance, ParaId, AssetId>,
     Balance: parity_scale_codec::Codec + Send + 'static,
     ParaId: parity_scale_codec::Codec + Send + 'static,
+    AssetId: parity_scale_codec::Codec + Send + 'static,
 {
     async fn block_cost(&self, para_id: ParaId) -> RpcResult<Balance> {
         let cost = self
@@ -80,6 +99,45 @@ where
             .map_err(internal_err)?;
         Ok(cost)
     }
+
+    async fn block_cost_in_asset(
+        &self,
+        para_id: ParaId,
+        asset_id: AssetId,
+    ) -> RpcResult<Option<Balance>> {
+        let cost = self
+            .client
+            .runtime_api()
+            .block_cost_in_asset(self.client.usage_info().chain.best_hash, para_id, asset_id)
+            .map_err(internal_err)?;
+        Ok(cost)
+    }
+
+    async fn collator_assignment_cost_in_asset(
+        &self,
+        para_id: ParaId,
+        asset_id: AssetId,
+    ) -> RpcResult<Option<Balance>> {
+        let cost = self
+            .client
+            .runtime_api()
+            .collator_assignment_cost_in_asset(
+                self.client.usage_info().chain.best_hash,
+                para_id,
+                asset_id,
+            )
+            .map_err(internal_err)?;
+        Ok(cost)
+    }
+
+    async fn spend_history(&self, para_id: ParaId) -> RpcResult<Vec<SessionSpend<Balance>>> {
+        let history = self
+            .client
+            .runtime_api()
+            .spend_history(self.client.usage_info().chain.best_hash, para_id)
+            .map_err(internal_err)?;
+        Ok(history)
+    }
 }
 
 pub fn internal_err<T: ToString>(message: T) -> jsonrpsee::types::ErrorObjectOwned {
@@ -14,21 +14,32 @@
 // You should have received a copy of the GNU General Public License
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
-//! RPC client for Author Noting pallet
+//! RPC client for Stream Payment pallet
 
 pub use pallet_stream_payment_runtime_api::StreamPaymentApi as StreamPaymentRuntimeApi;
 use {
-    core::marker::PhantomData,
+    core::{hash::Hash as StdHash, marker::PhantomData},
+    futures::StreamExt,
     jsonrpsee::{
-        core::{async_trait, RpcResult},
+        core::{async_trait, RpcResult, SubscriptionResult},
         proc_macros::rpc,
+        PendingSubscriptionSink, SubscriptionMessage,
     },
-    pallet_stream_payment_runtime_api::{StreamPaymentApiError, StreamPaymentApiStatus},
-    sp_api::ProvideRuntimeApi,
+    pallet_stream_payment_runtime_api::{
+        StreamPaymentApiError, StreamPaymentApiEvent, StreamPaymentApiEventKind,
+        StreamPaymentApiParty, StreamPaymentApiStatus,
+    },
+    sc_client_api::BlockchainEvents,
+    serde::{Deserialize, Serialize},
+    sp_api::{ApiExt, ProvideRuntimeApi},
+    sp_blockchain::HeaderBackend,
     sp_runtime::traits::Block as BlockT,
-    std::sync::Arc,
+    std::{collections::HashSet, sync::Arc},
 };
 
+/// Maximum amount of stream ids returned by `tanssi_streamPaymentAccountStreams`.
+pub const MAX_ACCOUNT_STREAMS_PAGE_SIZE: u32 = 1000;
+
 /// Top-level error type for the RPC handler.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -41,8 +52,28 @@ pub enum Error {
     StreamPaymentApiError(StreamPaymentApiError),
 }
 
+/// Which events should be sent by `tanssi_subscribeStreamPaymentEvents`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StreamPaymentEventsFilter<AccountId, StreamId> {
+    /// Events of given stream.
+    Stream(StreamId),
+    /// Events of all streams in which the account is the source, the target or an additional
+    /// target.
+    Account(AccountId),
+}
+
+/// Stream payment events of an imported block matching the subscription filter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamPaymentEvents<Hash, AccountId, StreamId, Balance> {
+    pub block: Hash,
+    /// Whenever the block is the new best block. Events of blocks that are not part of the
+    /// best chain may be reverted.
+    pub is_new_best: bool,
+    pub events: Vec<StreamPaymentApiEvent<AccountId, StreamId, Balance>>,
+}
+
 #[rpc(client, server)]
-pub trait StreamPaymentApi<Hash, StreamId, Instant, Balance> {
+pub trait StreamPaymentApi<Hash, StreamId, Instant, Balance, AccountId> {
     #[method(name = "tanssi_streamPaymentStatus")]
     async fn stream_payment_status(
         &self,
@@ -50,6 +81,31 @@ pub trait StreamPaymentApi<Hash, StreamId, Instant, Balance> {
         stream_id: StreamId,
         now: Option<Instant>,
     ) -> RpcResult<StreamPaymentApiStatus<Balance>>;
+
+    /// Streams in which the account is the given party. At most `limit` stream ids are
+    /// returned (capped to `MAX_ACCOUNT_STREAMS_PAGE_SIZE`), starting after `start_after`.
+    /// Next page can be fetched by providing the last stream id of the previous page.
+    #[method(name = "tanssi_streamPaymentAccountStreams")]
+    async fn account_streams(
+        &self,
+        block: Hash,
+        account: AccountId,
+        party: StreamPaymentApiParty,
+        start_after: Option<StreamId>,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<StreamId>>;
+
+    /// Subscribe to the stream payment events (payments, deposit and config changes, change
+    /// requests, closures) of imported blocks matching the filter.
+    #[subscription(
+        name = "tanssi_subscribeStreamPaymentEvents" => "tanssi_streamPaymentEvents",
+        unsubscribe = "tanssi_unsubscribeStreamPaymentEvents",
+        item = StreamPaymentEvents<Hash, AccountId, StreamId, Balance>,
+    )]
+    async fn subscribe_stream_payment_events(
+        &self,
+        filter: StreamPaymentEventsFilter<AccountId, StreamId>,
+    ) -> SubscriptionResult;
 }
 
 pub struct StreamPayment<Client, Block> {
@@ -66,17 +122,115 @@ impl<Client, Block> StreamPayment<Client, Block> {
     }
 }
 
+impl<Block, Client> StreamPayment<Client, Block>
+where
+    Block: BlockT,
+    Client: ProvideRuntimeApi<Block>,
+{
+    /// Fetch all the pages of streams in which the account is the source or a target.
+    fn all_account_streams<StreamId, Instant, Balance, AccountId>(
+        &self,
+        block: Block::Hash,
+        account: &AccountId,
+    ) -> Result<HashSet<StreamId>, Error>
+    where
+        Client::Api: StreamPaymentRuntimeApi<Block, StreamId, Instant, Balance, AccountId>,
+        StreamId: parity_scale_codec::Codec + Copy + Eq + StdHash,
+        Instant: parity_scale_codec::Codec,
+        Balance: parity_scale_codec::Codec,
+        AccountId: parity_scale_codec::Codec + Clone,
+    {
+        let mut streams = HashSet::new();
+
+        for party in [StreamPaymentApiParty::Source, StreamPaymentApiParty::Target] {
+            let mut start_after = None;
+
+            loop {
+                let page = self
+                    .client
+                    .runtime_api()
+                    .account_streams(
+                        block,
+                        account.clone(),
+                        party,
+                        start_after,
+                        MAX_ACCOUNT_STREAMS_PAGE_SIZE,
+                    )
+                    .map_err(Error::ApiError)?;
+
+                start_after = page.last().copied();
+                let last_page = page.len() < MAX_ACCOUNT_STREAMS_PAGE_SIZE as usize;
+                streams.extend(page);
+
+                if last_page {
+                    break;
+                }
+            }
+        }
+
+        Ok(streams)
+    }
+}
+
+impl<AccountId, StreamId> StreamPaymentEventsFilter<AccountId, StreamId>
+where
+    AccountId: PartialEq,
+    StreamId: Copy + Eq + StdHash,
+{
+    /// Whenever the event matches the filter. When filtering by account, `known_streams` must
+    /// contain the streams of the account and is kept up to date with opened and closed streams.
+    fn matches<Balance>(
+        &self,
+        event: &StreamPaymentApiEvent<AccountId, StreamId, Balance>,
+        known_streams: &mut HashSet<StreamId>,
+    ) -> bool {
+        let account = match self {
+            Self::Stream(stream_id) => return event.stream_id == *stream_id,
+            Self::Account(account) => account,
+        };
+
+        // Stream accounts are empty if the stream is closed at the end of the block, but
+        // payments still contain the accounts involved.
+        let matches = known_streams.contains(&event.stream_id)
+            || event.accounts.contains(account)
+            || match &event.kind {
+                StreamPaymentApiEventKind::Payment { source, target, .. } => {
+                    source == account || target == account
+                }
+                StreamPaymentApiEventKind::PaymentSplit { target, .. } => target == account,
+                _ => false,
+            };
+
+        if matches {
+            if let StreamPaymentApiEventKind::Closed { .. } = event.kind {
+                known_streams.remove(&event.stream_id);
+            } else {
+                known_streams.insert(event.stream_id);
+            }
+        }
+
+        matches
+    }
+}
+
 #[async_trait]
-impl<Block, Hash, Client, StreamId, Instant, Balance>
-    StreamPaymentApiServer<Hash, StreamId, Instant, Balance> for StreamPayment<Client, Block>
+impl<Block, Hash, Client, StreamId, Instant, Balance, AccountId>
+    StreamPaymentApiServer<Hash, StreamId, Instant, Balance, AccountId>
+    for StreamPayment<Client, Block>
 where
-    Hash: Send + 'static,
+    Hash: Serialize + Send + 'static,
     Block: BlockT<Hash = Hash>,
-    Client: ProvideRuntimeApi<Block> + Sync + Send + 'static,
-    Client::Api: StreamPaymentRuntimeApi<Block, StreamId, Instant, Balance>,
-    StreamId: parity_scale_codec::Codec + Send + 'static,
+    Client: ProvideRuntimeApi<Block>
+        + HeaderBackend<Block>
+        + BlockchainEvents<Block>
+        + Sync
+        + Send
+        + 'static,
+    Client::Api: StreamPaymentRuntimeApi<Block, StreamId, Instant, Balance, AccountId>,
+    StreamId: parity_scale_codec::Codec + Serialize + Copy + Eq + StdHash + Send + Sync + 'static,
     Instant: parity_scale_codec::Codec + Send + 'static,
-    Balance: parity_scale_codec::Codec + Send + 'static,
+    Balance: parity_scale_codec::Codec + Serialize + Send + Sync + 'static,
+    AccountId: parity_scale_codec::Codec + Serialize + Clone + PartialEq + Send + Sync + 'static,
 {
     async fn stream_payment_status(
         &self,
@@ -84,15 +238,96 @@ where
         stream_id: StreamId,
         now: Option<Instant>,
     ) -> RpcResult<StreamPaymentApiStatus<Balance>> {
-        let status = self
-            .client
-            .runtime_api()
-            .stream_payment_status(block, stream_id, now)
+        let api = self.client.runtime_api();
+        let api_version = api
+            .api_version::<dyn StreamPaymentRuntimeApi<Block, StreamId, Instant, Balance, AccountId>>(
+                block,
+            )
             .map_err(|e| internal_err(Error::ApiError(e)))?
-            .map_err(|e| internal_err(Error::StreamPaymentApiError(e)))?;
+            .unwrap_or_default();
+
+        let status = if api_version >= 2 {
+            api.stream_payment_status(block, stream_id, now)
+                .map_err(|e| internal_err(Error::ApiError(e)))?
+        } else {
+            #[allow(deprecated)]
+            api.stream_payment_status_before_version_2(block, stream_id, now)
+                .map_err(|e| internal_err(Error::ApiError(e)))?
+                .map(Into::into)
+        }
+        .map_err(|e| internal_err(Error::StreamPaymentApiError(e)))?;
 
         Ok(status)
     }
+
+    async fn account_streams(
+        &self,
+        block: Hash,
+        account: AccountId,
+        party: StreamPaymentApiParty,
+        start_after: Option<StreamId>,
+        limit: Option<u32>,
+    ) -> RpcResult<Vec<StreamId>> {
+        let limit = limit
+            .unwrap_or(MAX_ACCOUNT_STREAMS_PAGE_SIZE)
+            .min(MAX_ACCOUNT_STREAMS_PAGE_SIZE);
+
+        let streams = self
+            .client
+            .runtime_api()
+            .account_streams(block, account, party, start_after, limit)
+            .map_err(|e| internal_err(Error::ApiError(e)))?;
+
+        Ok(streams)
+    }
+
+    async fn subscribe_stream_payment_events(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: StreamPaymentEventsFilter<AccountId, StreamId>,
+    ) -> SubscriptionResult {
+        let mut known_streams = match &filter {
+            StreamPaymentEventsFilter::Account(account) => {
+                self.all_account_streams(self.client.info().best_hash, account)?
+            }
+            StreamPaymentEventsFilter::Stream(_) => HashSet::new(),
+        };
+
+        let sink = pending.accept().await?;
+        let mut imports = self.client.import_notification_stream();
+
+        while let Some(notification) = imports.next().await {
+            if sink.is_closed() {
+                break;
+            }
+
+            // Blocks using a runtime without the events API are skipped.
+            let Ok(events) = self.client.runtime_api().stream_events(notification.hash) else {
+                continue;
+            };
+
+            let events: Vec<_> = events
+                .into_iter()
+                .filter(|event| filter.matches(event, &mut known_streams))
+                .collect();
+
+            if events.is_empty() {
+                continue;
+            }
+
+            let message = SubscriptionMessage::from_json(&StreamPaymentEvents {
+                block: notification.hash,
+                is_new_best: notification.is_new_best,
+                events,
+            })?;
+
+            if sink.send(message).await.is_err() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 pub fn internal_err<T: ToString>(message: T) -> jsonrpsee::types::ErrorObjectOwned {
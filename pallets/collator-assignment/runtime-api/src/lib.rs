@@ -16,16 +16,66 @@
 
 //! Runtime API for CollatorAssignment pallet. Can be used by collators to check
 //! which parachain will they be collating, as well as the current assignment of
-//! collators to parachains and parachains to collators.
+//! collators to parachains and parachains to collators. It also allows to simulate the
+//! assignment that would result from a different host configuration, and to check the
+//! liveness score of a collator and the assignment of past sessions.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use scale_info::prelude::vec::Vec;
+use {
+    dp_collator_assignment::AssignedCollators,
+    parity_scale_codec::{Decode, Encode},
+    scale_info::prelude::vec::Vec,
+};
+
+/// Outcome of a simulated collator assignment.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo)]
+pub struct CollatorAssignmentSimulation<AccountId, ParaId> {
+    /// Assignment that would be pending after the next session change.
+    pub assignment: AssignedCollators<AccountId>,
+    /// Registered container chains that would not be assigned any collators, either because
+    /// they cannot pay for the assignment or because there are not enough collators or cores.
+    pub dropped_chains: Vec<ParaId>,
+    /// Whether the assigned container chains would be charged a tip because of congestion.
+    pub tips_charged: bool,
+}
+
+/// Blocks produced by a collator during the last session, compared to the number of slots it was
+/// expected to fill.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo)]
+pub struct CollatorLiveness {
+    pub blocks_produced: u32,
+    pub slots_expected: u32,
+}
+
+/// Why a registered container chain was not assigned any collators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo)]
+pub enum DroppedChainReason {
+    /// The chain could not pay for the assignment.
+    NotEnoughCredits,
+    /// There were not enough cores for all the parachains.
+    NotEnoughCores,
+    /// There were not enough collators for all the chains.
+    NotEnoughCollators,
+    /// Not enough collators accept to be assigned to the chain.
+    NotEnoughEligibleCollators,
+}
+
+/// Collators assigned to a chain in a past session.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo)]
+pub struct ChainAssignmentRecord<AccountId> {
+    /// Collators assigned to the chain, empty if the chain was dropped.
+    pub collators: Vec<AccountId>,
+    /// Why the chain was not assigned any collators, if it was dropped.
+    pub dropped_reason: Option<DroppedChainReason>,
+}
 
 sp_api::decl_runtime_apis! {
-    pub trait CollatorAssignmentApi<AccountId, ParaId> where
+    #[api_version(4)]
+    pub trait CollatorAssignmentApi<AccountId, ParaId, HostConfiguration> where
         AccountId: parity_scale_codec::Codec,
         ParaId: parity_scale_codec::Codec,
+        HostConfiguration: parity_scale_codec::Codec,
     {
         /// Return the parachain that the given `AccountId` is collating for.
         /// Returns `None` if the `AccountId` is not collating.
@@ -37,5 +87,29 @@ sp_api::decl_runtime_apis! {
         /// Return the list of collators of the given `ParaId`.
         /// Returns `None` if the `ParaId` is not in the registrar.
         fn parachain_collators(para_id: ParaId) -> Option<Vec<AccountId>>;
+        /// Simulate the assignment of the next session change as if the given host configuration
+        /// and collators were used, without modifying the storage. At most `max_collators` of the
+        /// given collators are used. Chains and collators are only shuffled if called in the last
+        /// block of a session, when the randomness used for the assignment is known.
+        /// Returns `None` if the assignment could not be simulated.
+        #[api_version(2)]
+        fn simulate_collator_assignment(
+            config: HostConfiguration,
+            collators: Vec<AccountId>,
+        ) -> Option<CollatorAssignmentSimulation<AccountId, ParaId>>;
+        /// Return the liveness score of the given `AccountId` in the last session.
+        /// Returns `None` if the `AccountId` was not assigned to a container chain that produced
+        /// blocks in the last session.
+        #[api_version(3)]
+        fn collator_liveness(account: AccountId) -> Option<CollatorLiveness>;
+        /// Return the collators assigned to the given `ParaId` in the given session, and why the
+        /// chain was not assigned any collators if it was dropped.
+        /// Returns `None` if the session is no longer kept in the assignment history, or the
+        /// `ParaId` was not registered in that session.
+        #[api_version(4)]
+        fn chain_assignment_at_session(
+            session_index: u32,
+            para_id: ParaId,
+        ) -> Option<ChainAssignmentRecord<AccountId>>;
     }
 }
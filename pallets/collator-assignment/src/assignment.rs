@@ -15,6 +15,7 @@
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
 use {
+    crate::{CollatorTags, ParaIdsFilter},
     dp_collator_assignment::AssignedCollators,
     frame_support::traits::Get,
     sp_std::{
@@ -54,6 +55,9 @@ where
     /// algorithm to truncate the list of collators and only shuffle the first N. This ensures that
     /// shuffling doesn't cause a collator with low priority to be assigned instead of a collator
     /// with higher priority.
+    ///
+    /// New collators are spread across distinct operators and regions using `collator_tags`, and
+    /// never assigned to a container chain excluded by their `para_ids_filters`, see `assign_full`.
     pub fn assign_collators_always_keep_old<TShuffle>(
         collators: Vec<T::AccountId>,
         orchestrator_chain: ChainNumCollators,
@@ -61,6 +65,8 @@ where
         mut old_assigned: AssignedCollators<T::AccountId>,
         mut shuffle: Option<TShuffle>,
         full_rotation_mode: FullRotationModes,
+        collator_tags: &BTreeMap<T::AccountId, CollatorTags>,
+        para_ids_filters: &BTreeMap<T::AccountId, ParaIdsFilter>,
     ) -> Result<AssignedCollators<T::AccountId>, AssignmentError>
     where
         TShuffle: FnMut(&mut Vec<T::AccountId>),
@@ -111,8 +117,14 @@ where
         // invulnerables again. The downside is that the new invulnerables can be different.
         Self::prioritize_invulnerables(&collators, orchestrator_chain, &mut old_assigned);
 
-        let new_assigned_chains =
-            Self::assign_full(collators, chains_with_collators, old_assigned, shuffle)?;
+        let new_assigned_chains = Self::assign_full(
+            collators,
+            chains_with_collators,
+            old_assigned,
+            shuffle,
+            collator_tags,
+            para_ids_filters,
+        )?;
 
         let mut new_assigned = AssignedCollators {
             container_chains: new_assigned_chains,
@@ -385,6 +397,14 @@ where
     /// * `shuffle` is used to shuffle the list collators. The list will be truncated to only have
     ///   the number of required collators, to ensure that shuffling doesn't cause a collator with low
     ///   priority to be assigned instead of a collator with higher priority.
+    /// * `collator_tags` is used to choose which of the new collators goes to each chain: the
+    ///   collators that share fewer operators and regions with the collators already assigned to
+    ///   the chain go first. It does not change which collators are assigned, only to which chain.
+    /// * `para_ids_filters` are the container chains accepted by each collator. Old collators that
+    ///   no longer accept their chain are unassigned, and new collators are only assigned to the
+    ///   chains they accept. If none of the new collators accept a chain, the next unassigned
+    ///   collators that accept it are used, and if there are none the chain is left with fewer
+    ///   collators than requested. The orchestrator chain is not filtered.
     ///
     /// # Returns
     ///
@@ -397,6 +417,8 @@ where
         chains: Vec<(ParaId, u32)>,
         mut old_assigned: BTreeMap<ParaId, Vec<T::AccountId>>,
         shuffle: Option<TShuffle>,
+        collator_tags: &BTreeMap<T::AccountId, CollatorTags>,
+        para_ids_filters: &BTreeMap<T::AccountId, ParaIdsFilter>,
     ) -> Result<BTreeMap<ParaId, Vec<T::AccountId>>, AssignmentError>
     where
         TShuffle: FnOnce(&mut Vec<T::AccountId>),
@@ -423,6 +445,9 @@ where
             BTreeSet::from_iter(chains.iter().map(|(para_id, _num_collators)| *para_id));
         let collators_set = BTreeSet::from_iter(collators.iter().cloned());
         Self::retain_valid_old_assigned(&mut old_assigned, &para_ids_set, &collators_set);
+        for (para_id, cs) in old_assigned.iter_mut() {
+            cs.retain(|c| Self::can_collate(para_ids_filters, c, para_id));
+        }
 
         // Truncate num collators to required
         for (para_id, num_collators) in chains.iter() {
@@ -454,30 +479,97 @@ where
                 // Keep collators not already assigned
                 !assigned_collators.contains(x)
             })
-            .take(needed_new_collators)
             .collect();
+        // Only used when no new collator accepts a chain
+        let mut spare_collators =
+            new_collators.split_off(cmp::min(needed_new_collators, new_collators.len()));
         if let Some(shuffle) = shuffle {
             shuffle(&mut new_collators);
         }
-        let mut new_collators = new_collators.into_iter();
 
         // Fill missing collators
         for (para_id, num_collators) in chains.iter() {
             let cs = old_assigned.entry(*para_id).or_default();
 
             while cs.len() < *num_collators as usize {
-                // This error should never happen because we calculated `needed_new_collators`
-                // using the same algorithm
-                let nc = new_collators
-                    .next()
-                    .ok_or(AssignmentError::NotEnoughCollators)?;
-                cs.push(nc);
+                let eligible_collators: Vec<T::AccountId> = new_collators
+                    .iter()
+                    .filter(|c| Self::can_collate(para_ids_filters, c, para_id))
+                    .cloned()
+                    .collect();
+                if let Some(index) =
+                    Self::most_diverse_collator(&eligible_collators, cs, collator_tags)
+                {
+                    let collator = &eligible_collators[index];
+                    new_collators.retain(|c| c != collator);
+                    cs.push(collator.clone());
+                } else if let Some(index) = spare_collators
+                    .iter()
+                    .position(|c| Self::can_collate(para_ids_filters, c, para_id))
+                {
+                    cs.push(spare_collators.remove(index));
+                } else if para_ids_filters.is_empty() {
+                    // This error should never happen because we calculated `needed_new_collators`
+                    // using the same algorithm
+                    return Err(AssignmentError::NotEnoughCollators);
+                } else {
+                    break;
+                }
             }
         }
 
         Ok(old_assigned)
     }
 
+    /// Returns true if `collator` accepts to be assigned to `para_id`. Collators without a filter
+    /// accept any chain, and the orchestrator chain is accepted by all collators.
+    pub fn can_collate(
+        para_ids_filters: &BTreeMap<T::AccountId, ParaIdsFilter>,
+        collator: &T::AccountId,
+        para_id: &ParaId,
+    ) -> bool {
+        *para_id == T::SelfParaId::get()
+            || para_ids_filters
+                .get(collator)
+                .map_or(true, |filter| filter.can_assign(para_id))
+    }
+
+    /// Returns the index of the collator in `candidates` that shares the fewest operators and
+    /// regions with the `assigned` collators. Sharing an operator counts more than sharing a
+    /// region. Collators without tags do not share anything with other collators.
+    ///
+    /// Ties are broken by choosing the first candidate, so when no collator has tags the
+    /// candidates are used in order.
+    ///
+    /// Returns `None` if `candidates` is empty.
+    pub fn most_diverse_collator(
+        candidates: &[T::AccountId],
+        assigned: &[T::AccountId],
+        collator_tags: &BTreeMap<T::AccountId, CollatorTags>,
+    ) -> Option<usize> {
+        candidates
+            .iter()
+            .enumerate()
+            .min_by_key(|(_index, candidate)| {
+                let Some(tags) = collator_tags.get(candidate) else {
+                    return (0, 0);
+                };
+                let mut same_operator = 0u32;
+                let mut same_region = 0u32;
+                for other in assigned.iter().filter_map(|c| collator_tags.get(c)) {
+                    if tags.operator.is_some() && tags.operator == other.operator {
+                        same_operator += 1;
+                    }
+                    if tags.region.is_some() && tags.region == other.region {
+                        same_region += 1;
+                    }
+                }
+
+                (same_operator, same_region)
+            })
+            .map(|(index, _candidate)| index)
+    }
+
     /// Insert invulnerables ensuring that they are always the first in the list.
     /// The order of both lists is preserved.
     /// `assigned` may already contain the invulnerables, in that case they are only moved to the front.
@@ -525,6 +617,8 @@ pub enum AssignmentError {
 
 /// A `ParaId` and a range of collators that need to be assigned to it.
 /// This can be a container chain, a parathread, or the orchestrator chain.
+/// The range comes from the host configuration, or from the collator count requested by the para
+/// manager of a container chain.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ChainNumCollators {
     /// Para id.
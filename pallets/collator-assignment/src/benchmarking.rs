@@ -26,7 +26,7 @@ use {
         traits::{Currency, EnsureOrigin, Get},
     },
     frame_system::{EventRecord, RawOrigin},
-    sp_std::collections::btree_map::BTreeMap,
+    sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet},
     sp_std::prelude::*,
 };
 
@@ -118,5 +118,72 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn set_collator_tags() -> Result<(), BenchmarkError> {
+        let collator = invulnerable::<T>(0, SEED);
+        // Registering session keys requires the account to exist
+        T::Currency::make_free_balance_be(
+            &collator,
+            T::Currency::minimum_balance() * 100u32.into(),
+        );
+        T::CollatorRegistration::register_collator(&collator);
+        let tags = CollatorTags {
+            operator: Some(u32::MAX),
+            region: Some(vec![u8::MAX; 32].try_into().unwrap()),
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(collator.clone()), Some(tags.clone()));
+
+        assert_eq!(RegisteredCollatorTags::<T>::get(&collator), Some(tags));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn remove_collator_tags() -> Result<(), BenchmarkError> {
+        let caller = invulnerable::<T>(0, SEED);
+        // Collator without registered session keys
+        let collator = invulnerable::<T>(1, SEED);
+        RegisteredCollatorTags::<T>::insert(
+            &collator,
+            CollatorTags {
+                operator: Some(u32::MAX),
+                region: Some(vec![u8::MAX; 32].try_into().unwrap()),
+            },
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), collator.clone());
+
+        assert_eq!(RegisteredCollatorTags::<T>::get(&collator), None);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_para_ids_filter() -> Result<(), BenchmarkError> {
+        let collator = invulnerable::<T>(0, SEED);
+        // Registering session keys requires the account to exist
+        T::Currency::make_free_balance_be(
+            &collator,
+            T::Currency::minimum_balance() * 100u32.into(),
+        );
+        T::CollatorRegistration::register_collator(&collator);
+        let para_ids: ParaIdList = (0..100u32)
+            .map(|i| ParaId::from(2000 + i))
+            .collect::<BTreeSet<_>>()
+            .try_into()
+            .unwrap();
+        let filter = ParaIdsFilter::Whitelist(para_ids);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(collator.clone()), Some(filter.clone()));
+
+        assert_eq!(CollatorParaIdsFilters::<T>::get(&collator), Some(filter));
+
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
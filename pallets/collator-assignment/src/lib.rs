@@ -23,7 +23,8 @@
 //! The set of container chains is retrieved thanks to the GetContainerChains trait
 //! The number of collators to assign to the orchestrator chain and the number
 //! of collators to assign to each container chain is retrieved through the GetHostConfiguration
-//! trait.
+//! trait, unless the para manager of a container chain requested a custom number of collators.
+//! Custom collator counts are only respected while there are enough collators for all the chains.
 //!  
 //! The pallet uses the following approach:
 //!
@@ -38,24 +39,58 @@
 //! The reason for the collator-assignment pallet to work with a one-session delay assignment is because
 //! we want collators to know at least one session in advance the container chain/orchestrator that they
 //! are assigned to.
+//!
+//! Collators with registered session keys can optionally register an operator id and a region tag.
+//! When assigning new collators to a chain, collators that share fewer operators and regions with the
+//! collators already assigned to that chain are preferred. Collators that were already assigned to a
+//! chain are never moved because of their tags.
+//!
+//! The blocks authored by each collator are counted through the `AuthorNotingHook`. At the end of
+//! each session, every collator assigned to a container chain gets a liveness score: the blocks it
+//! produced versus the number of slots it was expected to fill, its share of the
+//! `ContainerSlotsPerSession` slots of its chain. Collators scoring below `MinLivenessScore` are
+//! picked last when assigning new collators to a chain.
+//!
+//! The assignment of the last `AssignmentHistoryDepth` sessions is kept in the AssignmentHistory
+//! storage, together with the registered chains that were not assigned any collators and why.
+//!
+//! When the relay chain cores are limited (`CoreAllocationConfiguration`), parachains can request
+//! more than one core (elastic scaling). Each parachain takes as many of the cores available to
+//! parachains as it requested, or the ones left if there are not enough, and the cores of the
+//! current and next sessions are kept in the ParachainCores storage for the relay chain scheduler.
+//!
+//! Collators can also register the container chains they accept to be assigned to, as an allow or
+//! deny list. Collators are never assigned to a container chain their list excludes, and a chain
+//! that does not have enough eligible collators to reach its min is dropped, reported in the
+//! ChainLacksEligibleCollators event.
+//!
+//! When collators or cores are scarce, chains are prioritized by the max tip they set. This works
+//! as a uniform price auction: every assigned chain that set a max tip pays the lowest max tip
+//! among them, and the outcome is reported in the TipAuctionCleared event.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use {
     crate::assignment::{Assignment, ChainNumCollators},
     core::ops::Mul,
-    frame_support::{pallet_prelude::*, traits::Currency},
-    frame_system::pallet_prelude::BlockNumberFor,
+    frame_support::{pallet_prelude::*, storage::with_transaction, traits::Currency},
+    frame_system::pallet_prelude::*,
     rand::{seq::SliceRandom, SeedableRng},
     rand_chacha::ChaCha20Rng,
     sp_runtime::{
-        traits::{AtLeast32BitUnsigned, One, Zero},
-        Perbill, Saturating,
+        traits::{AtLeast32BitUnsigned, CheckedSub, One, Zero},
+        Perbill, Saturating, TransactionOutcome,
+    },
+    sp_std::{
+        collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+        fmt::Debug,
+        prelude::*,
+        vec,
     },
-    sp_std::{collections::btree_set::BTreeSet, fmt::Debug, prelude::*, vec},
     tp_traits::{
-        CollatorAssignmentTip, FullRotationModes, GetContainerChainAuthor, GetHostConfiguration,
-        GetSessionContainerChains, ParaId, ParaIdAssignmentHooks, RemoveInvulnerables,
+        AuthorNotingHook, AuthorNotingInfo, CollatorAssignmentTip, FullRotationModes,
+        GetContainerChainAuthor, GetHostConfiguration, GetSessionContainerChains,
+        IsCollatorRegistered, ParaId, ParaIdAssignmentHooks, RemoveInvulnerables,
         ShouldRotateAllCollators, Slot,
     },
 };
@@ -79,6 +114,102 @@ pub struct CoreAllocationConfiguration {
     pub max_parachain_percentage: Perbill,
 }
 
+/// Region tag of a collator, such as `eu-west` or the name of a data center.
+pub type RegionTag = BoundedVec<u8, ConstU32<32>>;
+
+/// Operator and region optionally registered by a collator, used to spread the collators of each
+/// chain across distinct operators and regions.
+#[derive(Clone, Default, Encode, Decode, Debug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct CollatorTags {
+    /// Id of the entity running the collator.
+    pub operator: Option<u32>,
+    /// Where the collator is running.
+    pub region: Option<RegionTag>,
+}
+
+/// List of container chains in a `ParaIdsFilter`.
+pub type ParaIdList = BoundedBTreeSet<ParaId, ConstU32<100>>;
+
+/// Container chains a collator accepts to be assigned to, optionally registered by the collator,
+/// like the para ids of data preserver profiles. The orchestrator chain is not affected.
+pub type ParaIdsFilter = tp_traits::ParaIdsFilter<ConstU32<100>>;
+
+/// Blocks produced by a collator during the last session, and the number of blocks it was
+/// expected to produce.
+#[derive(Clone, Default, Encode, Decode, Debug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct LivenessScore {
+    pub blocks_produced: u32,
+    pub slots_expected: u32,
+}
+
+impl LivenessScore {
+    /// Ratio of produced blocks to expected slots, capped at 100%.
+    pub fn ratio(&self) -> Perbill {
+        if self.slots_expected == 0 {
+            return Perbill::one();
+        }
+
+        Perbill::from_rational(
+            self.blocks_produced.min(self.slots_expected),
+            self.slots_expected,
+        )
+    }
+}
+
+/// Why a registered container chain was not assigned any collators.
+#[derive(Clone, Copy, Encode, Decode, Debug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum DroppedChainReason {
+    /// The chain could not pay for the assignment.
+    NotEnoughCredits,
+    /// There were not enough cores for all the parachains.
+    NotEnoughCores,
+    /// There were not enough collators for all the chains.
+    NotEnoughCollators,
+    /// Not enough collators accept to be assigned to the chain, see `ParaIdsFilter`.
+    NotEnoughEligibleCollators,
+}
+
+/// Assignment of a session, kept in the assignment history.
+#[derive(Clone, Default, Encode, Decode, Debug, PartialEq, Eq, TypeInfo)]
+pub struct SessionAssignment<AccountId> {
+    pub assignment: AssignedCollators<AccountId>,
+    /// Registered container chains that were not assigned any collators.
+    pub dropped_chains: BTreeMap<ParaId, DroppedChainReason>,
+}
+
+/// Host configuration values used to compute the collator assignment.
+#[derive(Clone, Encode, Decode, Debug, PartialEq, Eq, TypeInfo)]
+pub struct AssignmentHostConfiguration {
+    pub max_collators: u32,
+    pub min_orchestrator_collators: u32,
+    pub max_orchestrator_collators: u32,
+    pub collators_per_container: u32,
+    pub collators_per_parathread: u32,
+    pub max_parachain_cores_percentage: Option<Perbill>,
+    pub full_rotation_mode: FullRotationModes,
+}
+
+impl AssignmentHostConfiguration {
+    /// Read the configuration that applies at the given session.
+    pub fn at_session<T: Config>(session_index: T::SessionIndex) -> Self {
+        Self {
+            max_collators: T::HostConfiguration::max_collators(session_index),
+            min_orchestrator_collators: T::HostConfiguration::min_collators_for_orchestrator(
+                session_index,
+            ),
+            max_orchestrator_collators: T::HostConfiguration::max_collators_for_orchestrator(
+                session_index,
+            ),
+            collators_per_container: T::HostConfiguration::collators_per_container(session_index),
+            collators_per_parathread: T::HostConfiguration::collators_per_parathread(session_index),
+            max_parachain_cores_percentage: T::HostConfiguration::max_parachain_cores_percentage(
+                session_index,
+            ),
+            full_rotation_mode: T::HostConfiguration::full_rotation_mode(session_index),
+        }
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -110,6 +241,20 @@ pub mod pallet {
         type CollatorAssignmentTip: CollatorAssignmentTip<BalanceOf<Self>>;
         type ForceEmptyOrchestrator: Get<bool>;
         type CoreAllocationConfiguration: Get<Option<CoreAllocationConfiguration>>;
+        /// Only collators with registered session keys can register tags, to avoid filling the
+        /// storage with tags of accounts that will never be collators.
+        type CollatorRegistration: IsCollatorRegistered<Self::AccountId>;
+        /// Collators whose liveness score in the last session is below this value are only
+        /// assigned to new chains after every other collator.
+        #[pallet::constant]
+        type MinLivenessScore: Get<Perbill>;
+        /// Number of container chain slots in a session, used to compute the slots each collator
+        /// was expected to fill.
+        #[pallet::constant]
+        type ContainerSlotsPerSession: Get<u32>;
+        /// Number of sessions kept in the assignment history.
+        #[pallet::constant]
+        type AssignmentHistoryDepth: Get<u32>;
         /// The weight information of this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -123,6 +268,41 @@ pub mod pallet {
             target_session: T::SessionIndex,
             full_rotation_mode: FullRotationModes,
         },
+        /// A collator registered or removed its tags.
+        CollatorTagsSet {
+            collator: T::AccountId,
+            tags: Option<CollatorTags>,
+        },
+        /// A collator registered or removed the list of container chains it accepts.
+        ParaIdsFilterSet {
+            collator: T::AccountId,
+            filter: Option<ParaIdsFilter>,
+        },
+        /// Not enough collators accept to be assigned to `para_id` to reach its min collators, so
+        /// it will not be assigned collators in `target_session`.
+        ChainLacksEligibleCollators {
+            target_session: T::SessionIndex,
+            para_id: ParaId,
+        },
+        /// Collators or cores were scarce when computing the assignment of `target_session`, and
+        /// the chains that set a max tip competed for them. Winners pay the clearing price, the
+        /// lowest max tip among them. Losers set a max tip but were not assigned collators.
+        TipAuctionCleared {
+            target_session: T::SessionIndex,
+            clearing_price: BalanceOf<T>,
+            winners: Vec<ParaId>,
+            losers: Vec<ParaId>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The collator does not have registered session keys.
+        CollatorNotRegistered,
+        /// The collator still has registered session keys.
+        CollatorStillRegistered,
+        /// The collator does not have registered tags.
+        CollatorTagsNotFound,
     }
 
     #[pallet::storage]
@@ -152,8 +332,123 @@ pub mod pallet {
     #[pallet::storage]
     pub type CollatorFullnessRatio<T: Config> = StorageValue<_, Perbill, OptionQuery>;
 
+    /// Operator and region registered by each collator.
+    #[pallet::storage]
+    pub type RegisteredCollatorTags<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, CollatorTags, OptionQuery>;
+
+    /// Container chains each collator accepts to be assigned to. Collators without an entry
+    /// accept any chain.
+    #[pallet::storage]
+    pub type CollatorParaIdsFilters<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ParaIdsFilter, OptionQuery>;
+
+    /// Number of container chain blocks authored by each collator in the current session.
+    #[pallet::storage]
+    pub type BlocksProduced<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Liveness score of the collators that were assigned to a container chain in the last session.
+    #[pallet::storage]
+    pub type LivenessScores<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, LivenessScore, OptionQuery>;
+
+    /// Assignment of the last `AssignmentHistoryDepth` sessions, including the next session.
+    /// Entries older than that are removed when a new assignment is computed.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type AssignmentHistory<T: Config> =
+        StorageMap<_, Twox64Concat, T::SessionIndex, SessionAssignment<T::AccountId>, OptionQuery>;
+
+    /// Relay chain cores of the assigned parachains that requested more than one core, for the
+    /// current and next sessions. Only used with a `CoreAllocationConfiguration`.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type ParachainCores<T: Config> =
+        StorageMap<_, Twox64Concat, T::SessionIndex, BTreeMap<ParaId, u32>, ValueQuery>;
+
     #[pallet::call]
-    impl<T: Config> Pallet<T> {}
+    impl<T: Config> Pallet<T> {
+        /// Register the operator and region of the caller collator, used in the next
+        /// assignments. `None` or empty tags remove the previously registered tags.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::set_collator_tags())]
+        pub fn set_collator_tags(
+            origin: OriginFor<T>,
+            tags: Option<CollatorTags>,
+        ) -> DispatchResult {
+            let collator = ensure_signed(origin)?;
+            let tags = tags.filter(|tags| tags != &CollatorTags::default());
+
+            if let Some(tags) = &tags {
+                ensure!(
+                    T::CollatorRegistration::is_collator_registered(&collator),
+                    Error::<T>::CollatorNotRegistered
+                );
+                RegisteredCollatorTags::<T>::insert(&collator, tags);
+            } else {
+                RegisteredCollatorTags::<T>::remove(&collator);
+            }
+
+            Self::deposit_event(Event::CollatorTagsSet { collator, tags });
+
+            Ok(())
+        }
+
+        /// Register the container chains the caller collator accepts to be assigned to, used in
+        /// the next assignments. Collators already assigned to a chain they no longer accept are
+        /// moved. `None` or `AnyParaId` remove the previously registered filter.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::set_para_ids_filter())]
+        pub fn set_para_ids_filter(
+            origin: OriginFor<T>,
+            filter: Option<ParaIdsFilter>,
+        ) -> DispatchResult {
+            let collator = ensure_signed(origin)?;
+            let filter = filter.filter(|filter| filter != &ParaIdsFilter::AnyParaId);
+
+            if let Some(filter) = &filter {
+                ensure!(
+                    T::CollatorRegistration::is_collator_registered(&collator),
+                    Error::<T>::CollatorNotRegistered
+                );
+                CollatorParaIdsFilters::<T>::insert(&collator, filter);
+            } else {
+                CollatorParaIdsFilters::<T>::remove(&collator);
+            }
+
+            Self::deposit_event(Event::ParaIdsFilterSet { collator, filter });
+
+            Ok(())
+        }
+
+        /// Remove the tags of a collator that no longer has registered session keys. Can be
+        /// called by any account.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::remove_collator_tags())]
+        pub fn remove_collator_tags(
+            origin: OriginFor<T>,
+            collator: T::AccountId,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            ensure!(
+                !T::CollatorRegistration::is_collator_registered(&collator),
+                Error::<T>::CollatorStillRegistered
+            );
+            ensure!(
+                RegisteredCollatorTags::<T>::take(&collator).is_some(),
+                Error::<T>::CollatorTagsNotFound
+            );
+
+            Self::deposit_event(Event::CollatorTagsSet {
+                collator,
+                tags: None,
+            });
+
+            Ok(())
+        }
+    }
 
     /// A struct that holds the assignment that is active after the session change and optionally
     /// the assignment that becomes active after the next session change.
@@ -166,50 +461,110 @@ pub mod pallet {
         pub num_total_registered_paras: u32,
     }
 
+    /// Outcome of a simulated assignment, see [`Pallet::simulate_assignment`].
+    pub struct AssignmentSimulation<T: Config> {
+        /// Assignment that would be pending after the session change.
+        pub assignment: AssignedCollators<T::AccountId>,
+        /// Registered container chains that would not be assigned any collators.
+        pub dropped_chains: Vec<ParaId>,
+        /// Whether the assigned container chains would be charged a tip.
+        pub tips_charged: bool,
+    }
+
+    struct ComputedAssignment<T: Config> {
+        old_assigned: AssignedCollators<T::AccountId>,
+        new_assigned: AssignedCollators<T::AccountId>,
+        registered_para_ids: Vec<ParaId>,
+        dropped_chains: BTreeMap<ParaId, DroppedChainReason>,
+        maybe_tip: Option<BalanceOf<T>>,
+        parachain_cores: BTreeMap<ParaId, u32>,
+        chains_lacking_eligible_collators: Vec<ParaId>,
+    }
+
     impl<T: Config> Pallet<T> {
-        pub(crate) fn enough_collators_for_all_chains(
-            bulk_paras: &Vec<ChainNumCollators>,
-            pool_paras: &Vec<ChainNumCollators>,
-            target_session_index: T::SessionIndex,
+        pub(crate) fn enough_collators_for_all_chains<'a>(
+            chains: impl IntoIterator<Item = &'a ChainNumCollators>,
+            min_orchestrator_collators: u32,
+            number_of_collators: u32,
+        ) -> bool {
+            let required_collators = chains
+                .into_iter()
+                .fold(min_orchestrator_collators, |required, chain| {
+                    required.saturating_add(chain.min_collators)
+                });
+
+            number_of_collators >= required_collators
+        }
+
+        /// Collator counts requested by para managers are only respected if there are enough
+        /// collators for all the chains to reach their min. Otherwise, the min of chains that
+        /// requested more collators than the default is lowered to the default, so that those
+        /// chains cannot leave chains using the default values without collators. Their max is
+        /// kept, so they can still get extra collators once all the chains have reached their min.
+        /// This must be applied before ordering the chains, so that the tip is only charged if
+        /// the lowered min are still not enough.
+        pub(crate) fn limit_collator_count_requests_on_scarcity(
+            bulk_paras: &mut [ChainNumCollators],
+            pool_paras: &mut [ChainNumCollators],
+            min_orchestrator_collators: u32,
             number_of_collators: u32,
             collators_per_container: u32,
             collators_per_parathread: u32,
-        ) -> bool {
-            number_of_collators
-                >= T::HostConfiguration::min_collators_for_orchestrator(target_session_index)
-                    .saturating_add(collators_per_container.saturating_mul(bulk_paras.len() as u32))
-                    .saturating_add(
-                        collators_per_parathread.saturating_mul(pool_paras.len() as u32),
-                    )
+        ) {
+            if Self::enough_collators_for_all_chains(
+                bulk_paras.iter().chain(pool_paras.iter()),
+                min_orchestrator_collators,
+                number_of_collators,
+            ) {
+                return;
+            }
+
+            for chain in bulk_paras.iter_mut().chain(pool_paras.iter_mut()) {
+                let default_min = if chain.parathread {
+                    collators_per_parathread
+                } else {
+                    collators_per_container
+                };
+                chain.min_collators = chain.min_collators.min(default_min);
+            }
         }
 
         /// Takes the bulk paras (parachains) and pool paras (parathreads)
         /// and checks if we if a) Do we have enough collators? b) Do we have enough cores?
         /// If either of the answer is yes. We  separately sort bulk_paras and pool_paras and
         /// then append the two vectors.
+        /// Bulk paras use the number of cores in `core_count_requests`, or 1 core. The first para
+        /// that requested more cores than the ones left gets the remaining cores, and
+        /// `core_count_requests` is updated with the cores each bulk para gets.
         pub(crate) fn order_paras_with_core_config(
             mut bulk_paras: Vec<ChainNumCollators>,
             mut pool_paras: Vec<ChainNumCollators>,
             core_allocation_configuration: &CoreAllocationConfiguration,
-            target_session_index: T::SessionIndex,
+            core_count_requests: &mut BTreeMap<ParaId, u32>,
+            min_orchestrator_collators: u32,
             number_of_collators: u32,
-            collators_per_container: u32,
-            collators_per_parathread: u32,
         ) -> (Vec<ChainNumCollators>, bool) {
             let core_count = core_allocation_configuration.core_count;
             let max_number_of_bulk_paras = core_allocation_configuration
                 .max_parachain_percentage
                 .mul(core_count);
+            let cores_of = |chain: &ChainNumCollators| {
+                core_count_requests
+                    .get(&chain.para_id)
+                    .copied()
+                    .unwrap_or(1)
+            };
 
-            let enough_cores_for_bulk_paras = bulk_paras.len() <= max_number_of_bulk_paras as usize;
+            let enough_cores_for_bulk_paras = bulk_paras
+                .iter()
+                .map(cores_of)
+                .fold(0u32, |acc, cores| acc.saturating_add(cores))
+                <= max_number_of_bulk_paras;
 
             let enough_collators = Self::enough_collators_for_all_chains(
-                &bulk_paras,
-                &pool_paras,
-                target_session_index,
+                bulk_paras.iter().chain(pool_paras.iter()),
+                min_orchestrator_collators,
                 number_of_collators,
-                collators_per_container,
-                collators_per_parathread,
             );
 
             // We should charge tip if parachain demand exceeds the `max_number_of_bulk_paras` OR
@@ -233,7 +588,20 @@ pub mod pallet {
                 });
             }
 
-            bulk_paras.truncate(max_number_of_bulk_paras as usize);
+            // Keep the bulk paras in order while there are cores left. A para that requested more
+            // cores than the ones left gets the remaining ones.
+            let mut available_cores = max_number_of_bulk_paras;
+            let mut assigned_cores = BTreeMap::new();
+            bulk_paras.retain(|chain| {
+                let cores = cores_of(chain).min(available_cores);
+                if cores == 0 {
+                    return false;
+                }
+                available_cores -= cores;
+                assigned_cores.insert(chain.para_id, cores);
+                true
+            });
+            *core_count_requests = assigned_cores;
             // We are not truncating pool paras, since their workload is not continuous one core
             // can be shared by many paras during the session.
 
@@ -245,19 +613,14 @@ pub mod pallet {
         pub(crate) fn order_paras(
             bulk_paras: Vec<ChainNumCollators>,
             pool_paras: Vec<ChainNumCollators>,
-            target_session_index: T::SessionIndex,
+            min_orchestrator_collators: u32,
             number_of_collators: u32,
-            collators_per_container: u32,
-            collators_per_parathread: u32,
         ) -> (Vec<ChainNumCollators>, bool) {
             // Are there enough collators to satisfy the minimum demand?
             let enough_collators_for_all_chain = Self::enough_collators_for_all_chains(
-                &bulk_paras,
-                &pool_paras,
-                target_session_index,
+                bulk_paras.iter().chain(pool_paras.iter()),
+                min_orchestrator_collators,
                 number_of_collators,
-                collators_per_container,
-                collators_per_parathread,
             );
 
             let mut chains: Vec<_> = bulk_paras.into_iter().chain(pool_paras).collect();
@@ -281,21 +644,177 @@ pub mod pallet {
             random_seed: [u8; 32],
             collators: Vec<T::AccountId>,
         ) -> SessionChangeOutcome<T> {
-            let maybe_core_allocation_configuration = T::CoreAllocationConfiguration::get();
             // We work with one session delay to calculate assignments
             let session_delay = T::SessionIndex::one();
             let target_session_index = current_session_index.saturating_add(session_delay);
+            let config = AssignmentHostConfiguration::at_session::<T>(target_session_index);
+
+            // Blocks noted until now were produced in the session that is ending
+            if let Some(ending_session_index) = current_session_index.checked_sub(&One::one()) {
+                frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                    Self::update_liveness_scores(ending_session_index),
+                    DispatchClass::Mandatory,
+                );
+            }
+
+            // We assign new collators
+            // we use the config scheduled at the target_session_index
+            let full_rotation =
+                T::ShouldRotateAllCollators::should_rotate_all_collators(target_session_index);
+            if full_rotation {
+                log::info!(
+                    "Collator assignment: rotating collators. Session {:?}, Seed: {:?}",
+                    current_session_index.encode(),
+                    random_seed
+                );
+            } else {
+                log::info!(
+                    "Collator assignment: keep old assigned. Session {:?}, Seed: {:?}",
+                    current_session_index.encode(),
+                    random_seed
+                );
+            }
+
+            Self::deposit_event(Event::NewPendingAssignment {
+                random_seed,
+                full_rotation,
+                target_session: target_session_index,
+                full_rotation_mode: if full_rotation {
+                    config.full_rotation_mode.clone()
+                } else {
+                    FullRotationModes::keep_all()
+                },
+            });
+
+            let ComputedAssignment {
+                old_assigned,
+                new_assigned,
+                registered_para_ids,
+                dropped_chains,
+                maybe_tip,
+                parachain_cores,
+                chains_lacking_eligible_collators,
+            } = Self::compute_assignment(
+                target_session_index,
+                random_seed,
+                collators,
+                &config,
+                T::CoreAllocationConfiguration::get(),
+                full_rotation,
+            );
+            let num_total_registered_paras = registered_para_ids.len() as u32;
+
+            for para_id in chains_lacking_eligible_collators {
+                Self::deposit_event(Event::ChainLacksEligibleCollators {
+                    target_session: target_session_index,
+                    para_id,
+                });
+            }
+
+            if let Some(clearing_price) = maybe_tip {
+                let (winners, losers): (Vec<ParaId>, Vec<ParaId>) = registered_para_ids
+                    .iter()
+                    .cloned()
+                    .filter(|para_id| T::CollatorAssignmentTip::get_para_tip(*para_id).is_some())
+                    .partition(|para_id| {
+                        new_assigned
+                            .container_chains
+                            .get(para_id)
+                            .map_or(false, |collators| !collators.is_empty())
+                    });
+                Self::deposit_event(Event::TipAuctionCleared {
+                    target_session: target_session_index,
+                    clearing_price,
+                    winners,
+                    losers,
+                });
+            }
+
+            Self::store_collator_fullness(&new_assigned, config.max_collators);
+
+            let session_assignment = SessionAssignment {
+                assignment: new_assigned.clone(),
+                dropped_chains,
+            };
+            // The assignment of session 0 is also the assignment of session 1
+            if current_session_index == &T::SessionIndex::zero() {
+                Self::store_assignment_history(*current_session_index, session_assignment.clone());
+            }
+            Self::store_assignment_history(target_session_index, session_assignment);
+
+            // Only the cores of the current and next sessions are needed
+            if let Some(old_session) = current_session_index.checked_sub(&One::one()) {
+                ParachainCores::<T>::remove(old_session);
+            }
+            if current_session_index == &T::SessionIndex::zero() {
+                ParachainCores::<T>::insert(current_session_index, parachain_cores.clone());
+            }
+            ParachainCores::<T>::insert(target_session_index, parachain_cores);
+
+            let mut pending = PendingCollatorContainerChain::<T>::get();
+
+            let old_assigned_changed = old_assigned != new_assigned;
+            let mut pending_changed = false;
+            // Update CollatorContainerChain using last entry of pending, if needed
+            if let Some(current) = pending.take() {
+                pending_changed = true;
+                CollatorContainerChain::<T>::put(current);
+            }
+            if old_assigned_changed {
+                pending = Some(new_assigned.clone());
+                pending_changed = true;
+            }
+            // Update PendingCollatorContainerChain, if it changed
+            if pending_changed {
+                PendingCollatorContainerChain::<T>::put(pending);
+            }
 
-            let collators_per_container =
-                T::HostConfiguration::collators_per_container(target_session_index);
-            let collators_per_parathread =
-                T::HostConfiguration::collators_per_parathread(target_session_index);
+            // Only applies to session index 0
+            if current_session_index == &T::SessionIndex::zero() {
+                CollatorContainerChain::<T>::put(new_assigned.clone());
+                return SessionChangeOutcome {
+                    active_assignment: new_assigned.clone(),
+                    next_assignment: new_assigned,
+                    num_total_registered_paras,
+                };
+            }
+
+            SessionChangeOutcome {
+                active_assignment: old_assigned,
+                next_assignment: new_assigned,
+                num_total_registered_paras,
+            }
+        }
+
+        /// Compute the assignment of the target session using the given configuration. The
+        /// assignment hooks are called, but this pallet storage is not modified.
+        fn compute_assignment(
+            target_session_index: T::SessionIndex,
+            random_seed: [u8; 32],
+            collators: Vec<T::AccountId>,
+            config: &AssignmentHostConfiguration,
+            maybe_core_allocation_configuration: Option<CoreAllocationConfiguration>,
+            full_rotation: bool,
+        ) -> ComputedAssignment<T> {
+            let collators_per_container = config.collators_per_container;
+            let collators_per_parathread = config.collators_per_parathread;
 
             // We get the containerChains that we will have at the target session
             let container_chains =
                 T::ContainerChains::session_container_chains(target_session_index);
-            let num_total_registered_paras =
-                (container_chains.parachains.len() + container_chains.parathreads.len()) as u32;
+            let registered_para_ids: Vec<ParaId> = container_chains
+                .parachains
+                .iter()
+                .cloned()
+                .chain(
+                    container_chains
+                        .parathreads
+                        .iter()
+                        .map(|(para_id, _)| *para_id),
+                )
+                .collect();
+            let collator_count_requests = container_chains.collator_count_requests;
+            let mut core_count_requests = container_chains.core_count_requests;
             let mut container_chain_ids = container_chains.parachains;
             let mut parathreads: Vec<_> = container_chains
                 .parathreads
@@ -316,6 +835,11 @@ pub mod pallet {
             // TODO: parathreads should be treated a bit differently, they don't need to have the same amount of credits
             // as parathreads because they will not be producing blocks on every slot.
             T::ParaIdAssignmentHooks::pre_assignment(&mut parathreads, &old_assigned_para_ids);
+            let funded_para_ids: BTreeSet<ParaId> = container_chain_ids
+                .iter()
+                .chain(parathreads.iter())
+                .cloned()
+                .collect();
 
             let mut shuffle_collators = None;
             // If the random_seed is all zeros, we don't shuffle the list of collators nor the list
@@ -340,12 +864,8 @@ pub mod pallet {
             } else {
                 ChainNumCollators {
                     para_id: T::SelfParaId::get(),
-                    min_collators: T::HostConfiguration::min_collators_for_orchestrator(
-                        target_session_index,
-                    ),
-                    max_collators: T::HostConfiguration::max_collators_for_orchestrator(
-                        target_session_index,
-                    ),
+                    min_collators: config.min_orchestrator_collators,
+                    max_collators: config.max_orchestrator_collators,
                     parathread: false,
                 }
             };
@@ -355,88 +875,160 @@ pub mod pallet {
             // Chains will not be assigned less than `min_collators`, except the orchestrator chain.
             // First all chains will be assigned `min_collators`, and then the first one will be assigned up to `max`,
             // then the second one, and so on.
+            // Chains use the collator count requested by their para manager, if any, instead of the default values.
             let mut bulk_paras = vec![];
             let mut pool_paras = vec![];
 
             for para_id in &container_chain_ids {
+                let (min_collators, max_collators) = collator_count_requests
+                    .get(para_id)
+                    .map(|request| (request.min, request.max))
+                    .unwrap_or((collators_per_container, collators_per_container));
                 bulk_paras.push(ChainNumCollators {
                     para_id: *para_id,
-                    min_collators: collators_per_container,
-                    max_collators: collators_per_container,
+                    min_collators,
+                    max_collators,
                     parathread: false,
                 });
             }
             for para_id in &parathreads {
+                let (min_collators, max_collators) = collator_count_requests
+                    .get(para_id)
+                    .map(|request| (request.min, request.max))
+                    .unwrap_or((collators_per_parathread, collators_per_parathread));
                 pool_paras.push(ChainNumCollators {
                     para_id: *para_id,
-                    min_collators: collators_per_parathread,
-                    max_collators: collators_per_parathread,
+                    min_collators,
+                    max_collators,
                     parathread: true,
                 });
             }
 
-            let (chains, need_to_charge_tip) =
+            Self::limit_collator_count_requests_on_scarcity(
+                &mut bulk_paras,
+                &mut pool_paras,
+                config.min_orchestrator_collators,
+                collators.len() as u32,
+                collators_per_container,
+                collators_per_parathread,
+            );
+
+            let with_core_config = maybe_core_allocation_configuration.is_some();
+            let (mut chains, need_to_charge_tip) =
                 if let Some(core_allocation_configuration) = maybe_core_allocation_configuration {
                     Self::order_paras_with_core_config(
                         bulk_paras,
                         pool_paras,
                         &core_allocation_configuration,
-                        target_session_index,
+                        &mut core_count_requests,
+                        config.min_orchestrator_collators,
                         collators.len() as u32,
-                        collators_per_container,
-                        collators_per_parathread,
                     )
                 } else {
                     Self::order_paras(
                         bulk_paras,
                         pool_paras,
-                        target_session_index,
+                        config.min_orchestrator_collators,
                         collators.len() as u32,
-                        collators_per_container,
-                        collators_per_parathread,
                     )
                 };
 
-            // We assign new collators
-            // we use the config scheduled at the target_session_index
-            let full_rotation =
-                T::ShouldRotateAllCollators::should_rotate_all_collators(target_session_index);
-            if full_rotation {
-                log::info!(
-                    "Collator assignment: rotating collators. Session {:?}, Seed: {:?}",
-                    current_session_index.encode(),
-                    random_seed
-                );
-            } else {
-                log::info!(
-                    "Collator assignment: keep old assigned. Session {:?}, Seed: {:?}",
-                    current_session_index.encode(),
-                    random_seed
-                );
+            // Parachains that did not fit in the available cores are not in `chains`
+            let para_ids_with_cores: BTreeSet<ParaId> =
+                chains.iter().map(|chain| chain.para_id).collect();
+
+            // Chains accepted by fewer collators than their min cannot be assigned collators
+            let para_ids_filters: BTreeMap<T::AccountId, ParaIdsFilter> = collators
+                .iter()
+                .filter_map(|collator| {
+                    CollatorParaIdsFilters::<T>::get(collator)
+                        .map(|filter| (collator.clone(), filter))
+                })
+                .collect();
+            let mut chains_lacking_eligible_collators = vec![];
+            if !para_ids_filters.is_empty() {
+                chains.retain(|chain| {
+                    let eligible_collators = collators
+                        .iter()
+                        .filter(|collator| {
+                            para_ids_filters
+                                .get(*collator)
+                                .map_or(true, |filter| filter.can_assign(&chain.para_id))
+                        })
+                        .count();
+                    if eligible_collators < chain.min_collators as usize {
+                        chains_lacking_eligible_collators.push(chain.para_id);
+                        false
+                    } else {
+                        true
+                    }
+                });
             }
 
+            let min_collators: BTreeMap<ParaId, u32> = chains
+                .iter()
+                .map(|chain| (chain.para_id, chain.min_collators))
+                .collect();
+
             let full_rotation_mode = if full_rotation {
-                T::HostConfiguration::full_rotation_mode(target_session_index)
+                config.full_rotation_mode.clone()
             } else {
                 // On sessions where there is no rotation, we try to keep all collators assigned to the same chains
                 FullRotationModes::keep_all()
             };
 
-            Self::deposit_event(Event::NewPendingAssignment {
-                random_seed,
-                full_rotation,
-                target_session: target_session_index,
-                full_rotation_mode: full_rotation_mode.clone(),
-            });
+            // Collators that missed many of their slots in the last session are picked last
+            let min_liveness_score = T::MinLivenessScore::get();
+            let (mut collators, low_liveness_collators): (Vec<_>, Vec<_>) =
+                collators.into_iter().partition(|collator| {
+                    LivenessScores::<T>::get(collator)
+                        .map_or(true, |score| score.ratio() >= min_liveness_score)
+                });
+            collators.extend(low_liveness_collators);
 
-            let new_assigned = Assignment::<T>::assign_collators_always_keep_old(
-                collators,
-                orchestrator_chain,
-                chains,
-                old_assigned.clone(),
-                shuffle_collators,
-                full_rotation_mode,
-            );
+            let collator_tags: BTreeMap<T::AccountId, CollatorTags> = collators
+                .iter()
+                .filter_map(|collator| {
+                    RegisteredCollatorTags::<T>::get(collator).map(|tags| (collator.clone(), tags))
+                })
+                .collect();
+
+            // The eligible collators of a chain can be assigned to chains with more priority first.
+            // Chains left with fewer collators than their min are removed and the assignment is
+            // computed again, so that their collators can be assigned to other chains.
+            let new_assigned = loop {
+                let new_assigned = Assignment::<T>::assign_collators_always_keep_old(
+                    collators.clone(),
+                    orchestrator_chain,
+                    chains.clone(),
+                    old_assigned.clone(),
+                    shuffle_collators.clone(),
+                    full_rotation_mode.clone(),
+                    &collator_tags,
+                    &para_ids_filters,
+                );
+                let Ok(assigned) = &new_assigned else {
+                    break new_assigned;
+                };
+
+                let para_ids_below_min: Vec<ParaId> = assigned
+                    .container_chains
+                    .iter()
+                    .filter(|(para_id, collators)| {
+                        !collators.is_empty()
+                            && min_collators
+                                .get(para_id)
+                                .is_some_and(|min| (collators.len() as u32) < *min)
+                    })
+                    .map(|(para_id, _)| *para_id)
+                    .collect();
+                if para_ids_filters.is_empty() || para_ids_below_min.is_empty() {
+                    break new_assigned;
+                }
+
+                chains.retain(|chain| !para_ids_below_min.contains(&chain.para_id));
+                chains_lacking_eligible_collators.extend(para_ids_below_min);
+            };
 
             let mut new_assigned = match new_assigned {
                 Ok(x) => x,
@@ -450,65 +1042,189 @@ pub mod pallet {
                 }
             };
 
-            let mut assigned_containers = new_assigned.container_chains.clone();
-            assigned_containers.retain(|_, v| !v.is_empty());
+            let assigned_para_ids: BTreeSet<ParaId> = new_assigned
+                .container_chains
+                .iter()
+                .filter(|(_, collators)| !collators.is_empty())
+                .map(|(para_id, _)| *para_id)
+                .collect();
 
-            // On congestion, prioritized chains need to pay the minimum tip of the prioritized chains
-            let maybe_tip: Option<BalanceOf<T>> = if !need_to_charge_tip {
-                None
+            // Parachains that were assigned collators get the cores they were given above. The
+            // assignment hooks charge parachains for each core.
+            let mut parachain_cores: BTreeMap<ParaId, u32> = if with_core_config {
+                core_count_requests
+                    .into_iter()
+                    .filter(|(para_id, cores)| *cores > 1 && assigned_para_ids.contains(para_id))
+                    .collect()
             } else {
-                assigned_containers
-                    .into_keys()
-                    .filter_map(T::CollatorAssignmentTip::get_para_tip)
-                    .min()
+                BTreeMap::new()
             };
 
-            // TODO: this probably is asking for a refactor
-            // only apply the onCollatorAssignedHook if sufficient collators
-            T::ParaIdAssignmentHooks::post_assignment(
-                &old_assigned_para_ids,
-                &mut new_assigned.container_chains,
-                &maybe_tip,
-            );
+            // On congestion, prioritized chains need to pay the minimum tip of the prioritized chains.
+            // Tipping chains removed by the assignment hooks don't set the tip, so the hooks are
+            // called again without them until all the tipping chains left can pay it.
+            let mut tipping_para_ids: BTreeSet<ParaId> = if need_to_charge_tip {
+                assigned_para_ids
+                    .iter()
+                    .filter(|para_id| T::CollatorAssignmentTip::get_para_tip(**para_id).is_some())
+                    .cloned()
+                    .collect()
+            } else {
+                BTreeSet::new()
+            };
+            let maybe_tip = loop {
+                let maybe_tip: Option<BalanceOf<T>> = tipping_para_ids
+                    .iter()
+                    .cloned()
+                    .filter_map(T::CollatorAssignmentTip::get_para_tip)
+                    .min();
+
+                // TODO: this probably is asking for a refactor
+                // only apply the onCollatorAssignedHook if sufficient collators
+                let outcome: Result<Result<_, BTreeSet<ParaId>>, DispatchError> =
+                    with_transaction(|| {
+                        let mut container_chains = new_assigned.container_chains.clone();
+                        T::ParaIdAssignmentHooks::post_assignment(
+                            &old_assigned_para_ids,
+                            &mut container_chains,
+                            &parachain_cores,
+                            &maybe_tip,
+                        );
+                        let removed_tipping_para_ids: BTreeSet<ParaId> = tipping_para_ids
+                            .iter()
+                            .filter(|para_id| {
+                                container_chains
+                                    .get(para_id)
+                                    .map_or(true, |collators| collators.is_empty())
+                            })
+                            .cloned()
+                            .collect();
+
+                        if removed_tipping_para_ids.is_empty() {
+                            TransactionOutcome::Commit(Ok(Ok(container_chains)))
+                        } else {
+                            TransactionOutcome::Rollback(Ok(Err(removed_tipping_para_ids)))
+                        }
+                    });
+
+                match outcome {
+                    Ok(Ok(container_chains)) => {
+                        new_assigned.container_chains = container_chains;
+                        break maybe_tip;
+                    }
+                    Ok(Err(removed_tipping_para_ids)) => {
+                        tipping_para_ids
+                            .retain(|para_id| !removed_tipping_para_ids.contains(para_id));
+                    }
+                    Err(e) => {
+                        log::error!("Error calling the assignment hooks: {:?}", e);
+                        T::ParaIdAssignmentHooks::post_assignment(
+                            &old_assigned_para_ids,
+                            &mut new_assigned.container_chains,
+                            &parachain_cores,
+                            &maybe_tip,
+                        );
+                        break maybe_tip;
+                    }
+                }
+            };
 
-            Self::store_collator_fullness(
-                &new_assigned,
-                T::HostConfiguration::max_collators(target_session_index),
-            );
+            // Chains that were assigned collators but then removed by the assignment hooks could
+            // not pay for the assignment or the tip.
+            let dropped_chains = registered_para_ids
+                .iter()
+                .filter(|para_id| {
+                    new_assigned
+                        .container_chains
+                        .get(para_id)
+                        .map_or(true, |collators| collators.is_empty())
+                })
+                .map(|para_id| {
+                    let reason = if !funded_para_ids.contains(para_id) {
+                        DroppedChainReason::NotEnoughCredits
+                    } else if !para_ids_with_cores.contains(para_id) {
+                        DroppedChainReason::NotEnoughCores
+                    } else if chains_lacking_eligible_collators.contains(para_id) {
+                        DroppedChainReason::NotEnoughEligibleCollators
+                    } else if !assigned_para_ids.contains(para_id) {
+                        DroppedChainReason::NotEnoughCollators
+                    } else {
+                        DroppedChainReason::NotEnoughCredits
+                    };
+
+                    (*para_id, reason)
+                })
+                .collect();
 
-            let mut pending = PendingCollatorContainerChain::<T>::get();
+            // Parachains removed by the assignment hooks don't use their cores
+            parachain_cores.retain(|para_id, _| {
+                new_assigned
+                    .container_chains
+                    .get(para_id)
+                    .is_some_and(|collators| !collators.is_empty())
+            });
 
-            let old_assigned_changed = old_assigned != new_assigned;
-            let mut pending_changed = false;
-            // Update CollatorContainerChain using last entry of pending, if needed
-            if let Some(current) = pending.take() {
-                pending_changed = true;
-                CollatorContainerChain::<T>::put(current);
-            }
-            if old_assigned_changed {
-                pending = Some(new_assigned.clone());
-                pending_changed = true;
-            }
-            // Update PendingCollatorContainerChain, if it changed
-            if pending_changed {
-                PendingCollatorContainerChain::<T>::put(pending);
+            ComputedAssignment {
+                old_assigned,
+                new_assigned,
+                registered_para_ids,
+                dropped_chains,
+                maybe_tip,
+                parachain_cores,
+                chains_lacking_eligible_collators,
             }
+        }
 
-            // Only applies to session index 0
-            if current_session_index == &T::SessionIndex::zero() {
-                CollatorContainerChain::<T>::put(new_assigned.clone());
-                return SessionChangeOutcome {
-                    active_assignment: new_assigned.clone(),
-                    next_assignment: new_assigned,
-                    num_total_registered_paras,
-                };
-            }
+        /// Compute the assignment that would be calculated when `session_index` starts, as if the
+        /// given host configuration and collators were used, without modifying the storage. At
+        /// most `config.max_collators` collators are used. The full rotation schedule and the core
+        /// count of the runtime are kept, as well as its `max_parachain_cores_percentage` if the
+        /// given configuration does not set one.
+        ///
+        /// Chains and collators are shuffled with the stored [`Randomness`], which is only set in
+        /// the last block of a session. In any other block it is all zeros, so nothing is shuffled
+        /// and the simulated assignment can differ from the one computed at the session change.
+        pub fn simulate_assignment(
+            session_index: T::SessionIndex,
+            config: AssignmentHostConfiguration,
+            mut collators: Vec<T::AccountId>,
+        ) -> Result<AssignmentSimulation<T>, DispatchError> {
+            let target_session_index = session_index.saturating_add(One::one());
+            let full_rotation =
+                T::ShouldRotateAllCollators::should_rotate_all_collators(target_session_index);
+            let maybe_core_allocation_configuration =
+                T::CoreAllocationConfiguration::get().map(|core_allocation_configuration| {
+                    CoreAllocationConfiguration {
+                        core_count: core_allocation_configuration.core_count,
+                        max_parachain_percentage: config
+                            .max_parachain_cores_percentage
+                            .unwrap_or(core_allocation_configuration.max_parachain_percentage),
+                    }
+                });
+            collators.truncate(config.max_collators as usize);
+
+            // Assignment hooks charge the chains, so their changes need to be reverted.
+            with_transaction(|| {
+                let ComputedAssignment {
+                    new_assigned,
+                    dropped_chains,
+                    maybe_tip,
+                    ..
+                } = Self::compute_assignment(
+                    target_session_index,
+                    Randomness::<T>::get(),
+                    collators,
+                    &config,
+                    maybe_core_allocation_configuration,
+                    full_rotation,
+                );
 
-            SessionChangeOutcome {
-                active_assignment: old_assigned,
-                next_assignment: new_assigned,
-                num_total_registered_paras,
-            }
+                TransactionOutcome::Rollback(Ok(AssignmentSimulation {
+                    assignment: new_assigned,
+                    dropped_chains: dropped_chains.into_keys().collect(),
+                    tips_charged: maybe_tip.is_some(),
+                }))
+            })
         }
 
         /// Count number of collators assigned to any chain, divide that by `max_collators` and store
@@ -535,6 +1251,121 @@ pub mod pallet {
             CollatorFullnessRatio::<T>::put(ratio);
         }
 
+        /// Compute the liveness score of the collators assigned to container chains in
+        /// `ending_session_index`, and start counting blocks for the new session.
+        ///
+        /// Collators author in turns, so each collator of a chain is expected to fill its share of
+        /// the `ContainerSlotsPerSession` slots, and parathreads only need to produce a block every
+        /// `slot_frequency.max` slots. Chains that did not produce any block are skipped, as that
+        /// is not the fault of a single collator.
+        ///
+        /// Blocks are only noted for, and scores only stored for, collators that authored container
+        /// chain blocks, so both maps are fully cleared. Noted blocks of collators that are no longer
+        /// assigned are discarded.
+        fn update_liveness_scores(ending_session_index: T::SessionIndex) -> Weight {
+            let slots_per_session = T::ContainerSlotsPerSession::get();
+            let parathread_slot_frequencies: BTreeMap<ParaId, u32> =
+                T::ContainerChains::session_container_chains(ending_session_index)
+                    .parathreads
+                    .into_iter()
+                    .map(|(para_id, params)| (para_id, params.slot_frequency.max.max(1)))
+                    .collect();
+            let blocks_produced: BTreeMap<T::AccountId, u32> =
+                BlocksProduced::<T>::drain().collect();
+            let scores_removed = LivenessScores::<T>::clear(u32::MAX, None).unique;
+            let mut scores_inserted: u32 = 0;
+
+            for (para_id, collators) in CollatorContainerChain::<T>::get().container_chains {
+                let blocks: Vec<u32> = collators
+                    .iter()
+                    .map(|collator| blocks_produced.get(collator).copied().unwrap_or_default())
+                    .collect();
+                if blocks.iter().all(|blocks| *blocks == 0) {
+                    continue;
+                }
+
+                let slot_frequency = parathread_slot_frequencies
+                    .get(&para_id)
+                    .copied()
+                    .unwrap_or(1);
+                let slots_expected = (slots_per_session / slot_frequency)
+                    .checked_div(collators.len() as u32)
+                    .unwrap_or_default();
+                if slots_expected == 0 {
+                    continue;
+                }
+
+                for (collator, blocks_produced) in collators.iter().zip(blocks) {
+                    LivenessScores::<T>::insert(
+                        collator,
+                        LivenessScore {
+                            blocks_produced,
+                            slots_expected,
+                        },
+                    );
+                    scores_inserted.saturating_inc();
+                }
+            }
+
+            // Drained blocks and removed scores, plus the assignment and the container chains
+            let removed = (blocks_produced.len() as u64).saturating_add(scores_removed.into());
+            T::DbWeight::get().reads_writes(
+                removed.saturating_add(2),
+                removed.saturating_add(scores_inserted.into()),
+            )
+        }
+
+        /// Store the assignment of `session_index` in the assignment history, removing the entry
+        /// that falls out of the history.
+        fn store_assignment_history(
+            session_index: T::SessionIndex,
+            session_assignment: SessionAssignment<T::AccountId>,
+        ) {
+            let depth = T::AssignmentHistoryDepth::get();
+            if depth == 0 {
+                return;
+            }
+
+            if let Some(oldest) = session_index.checked_sub(&depth.into()) {
+                AssignmentHistory::<T>::remove(oldest);
+            }
+            AssignmentHistory::<T>::insert(session_index, session_assignment);
+        }
+
+        /// Return the collators assigned to `para_id` in `session_index` and, if the chain was
+        /// not assigned any collators, the reason why. Returns `None` if the session is not in
+        /// the assignment history or the chain was not registered in that session.
+        pub fn chain_assignment_at_session(
+            session_index: T::SessionIndex,
+            para_id: ParaId,
+        ) -> Option<(Vec<T::AccountId>, Option<DroppedChainReason>)> {
+            let SessionAssignment {
+                assignment,
+                dropped_chains,
+            } = AssignmentHistory::<T>::get(session_index)?;
+
+            if let Some(reason) = dropped_chains.get(&para_id) {
+                return Some((vec![], Some(*reason)));
+            }
+
+            let collators = if para_id == T::SelfParaId::get() {
+                assignment.orchestrator_chain
+            } else {
+                assignment.container_chains.get(&para_id)?.clone()
+            };
+
+            Some((collators, None))
+        }
+
+        /// Number of relay chain cores of `para_id` in `session_index`. Parachains use 1 core
+        /// unless they requested more and got them.
+        pub fn parachain_cores(session_index: T::SessionIndex, para_id: ParaId) -> u32 {
+            ParachainCores::<T>::get(session_index)
+                .get(&para_id)
+                .copied()
+                .unwrap_or(1)
+        }
+
         // Returns the assigned collators as read from storage.
         // If there is any item in PendingCollatorContainerChain, returns that element.
         // Otherwise, reads and returns the current CollatorContainerChain
@@ -628,6 +1459,24 @@ pub mod pallet {
     }
 }
 
+impl<T: Config> AuthorNotingHook<T::AccountId> for Pallet<T> {
+    fn on_container_authors_noted(info: &[AuthorNotingInfo<T::AccountId>]) -> Weight {
+        for noted in info {
+            BlocksProduced::<T>::mutate(&noted.author, |blocks| *blocks = blocks.saturating_add(1));
+        }
+
+        T::DbWeight::get().reads_writes(info.len() as u64, info.len() as u64)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn prepare_worst_case_for_bench(
+        _a: &T::AccountId,
+        _b: tp_traits::BlockNumber,
+        _para_id: ParaId,
+    ) {
+    }
+}
+
 /// Balance used by this pallet
 pub type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -22,7 +22,7 @@ use {
     dp_collator_assignment::AssignedCollators,
     frame_support::{
         parameter_types,
-        traits::{ConstBool, ConstU16, ConstU64, Hooks},
+        traits::{ConstBool, ConstU16, ConstU32, ConstU64, Hooks},
         weights::Weight,
     },
     frame_system as system,
@@ -37,8 +37,9 @@ use {
         ops::Range,
     },
     tp_traits::{
-        CollatorAssignmentTip, FullRotationModes, ParaId, ParaIdAssignmentHooks, ParathreadParams,
-        RemoveInvulnerables, SessionContainerChains,
+        CollatorAssignmentTip, CollatorCountRequest, FullRotationModes, IsCollatorRegistered,
+        ParaId, ParaIdAssignmentHooks, ParathreadParams, RemoveInvulnerables,
+        SessionContainerChains,
     },
     tracing_subscriber::{layer::SubscriberExt, FmtSubscriber},
 };
@@ -150,13 +151,20 @@ pub struct Mocks {
     pub collators: Vec<u64>,
     pub container_chains: Vec<u32>,
     pub parathreads: Vec<u32>,
+    pub collator_count_requests: Vec<(u32, CollatorCountRequest)>,
+    pub core_count_requests: Vec<(u32, u32)>,
     pub random_seed: [u8; 32],
     pub chains_that_are_tipping: Vec<ParaId>,
+    // Tip of the chains that are tipping, 1_000 if not set
+    pub tips: Vec<(ParaId, u32)>,
+    // Chains removed by the assignment hooks when a tip is charged
+    pub chains_that_cannot_pay_tips: Vec<ParaId>,
     // None means 5
     pub full_rotation_period: Option<u32>,
     pub full_rotation_mode: FullRotationModes,
     pub apply_tip: bool,
     pub assignment_hook_errors: bool,
+    pub min_liveness_score: Perbill,
 }
 
 impl Default for Mocks {
@@ -172,12 +180,17 @@ impl Default for Mocks {
             collators: vec![100],
             container_chains: Default::default(),
             parathreads: Default::default(),
+            collator_count_requests: Default::default(),
+            core_count_requests: Default::default(),
             random_seed: Default::default(),
             chains_that_are_tipping: vec![1003.into(), 1004.into()],
+            tips: Default::default(),
+            chains_that_cannot_pay_tips: Default::default(),
             full_rotation_period: Default::default(),
             full_rotation_mode: Default::default(),
             apply_tip: Default::default(),
             assignment_hook_errors: Default::default(),
+            min_liveness_score: Perbill::zero(),
         }
     }
 }
@@ -269,9 +282,23 @@ impl tp_traits::GetSessionContainerChains<u32> for ContainerChainsGetter {
             })
             .collect();
 
+        let collator_count_requests = MockData::mock()
+            .collator_count_requests
+            .iter()
+            .map(|(para_id, request)| (ParaId::from(*para_id), *request))
+            .collect();
+
+        let core_count_requests = MockData::mock()
+            .core_count_requests
+            .iter()
+            .map(|(para_id, cores)| (ParaId::from(*para_id), *cores))
+            .collect();
+
         SessionContainerChains {
             parachains,
             parathreads,
+            collator_count_requests,
+            core_count_requests,
         }
     }
 
@@ -307,14 +334,27 @@ impl Get<u32> for MockCollatorRotationSessionPeriod {
     }
 }
 
+pub struct MockMinLivenessScore;
+
+impl Get<Perbill> for MockMinLivenessScore {
+    fn get() -> Perbill {
+        MockData::mock().min_liveness_score
+    }
+}
+
 // Mock the service payment tip as only for 1003
 pub struct MockCollatorAssignmentTip;
 
 impl CollatorAssignmentTip<u32> for MockCollatorAssignmentTip {
     fn get_para_tip(para_id: ParaId) -> Option<u32> {
-        if MockData::mock().apply_tip && MockData::mock().chains_that_are_tipping.contains(&para_id)
-        {
-            Some(1_000u32)
+        let mock = MockData::mock();
+        if mock.apply_tip && mock.chains_that_are_tipping.contains(&para_id) {
+            Some(
+                mock.tips
+                    .iter()
+                    .find(|(tipping_para_id, _)| *tipping_para_id == para_id)
+                    .map_or(1_000u32, |(_, tip)| *tip),
+            )
         } else {
             None
         }
@@ -335,6 +375,13 @@ impl Get<Option<CoreAllocationConfiguration>> for GetCoreAllocationConfiguration
     }
 }
 
+pub struct IsRegistered;
+impl IsCollatorRegistered<u64> for IsRegistered {
+    fn is_collator_registered(id: &u64) -> bool {
+        *id != 42u64
+    }
+}
+
 impl pallet_collator_assignment::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type SessionIndex = u32;
@@ -350,6 +397,10 @@ impl pallet_collator_assignment::Config for Test {
     type ForceEmptyOrchestrator = ConstBool<false>;
     type Currency = ();
     type CoreAllocationConfiguration = GetCoreAllocationConfigurationImpl;
+    type CollatorRegistration = IsRegistered;
+    type MinLivenessScore = MockMinLivenessScore;
+    type ContainerSlotsPerSession = ConstU32<8>;
+    type AssignmentHistoryDepth = ConstU32<3>;
     type WeightInfo = ();
 }
 
@@ -405,7 +456,8 @@ impl RemoveInvulnerables<u64> for RemoveAccountIdsAbove100 {
     }
 }
 
-/// Any ParaId >= 5000 will be considered to not have enough credits
+/// Any ParaId >= 5000 will be considered to not have enough credits, and chains that cannot pay tips
+/// are removed when a tip is charged
 pub struct MockParaIdAssignmentHooksImpl;
 
 impl<AC> ParaIdAssignmentHooks<u32, AC> for MockParaIdAssignmentHooksImpl {
@@ -416,9 +468,14 @@ impl<AC> ParaIdAssignmentHooks<u32, AC> for MockParaIdAssignmentHooksImpl {
     fn post_assignment(
         _current_assigned: &BTreeSet<ParaId>,
         new_assigned: &mut BTreeMap<ParaId, Vec<AC>>,
-        _maybe_tip: &Option<u32>,
+        _parachain_cores: &BTreeMap<ParaId, u32>,
+        maybe_tip: &Option<u32>,
     ) -> Weight {
         new_assigned.retain(|para_id, _| *para_id <= ParaId::from(5000));
+        if maybe_tip.is_some() {
+            let chains_that_cannot_pay_tips = MockData::mock().chains_that_cannot_pay_tips;
+            new_assigned.retain(|para_id, _| !chains_that_cannot_pay_tips.contains(para_id));
+        }
         Weight::zero()
     }
 
@@ -15,11 +15,21 @@
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
 use {
-    crate::{mock::*, CollatorContainerChain, Event, PendingCollatorContainerChain},
+    crate::{
+        mock::*, AssignmentHistory, AssignmentHostConfiguration, BlocksProduced,
+        CollatorContainerChain, CollatorFullnessRatio, CollatorParaIdsFilters, CollatorTags,
+        CoreAllocationConfiguration, DroppedChainReason, Error, Event, LivenessScore,
+        LivenessScores, ParaIdsFilter, ParachainCores, PendingCollatorContainerChain,
+        RegisteredCollatorTags,
+    },
     dp_collator_assignment::AssignedCollators,
+    frame_support::{assert_noop, assert_ok},
     sp_runtime::Perbill,
-    std::collections::BTreeMap,
-    tp_traits::{FullRotationMode, FullRotationModes},
+    std::collections::{BTreeMap, BTreeSet},
+    tp_traits::{
+        AuthorNotingHook, AuthorNotingInfo, CollatorCountRequest, FullRotationMode,
+        FullRotationModes,
+    },
 };
 
 mod assign_full;
@@ -1351,6 +1361,85 @@ fn assign_collators_prioritizing_tip() {
     });
 }
 
+#[test]
+fn tip_auction_outcome_is_reported() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 5;
+            m.max_orchestrator_chain_collators = 5;
+
+            // Only 2 chains can be assigned collators, and 3 chains tip for them
+            m.collators = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+            m.container_chains = vec![1001, 1002, 1003, 1004];
+            m.chains_that_are_tipping = vec![1002.into(), 1003.into(), 1004.into()];
+            m.apply_tip = true;
+        });
+
+        run_to_block(11);
+
+        System::assert_has_event(
+            Event::TipAuctionCleared {
+                target_session: 3,
+                clearing_price: 1_000,
+                winners: vec![1002.into(), 1003.into()],
+                losers: vec![1004.into()],
+            }
+            .into(),
+        );
+
+        // No auction without congestion
+        MockData::mutate(|m| m.container_chains = vec![1002, 1003]);
+        run_to_block(16);
+
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::CollatorAssignment(Event::TipAuctionCleared { .. })
+        )));
+    });
+}
+
+#[test]
+fn tip_auction_clearing_price_ignores_chains_that_cannot_pay() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 5;
+            m.max_orchestrator_chain_collators = 5;
+
+            // Only 3 chains can be assigned collators, and the lowest tipper cannot pay its tip
+            m.collators = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+            m.container_chains = vec![1001, 1002, 1003, 1004];
+            m.chains_that_are_tipping = vec![1002.into(), 1003.into(), 1004.into()];
+            m.tips = vec![
+                (1002.into(), 500),
+                (1003.into(), 1_000),
+                (1004.into(), 2_000),
+            ];
+            m.chains_that_cannot_pay_tips = vec![1002.into()];
+            m.apply_tip = true;
+        });
+
+        run_to_block(11);
+
+        System::assert_has_event(
+            Event::TipAuctionCleared {
+                target_session: 3,
+                clearing_price: 1_000,
+                winners: vec![1003.into(), 1004.into()],
+                losers: vec![1002.into()],
+            }
+            .into(),
+        );
+        let assigned_para_ids: BTreeSet<u32> = assigned_collators().into_values().collect();
+        assert_eq!(assigned_para_ids, BTreeSet::from([1000, 1003, 1004]));
+    });
+}
+
 #[test]
 fn assign_collators_truncates_before_shuffling() {
     // Check that if there are more collators than needed, we only assign the first collators
@@ -1437,3 +1526,897 @@ fn keep_subset_uses_correct_config() {
         assert_eq!(max_parathread_rotate, 1);
     });
 }
+
+#[test]
+fn assign_collators_with_collator_count_request() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.collators_per_parathread = 2;
+            m.min_orchestrator_chain_collators = 5;
+            m.max_orchestrator_chain_collators = 5;
+
+            m.collators = (1..=12).collect();
+            m.container_chains = vec![1001, 1002];
+            m.collator_count_requests = vec![(1002, CollatorCountRequest { min: 3, max: 4 })];
+        });
+
+        assert_eq!(assigned_collators(), initial_collators(),);
+        run_to_block(11);
+
+        // 1002 gets its min and the extra collator up to its max, 12 is not assigned
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 1000),
+                (2, 1000),
+                (3, 1000),
+                (4, 1000),
+                (5, 1000),
+                (6, 1001),
+                (7, 1001),
+                (8, 1002),
+                (9, 1002),
+                (10, 1002),
+                (11, 1002),
+            ]),
+        );
+    });
+}
+
+#[test]
+fn collator_count_request_min_is_lowered_when_collators_are_scarce() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.collators_per_parathread = 2;
+            m.min_orchestrator_chain_collators = 5;
+            m.max_orchestrator_chain_collators = 5;
+
+            // Chains need 5 + 2 + 4 = 11 collators, but there are only 10
+            m.collators = (1..=10).collect();
+            m.container_chains = vec![1001, 1002];
+            m.collator_count_requests = vec![(1002, CollatorCountRequest { min: 4, max: 4 })];
+        });
+
+        assert_eq!(assigned_collators(), initial_collators(),);
+        run_to_block(11);
+
+        // 1002 gets the default min, and then the remaining collator because of its max
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 1000),
+                (2, 1000),
+                (3, 1000),
+                (4, 1000),
+                (5, 1000),
+                (6, 1001),
+                (7, 1001),
+                (8, 1002),
+                (9, 1002),
+                (10, 1002),
+            ]),
+        );
+    });
+}
+
+#[test]
+fn tip_is_not_charged_when_lowered_collator_count_requests_are_enough() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.collators_per_parathread = 2;
+            m.min_orchestrator_chain_collators = 5;
+            m.max_orchestrator_chain_collators = 5;
+
+            // Chains need 5 + 2 + 4 = 11 collators, but only 9 once 1002 is lowered to the default
+            m.collators = (1..=10).collect();
+            m.container_chains = vec![1001, 1002];
+            m.collator_count_requests = vec![(1002, CollatorCountRequest { min: 4, max: 4 })];
+            m.chains_that_are_tipping = vec![1001.into(), 1002.into()];
+            m.apply_tip = true;
+        });
+
+        run_to_block(11);
+
+        let simulation = CollatorAssignment::simulate_assignment(
+            3,
+            AssignmentHostConfiguration {
+                max_collators: 100,
+                min_orchestrator_collators: 5,
+                max_orchestrator_collators: 5,
+                collators_per_container: 2,
+                collators_per_parathread: 2,
+                max_parachain_cores_percentage: None,
+                full_rotation_mode: Default::default(),
+            },
+            (1..=10).collect(),
+        )
+        .unwrap();
+
+        assert!(simulation.dropped_chains.is_empty());
+        assert!(!simulation.tips_charged);
+    });
+}
+
+#[test]
+fn collator_count_request_below_default_is_kept_when_collators_are_scarce() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.collators_per_parathread = 2;
+            m.min_orchestrator_chain_collators = 5;
+            m.max_orchestrator_chain_collators = 5;
+
+            // Chains need 5 + 2 + 2 + 1 = 10 collators, but there are only 8
+            m.collators = (1..=8).collect();
+            m.container_chains = vec![1001, 1002, 1003];
+            m.collator_count_requests = vec![(1003, CollatorCountRequest { min: 1, max: 1 })];
+        });
+
+        assert_eq!(assigned_collators(), initial_collators(),);
+        run_to_block(11);
+
+        // 1002 cannot reach its min, but 1003 only needs 1 collator
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 1000),
+                (2, 1000),
+                (3, 1000),
+                (4, 1000),
+                (5, 1000),
+                (6, 1001),
+                (7, 1001),
+                (8, 1003),
+            ]),
+        );
+    });
+}
+
+#[test]
+fn simulate_assignment_uses_config_override_without_modifying_storage() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.max_collators = 100;
+            m.collators_per_container = 2;
+            m.collators_per_parathread = 2;
+            m.min_orchestrator_chain_collators = 5;
+            m.max_orchestrator_chain_collators = 5;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+            m.container_chains = vec![1001, 1002, 1003, 1004];
+            m.apply_tip = true;
+        });
+
+        run_to_block(11);
+
+        let current = CollatorContainerChain::<Test>::get();
+        let pending = PendingCollatorContainerChain::<Test>::get();
+        let fullness = CollatorFullnessRatio::<Test>::get();
+        let num_events = System::events().len();
+
+        let config = AssignmentHostConfiguration {
+            max_collators: 100,
+            min_orchestrator_collators: 2,
+            max_orchestrator_collators: 2,
+            collators_per_container: 2,
+            collators_per_parathread: 2,
+            max_parachain_cores_percentage: None,
+            full_rotation_mode: Default::default(),
+        };
+
+        // Chains need 2 + 2 * 4 = 10 collators, but there are only 9
+        let simulation =
+            CollatorAssignment::simulate_assignment(3, config.clone(), (1..=9).collect()).unwrap();
+
+        assert_eq!(simulation.assignment.orchestrator_chain.len(), 2);
+        assert_eq!(simulation.dropped_chains, vec![1002.into()]);
+        assert!(simulation.tips_charged);
+
+        // Only the first 8 collators are used, enough for all the chains when there are no tips
+        MockData::mutate(|m| m.apply_tip = false);
+        let simulation = CollatorAssignment::simulate_assignment(
+            3,
+            AssignmentHostConfiguration {
+                max_collators: 8,
+                min_orchestrator_collators: 0,
+                ..config
+            },
+            (1..=9).collect(),
+        )
+        .unwrap();
+
+        let assigned: Vec<u64> = simulation
+            .assignment
+            .container_chains
+            .values()
+            .flatten()
+            .chain(&simulation.assignment.orchestrator_chain)
+            .cloned()
+            .collect();
+        assert_eq!(assigned.len(), 8);
+        assert!(!assigned.contains(&9));
+        assert!(simulation.dropped_chains.is_empty());
+        assert!(!simulation.tips_charged);
+
+        assert_eq!(CollatorContainerChain::<Test>::get(), current);
+        assert_eq!(PendingCollatorContainerChain::<Test>::get(), pending);
+        assert_eq!(CollatorFullnessRatio::<Test>::get(), fullness);
+        assert_eq!(System::events().len(), num_events);
+    });
+}
+
+#[test]
+fn set_collator_tags_stores_and_removes_tags() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        let tags = CollatorTags {
+            operator: Some(1),
+            region: Some(b"eu-west".to_vec().try_into().unwrap()),
+        };
+        assert_ok!(CollatorAssignment::set_collator_tags(
+            RuntimeOrigin::signed(1),
+            Some(tags.clone())
+        ));
+        assert_eq!(RegisteredCollatorTags::<Test>::get(1), Some(tags.clone()));
+        System::assert_last_event(
+            Event::CollatorTagsSet {
+                collator: 1,
+                tags: Some(tags),
+            }
+            .into(),
+        );
+
+        // Empty tags are the same as removing them
+        assert_ok!(CollatorAssignment::set_collator_tags(
+            RuntimeOrigin::signed(1),
+            Some(CollatorTags::default())
+        ));
+        assert_eq!(RegisteredCollatorTags::<Test>::get(1), None);
+        System::assert_last_event(
+            Event::CollatorTagsSet {
+                collator: 1,
+                tags: None,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn set_collator_tags_requires_registered_keys() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_noop!(
+            CollatorAssignment::set_collator_tags(
+                RuntimeOrigin::signed(42),
+                Some(CollatorTags {
+                    operator: Some(1),
+                    region: None,
+                })
+            ),
+            Error::<Test>::CollatorNotRegistered
+        );
+    });
+}
+
+#[test]
+fn remove_collator_tags_of_unregistered_collator() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        // Collator 42 had tags before removing its session keys
+        let tags = CollatorTags {
+            operator: Some(1),
+            region: None,
+        };
+        RegisteredCollatorTags::<Test>::insert(42, tags.clone());
+        RegisteredCollatorTags::<Test>::insert(1, tags);
+
+        assert_noop!(
+            CollatorAssignment::remove_collator_tags(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::CollatorStillRegistered
+        );
+
+        assert_ok!(CollatorAssignment::remove_collator_tags(
+            RuntimeOrigin::signed(2),
+            42
+        ));
+        assert_eq!(RegisteredCollatorTags::<Test>::get(42), None);
+        System::assert_last_event(
+            Event::CollatorTagsSet {
+                collator: 42,
+                tags: None,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            CollatorAssignment::remove_collator_tags(RuntimeOrigin::signed(2), 42),
+            Error::<Test>::CollatorTagsNotFound
+        );
+    });
+}
+
+#[test]
+fn assign_collators_spreads_operators() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.collators_per_parathread = 2;
+            m.min_orchestrator_chain_collators = 5;
+            m.max_orchestrator_chain_collators = 5;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+            m.container_chains = vec![1001, 1002];
+        });
+
+        // Collators 6 and 7 are run by the same operator
+        for collator in [6, 7] {
+            assert_ok!(CollatorAssignment::set_collator_tags(
+                RuntimeOrigin::signed(collator),
+                Some(CollatorTags {
+                    operator: Some(1),
+                    region: None,
+                })
+            ));
+        }
+
+        run_to_block(11);
+
+        // Without tags, 6 and 7 would be assigned to 1001
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 1000),
+                (2, 1000),
+                (3, 1000),
+                (4, 1000),
+                (5, 1000),
+                (6, 1001),
+                (7, 1002),
+                (8, 1001),
+                (9, 1002),
+            ]),
+        );
+    });
+}
+
+fn note_blocks(author: u64, para_id: u32, blocks: u32) {
+    let info: Vec<_> = (0..blocks)
+        .map(|block_number| AuthorNotingInfo {
+            author,
+            block_number,
+            para_id: para_id.into(),
+        })
+        .collect();
+    CollatorAssignment::on_container_authors_noted(&info);
+}
+
+#[test]
+fn liveness_scores_compare_collators_of_same_chain() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 5;
+            m.max_orchestrator_chain_collators = 5;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+            m.container_chains = vec![1001, 1002];
+        });
+
+        run_to_block(11);
+        assert_eq!(assigned_collators().get(&6), Some(&1001));
+        assert_eq!(assigned_collators().get(&7), Some(&1001));
+
+        // Collator 7 misses most of its slots, chain 1002 does not produce any block
+        note_blocks(6, 1001, 4);
+        note_blocks(7, 1001, 1);
+        assert_eq!(BlocksProduced::<Test>::get(6), 4);
+
+        run_to_block(16);
+
+        assert_eq!(
+            LivenessScores::<Test>::get(6),
+            Some(LivenessScore {
+                blocks_produced: 4,
+                slots_expected: 4,
+            })
+        );
+        assert_eq!(
+            LivenessScores::<Test>::get(7),
+            Some(LivenessScore {
+                blocks_produced: 1,
+                slots_expected: 4,
+            })
+        );
+        assert_eq!(LivenessScores::<Test>::get(8), None);
+        assert_eq!(LivenessScores::<Test>::get(9), None);
+        assert_eq!(BlocksProduced::<Test>::get(6), 0);
+
+        // Scores only last one session
+        run_to_block(21);
+        assert_eq!(LivenessScores::<Test>::get(6), None);
+        assert_eq!(LivenessScores::<Test>::get(7), None);
+    });
+}
+
+#[test]
+fn liveness_scores_use_expected_slots_of_session() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 5;
+            m.max_orchestrator_chain_collators = 5;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6, 7];
+            m.container_chains = vec![1001];
+        });
+
+        run_to_block(11);
+
+        // Both collators of 1001 miss half of their slots
+        note_blocks(6, 1001, 2);
+        note_blocks(7, 1001, 2);
+
+        run_to_block(16);
+
+        // 8 slots per session shared by 2 collators
+        for collator in [6, 7] {
+            assert_eq!(
+                LivenessScores::<Test>::get(collator),
+                Some(LivenessScore {
+                    blocks_produced: 2,
+                    slots_expected: 4,
+                })
+            );
+        }
+    });
+}
+
+#[test]
+fn liveness_scores_use_all_blocks_when_max_collators_decreases() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.max_collators = 100;
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 2;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6];
+            m.container_chains = vec![1001, 1002];
+        });
+
+        run_to_block(11);
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 1000),
+                (2, 1000),
+                (3, 1001),
+                (4, 1001),
+                (5, 1002),
+                (6, 1002),
+            ]),
+        );
+
+        for collator in [3, 4] {
+            note_blocks(collator, 1001, 4);
+        }
+        for collator in [5, 6] {
+            note_blocks(collator, 1002, 2);
+        }
+
+        // The next session uses fewer collators than the one that is ending
+        MockData::mutate(|m| {
+            m.max_collators = 2;
+        });
+
+        run_to_block(16);
+
+        for (collator, blocks_produced) in [(3, 4), (4, 4), (5, 2), (6, 2)] {
+            assert_eq!(
+                LivenessScores::<Test>::get(collator),
+                Some(LivenessScore {
+                    blocks_produced,
+                    slots_expected: 4,
+                })
+            );
+        }
+        assert_eq!(BlocksProduced::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn low_liveness_collators_are_picked_last() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 2;
+            m.min_liveness_score = Perbill::from_percent(50);
+
+            m.collators = vec![1, 2, 3, 4, 5];
+            m.container_chains = vec![1001];
+        });
+
+        run_to_block(11);
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![(1, 1000), (2, 1000), (3, 1001), (4, 1001),]),
+        );
+
+        // Collator 4 does not produce any block
+        note_blocks(3, 1001, 4);
+        MockData::mutate(|m| {
+            m.container_chains = vec![1002];
+        });
+
+        run_to_block(21);
+
+        // Without liveness scores, 3 and 4 would be assigned to 1002
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![(1, 1000), (2, 1000), (3, 1002), (5, 1002),]),
+        );
+    });
+}
+
+#[test]
+fn assignment_history_keeps_last_sessions() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        // Session 0 and the next session use the same assignment
+        assert!(AssignmentHistory::<Test>::get(0).is_some());
+        assert_eq!(
+            AssignmentHistory::<Test>::get(0),
+            AssignmentHistory::<Test>::get(1)
+        );
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 2;
+
+            m.collators = vec![1, 2, 3, 4];
+            m.container_chains = vec![1001];
+        });
+
+        run_to_block(16);
+
+        // History depth is 3 in the mock
+        assert_eq!(AssignmentHistory::<Test>::get(0), None);
+        assert_eq!(AssignmentHistory::<Test>::get(1), None);
+        for session in 2..=4 {
+            assert!(AssignmentHistory::<Test>::get(session).is_some());
+        }
+
+        assert_eq!(
+            CollatorAssignment::chain_assignment_at_session(3, 1000.into()),
+            Some((vec![1, 2], None))
+        );
+        assert_eq!(
+            CollatorAssignment::chain_assignment_at_session(3, 1001.into()),
+            Some((vec![3, 4], None))
+        );
+        assert_eq!(
+            CollatorAssignment::chain_assignment_at_session(1, 1001.into()),
+            None
+        );
+        assert_eq!(
+            CollatorAssignment::chain_assignment_at_session(3, 1002.into()),
+            None
+        );
+    });
+}
+
+#[test]
+fn assignment_history_records_dropped_chains() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.collators_per_parathread = 2;
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 2;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6];
+            // 5001 does not have enough credits
+            m.container_chains = vec![1001, 1002, 1003, 5001];
+            m.parathreads = vec![3001];
+        });
+        // Only 2 parachains fit in the cores
+        GetCoreAllocationConfigurationImpl::set(Some(CoreAllocationConfiguration {
+            core_count: 2,
+            max_parachain_percentage: Perbill::from_percent(100),
+        }));
+
+        run_to_block(11);
+
+        let history = AssignmentHistory::<Test>::get(3).unwrap();
+        assert_eq!(
+            history.dropped_chains,
+            BTreeMap::from_iter(vec![
+                (1003.into(), DroppedChainReason::NotEnoughCores),
+                (3001.into(), DroppedChainReason::NotEnoughCollators),
+                (5001.into(), DroppedChainReason::NotEnoughCredits),
+            ])
+        );
+        assert_eq!(
+            history.assignment.container_chains[&1001.into()],
+            vec![3, 4]
+        );
+        assert_eq!(
+            history.assignment.container_chains[&1002.into()],
+            vec![5, 6]
+        );
+
+        assert_eq!(
+            CollatorAssignment::chain_assignment_at_session(3, 1003.into()),
+            Some((vec![], Some(DroppedChainReason::NotEnoughCores)))
+        );
+    });
+}
+
+#[test]
+fn parachains_get_requested_cores() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 2;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6, 7, 8];
+            m.container_chains = vec![1001, 1002, 1003];
+            // 1003 gets the only core left by 1001 and 1002
+            m.core_count_requests = vec![(1001, 3), (1003, 2)];
+        });
+        GetCoreAllocationConfigurationImpl::set(Some(CoreAllocationConfiguration {
+            core_count: 5,
+            max_parachain_percentage: Perbill::from_percent(100),
+        }));
+
+        run_to_block(11);
+
+        assert_eq!(
+            ParachainCores::<Test>::get(3),
+            BTreeMap::from_iter(vec![(1001.into(), 3)])
+        );
+        assert_eq!(CollatorAssignment::parachain_cores(3, 1001.into()), 3);
+        assert_eq!(CollatorAssignment::parachain_cores(3, 1002.into()), 1);
+        assert_eq!(CollatorAssignment::parachain_cores(3, 1003.into()), 1);
+        assert_eq!(
+            AssignmentHistory::<Test>::get(3)
+                .unwrap()
+                .dropped_chains
+                .get(&1003.into()),
+            None
+        );
+
+        // Only the cores of the current and next sessions are kept
+        assert!(!ParachainCores::<Test>::contains_key(1));
+        assert!(ParachainCores::<Test>::contains_key(2));
+    });
+}
+
+#[test]
+fn set_para_ids_filter_stores_and_removes_filter() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        let filter =
+            ParaIdsFilter::Blacklist(BTreeSet::from_iter(vec![1001.into()]).try_into().unwrap());
+        assert_ok!(CollatorAssignment::set_para_ids_filter(
+            RuntimeOrigin::signed(1),
+            Some(filter.clone())
+        ));
+        assert_eq!(CollatorParaIdsFilters::<Test>::get(1), Some(filter.clone()));
+        System::assert_last_event(
+            Event::ParaIdsFilterSet {
+                collator: 1,
+                filter: Some(filter),
+            }
+            .into(),
+        );
+
+        // Accepting any chain is the same as removing the filter
+        assert_ok!(CollatorAssignment::set_para_ids_filter(
+            RuntimeOrigin::signed(1),
+            Some(ParaIdsFilter::AnyParaId)
+        ));
+        assert_eq!(CollatorParaIdsFilters::<Test>::get(1), None);
+
+        assert_noop!(
+            CollatorAssignment::set_para_ids_filter(
+                RuntimeOrigin::signed(42),
+                Some(ParaIdsFilter::Whitelist(Default::default()))
+            ),
+            Error::<Test>::CollatorNotRegistered
+        );
+    });
+}
+
+#[test]
+fn chain_without_eligible_collators_is_dropped() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 2;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6];
+            m.container_chains = vec![1001, 1002];
+        });
+        // Only collator 1 accepts 1002
+        for collator in 2..=6 {
+            assert_ok!(CollatorAssignment::set_para_ids_filter(
+                RuntimeOrigin::signed(collator),
+                Some(ParaIdsFilter::Whitelist(
+                    BTreeSet::from_iter(vec![1001.into()]).try_into().unwrap()
+                ))
+            ));
+        }
+
+        run_to_block(11);
+
+        System::assert_has_event(
+            Event::ChainLacksEligibleCollators {
+                target_session: 3,
+                para_id: 1002.into(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            CollatorAssignment::chain_assignment_at_session(3, 1002.into()),
+            Some((vec![], Some(DroppedChainReason::NotEnoughEligibleCollators)))
+        );
+        // The orchestrator chain is not affected by the filters
+        let assigned = CollatorContainerChain::<Test>::get();
+        assert_eq!(assigned.orchestrator_chain.len(), 2);
+        assert_eq!(assigned.container_chains[&1001.into()].len(), 2);
+    });
+}
+
+#[test]
+fn chain_left_below_min_by_filters_is_dropped() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 2;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6];
+            m.container_chains = vec![1001, 1002];
+        });
+        // Collators 1 and 3 only accept 1002, and the rest only accept 1001. But collator 1 is
+        // assigned to the orchestrator chain first, so 1002 only gets collator 3.
+        for collator in [1, 3] {
+            assert_ok!(CollatorAssignment::set_para_ids_filter(
+                RuntimeOrigin::signed(collator),
+                Some(ParaIdsFilter::Whitelist(
+                    BTreeSet::from_iter(vec![1002.into()]).try_into().unwrap()
+                ))
+            ));
+        }
+        for collator in [2, 4, 5, 6] {
+            assert_ok!(CollatorAssignment::set_para_ids_filter(
+                RuntimeOrigin::signed(collator),
+                Some(ParaIdsFilter::Whitelist(
+                    BTreeSet::from_iter(vec![1001.into()]).try_into().unwrap()
+                ))
+            ));
+        }
+
+        run_to_block(11);
+
+        System::assert_has_event(
+            Event::ChainLacksEligibleCollators {
+                target_session: 3,
+                para_id: 1002.into(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            CollatorAssignment::chain_assignment_at_session(3, 1002.into()),
+            Some((vec![], Some(DroppedChainReason::NotEnoughEligibleCollators)))
+        );
+        let assigned = CollatorContainerChain::<Test>::get();
+        assert_eq!(assigned.container_chains[&1001.into()].len(), 2);
+        assert!(assigned
+            .container_chains
+            .get(&1002.into())
+            .map_or(true, |collators| collators.is_empty()));
+    });
+}
+
+#[test]
+fn collators_of_chain_left_below_min_by_filters_are_assigned_to_other_chains() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 2;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6];
+            m.container_chains = vec![1001, 1002, 1003];
+        });
+        // Only collators 1 and 3 accept 1001, but collator 1 is assigned to the orchestrator chain
+        // first. Without 1001, collator 3 can be assigned to 1003, which is waiting for collators.
+        assert_ok!(CollatorAssignment::set_para_ids_filter(
+            RuntimeOrigin::signed(1),
+            Some(ParaIdsFilter::Whitelist(
+                BTreeSet::from_iter(vec![1001.into()]).try_into().unwrap()
+            ))
+        ));
+        assert_ok!(CollatorAssignment::set_para_ids_filter(
+            RuntimeOrigin::signed(3),
+            Some(ParaIdsFilter::Whitelist(
+                BTreeSet::from_iter(vec![1001.into(), 1003.into()])
+                    .try_into()
+                    .unwrap()
+            ))
+        ));
+        for collator in [2, 4, 5, 6] {
+            assert_ok!(CollatorAssignment::set_para_ids_filter(
+                RuntimeOrigin::signed(collator),
+                Some(ParaIdsFilter::Whitelist(
+                    BTreeSet::from_iter(vec![1002.into(), 1003.into()])
+                        .try_into()
+                        .unwrap()
+                ))
+            ));
+        }
+
+        run_to_block(11);
+
+        System::assert_has_event(
+            Event::ChainLacksEligibleCollators {
+                target_session: 3,
+                para_id: 1001.into(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            CollatorAssignment::chain_assignment_at_session(3, 1001.into()),
+            Some((vec![], Some(DroppedChainReason::NotEnoughEligibleCollators)))
+        );
+        let assigned = CollatorContainerChain::<Test>::get();
+        assert_eq!(assigned.container_chains[&1002.into()].len(), 2);
+        assert_eq!(assigned.container_chains[&1003.into()].len(), 2);
+        assert!(assigned.container_chains[&1003.into()].contains(&3));
+    });
+}
@@ -18,16 +18,30 @@ use {
     crate::{
         assignment::{Assignment, AssignmentError},
         tests::Test,
+        CollatorTags, ParaIdsFilter,
     },
     rand::{seq::SliceRandom, SeedableRng},
     rand_chacha::ChaCha20Rng,
-    sp_std::collections::btree_map::BTreeMap,
+    sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet},
 };
 
 fn no_shuffle() -> Option<fn(&mut Vec<u64>)> {
     None
 }
 
+fn tags(operator: u32, region: Option<&str>) -> CollatorTags {
+    CollatorTags {
+        operator: Some(operator),
+        region: region.map(|region| region.as_bytes().to_vec().try_into().unwrap()),
+    }
+}
+
+fn para_ids(para_ids: &[u32]) -> crate::ParaIdList {
+    BTreeSet::from_iter(para_ids.iter().map(|para_id| (*para_id).into()))
+        .try_into()
+        .unwrap()
+}
+
 #[test]
 fn assign_full_old_assigned_priority() {
     // Collators in old_assigned will be selected before other collators
@@ -35,9 +49,15 @@ fn assign_full_old_assigned_priority() {
     let container_chains = vec![(1000.into(), 5)];
     let old_assigned = BTreeMap::from_iter(vec![(1000.into(), vec![3, 4])]);
 
-    let new_assigned =
-        Assignment::<Test>::assign_full(collators, container_chains, old_assigned, no_shuffle())
-            .unwrap();
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        no_shuffle(),
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+    )
+    .unwrap();
     let expected = BTreeMap::from_iter(vec![(1000.into(), vec![3, 4, 1, 2, 5])]);
     assert_eq!(new_assigned, expected);
 }
@@ -49,9 +69,15 @@ fn assign_full_invalid_old_assigned_collators_removed() {
     let container_chains = vec![(1000.into(), 5)];
     let old_assigned = BTreeMap::from_iter(vec![(1000.into(), vec![20, 21])]);
 
-    let new_assigned =
-        Assignment::<Test>::assign_full(collators, container_chains, old_assigned, no_shuffle())
-            .unwrap();
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        no_shuffle(),
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+    )
+    .unwrap();
     let expected = BTreeMap::from_iter(vec![(1000.into(), vec![1, 2, 3, 4, 5])]);
     assert_eq!(new_assigned, expected);
 }
@@ -63,9 +89,15 @@ fn assign_full_invalid_chains_removed() {
     let container_chains = vec![(1000.into(), 5)];
     let old_assigned = BTreeMap::from_iter(vec![(1001.into(), vec![1, 2, 3, 4, 5])]);
 
-    let new_assigned =
-        Assignment::<Test>::assign_full(collators, container_chains, old_assigned, no_shuffle())
-            .unwrap();
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        no_shuffle(),
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+    )
+    .unwrap();
     let expected = BTreeMap::from_iter(vec![(1000.into(), vec![1, 2, 3, 4, 5])]);
     assert_eq!(new_assigned, expected);
 }
@@ -80,9 +112,15 @@ fn assign_full_truncates_collators() {
         (2000.into(), vec![6, 7, 8, 9, 10]),
     ]);
 
-    let new_assigned =
-        Assignment::<Test>::assign_full(collators, container_chains, old_assigned, no_shuffle())
-            .unwrap();
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        no_shuffle(),
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+    )
+    .unwrap();
     let expected = BTreeMap::from_iter(vec![(1000.into(), vec![1, 2]), (2000.into(), vec![6, 7])]);
     assert_eq!(new_assigned, expected);
 }
@@ -94,8 +132,14 @@ fn assign_full_old_assigned_error_if_not_enough_collators() {
     let collators = vec![1, 2];
     let container_chains = vec![(1000.into(), 2), (2000.into(), 2)];
     let old_assigned = BTreeMap::from_iter(vec![(2000.into(), vec![1, 2])]);
-    let new_assigned =
-        Assignment::<Test>::assign_full(collators, container_chains, old_assigned, no_shuffle());
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        no_shuffle(),
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+    );
     assert_eq!(
         new_assigned.unwrap_err(),
         AssignmentError::NotEnoughCollators
@@ -111,9 +155,15 @@ fn assign_full_list_priority() {
     let container_chains = vec![(1000.into(), 2), (2000.into(), 2)];
     let old_assigned = BTreeMap::from_iter(vec![]);
 
-    let new_assigned =
-        Assignment::<Test>::assign_full(collators, container_chains, old_assigned, no_shuffle())
-            .unwrap();
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        no_shuffle(),
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+    )
+    .unwrap();
     let expected = BTreeMap::from_iter(vec![(1000.into(), vec![1, 2]), (2000.into(), vec![3, 4])]);
     assert_eq!(new_assigned, expected);
 }
@@ -133,9 +183,15 @@ fn assign_full_list_priority_shuffle() {
         collators.shuffle(&mut rng);
     });
 
-    let new_assigned =
-        Assignment::<Test>::assign_full(collators, container_chains, old_assigned, shuffle)
-            .unwrap();
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        shuffle,
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+    )
+    .unwrap();
     // Expect only [1, 2, 3, 4] to be assigned, in random order
     let expected = BTreeMap::from_iter(vec![(1000.into(), vec![3, 2]), (2000.into(), vec![1, 4])]);
     assert_eq!(new_assigned, expected);
@@ -148,9 +204,15 @@ fn assign_full_solochain() {
     let container_chains = vec![(1000.into(), 0), (2000.into(), 3), (2001.into(), 2)];
     let old_assigned = BTreeMap::from_iter(vec![]);
 
-    let new_assigned =
-        Assignment::<Test>::assign_full(collators, container_chains, old_assigned, no_shuffle())
-            .unwrap();
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        no_shuffle(),
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+    )
+    .unwrap();
     let expected = BTreeMap::from_iter(vec![
         (1000.into(), vec![]),
         (2000.into(), vec![1, 2, 3]),
@@ -166,9 +228,98 @@ fn assign_full_solochain_zero_collators() {
     let container_chains = vec![(1000.into(), 0)];
     let old_assigned = BTreeMap::from_iter(vec![]);
 
-    let new_assigned =
-        Assignment::<Test>::assign_full(collators, container_chains, old_assigned, no_shuffle())
-            .unwrap();
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        no_shuffle(),
+        &BTreeMap::new(),
+        &BTreeMap::new(),
+    )
+    .unwrap();
     let expected = BTreeMap::from_iter(vec![(1000.into(), vec![])]);
     assert_eq!(new_assigned, expected);
 }
+
+#[test]
+fn assign_full_spreads_operators_and_regions() {
+    // Without tags, the assignment would be 1000: [1, 2], 2000: [3, 4], 3000: [5, 6]
+    let collators = vec![1, 2, 3, 4, 5, 6];
+    let container_chains = vec![(1000.into(), 2), (2000.into(), 2), (3000.into(), 2)];
+    let old_assigned = BTreeMap::from_iter(vec![(1000.into(), vec![1])]);
+    let collator_tags = BTreeMap::from_iter(vec![
+        (1, tags(1, None)),
+        (2, tags(1, Some("eu"))),
+        (3, tags(2, None)),
+        (4, tags(1, Some("eu"))),
+        (5, tags(3, Some("eu"))),
+    ]);
+
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        no_shuffle(),
+        &collator_tags,
+        &BTreeMap::new(),
+    )
+    .unwrap();
+    // Old collator 1 stays, so 1000 gets collator 3 from a different operator.
+    // 2000 gets collator 2 (first candidate), and then collator 6 because collators 4 and 5 share
+    // the region with collator 2.
+    let expected = BTreeMap::from_iter(vec![
+        (1000.into(), vec![1, 3]),
+        (2000.into(), vec![2, 6]),
+        (3000.into(), vec![4, 5]),
+    ]);
+    assert_eq!(new_assigned, expected);
+}
+
+#[test]
+fn assign_full_respects_para_ids_filters() {
+    // Without filters, the assignment would be 2000: [1, 3], 3000: [2, 4]
+    let collators = vec![1, 2, 3, 4];
+    let container_chains = vec![(2000.into(), 2), (3000.into(), 2)];
+    let old_assigned = BTreeMap::from_iter(vec![(2000.into(), vec![1])]);
+    let para_ids_filters = BTreeMap::from_iter(vec![
+        (1, ParaIdsFilter::Blacklist(para_ids(&[2000]))),
+        (2, ParaIdsFilter::Whitelist(para_ids(&[3000]))),
+    ]);
+
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        no_shuffle(),
+        &BTreeMap::new(),
+        &para_ids_filters,
+    )
+    .unwrap();
+    // Collator 1 no longer accepts 2000, so it is moved to 3000
+    let expected = BTreeMap::from_iter(vec![(2000.into(), vec![3, 4]), (3000.into(), vec![1, 2])]);
+    assert_eq!(new_assigned, expected);
+}
+
+#[test]
+fn assign_full_uses_spare_collators_accepting_the_chain() {
+    // Only collator 3 accepts 3000, but it would not be needed without filters
+    let collators = vec![1, 2, 3];
+    let container_chains = vec![(2000.into(), 1), (3000.into(), 1)];
+    let old_assigned = BTreeMap::new();
+    let para_ids_filters = BTreeMap::from_iter(vec![
+        (1, ParaIdsFilter::Blacklist(para_ids(&[3000]))),
+        (2, ParaIdsFilter::Blacklist(para_ids(&[3000]))),
+    ]);
+
+    let new_assigned = Assignment::<Test>::assign_full(
+        collators,
+        container_chains,
+        old_assigned,
+        no_shuffle(),
+        &BTreeMap::new(),
+        &para_ids_filters,
+    )
+    .unwrap();
+    let expected = BTreeMap::from_iter(vec![(2000.into(), vec![1]), (3000.into(), vec![3])]);
+    assert_eq!(new_assigned, expected);
+}
@@ -17,14 +17,15 @@
 use {
     crate::{assignment::ChainNumCollators, mock::*, CoreAllocationConfiguration, Pallet},
     sp_runtime::Perbill,
+    std::collections::BTreeMap,
     tp_traits::ParaId,
 };
 
 fn create_blank_chain_num_collator(id: u32) -> ChainNumCollators {
     ChainNumCollators {
         para_id: ParaId::new(id),
-        min_collators: 0,
-        max_collators: 0,
+        min_collators: 1,
+        max_collators: 1,
         parathread: false,
     }
 }
@@ -238,10 +239,9 @@ fn test_combine_paras_with_core_config() {
                 generated_parachains.clone(),
                 generated_parathreads.clone(),
                 &config,
-                1,
+                &mut BTreeMap::new(),
+                MockData::mock().min_orchestrator_chain_collators,
                 number_of_collators,
-                1,
-                1,
             );
 
             assert_eq!(chains, result_chains);
@@ -249,3 +249,64 @@ fn test_combine_paras_with_core_config() {
         }
     });
 }
+
+#[test]
+fn test_paras_with_multiple_cores() {
+    let (generated_parachains, generated_parathreads) = generate_parachains_and_parathreads(3, 2);
+    let config = CoreAllocationConfiguration {
+        core_count: 10,
+        max_parachain_percentage: Perbill::from_percent(50),
+    };
+
+    new_test_ext().execute_with(|| {
+        // 4 cores requested out of 5, all parachains fit
+        let mut core_count_requests = BTreeMap::from_iter(vec![(ParaId::from(1), 2)]);
+        let (chains, ordered_by_tip) = Pallet::<Test>::order_paras_with_core_config(
+            generated_parachains.clone(),
+            generated_parathreads.clone(),
+            &config,
+            &mut core_count_requests,
+            MockData::mock().min_orchestrator_chain_collators,
+            100,
+        );
+        assert_eq!(
+            chains,
+            (1..=5)
+                .map(create_blank_chain_num_collator)
+                .collect::<Vec<_>>()
+        );
+        assert!(!ordered_by_tip);
+        assert_eq!(
+            core_count_requests,
+            BTreeMap::from_iter(vec![
+                (ParaId::from(1), 2),
+                (ParaId::from(2), 1),
+                (ParaId::from(3), 1)
+            ])
+        );
+
+        // 7 cores requested out of 5, para 2 gets the 2 cores left and para 3 gets none
+        let mut core_count_requests =
+            BTreeMap::from_iter(vec![(ParaId::from(1), 3), (ParaId::from(2), 3)]);
+        let (chains, ordered_by_tip) = Pallet::<Test>::order_paras_with_core_config(
+            generated_parachains.clone(),
+            generated_parathreads.clone(),
+            &config,
+            &mut core_count_requests,
+            MockData::mock().min_orchestrator_chain_collators,
+            100,
+        );
+        assert_eq!(
+            chains,
+            [1, 2, 4, 5]
+                .into_iter()
+                .map(create_blank_chain_num_collator)
+                .collect::<Vec<_>>()
+        );
+        assert!(ordered_by_tip);
+        assert_eq!(
+            core_count_requests,
+            BTreeMap::from_iter(vec![(ParaId::from(1), 3), (ParaId::from(2), 2)])
+        );
+    });
+}
@@ -53,6 +53,9 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_collator_assignment.
 pub trait WeightInfo {
 	fn new_session(x: u32, y: u32, ) -> Weight;
+	fn set_collator_tags() -> Weight;
+	fn set_para_ids_filter() -> Weight;
+	fn remove_collator_tags() -> Weight;
 }
 
 /// Weights for pallet_collator_assignment using the Substrate node and recommended hardware.
@@ -95,6 +98,45 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 			.saturating_add(Weight::from_parts(0, 2499).saturating_mul(y.into()))
 	}
+	/// Storage: `Session::NextKeys` (r:1 w:0)
+	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CollatorAssignment::RegisteredCollatorTags` (r:0 w:1)
+	/// Proof: `CollatorAssignment::RegisteredCollatorTags` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	fn set_collator_tags() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `309`
+		//  Estimated: `3774`
+		// Minimum execution time: 14_182_000 picoseconds.
+		Weight::from_parts(14_683_000, 3774)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Session::NextKeys` (r:1 w:0)
+	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CollatorAssignment::CollatorParaIdsFilters` (r:0 w:1)
+	/// Proof: `CollatorAssignment::CollatorParaIdsFilters` (`max_values`: None, `max_size`: Some(426), added: 2901, mode: `MaxEncodedLen`)
+	fn set_para_ids_filter() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `309`
+		//  Estimated: `3774`
+		// Minimum execution time: 15_021_000 picoseconds.
+		Weight::from_parts(15_604_000, 3774)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Session::NextKeys` (r:1 w:0)
+	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CollatorAssignment::RegisteredCollatorTags` (r:1 w:1)
+	/// Proof: `CollatorAssignment::RegisteredCollatorTags` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	fn remove_collator_tags() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `395`
+		//  Estimated: `3860`
+		// Minimum execution time: 16_093_000 picoseconds.
+		Weight::from_parts(16_541_000, 3860)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -136,4 +178,43 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 			.saturating_add(Weight::from_parts(0, 2499).saturating_mul(y.into()))
 	}
+	/// Storage: `Session::NextKeys` (r:1 w:0)
+	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CollatorAssignment::RegisteredCollatorTags` (r:0 w:1)
+	/// Proof: `CollatorAssignment::RegisteredCollatorTags` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	fn set_collator_tags() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `309`
+		//  Estimated: `3774`
+		// Minimum execution time: 14_182_000 picoseconds.
+		Weight::from_parts(14_683_000, 3774)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Session::NextKeys` (r:1 w:0)
+	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CollatorAssignment::CollatorParaIdsFilters` (r:0 w:1)
+	/// Proof: `CollatorAssignment::CollatorParaIdsFilters` (`max_values`: None, `max_size`: Some(426), added: 2901, mode: `MaxEncodedLen`)
+	fn set_para_ids_filter() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `309`
+		//  Estimated: `3774`
+		// Minimum execution time: 15_021_000 picoseconds.
+		Weight::from_parts(15_604_000, 3774)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Session::NextKeys` (r:1 w:0)
+	/// Proof: `Session::NextKeys` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CollatorAssignment::RegisteredCollatorTags` (r:1 w:1)
+	/// Proof: `CollatorAssignment::RegisteredCollatorTags` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	fn remove_collator_tags() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `395`
+		//  Estimated: `3860`
+		// Minimum execution time: 16_093_000 picoseconds.
+		Weight::from_parts(16_541_000, 3860)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -18,14 +18,47 @@
 
 //! Benchmarking
 use {
-    crate::{Call, Config, Pallet},
+    crate::{Call, Config, HostConfigurationPatch, Pallet, PendingConfigPatches},
     frame_benchmarking::benchmarks,
+    frame_support::{traits::Get, BoundedVec},
     frame_system::RawOrigin,
+    sp_runtime::Saturating,
+    sp_std::vec::Vec,
 };
 
+fn patch() -> HostConfigurationPatch {
+    HostConfigurationPatch {
+        max_collators: Some(100),
+        min_orchestrator_collators: Some(2),
+        max_orchestrator_collators: Some(5),
+        collators_per_container: Some(2),
+        ..Default::default()
+    }
+}
+
+/// Fill the pending patches up to `count` patches, scheduled after `session_index`.
+fn fill_pending_patches<T: Config>(session_index: T::SessionIndex, count: u32) {
+    let patches: Vec<_> = (1..=count)
+        .map(|i| (session_index.saturating_add(i.into()), patch()))
+        .collect();
+    PendingConfigPatches::<T>::put(BoundedVec::truncate_from(patches));
+}
+
 benchmarks! {
     set_config_with_u32 {}: set_max_collators(RawOrigin::Root, 100)
 
+    schedule_config_patch {
+        let session_index = Pallet::<T>::scheduled_session();
+        // The new patch is inserted before all the other patches
+        fill_pending_patches::<T>(session_index, T::MaxPendingConfigPatches::get().saturating_sub(1));
+    }: _(RawOrigin::Root, session_index, patch())
+
+    cancel_config_patch {
+        let session_index = Pallet::<T>::scheduled_session();
+        fill_pending_patches::<T>(session_index, T::MaxPendingConfigPatches::get().saturating_sub(1));
+        Pallet::<T>::schedule_config_patch(RawOrigin::Root.into(), session_index, patch())?;
+    }: _(RawOrigin::Root, session_index)
+
     impl_benchmark_test_suite!(
         Pallet,
         crate::mock::new_test_ext(),
@@ -26,6 +26,10 @@
 //! All configuration changes are protected behind the root origin
 //! CHanges to the configuration are not immeditaly applied, but rather we wait
 //! T::SessionDelay to apply these changes
+//!
+//! Several fields can also be changed at once with a `HostConfigurationPatch` scheduled for a
+//! chosen session, at least T::SessionDelay sessions in the future. Pending patches are applied
+//! on top of the configuration of that session and can be cancelled until then.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -47,7 +51,7 @@ use {
     frame_support::pallet_prelude::*,
     frame_system::pallet_prelude::*,
     serde::{Deserialize, Serialize},
-    sp_runtime::{traits::AtLeast32BitUnsigned, Perbill, Saturating},
+    sp_runtime::{traits::AtLeast32BitUnsigned, BoundedVec, Perbill, Saturating},
     sp_std::prelude::*,
     tp_traits::GetSessionIndex,
 };
@@ -78,10 +82,8 @@ pub struct HostConfiguration {
     pub collators_per_container: u32,
     /// Rotate all collators once every n sessions. If this value is 0 means that there is no rotation
     pub full_rotation_period: u32,
-    /// How many collators to assign to one parathread
-    // TODO: for now we only support 1 collator per parathread because using Aura for consensus conflicts with
-    // the idea of being able to create blocks every n slots: if there are 2 collators and we create blocks
-    // every 2 slots, 1 collator will create all the blocks.
+    /// How many collators to assign to one parathread. Parathread collators take turns to author
+    /// blocks, see `SlotFrequency::next_parathread_block_slot`.
     pub collators_per_parathread: u32,
     /// How many parathreads can be assigned to one collator
     pub parathreads_per_collator: u32,
@@ -110,6 +112,96 @@ impl Default for HostConfiguration {
     }
 }
 
+/// Partial `HostConfiguration`, only the fields set to `Some` are changed when it is applied.
+#[derive(
+    Clone,
+    Default,
+    Encode,
+    Decode,
+    PartialEq,
+    sp_core::RuntimeDebug,
+    scale_info::TypeInfo,
+    Serialize,
+    Deserialize,
+    MaxEncodedLen,
+)]
+pub struct HostConfigurationPatch {
+    pub max_collators: Option<u32>,
+    pub min_orchestrator_collators: Option<u32>,
+    pub max_orchestrator_collators: Option<u32>,
+    pub collators_per_container: Option<u32>,
+    pub full_rotation_period: Option<u32>,
+    pub collators_per_parathread: Option<u32>,
+    pub parathreads_per_collator: Option<u32>,
+    pub target_container_chain_fullness: Option<Perbill>,
+    pub max_parachain_cores_percentage: Option<Option<Perbill>>,
+    pub full_rotation_mode: Option<FullRotationModes>,
+}
+
+impl HostConfigurationPatch {
+    /// Overwrite the fields of `config` that are set in this patch.
+    pub fn apply(&self, config: &mut HostConfiguration) {
+        if let Some(max_collators) = self.max_collators {
+            config.max_collators = max_collators;
+        }
+        if let Some(min_orchestrator_collators) = self.min_orchestrator_collators {
+            config.min_orchestrator_collators = min_orchestrator_collators;
+        }
+        if let Some(max_orchestrator_collators) = self.max_orchestrator_collators {
+            config.max_orchestrator_collators = max_orchestrator_collators;
+        }
+        if let Some(collators_per_container) = self.collators_per_container {
+            config.collators_per_container = collators_per_container;
+        }
+        if let Some(full_rotation_period) = self.full_rotation_period {
+            config.full_rotation_period = full_rotation_period;
+        }
+        if let Some(collators_per_parathread) = self.collators_per_parathread {
+            config.collators_per_parathread = collators_per_parathread;
+        }
+        if let Some(parathreads_per_collator) = self.parathreads_per_collator {
+            config.parathreads_per_collator = parathreads_per_collator;
+        }
+        if let Some(target_container_chain_fullness) = self.target_container_chain_fullness {
+            config.target_container_chain_fullness = target_container_chain_fullness;
+        }
+        if let Some(max_parachain_cores_percentage) = self.max_parachain_cores_percentage {
+            config.max_parachain_cores_percentage = max_parachain_cores_percentage;
+        }
+        if let Some(ref full_rotation_mode) = self.full_rotation_mode {
+            config.full_rotation_mode = full_rotation_mode.clone();
+        }
+    }
+
+    /// Merge `other` into this patch. The fields set in `other` take precedence.
+    pub fn merge(&mut self, other: HostConfigurationPatch) {
+        self.max_collators = other.max_collators.or(self.max_collators);
+        self.min_orchestrator_collators = other
+            .min_orchestrator_collators
+            .or(self.min_orchestrator_collators);
+        self.max_orchestrator_collators = other
+            .max_orchestrator_collators
+            .or(self.max_orchestrator_collators);
+        self.collators_per_container = other
+            .collators_per_container
+            .or(self.collators_per_container);
+        self.full_rotation_period = other.full_rotation_period.or(self.full_rotation_period);
+        self.collators_per_parathread = other
+            .collators_per_parathread
+            .or(self.collators_per_parathread);
+        self.parathreads_per_collator = other
+            .parathreads_per_collator
+            .or(self.parathreads_per_collator);
+        self.target_container_chain_fullness = other
+            .target_container_chain_fullness
+            .or(self.target_container_chain_fullness);
+        self.max_parachain_cores_percentage = other
+            .max_parachain_cores_percentage
+            .or(self.max_parachain_cores_percentage);
+        self.full_rotation_mode = other.full_rotation_mode.or(self.full_rotation_mode.take());
+    }
+}
+
 /// Enumerates the possible inconsistencies of `HostConfiguration`.
 #[derive(Debug)]
 pub enum InconsistentError {
@@ -175,7 +267,14 @@ pub mod pallet {
     /// Configure the pallet by specifying the parameters and types on which it depends.
     #[pallet::config]
     pub trait Config: frame_system::Config {
-        type SessionIndex: parity_scale_codec::FullCodec + TypeInfo + Copy + AtLeast32BitUnsigned;
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        type SessionIndex: parity_scale_codec::FullCodec
+            + TypeInfo
+            + Copy
+            + AtLeast32BitUnsigned
+            + sp_std::fmt::Debug;
 
         // `SESSION_DELAY` is used to delay any changes to Paras registration or configurations.
         // Wait until the session index is 2 larger then the current index to apply any changes,
@@ -187,14 +286,43 @@ pub mod pallet {
 
         type ForceEmptyOrchestrator: Get<bool>;
 
+        /// Maximum number of configuration patches that can be pending at the same time.
+        #[pallet::constant]
+        type MaxPendingConfigPatches: Get<u32>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
 
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A configuration patch was scheduled. If there was already a patch for that session,
+        /// both patches were merged.
+        ConfigPatchScheduled {
+            session_index: T::SessionIndex,
+            patch: HostConfigurationPatch,
+        },
+        /// The configuration patch scheduled for a session was cancelled.
+        ConfigPatchCancelled { session_index: T::SessionIndex },
+        /// The configuration patch scheduled for a session was not applied because the resulting
+        /// configuration was inconsistent.
+        ConfigPatchSkipped {
+            session_index: T::SessionIndex,
+            patch: HostConfigurationPatch,
+        },
+    }
+
     #[pallet::error]
     pub enum Error<T> {
         /// The new value for a configuration parameter is invalid.
         InvalidNewValue,
+        /// The session of the patch is earlier than the current session plus `SessionDelay`.
+        PatchSessionTooEarly,
+        /// There are already `MaxPendingConfigPatches` pending patches.
+        TooManyPendingConfigPatches,
+        /// There is no pending patch for the given session.
+        ConfigPatchNotFound,
     }
 
     /// The active configuration for the current session.
@@ -216,6 +344,20 @@ pub mod pallet {
     pub type PendingConfigs<T: Config> =
         StorageValue<_, Vec<(T::SessionIndex, HostConfiguration)>, ValueQuery>;
 
+    /// Pending configuration patches, sorted ascending by session index, with at most one patch
+    /// per session.
+    ///
+    /// Patches are applied on top of the configuration of their session when that session starts,
+    /// so they take precedence over configuration changes scheduled for the same session. Later
+    /// configuration changes are based on the patched configuration.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type PendingConfigPatches<T: Config> = StorageValue<
+        _,
+        BoundedVec<(T::SessionIndex, HostConfigurationPatch), T::MaxPendingConfigPatches>,
+        ValueQuery,
+    >;
+
     /// If this is set, then the configuration setters will bypass the consistency checks. This
     /// is meant to be used only as the last resort.
     #[pallet::storage]
@@ -385,6 +527,97 @@ pub mod pallet {
             })
         }
 
+        /// Schedule a change of several configuration fields at once, applied when `session_index`
+        /// starts. If there is already a patch for that session, both patches are merged.
+        #[pallet::call_index(10)]
+        #[pallet::weight((
+        T::WeightInfo::schedule_config_patch(),
+        DispatchClass::Operational,
+        ))]
+        pub fn schedule_config_patch(
+            origin: OriginFor<T>,
+            session_index: T::SessionIndex,
+            patch: HostConfigurationPatch,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                session_index >= Self::scheduled_session(),
+                Error::<T>::PatchSessionTooEarly
+            );
+
+            let mut patches = PendingConfigPatches::<T>::get();
+            let index = match patches
+                .binary_search_by(|(apply_at_session, _)| apply_at_session.cmp(&session_index))
+            {
+                Ok(index) => {
+                    patches[index].1.merge(patch.clone());
+                    index
+                }
+                Err(index) => {
+                    patches
+                        .try_insert(index, (session_index, patch.clone()))
+                        .map_err(|_| Error::<T>::TooManyPendingConfigPatches)?;
+                    index
+                }
+            };
+
+            // The merged patch is checked on top of the configuration it will be applied to
+            let mut new_config =
+                Self::config_at_session_with_patches(session_index, &patches[..index]);
+            patches[index].1.apply(&mut new_config);
+            if BypassConsistencyCheck::<T>::get() {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "Bypassing the consistency check for the configuration patch!",
+                );
+            } else if let Err(e) = new_config.check_consistency(T::ForceEmptyOrchestrator::get()) {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "Configuration patch rejected due to invalid configuration: {:?}",
+                    e,
+                );
+                return Err(Error::<T>::InvalidNewValue.into());
+            }
+
+            PendingConfigPatches::<T>::put(patches);
+            Self::deposit_event(Event::ConfigPatchScheduled {
+                session_index,
+                patch,
+            });
+
+            Ok(())
+        }
+
+        /// Cancel the configuration patch scheduled for `session_index`. Patches for sessions
+        /// earlier than the current session plus `SessionDelay` may already be in use and cannot
+        /// be cancelled.
+        #[pallet::call_index(11)]
+        #[pallet::weight((
+        T::WeightInfo::cancel_config_patch(),
+        DispatchClass::Operational,
+        ))]
+        pub fn cancel_config_patch(
+            origin: OriginFor<T>,
+            session_index: T::SessionIndex,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                session_index >= Self::scheduled_session(),
+                Error::<T>::PatchSessionTooEarly
+            );
+
+            let mut patches = PendingConfigPatches::<T>::get();
+            let index = patches
+                .binary_search_by(|(apply_at_session, _)| apply_at_session.cmp(&session_index))
+                .map_err(|_| Error::<T>::ConfigPatchNotFound)?;
+            patches.remove(index);
+
+            PendingConfigPatches::<T>::put(patches);
+            Self::deposit_event(Event::ConfigPatchCancelled { session_index });
+
+            Ok(())
+        }
+
         /// Setting this to true will disable consistency checks for the configuration setters.
         /// Use with caution.
         #[pallet::call_index(44)]
@@ -416,10 +649,11 @@ pub mod pallet {
         /// be the same.
         pub fn initializer_on_new_session(session_index: &T::SessionIndex) -> SessionChangeOutcome {
             let pending_configs = <PendingConfigs<T>>::get();
+            let pending_patches = PendingConfigPatches::<T>::get();
             let prev_config = ActiveConfig::<T>::get();
 
             // No pending configuration changes, so we're done.
-            if pending_configs.is_empty() {
+            if pending_configs.is_empty() && pending_patches.is_empty() {
                 return SessionChangeOutcome {
                     prev_config,
                     new_config: None,
@@ -443,7 +677,37 @@ pub mod pallet {
                 );
             }
 
-            let new_config = past_and_present.pop().map(|(_, config)| config);
+            let mut new_config = past_and_present.pop().map(|(_, config)| config);
+
+            let (due_patches, future_patches) =
+                pending_patches
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|&(apply_at_session, _)| {
+                        apply_at_session <= *session_index
+                    });
+
+            for (apply_at_session, patch) in due_patches {
+                let mut patched_config = new_config.clone().unwrap_or_else(|| prev_config.clone());
+
+                if let Err(e) = Self::try_apply_patch(&mut patched_config, &patch) {
+                    // The configuration changed since the patch was scheduled
+                    log::error!(
+                        target: LOG_TARGET,
+                        "Skipping configuration patch that results in an invalid configuration: {:?}",
+                        e,
+                    );
+                    Self::deposit_event(Event::ConfigPatchSkipped {
+                        session_index: apply_at_session,
+                        patch,
+                    });
+                    continue;
+                }
+
+                new_config = Some(patched_config);
+            }
+
+            PendingConfigPatches::<T>::put(BoundedVec::truncate_from(future_patches));
+
             if let Some(ref new_config) = new_config {
                 // Apply the new configuration.
                 ActiveConfig::<T>::put(new_config);
@@ -459,7 +723,7 @@ pub mod pallet {
         }
 
         /// Return the session index that should be used for any future scheduled changes.
-        fn scheduled_session() -> T::SessionIndex {
+        pub(crate) fn scheduled_session() -> T::SessionIndex {
             T::CurrentSessionIndex::session_index().saturating_add(T::SessionDelay::get())
         }
 
@@ -514,11 +778,10 @@ pub mod pallet {
             // There cannot be (cur, X) because those are applied in the session change handler for the
             // current session.
 
-            // First, we need to decide what we should use as the base configuration.
-            let mut base_config = pending_configs
-                .last()
-                .map(|(_, config)| config.clone())
-                .unwrap_or_else(Self::config);
+            // First, we need to decide what we should use as the base configuration. Pending
+            // configuration patches up to the scheduled session are included, so that they are
+            // kept by the new configuration.
+            let mut base_config = Self::config_at_session(Self::scheduled_session());
             let base_config_consistent = base_config
                 .check_consistency(T::ForceEmptyOrchestrator::get())
                 .is_ok();
@@ -585,19 +848,62 @@ pub mod pallet {
             PendingConfigs::<T>::get()
         }
 
+        pub fn pending_config_patches() -> Vec<(T::SessionIndex, HostConfigurationPatch)> {
+            PendingConfigPatches::<T>::get().into_inner()
+        }
+
         pub fn config_at_session(session_index: T::SessionIndex) -> HostConfiguration {
+            Self::config_at_session_with_patches(session_index, &PendingConfigPatches::<T>::get())
+        }
+
+        /// Configuration at `session_index`, including the given pending patches of that session
+        /// and earlier sessions, as it will be applied by `initializer_on_new_session`.
+        ///
+        /// Pending configuration changes already include the patches of earlier sessions, so only
+        /// the patches from the session of the latest pending configuration change are applied.
+        /// Patches resulting in an inconsistent configuration are skipped.
+        fn config_at_session_with_patches(
+            session_index: T::SessionIndex,
+            patches: &[(T::SessionIndex, HostConfigurationPatch)],
+        ) -> HostConfiguration {
             let (past_and_present, _) = Pallet::<T>::pending_configs()
                 .into_iter()
                 .partition::<Vec<_>, _>(|&(apply_at_session, _)| apply_at_session <= session_index);
 
-            let config = if let Some(last) = past_and_present.last() {
-                last.1.clone()
+            let (mut config, maybe_base_session) = if let Some(last) = past_and_present.last() {
+                (last.1.clone(), Some(last.0))
             } else {
-                Pallet::<T>::config()
+                (Pallet::<T>::config(), None)
             };
 
+            for (_, patch) in patches.iter().filter(|(apply_at_session, _)| {
+                *apply_at_session <= session_index
+                    && maybe_base_session
+                        .map_or(true, |base_session| *apply_at_session >= base_session)
+            }) {
+                let _ = Self::try_apply_patch(&mut config, patch);
+            }
+
             config
         }
+
+        /// Apply `patch` to `config`, unless the resulting configuration is inconsistent and the
+        /// consistency checks are not bypassed, in which case `config` is left unchanged.
+        fn try_apply_patch(
+            config: &mut HostConfiguration,
+            patch: &HostConfigurationPatch,
+        ) -> Result<(), InconsistentError> {
+            let mut patched_config = config.clone();
+            patch.apply(&mut patched_config);
+
+            if !BypassConsistencyCheck::<T>::get() {
+                patched_config.check_consistency(T::ForceEmptyOrchestrator::get())?;
+            }
+
+            *config = patched_config;
+
+            Ok(())
+        }
     }
 
     impl<T: Config> GetHostConfiguration<T::SessionIndex> for Pallet<T> {
@@ -79,11 +79,13 @@ impl pallet_configuration::GetSessionIndex<u32> for CurrentSessionIndexGetter {
 }
 
 impl pallet_configuration::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type SessionDelay = ConstU32<2>;
     type SessionIndex = u32;
     type CurrentSessionIndex = CurrentSessionIndexGetter;
     type ForceEmptyOrchestrator = ConstBool<false>;
+    type MaxPendingConfigPatches = ConstU32<2>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -15,7 +15,7 @@
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
 use {
-    crate::{mock::*, Error, HostConfiguration, PendingConfigs},
+    crate::{mock::*, Error, Event, HostConfiguration, HostConfigurationPatch, PendingConfigs},
     frame_support::{assert_noop, assert_ok, dispatch::GetDispatchInfo},
     sp_std::vec,
 };
@@ -444,3 +444,296 @@ fn set_max_collators_below_min_orch_collators_errors_reverse() {
         );
     });
 }
+
+#[test]
+fn config_patch_applies_all_fields_at_session() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let patch = HostConfigurationPatch {
+            max_collators: Some(50),
+            collators_per_container: Some(3),
+            ..Default::default()
+        };
+        assert_ok!(Configuration::schedule_config_patch(
+            RuntimeOrigin::root(),
+            3,
+            patch.clone()
+        ));
+        System::assert_last_event(
+            Event::ConfigPatchScheduled {
+                session_index: 3,
+                patch: patch.clone(),
+            }
+            .into(),
+        );
+        assert_eq!(Configuration::pending_config_patches(), vec![(3, patch)]);
+
+        assert_eq!(Configuration::config_at_session(2).max_collators, 100);
+        assert_eq!(Configuration::config_at_session(3).max_collators, 50);
+        assert_eq!(
+            Configuration::config_at_session(3).collators_per_container,
+            3
+        );
+
+        // First block of session 2
+        run_to_block(11);
+        assert_eq!(Configuration::config().max_collators, 100);
+        // First block of session 3
+        run_to_block(16);
+        assert_eq!(Configuration::config().max_collators, 50);
+        assert_eq!(Configuration::config().collators_per_container, 3);
+        assert!(Configuration::pending_config_patches().is_empty());
+    });
+}
+
+#[test]
+fn config_patch_before_session_delay_fails() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_noop!(
+            Configuration::schedule_config_patch(
+                RuntimeOrigin::root(),
+                1,
+                HostConfigurationPatch {
+                    max_collators: Some(50),
+                    ..Default::default()
+                }
+            ),
+            Error::<Test>::PatchSessionTooEarly
+        );
+    });
+}
+
+#[test]
+fn config_patch_checks_merged_config() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_noop!(
+            Configuration::schedule_config_patch(
+                RuntimeOrigin::root(),
+                2,
+                HostConfigurationPatch {
+                    max_collators: Some(1),
+                    ..Default::default()
+                }
+            ),
+            Error::<Test>::InvalidNewValue
+        );
+
+        assert_ok!(Configuration::schedule_config_patch(
+            RuntimeOrigin::root(),
+            2,
+            HostConfigurationPatch {
+                max_orchestrator_collators: Some(10),
+                ..Default::default()
+            }
+        ));
+        // Merged with the previous patch, min is greater than max
+        assert_noop!(
+            Configuration::schedule_config_patch(
+                RuntimeOrigin::root(),
+                2,
+                HostConfigurationPatch {
+                    min_orchestrator_collators: Some(20),
+                    ..Default::default()
+                }
+            ),
+            Error::<Test>::InvalidNewValue
+        );
+        assert_ok!(Configuration::schedule_config_patch(
+            RuntimeOrigin::root(),
+            2,
+            HostConfigurationPatch {
+                min_orchestrator_collators: Some(8),
+                ..Default::default()
+            }
+        ));
+
+        assert_eq!(
+            Configuration::pending_config_patches(),
+            vec![(
+                2,
+                HostConfigurationPatch {
+                    min_orchestrator_collators: Some(8),
+                    max_orchestrator_collators: Some(10),
+                    ..Default::default()
+                }
+            )]
+        );
+    });
+}
+
+#[test]
+fn config_patch_limit_is_enforced() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let patch = HostConfigurationPatch {
+            max_collators: Some(50),
+            ..Default::default()
+        };
+        assert_ok!(Configuration::schedule_config_patch(
+            RuntimeOrigin::root(),
+            2,
+            patch.clone()
+        ));
+        assert_ok!(Configuration::schedule_config_patch(
+            RuntimeOrigin::root(),
+            4,
+            patch.clone()
+        ));
+        assert_noop!(
+            Configuration::schedule_config_patch(RuntimeOrigin::root(), 3, patch),
+            Error::<Test>::TooManyPendingConfigPatches
+        );
+    });
+}
+
+#[test]
+fn cancel_config_patch_removes_patch() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Configuration::schedule_config_patch(
+            RuntimeOrigin::root(),
+            2,
+            HostConfigurationPatch {
+                max_collators: Some(50),
+                ..Default::default()
+            }
+        ));
+        assert_ok!(Configuration::cancel_config_patch(RuntimeOrigin::root(), 2));
+        System::assert_last_event(Event::ConfigPatchCancelled { session_index: 2 }.into());
+        assert!(Configuration::pending_config_patches().is_empty());
+
+        assert_noop!(
+            Configuration::cancel_config_patch(RuntimeOrigin::root(), 2),
+            Error::<Test>::ConfigPatchNotFound
+        );
+
+        run_to_block(11);
+        assert_eq!(Configuration::config().max_collators, 100);
+    });
+}
+
+#[test]
+fn cancel_config_patch_in_use_fails() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Configuration::schedule_config_patch(
+            RuntimeOrigin::root(),
+            2,
+            HostConfigurationPatch {
+                max_collators: Some(50),
+                ..Default::default()
+            }
+        ));
+
+        // First block of session 1, the patch for session 2 may already be in use
+        run_to_block(6);
+        assert_noop!(
+            Configuration::cancel_config_patch(RuntimeOrigin::root(), 2),
+            Error::<Test>::PatchSessionTooEarly
+        );
+    });
+}
+
+#[test]
+fn config_patch_is_kept_by_later_config_changes() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Configuration::schedule_config_patch(
+            RuntimeOrigin::root(),
+            2,
+            HostConfigurationPatch {
+                max_collators: Some(50),
+                ..Default::default()
+            }
+        ));
+
+        // Scheduled for session 3, based on the config of session 3 which includes the patch
+        run_to_block(6);
+        assert_ok!(Configuration::set_collators_per_container(
+            RuntimeOrigin::root(),
+            5
+        ));
+        assert_eq!(Configuration::config_at_session(3).max_collators, 50);
+
+        run_to_block(11);
+        assert_eq!(Configuration::config().max_collators, 50);
+        run_to_block(16);
+        assert_eq!(Configuration::config().max_collators, 50);
+        assert_eq!(Configuration::config().collators_per_container, 5);
+    });
+}
+
+#[test]
+fn later_config_change_of_patched_field_is_kept() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Configuration::schedule_config_patch(
+            RuntimeOrigin::root(),
+            2,
+            HostConfigurationPatch {
+                max_collators: Some(50),
+                ..Default::default()
+            }
+        ));
+
+        // Scheduled for session 3, after the patch
+        run_to_block(6);
+        assert_ok!(Configuration::set_max_collators(RuntimeOrigin::root(), 70));
+        assert_eq!(Configuration::config_at_session(2).max_collators, 50);
+        assert_eq!(Configuration::config_at_session(3).max_collators, 70);
+
+        run_to_block(11);
+        assert_eq!(Configuration::config().max_collators, 50);
+        run_to_block(16);
+        assert_eq!(Configuration::config().max_collators, 70);
+    });
+}
+
+#[test]
+fn inconsistent_config_patch_is_skipped() {
+    new_test_ext_with_genesis(HostConfiguration {
+        max_collators: 100,
+        min_orchestrator_collators: 2,
+        max_orchestrator_collators: 5,
+        collators_per_container: 2,
+        full_rotation_period: 24,
+        ..Default::default()
+    })
+    .execute_with(|| {
+        run_to_block(1);
+        let patch = HostConfigurationPatch {
+            min_orchestrator_collators: Some(4),
+            ..Default::default()
+        };
+        assert_ok!(Configuration::schedule_config_patch(
+            RuntimeOrigin::root(),
+            3,
+            patch.clone()
+        ));
+        // Scheduled for session 2, before the patch
+        assert_ok!(Configuration::set_max_orchestrator_collators(
+            RuntimeOrigin::root(),
+            3
+        ));
+
+        // The patch would make min greater than max, so it is not used
+        assert_eq!(
+            Configuration::config_at_session(3).min_orchestrator_collators,
+            2
+        );
+
+        run_to_block(16);
+        System::assert_has_event(
+            Event::ConfigPatchSkipped {
+                session_index: 3,
+                patch,
+            }
+            .into(),
+        );
+        assert_eq!(Configuration::config().min_orchestrator_collators, 2);
+        assert_eq!(Configuration::config().max_orchestrator_collators, 3);
+        assert!(Configuration::pending_config_patches().is_empty());
+    });
+}
@@ -53,6 +53,8 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_configuration.
 pub trait WeightInfo {
 	fn set_config_with_u32() -> Weight;
+	fn schedule_config_patch() -> Weight;
+	fn cancel_config_patch() -> Weight;
 }
 
 /// Weights for pallet_configuration using the Substrate node and recommended hardware.
@@ -66,13 +68,47 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: Configuration BypassConsistencyCheck (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Session CurrentIndex (r:1 w:0)
 	/// Proof Skipped: Session CurrentIndex (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Configuration PendingConfigPatches (r:1 w:0)
+	/// Proof Skipped: Configuration PendingConfigPatches (max_values: Some(1), max_size: None, mode: Measured)
 	fn set_config_with_u32() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `252`
 		//  Estimated: `6948`
 		// Minimum execution time: 9_507_000 picoseconds.
 		Weight::from_parts(9_924_000, 6948)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Configuration PendingConfigPatches (r:1 w:1)
+	/// Proof Skipped: Configuration PendingConfigPatches (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Configuration PendingConfigs (r:1 w:0)
+	/// Proof Skipped: Configuration PendingConfigs (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Configuration ActiveConfig (r:1 w:0)
+	/// Proof Skipped: Configuration ActiveConfig (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Configuration BypassConsistencyCheck (r:1 w:0)
+	/// Proof Skipped: Configuration BypassConsistencyCheck (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session CurrentIndex (r:1 w:0)
+	/// Proof Skipped: Session CurrentIndex (max_values: Some(1), max_size: None, mode: Measured)
+	fn schedule_config_patch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `1897`
+		// Minimum execution time: 14_215_000 picoseconds.
+		Weight::from_parts(14_803_000, 1897)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Configuration PendingConfigPatches (r:1 w:1)
+	/// Proof Skipped: Configuration PendingConfigPatches (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session CurrentIndex (r:1 w:0)
+	/// Proof Skipped: Session CurrentIndex (max_values: Some(1), max_size: None, mode: Measured)
+	fn cancel_config_patch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `398`
+		//  Estimated: `1883`
+		// Minimum execution time: 9_872_000 picoseconds.
+		Weight::from_parts(10_241_000, 1883)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 }
@@ -87,13 +123,47 @@ impl WeightInfo for () {
 	/// Proof Skipped: Configuration BypassConsistencyCheck (max_values: Some(1), max_size: None, mode: Measured)
 	/// Storage: Session CurrentIndex (r:1 w:0)
 	/// Proof Skipped: Session CurrentIndex (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Configuration PendingConfigPatches (r:1 w:0)
+	/// Proof Skipped: Configuration PendingConfigPatches (max_values: Some(1), max_size: None, mode: Measured)
 	fn set_config_with_u32() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `252`
 		//  Estimated: `6948`
 		// Minimum execution time: 9_507_000 picoseconds.
 		Weight::from_parts(9_924_000, 6948)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Configuration PendingConfigPatches (r:1 w:1)
+	/// Proof Skipped: Configuration PendingConfigPatches (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Configuration PendingConfigs (r:1 w:0)
+	/// Proof Skipped: Configuration PendingConfigs (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Configuration ActiveConfig (r:1 w:0)
+	/// Proof Skipped: Configuration ActiveConfig (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Configuration BypassConsistencyCheck (r:1 w:0)
+	/// Proof Skipped: Configuration BypassConsistencyCheck (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session CurrentIndex (r:1 w:0)
+	/// Proof Skipped: Session CurrentIndex (max_values: Some(1), max_size: None, mode: Measured)
+	fn schedule_config_patch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `1897`
+		// Minimum execution time: 14_215_000 picoseconds.
+		Weight::from_parts(14_803_000, 1897)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Configuration PendingConfigPatches (r:1 w:1)
+	/// Proof Skipped: Configuration PendingConfigPatches (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session CurrentIndex (r:1 w:0)
+	/// Proof Skipped: Session CurrentIndex (max_values: Some(1), max_size: None, mode: Measured)
+	fn cancel_config_patch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `398`
+		//  Estimated: `1883`
+		// Minimum execution time: 9_872_000 picoseconds.
+		Weight::from_parts(10_241_000, 1883)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 }
@@ -22,44 +22,19 @@ use {
     tp_traits::{apply, derive_scale_codec, derive_storage_traits},
 };
 
+pub use tp_traits::ParaIdsFilter;
+
 // Data preserver profile.
 #[apply(derive_scale_codec)]
 #[derive(RuntimeDebugNoBound, PartialEqNoBound, EqNoBound, CloneNoBound, MaxEncodedLen)]
 #[scale_info(skip_type_params(T))]
 pub struct Profile<T: Config> {
     pub url: BoundedVec<u8, T::MaxNodeUrlLen>,
-    pub para_ids: ParaIdsFilter<T>,
+    pub para_ids: ParaIdsFilter<T::MaxParaIdsVecLen>,
     pub mode: ProfileMode,
     pub assignment_request: ProviderRequestOf<T>,
 }
 
-#[apply(derive_scale_codec)]
-#[derive(RuntimeDebugNoBound, PartialEqNoBound, EqNoBound, CloneNoBound, MaxEncodedLen)]
-#[scale_info(skip_type_params(T))]
-pub enum ParaIdsFilter<T: Config> {
-    AnyParaId,
-    Whitelist(BoundedBTreeSet<ParaId, T::MaxParaIdsVecLen>),
-    Blacklist(BoundedBTreeSet<ParaId, T::MaxParaIdsVecLen>),
-}
-
-impl<T: Config> ParaIdsFilter<T> {
-    #[allow(clippy::len_without_is_empty)]
-    pub fn len(&self) -> usize {
-        match self {
-            Self::AnyParaId => 0,
-            Self::Whitelist(list) | Self::Blacklist(list) => list.len(),
-        }
-    }
-
-    pub fn can_assign(&self, para_id: &ParaId) -> bool {
-        match self {
-            ParaIdsFilter::AnyParaId => true,
-            ParaIdsFilter::Whitelist(list) => list.contains(para_id),
-            ParaIdsFilter::Blacklist(list) => !list.contains(para_id),
-        }
-    }
-}
-
 #[apply(derive_storage_traits)]
 #[derive(MaxEncodedLen)]
 pub enum ProfileMode {
@@ -115,7 +115,7 @@ mod benchmarks {
 
         #[block]
         {
-            processed = Pallet::<T>::process_slashes_queue(s);
+            (processed, _) = Pallet::<T>::process_slashes_queue(s);
         }
 
         assert_eq!(UnreportedSlashesQueue::<T>::get().len(), 1);
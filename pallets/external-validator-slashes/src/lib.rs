@@ -20,6 +20,7 @@
 //! Slashes can be cancelled during the DeferPeriod through cancel_deferred_slash
 //! Slashes can also be forcedly injected via the force_inject_slash extrinsic
 //! Slashes for a particular era are removed after the bondingPeriod has elapsed
+//! Slashes are notified to the OnSlashApplied hook when they are processed from the queue
 //!
 //! ## OnOffence trait
 //!
@@ -46,7 +47,7 @@ use {
     sp_std::collections::vec_deque::VecDeque,
     sp_std::vec,
     sp_std::vec::Vec,
-    tp_traits::{EraIndexProvider, InvulnerablesProvider, OnEraStart},
+    tp_traits::{EraIndexProvider, InvulnerablesProvider, OnEraStart, OnSlashApplied},
 };
 
 use snowbridge_core::ChannelId;
@@ -145,6 +146,9 @@ pub mod pallet {
         #[pallet::constant]
         type QueuedSlashesProcessedPerBlock: Get<u32>;
 
+        /// Hook called for each slash when it is processed from the queue and reported.
+        type OnSlashApplied: OnSlashApplied<Self::AccountId>;
+
         /// The weight information of this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -350,8 +354,9 @@ pub mod pallet {
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
-            let processed = Self::process_slashes_queue(T::QueuedSlashesProcessedPerBlock::get());
-            T::WeightInfo::process_slashes_queue(processed)
+            let (processed, hooks_weight) =
+                Self::process_slashes_queue(T::QueuedSlashesProcessedPerBlock::get());
+            T::WeightInfo::process_slashes_queue(processed).saturating_add(hooks_weight)
         }
     }
 }
@@ -520,9 +525,11 @@ impl<T: Config> Pallet<T> {
         UnreportedSlashesQueue::<T>::mutate(|queue| queue.append(&mut slashes));
     }
 
-    /// Returns number of slashes that were sent to ethereum.
-    fn process_slashes_queue(amount: u32) -> u32 {
+    /// Returns number of slashes that were sent to ethereum, and the weight consumed by
+    /// the `OnSlashApplied` hooks.
+    fn process_slashes_queue(amount: u32) -> (u32, Weight) {
         let mut slashes_to_send: Vec<_> = vec![];
+        let mut hooks_weight = Weight::zero();
         let era_index = T::EraIndexProvider::active_era().index;
 
         UnreportedSlashesQueue::<T>::mutate(|queue| {
@@ -532,6 +539,11 @@ impl<T: Config> Pallet<T> {
                     break;
                 };
 
+                hooks_weight.saturating_accrue(T::OnSlashApplied::on_slash_applied(
+                    &slash.validator,
+                    slash.percentage,
+                ));
+
                 slashes_to_send.push((
                     slash.validator.clone().encode(),
                     slash.percentage.deconstruct(),
@@ -540,7 +552,7 @@ impl<T: Config> Pallet<T> {
         });
 
         if slashes_to_send.is_empty() {
-            return 0;
+            return (0, hooks_weight);
         }
 
         let slashes_count = slashes_to_send.len() as u32;
@@ -577,7 +589,7 @@ impl<T: Config> Pallet<T> {
             }
         };
 
-        slashes_count
+        (slashes_count, hooks_weight)
     }
 }
 
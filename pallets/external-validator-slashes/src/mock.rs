@@ -19,6 +19,7 @@ use {
     frame_support::{
         parameter_types,
         traits::{ConstU16, ConstU32, ConstU64, Get, Hooks},
+        weights::Weight,
     },
     frame_system as system,
     snowbridge_core::outbound::{SendError, SendMessageFeeProvider},
@@ -26,11 +27,11 @@ use {
     sp_runtime::{
         testing::UintAuthorityId,
         traits::{BlakeTwo256, ConvertInto, IdentityLookup},
-        BuildStorage,
+        BuildStorage, Perbill,
     },
     sp_staking::SessionIndex,
     sp_std::cell::RefCell,
-    tp_traits::{ActiveEraInfo, EraIndex, EraIndexProvider, InvulnerablesProvider},
+    tp_traits::{ActiveEraInfo, EraIndex, EraIndexProvider, InvulnerablesProvider, OnSlashApplied},
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -125,6 +126,7 @@ thread_local! {
     pub static ERA_INDEX: RefCell<EraIndex> = const { RefCell::new(0) };
     pub static DEFER_PERIOD: RefCell<EraIndex> = const { RefCell::new(2) };
     pub static SENT_ETHEREUM_MESSAGE_NONCE: RefCell<u64> = const { RefCell::new(0) };
+    pub static APPLIED_SLASHES: RefCell<Vec<(u64, Perbill)>> = const { RefCell::new(vec![]) };
 
 }
 
@@ -231,6 +233,18 @@ impl SendMessageFeeProvider for MockOkOutboundQueue {
     }
 }
 
+pub fn applied_slashes() -> Vec<(u64, Perbill)> {
+    APPLIED_SLASHES.with(|q| q.borrow().clone())
+}
+
+pub struct MockOnSlashApplied;
+impl OnSlashApplied<u64> for MockOnSlashApplied {
+    fn on_slash_applied(offender: &u64, fraction: Perbill) -> Weight {
+        APPLIED_SLASHES.with(|r| r.borrow_mut().push((*offender, fraction)));
+        Weight::zero()
+    }
+}
+
 pub struct TimestampProvider;
 impl Get<u64> for TimestampProvider {
     fn get() -> u64 {
@@ -256,6 +270,7 @@ impl external_validator_slashes::Config for Test {
     type OutboundQueue = MockOkOutboundQueue;
     type TimestampProvider = TimestampProvider;
     type QueuedSlashesProcessedPerBlock = ConstU32<20>;
+    type OnSlashApplied = MockOnSlashApplied;
     type WeightInfo = ();
 }
 
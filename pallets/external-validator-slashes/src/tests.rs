@@ -18,9 +18,9 @@ use {
     super::*,
     crate::{
         mock::{
-            new_test_ext, roll_one_block, sent_ethereum_message_nonce, DeferPeriodGetter,
-            ExternalValidatorSlashes, MockEraIndexProvider, RuntimeEvent, RuntimeOrigin, System,
-            Test,
+            applied_slashes, new_test_ext, roll_one_block, sent_ethereum_message_nonce,
+            DeferPeriodGetter, ExternalValidatorSlashes, MockEraIndexProvider, RuntimeEvent,
+            RuntimeOrigin, System, Test,
         },
         Slash,
     },
@@ -330,6 +330,36 @@ fn test_slashes_command_matches_event() {
     });
 }
 
+#[test]
+fn test_processed_slashes_are_notified_to_hook() {
+    new_test_ext().execute_with(|| {
+        crate::mock::DeferPeriodGetter::with_defer_period(0);
+        start_era(0, 0);
+        start_era(1, 1);
+        Pallet::<Test>::on_offence(
+            &[OffenceDetails {
+                // 1 and 2 are invulnerables
+                offender: (3, ()),
+                reporters: vec![],
+            }],
+            &[Perbill::from_percent(75)],
+            0,
+        );
+
+        // Not applied until the slash is processed from the queue
+        assert_eq!(applied_slashes(), vec![]);
+        start_era(2, 2);
+        assert_eq!(applied_slashes(), vec![]);
+
+        roll_one_block();
+        assert_eq!(applied_slashes(), vec![(3, Perbill::from_percent(75))]);
+
+        // Queue is empty, nothing else gets applied
+        roll_one_block();
+        assert_eq!(applied_slashes(), vec![(3, Perbill::from_percent(75))]);
+    });
+}
+
 #[test]
 fn test_on_offence_defer_period_0_messages_get_queued() {
     new_test_ext().execute_with(|| {
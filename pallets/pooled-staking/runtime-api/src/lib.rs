@@ -0,0 +1,136 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Runtime API for Pooled Staking pallet
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use {
+    alloc::vec::Vec,
+    parity_scale_codec::{Decode, Encode},
+    serde::{Deserialize, Serialize},
+    sp_arithmetic::Perbill,
+};
+
+/// Shares owned by a delegator in a pool and their value in staked currency.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    scale_info::TypeInfo,
+    Serialize,
+    Deserialize,
+)]
+pub struct PoolStake<Balance> {
+    pub shares: Balance,
+    pub stake: Balance,
+}
+
+/// Position of a delegator in all the pools of a candidate.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    scale_info::TypeInfo,
+    Serialize,
+    Deserialize,
+)]
+pub struct DelegatorStake<Balance> {
+    pub joining: PoolStake<Balance>,
+    pub auto_compounding: PoolStake<Balance>,
+    pub manual_rewards: PoolStake<Balance>,
+    pub leaving: PoolStake<Balance>,
+    /// Rewards of the manual rewards pool that can be claimed.
+    pub claimable_manual_rewards: Balance,
+}
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo, Serialize, Deserialize,
+)]
+pub enum PendingOperationKind {
+    JoiningAutoCompounding,
+    JoiningManualRewards,
+    Leaving,
+}
+
+/// Joining or leaving request of a delegator.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo, Serialize, Deserialize,
+)]
+pub struct PendingOperation<AccountId, Instant, Balance> {
+    pub candidate: AccountId,
+    pub kind: PendingOperationKind,
+    /// Instant at which the request was made.
+    pub at: Instant,
+    /// Instant from which the request can be executed, `None` if it cannot be computed.
+    pub unlock_at: Option<Instant>,
+    /// Whenever the request can be executed now.
+    pub executable: bool,
+    /// Shares in the joining or leaving pool.
+    pub shares: Balance,
+    /// Current value of the shares.
+    pub stake: Balance,
+}
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo, Serialize, Deserialize,
+)]
+pub struct CandidateStake<Balance> {
+    /// Total stake backing the candidate across all pools.
+    pub total_stake: Balance,
+    /// Position in the sorted list of eligible candidates, starting at 0 for the candidate
+    /// with the highest stake. `None` if the candidate is not part of the list.
+    pub rank: Option<u32>,
+}
+
+sp_api::decl_runtime_apis! {
+    pub trait PooledStakingApi<AccountId, Balance, Instant>
+    where
+        AccountId: parity_scale_codec::Codec,
+        Balance: parity_scale_codec::Codec,
+        Instant: parity_scale_codec::Codec,
+    {
+        /// Commission applied to the rewards of the candidate.
+        fn candidate_commission(candidate: AccountId) -> Perbill;
+
+        /// Candidates for which the delegator has shares in any pool.
+        /// This iterates over the pools of all candidates.
+        fn delegator_candidates(delegator: AccountId) -> Vec<AccountId>;
+
+        /// Position of the delegator in the pools of the candidate.
+        fn delegator_stake(candidate: AccountId, delegator: AccountId) -> DelegatorStake<Balance>;
+
+        /// Pending joining and leaving requests of the delegator.
+        fn delegator_pending_operations(
+            delegator: AccountId,
+        ) -> Vec<PendingOperation<AccountId, Instant, Balance>>;
+
+        /// Total stake of the candidate and its rank among eligible candidates.
+        fn candidate_stake(candidate: AccountId) -> CandidateStake<Balance>;
+    }
+}
@@ -26,12 +26,14 @@ use {
     frame_support::{
         dispatch::RawOrigin,
         traits::{
-            fungible::{InspectHold, Mutate, MutateHold},
-            tokens::{fungible::Balanced, Precision},
+            fungible::{Inspect, InspectHold, Mutate, MutateHold},
+            tokens::fungible::Balanced,
             Get,
         },
+        BoundedVec,
     },
     frame_system::EventRecord,
+    sp_runtime::{traits::Zero, Perbill},
     sp_std::prelude::*,
 };
 
@@ -40,6 +42,24 @@ fn min_candidate_stk<T: Config>() -> T::Balance {
     <<T as Config>::MinimumSelfDelegation as Get<T::Balance>>::get()
 }
 
+/// Candidate with enough self delegation to change its commission.
+fn create_self_delegated_candidate<T: Config>() -> T::AccountId {
+    const USER_SEED: u32 = 1;
+
+    let (caller, _deposit_amount) =
+        create_funded_user::<T>("caller", USER_SEED, min_candidate_stk::<T>());
+
+    PooledStaking::<T>::request_delegate(
+        RawOrigin::Signed(caller.clone()).into(),
+        caller.clone(),
+        TargetPool::AutoCompounding,
+        min_candidate_stk::<T>(),
+    )
+    .expect("candidate can self delegate");
+
+    caller
+}
+
 fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
     let events = frame_system::Pallet::<T>::events();
     let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
@@ -413,17 +433,18 @@ mod benchmarks {
             min_candidate_stk::<T>(),
         )?;
 
-        let fake_hold = min_candidate_stk::<T>() / 2u32.into();
+        let excess_hold = min_candidate_stk::<T>() / 2u32.into();
 
-        // We manually hack it such that hold!=stake
-        pools::Joining::<T>::set_hold(&candidate, &caller, Stake(fake_hold));
-        let on_hold_before = T::Currency::balance_on_hold(&HoldReason::PooledStake.into(), &caller);
-        T::Currency::release(
-            &HoldReason::PooledStake.into(),
+        // We manually hack it such that hold > stake, as if the candidate was slashed
+        // while the staking account couldn't advance the slashed funds, which is the
+        // worst case.
+        pools::Joining::<T>::set_hold(
+            &candidate,
             &caller,
-            on_hold_before - fake_hold,
-            Precision::Exact,
-        )?;
+            Stake(min_candidate_stk::<T>() + excess_hold),
+        );
+        T::Currency::hold(&HoldReason::PooledStake.into(), &caller, excess_hold)?;
+        PendingSlashedFunds::<T>::put(excess_hold);
 
         #[extrinsic_call]
         _(
@@ -436,6 +457,7 @@ mod benchmarks {
         // After this hold should have been rebalanced
         let on_hold = T::Currency::balance_on_hold(&HoldReason::PooledStake.into(), &caller);
         assert_eq!(on_hold, min_candidate_stk::<T>());
+        assert!(PendingSlashedFunds::<T>::get().is_zero());
         Ok(())
     }
 
@@ -607,6 +629,270 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn slash_candidate(
+        r: Linear<0, { T::MaxRedelegationsPerCandidate::get() }>,
+    ) -> Result<(), BenchmarkError> {
+        const USER_SEED: u32 = 1;
+
+        let source_stake = min_candidate_stk::<T>() * 10u32.into();
+
+        let (caller, _deposit_amount) =
+            create_funded_user::<T>("caller", USER_SEED, source_stake * 5u32.into());
+
+        T::EligibleCandidatesFilter::make_candidate_eligible(&caller, true);
+
+        let (target, _) = create_funded_user::<T>("target", USER_SEED, source_stake);
+        T::EligibleCandidatesFilter::make_candidate_eligible(&target, true);
+        PooledStaking::<T>::request_delegate(
+            RawOrigin::Signed(caller.clone()).into(),
+            target.clone(),
+            TargetPool::AutoCompounding,
+            source_stake,
+        )?;
+
+        PooledStaking::<T>::request_delegate(
+            RawOrigin::Signed(caller.clone()).into(),
+            caller.clone(),
+            TargetPool::AutoCompounding,
+            source_stake,
+        )?;
+        PooledStaking::<T>::request_delegate(
+            RawOrigin::Signed(caller.clone()).into(),
+            caller.clone(),
+            TargetPool::ManualRewards,
+            source_stake,
+        )?;
+
+        let timer = T::JoiningRequestTimer::now();
+
+        T::JoiningRequestTimer::skip_to_elapsed();
+
+        PooledStaking::<T>::execute_pending_operations(
+            RawOrigin::Signed(caller.clone()).into(),
+            vec![
+                PendingOperationQuery {
+                    delegator: caller.clone(),
+                    operation: JoiningAutoCompounding {
+                        candidate: caller.clone(),
+                        at: timer.clone(),
+                    },
+                },
+                PendingOperationQuery {
+                    delegator: caller.clone(),
+                    operation: JoiningManualRewards {
+                        candidate: caller.clone(),
+                        at: timer.clone(),
+                    },
+                },
+                PendingOperationQuery {
+                    delegator: caller.clone(),
+                    operation: JoiningAutoCompounding {
+                        candidate: target.clone(),
+                        at: timer.clone(),
+                    },
+                },
+            ],
+        )?;
+
+        // Each redelegation still in cooldown must be slashed too.
+        let redelegation = Redelegation {
+            delegator: caller.clone(),
+            target: target.clone(),
+            pool: TargetPool::AutoCompounding,
+            stake: source_stake / T::MaxRedelegationsPerCandidate::get().max(1).into(),
+            at: T::RedelegationTimer::now(),
+        };
+        let redelegations: Vec<_> = (0..r).map(|_| redelegation.clone()).collect();
+        Redelegations::<T>::insert(
+            &caller,
+            BoundedVec::<_, T::MaxRedelegationsPerCandidate>::try_from(redelegations)
+                .expect("r is at most MaxRedelegationsPerCandidate"),
+        );
+
+        // Worst case is having stake in all pools.
+        PooledStaking::<T>::request_delegate(
+            RawOrigin::Signed(caller.clone()).into(),
+            caller.clone(),
+            TargetPool::AutoCompounding,
+            source_stake,
+        )?;
+        PooledStaking::<T>::request_undelegate(
+            RawOrigin::Signed(caller.clone()).into(),
+            caller.clone(),
+            TargetPool::ManualRewards,
+            SharesOrStake::Stake(source_stake / 2u32.into()),
+        )?;
+
+        // Worst case is the staking account advancing the slashed funds.
+        let staking_balance = source_stake * 10u32.into();
+        T::Currency::set_balance(&T::StakingAccount::get(), staking_balance);
+
+        let slashed;
+
+        #[block]
+        {
+            slashed = crate::pools::slash_candidate::<T>(&caller, Perbill::from_percent(50))?;
+        }
+
+        assert!(PendingSlashedFunds::<T>::get().is_zero());
+        assert_eq!(
+            T::Currency::balance(&T::StakingAccount::get()),
+            staking_balance - slashed
+        );
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn request_commission_change() -> Result<(), BenchmarkError> {
+        let caller = create_self_delegated_candidate::<T>();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), Perbill::from_percent(10));
+
+        assert!(PendingCommissionChanges::<T>::contains_key(&caller));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn execute_commission_change() -> Result<(), BenchmarkError> {
+        let caller = create_self_delegated_candidate::<T>();
+
+        PooledStaking::<T>::request_commission_change(
+            RawOrigin::Signed(caller.clone()).into(),
+            Perbill::from_percent(10),
+        )?;
+
+        T::CommissionChangeTimer::skip_to_elapsed();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), caller.clone());
+
+        assert_eq!(
+            CandidateCommission::<T>::get(&caller),
+            Some(Perbill::from_percent(10))
+        );
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn cancel_commission_change() -> Result<(), BenchmarkError> {
+        let caller = create_self_delegated_candidate::<T>();
+
+        PooledStaking::<T>::request_commission_change(
+            RawOrigin::Signed(caller.clone()).into(),
+            Perbill::from_percent(10),
+        )?;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()));
+
+        assert!(!PendingCommissionChanges::<T>::contains_key(&caller));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_auto_execution() -> Result<(), BenchmarkError> {
+        const USER_SEED: u32 = 1;
+
+        let (caller, _deposit_amount) =
+            create_funded_user::<T>("caller", USER_SEED, min_candidate_stk::<T>());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), true);
+
+        assert!(AutoExecutionOptIn::<T>::contains_key(&caller));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn redelegate(
+        r: Linear<0, { T::MaxRedelegationsPerCandidate::get() }>,
+    ) -> Result<(), BenchmarkError> {
+        const USER_SEED: u32 = 1;
+
+        let source_stake = min_candidate_stk::<T>() * 10u32.into();
+
+        let (caller, _deposit_amount) =
+            create_funded_user::<T>("caller", USER_SEED, source_stake * 2u32.into());
+        let (source, _) = create_funded_user::<T>("source", USER_SEED, source_stake);
+        let (target, _) = create_funded_user::<T>("target", USER_SEED, source_stake);
+
+        T::EligibleCandidatesFilter::make_candidate_eligible(&source, true);
+        T::EligibleCandidatesFilter::make_candidate_eligible(&target, true);
+
+        // Worst case is having an existing pool at the target, so that both
+        // pools and the sorted candidates list get updated.
+        for candidate in [&source, &target] {
+            PooledStaking::<T>::request_delegate(
+                RawOrigin::Signed(caller.clone()).into(),
+                candidate.clone(),
+                TargetPool::AutoCompounding,
+                source_stake,
+            )?;
+        }
+
+        let timer = T::JoiningRequestTimer::now();
+        T::JoiningRequestTimer::skip_to_elapsed();
+
+        PooledStaking::<T>::execute_pending_operations(
+            RawOrigin::Signed(caller.clone()).into(),
+            [&source, &target]
+                .into_iter()
+                .map(|candidate| PendingOperationQuery {
+                    delegator: caller.clone(),
+                    operation: JoiningAutoCompounding {
+                        candidate: candidate.clone(),
+                        at: timer.clone(),
+                    },
+                })
+                .collect(),
+        )?;
+
+        // Elapsed redelegations from the source are pruned along with their
+        // `LastRedelegationTo` entries.
+        let at = T::RedelegationTimer::now();
+        let redelegations: Vec<_> = (0..r)
+            .map(|i| {
+                let delegator: T::AccountId = account("redelegator", i, USER_SEED);
+                LastRedelegationTo::<T>::insert(&delegator, &target, at.clone());
+                Redelegation {
+                    delegator,
+                    target: target.clone(),
+                    pool: TargetPool::AutoCompounding,
+                    stake: min_candidate_stk::<T>(),
+                    at: at.clone(),
+                }
+            })
+            .collect();
+        Redelegations::<T>::insert(
+            &source,
+            BoundedVec::<_, T::MaxRedelegationsPerCandidate>::try_from(redelegations)
+                .expect("r is at most MaxRedelegationsPerCandidate"),
+        );
+        T::RedelegationTimer::skip_to_elapsed();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            source.clone(),
+            target.clone(),
+            TargetPool::AutoCompounding,
+            SharesOrStake::Stake(source_stake / 2u32.into()),
+        );
+
+        assert_eq!(Redelegations::<T>::get(&source).len(), 1);
+        assert!(LastRedelegationTo::<T>::contains_key(&caller, &target));
+        assert_eq!(LastRedelegationTo::<T>::iter().count(), 1);
+
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(
         PooledStaking,
         crate::mock::ExtBuilder::default().build(),
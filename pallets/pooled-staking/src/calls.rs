@@ -19,19 +19,26 @@ use {
         candidate::Candidates,
         pools::{self, Pool},
         traits::Timer,
-        AllTargetPool, Candidate, Config, Delegator, Error, Event, HoldReason, Pallet,
-        PendingOperationKey, PendingOperationQuery, PendingOperationQueryOf, PendingOperations,
-        Shares, SharesOrStake, Stake, TargetPool,
+        weights::WeightInfo,
+        AllTargetPool, AutoExecutionCursor, AutoExecutionOptIn, Candidate, CandidateCommission,
+        CommissionChangeRequest, Config, Delegator, Error, Event, HoldReason, LastCommissionChange,
+        LastRedelegationTo, Pallet, PendingCommissionChanges, PendingOperationKey,
+        PendingOperationKeyOf, PendingOperationQuery, PendingOperationQueryOf, PendingOperations,
+        Redelegation, RedelegationOf, Redelegations, Shares, SharesOrStake, Stake, TargetPool,
     },
     frame_support::{
         dispatch::DispatchErrorWithPostInfo,
         pallet_prelude::*,
+        storage::with_storage_layer,
         traits::{
             fungible::{Mutate, MutateHold},
             tokens::{Precision, Preservation},
         },
     },
-    sp_runtime::traits::{CheckedSub, Zero},
+    sp_runtime::{
+        traits::{CheckedSub, Saturating, Zero},
+        Perbill,
+    },
     sp_std::vec::Vec,
     tp_maths::{ErrAdd, ErrSub},
 };
@@ -97,12 +104,16 @@ impl<T: Config> Calls<T> {
                 diff,
                 Precision::Exact,
             )?;
+
             T::Currency::transfer(
                 &delegator,
                 &T::StakingAccount::get(),
                 diff,
                 Preservation::Preserve,
             )?;
+
+            // Slashed currency the staking account couldn't advance can now be sent.
+            pools::settle_slashed_funds::<T>(Zero::zero())?;
             return Ok(().into());
         }
 
@@ -172,6 +183,8 @@ impl<T: Config> Calls<T> {
         pool: TargetPool,
         amount: SharesOrStake<T::Balance>,
     ) -> DispatchResultWithPostInfo {
+        Self::ensure_not_in_redelegation_cooldown(&candidate, &delegator)?;
+
         // Converts amount to shares of the correct pool
         let shares = match (amount, pool) {
             (SharesOrStake::Shares(s), _) => s,
@@ -272,6 +285,120 @@ impl<T: Config> Calls<T> {
         Ok(().into())
     }
 
+    /// Executes pending operations of delegators that opted in that can be executed, starting
+    /// after the cursor left by the previous block. At most `AutoExecutionScanLimit` operations
+    /// are checked, and no operation is executed if it could exceed `remaining_weight`.
+    pub fn auto_execute_pending_operations(remaining_weight: Weight) -> Weight {
+        let scan_limit = T::AutoExecutionScanLimit::get();
+        // Read and write of the cursor.
+        let mut used_weight = T::DbWeight::get().reads_writes(1, 1);
+
+        if scan_limit == 0 || used_weight.any_gt(remaining_weight) {
+            return Weight::zero();
+        }
+
+        // Same as the extrinsic, with the bounty transfer.
+        let execution_weight = T::WeightInfo::execute_pending_operations(1)
+            .saturating_add(T::WeightInfo::claim_manual_rewards(1))
+            .saturating_add(T::DbWeight::get().reads_writes(2, 2));
+        // Pending operation and opt in.
+        let scan_weight = T::DbWeight::get().reads(2);
+        let keeper = T::AutoExecutionKeeper::get();
+
+        let mut cursor = AutoExecutionCursor::<T>::get();
+        let mut iter = match &cursor {
+            Some((delegator, operation)) => PendingOperations::<T>::iter_from(
+                PendingOperations::<T>::hashed_key_for(delegator, operation),
+            ),
+            None => PendingOperations::<T>::iter(),
+        };
+        let mut reached_end = false;
+
+        for _ in 0..scan_limit {
+            if used_weight
+                .saturating_add(scan_weight)
+                .saturating_add(execution_weight)
+                .any_gt(remaining_weight)
+            {
+                break;
+            }
+
+            let Some((delegator, operation, _)) = iter.next() else {
+                reached_end = true;
+                break;
+            };
+
+            used_weight.saturating_accrue(scan_weight);
+            cursor = Some((delegator.clone(), operation.clone()));
+
+            if !Self::is_executable(&operation)
+                || !AutoExecutionOptIn::<T>::contains_key(&delegator)
+            {
+                continue;
+            }
+
+            used_weight.saturating_accrue(execution_weight);
+
+            let query = PendingOperationQuery {
+                delegator: delegator.clone(),
+                operation,
+            };
+
+            if let Err(e) =
+                with_storage_layer(|| Self::execute_pending_operations(sp_std::vec![query]))
+            {
+                log::warn!(
+                    target: "pooled_staking",
+                    "failed to automatically execute pending operation of {:?}: {:?}",
+                    delegator,
+                    e
+                );
+                continue;
+            }
+
+            if let Some(keeper) = &keeper {
+                Self::pay_auto_execution_bounty(&delegator, keeper);
+            }
+        }
+
+        if reached_end {
+            AutoExecutionCursor::<T>::kill();
+        } else {
+            AutoExecutionCursor::<T>::set(cursor);
+        }
+
+        used_weight
+    }
+
+    fn is_executable(operation: &PendingOperationKeyOf<T>) -> bool {
+        match operation {
+            PendingOperationKey::JoiningAutoCompounding { at, .. }
+            | PendingOperationKey::JoiningManualRewards { at, .. } => {
+                T::JoiningRequestTimer::is_elapsed(at)
+            }
+            PendingOperationKey::Leaving { at, .. } => T::LeavingRequestTimer::is_elapsed(at),
+        }
+    }
+
+    fn pay_auto_execution_bounty(delegator: &Delegator<T>, keeper: &T::AccountId) {
+        let bounty = T::AutoExecutionBounty::get();
+
+        if bounty.is_zero() || delegator == keeper {
+            return;
+        }
+
+        // The bounty is never taken from the stake.
+        if T::Currency::transfer(delegator, keeper, bounty, Preservation::Preserve).is_err() {
+            return;
+        }
+
+        Pallet::<T>::deposit_event(Event::<T>::PaidAutoExecutionBounty {
+            delegator: delegator.clone(),
+            keeper: keeper.clone(),
+            amount: bounty,
+        });
+    }
+
     fn execute_joining(
         candidate: Candidate<T>,
         delegator: Delegator<T>,
@@ -444,6 +571,8 @@ impl<T: Config> Calls<T> {
         source_pool: TargetPool,
         amount: SharesOrStake<T::Balance>,
     ) -> DispatchResultWithPostInfo {
+        Self::ensure_not_in_redelegation_cooldown(&candidate, &delegator)?;
+
         // Converts amount to shares of the correct pool
         let old_shares = match (amount, source_pool) {
             (SharesOrStake::Shares(s), _) => s,
@@ -525,6 +654,318 @@ impl<T: Config> Calls<T> {
         Ok(().into())
     }
 
+    pub fn redelegate(
+        source: Candidate<T>,
+        target: Candidate<T>,
+        delegator: Delegator<T>,
+        pool: TargetPool,
+        amount: SharesOrStake<T::Balance>,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(source != target, Error::<T>::RedelegateToSameCandidate);
+        Self::ensure_not_in_redelegation_cooldown(&source, &delegator)?;
+
+        // Converts amount to shares of the source pool
+        let source_shares = match (amount, pool) {
+            (SharesOrStake::Shares(s), _) => s,
+            (SharesOrStake::Stake(s), TargetPool::AutoCompounding) => {
+                pools::AutoCompounding::<T>::stake_to_shares(&source, Stake(s))?.0
+            }
+            (SharesOrStake::Stake(s), TargetPool::ManualRewards) => {
+                pools::ManualRewards::<T>::stake_to_shares(&source, Stake(s))?.0
+            }
+        };
+
+        // Any change in the amount of Manual Rewards shares requires to claim manual rewards.
+        if let TargetPool::ManualRewards = pool {
+            Self::claim_manual_rewards(&[
+                (source.clone(), delegator.clone()),
+                (target.clone(), delegator.clone()),
+            ])?;
+        }
+
+        // Destroy shares from the source pool.
+        let removed_stake = Self::destroy_shares(&source, &delegator, pool, Shares(source_shares))?;
+        ensure!(
+            removed_stake.0 >= T::MinimumRedelegation::get(),
+            Error::<T>::RedelegationBelowMinimum
+        );
+        Candidates::<T>::sub_total_stake(&source, removed_stake)?;
+
+        // Convert removed amount to target pool shares.
+        let target_shares = match pool {
+            TargetPool::AutoCompounding => {
+                pools::AutoCompounding::<T>::stake_to_shares_or_init(&target, removed_stake)?
+            }
+            TargetPool::ManualRewards => {
+                pools::ManualRewards::<T>::stake_to_shares_or_init(&target, removed_stake)?
+            }
+        };
+
+        ensure!(
+            !target_shares.0.is_zero(),
+            Error::<T>::RedelegationResultsInZeroShares
+        );
+
+        // We create new shares in the target pool. It returns the actual amount of stake those
+        // shares represents (due to rounding).
+        let actually_staked = match pool {
+            TargetPool::AutoCompounding => {
+                let stake =
+                    pools::AutoCompounding::<T>::add_shares(&target, &delegator, target_shares)?;
+                pools::AutoCompounding::<T>::increase_hold(&target, &delegator, &stake)?;
+                stake
+            }
+            TargetPool::ManualRewards => {
+                let stake =
+                    pools::ManualRewards::<T>::add_shares(&target, &delegator, target_shares)?;
+                pools::ManualRewards::<T>::increase_hold(&target, &delegator, &stake)?;
+                stake
+            }
+        };
+        Candidates::<T>::add_total_stake(&target, &actually_staked)?;
+
+        // The rounding is released immediately.
+        let released = removed_stake
+            .0
+            .err_sub(&actually_staked.0)
+            .map_err(Error::<T>::from)?;
+
+        if !released.is_zero() {
+            T::Currency::release(
+                &HoldReason::PooledStake.into(),
+                &delegator,
+                released,
+                Precision::Exact,
+            )?;
+        }
+
+        // Keep track of the redelegation so that it is slashable for offences of the source.
+        let now = T::RedelegationTimer::now();
+        let mut redelegations = Self::pruned_redelegations(&source);
+        redelegations
+            .try_push(Redelegation {
+                delegator: delegator.clone(),
+                target: target.clone(),
+                pool,
+                stake: actually_staked.0,
+                at: now.clone(),
+            })
+            .map_err(|_| Error::<T>::TooManyRedelegations)?;
+        Redelegations::<T>::insert(&source, redelegations);
+        LastRedelegationTo::<T>::insert(&delegator, &target, now);
+
+        pools::check_candidate_consistency::<T>(&source)?;
+        pools::check_candidate_consistency::<T>(&target)?;
+
+        Pallet::<T>::deposit_event(Event::<T>::Redelegated {
+            source,
+            target,
+            delegator,
+            pool,
+            source_shares,
+            source_stake: removed_stake.0,
+            target_shares: target_shares.0,
+            target_stake: actually_staked.0,
+            released,
+        });
+
+        Ok(().into())
+    }
+
+    /// Stake redelegated towards a candidate cannot be moved out of it while it is
+    /// still slashable for offences of the source candidate.
+    fn ensure_not_in_redelegation_cooldown(
+        candidate: &Candidate<T>,
+        delegator: &Delegator<T>,
+    ) -> Result<(), Error<T>> {
+        let Some(at) = LastRedelegationTo::<T>::get(delegator, candidate) else {
+            return Ok(());
+        };
+
+        ensure!(
+            T::RedelegationTimer::is_elapsed(&at),
+            Error::<T>::RedelegatedStakeInCooldown
+        );
+
+        LastRedelegationTo::<T>::remove(delegator, candidate);
+
+        Ok(())
+    }
+
+    /// Redelegations from `source` that are still slashable. `LastRedelegationTo` entries of
+    /// the elapsed ones are removed if they have not been updated by a later redelegation.
+    fn pruned_redelegations(
+        source: &Candidate<T>,
+    ) -> BoundedVec<RedelegationOf<T>, T::MaxRedelegationsPerCandidate> {
+        let mut redelegations = Redelegations::<T>::get(source);
+        redelegations.retain(|r| {
+            if !T::RedelegationTimer::is_elapsed(&r.at) {
+                return true;
+            }
+
+            let last = LastRedelegationTo::<T>::get(&r.delegator, &r.target);
+            if last.is_some_and(|at| T::RedelegationTimer::is_elapsed(&at)) {
+                LastRedelegationTo::<T>::remove(&r.delegator, &r.target);
+            }
+
+            false
+        });
+        redelegations
+    }
+
+    /// Slash the stake redelegated from `source` that is still slashable, by
+    /// destroying shares of the delegators in the target pools. The slashed currency
+    /// stays held in the pools of the target until rebalanced.
+    /// Returns the total amount slashed.
+    pub fn slash_redelegations(
+        source: &Candidate<T>,
+        fraction: Perbill,
+    ) -> Result<T::Balance, DispatchErrorWithPostInfo> {
+        let mut redelegations = Self::pruned_redelegations(source);
+
+        let mut total_slash = T::Balance::zero();
+
+        for redelegation in redelegations.iter_mut() {
+            let slash = fraction.mul_floor(redelegation.stake);
+            if slash.is_zero() {
+                continue;
+            }
+
+            let Redelegation {
+                delegator,
+                target,
+                pool,
+                ..
+            } = &*redelegation;
+
+            let slashed = match pool {
+                TargetPool::AutoCompounding => Self::slash_redelegated_shares::<
+                    pools::AutoCompounding<T>,
+                >(target, delegator, Stake(slash))?,
+                TargetPool::ManualRewards => {
+                    Self::claim_manual_rewards(&[(target.clone(), delegator.clone())])?;
+                    Self::slash_redelegated_shares::<pools::ManualRewards<T>>(
+                        target,
+                        delegator,
+                        Stake(slash),
+                    )?
+                }
+            };
+
+            if slashed.0.is_zero() {
+                continue;
+            }
+
+            Candidates::<T>::sub_total_stake(target, slashed)?;
+            pools::check_candidate_consistency::<T>(target)?;
+
+            Pallet::<T>::deposit_event(Event::<T>::SlashedRedelegation {
+                source: source.clone(),
+                target: target.clone(),
+                delegator: delegator.clone(),
+                slashed: slashed.0,
+            });
+
+            redelegation.stake = redelegation.stake.saturating_sub(slashed.0);
+            total_slash = total_slash.err_add(&slashed.0).map_err(Error::<T>::from)?;
+        }
+
+        Redelegations::<T>::insert(source, redelegations);
+
+        Ok(total_slash)
+    }
+
+    /// Destroy shares worth up to `stake` without decreasing the hold, which
+    /// will be rebalanced later.
+    fn slash_redelegated_shares<P: Pool<T>>(
+        target: &Candidate<T>,
+        delegator: &Delegator<T>,
+        stake: Stake<T::Balance>,
+    ) -> Result<Stake<T::Balance>, Error<T>> {
+        let Shares(owned) = P::shares(target, delegator);
+        if owned.is_zero() {
+            return Ok(Stake(Zero::zero()));
+        }
+
+        let shares = P::stake_to_shares(target, stake)?.0.min(owned);
+        if shares.is_zero() {
+            return Ok(Stake(Zero::zero()));
+        }
+
+        P::sub_shares(target, delegator, Shares(shares))
+    }
+
+    pub fn request_commission_change(
+        candidate: Candidate<T>,
+        commission: Perbill,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(
+            commission <= T::MaxCommission::get(),
+            Error::<T>::CommissionTooHigh
+        );
+        ensure!(
+            Candidates::<T>::self_delegation(&candidate)? >= T::MinimumSelfDelegation::get(),
+            Error::<T>::NotEnoughSelfDelegation
+        );
+        ensure!(
+            !PendingCommissionChanges::<T>::contains_key(&candidate),
+            Error::<T>::CommissionChangeAlreadyPending
+        );
+        if let Some(last) = LastCommissionChange::<T>::get(&candidate) {
+            ensure!(
+                T::CommissionChangeTimer::is_elapsed(&last),
+                Error::<T>::CommissionChangeTooSoon
+            );
+        }
+
+        PendingCommissionChanges::<T>::insert(
+            &candidate,
+            CommissionChangeRequest {
+                commission,
+                at: T::CommissionChangeTimer::now(),
+            },
+        );
+
+        Pallet::<T>::deposit_event(Event::<T>::RequestedCommissionChange {
+            candidate,
+            commission,
+        });
+
+        Ok(().into())
+    }
+
+    pub fn execute_commission_change(candidate: Candidate<T>) -> DispatchResultWithPostInfo {
+        let request = PendingCommissionChanges::<T>::get(&candidate)
+            .ok_or(Error::<T>::NoPendingCommissionChange)?;
+
+        ensure!(
+            T::CommissionChangeTimer::is_elapsed(&request.at),
+            Error::<T>::CommissionChangeCannotBeExecuted
+        );
+
+        PendingCommissionChanges::<T>::remove(&candidate);
+        CandidateCommission::<T>::insert(&candidate, request.commission);
+        LastCommissionChange::<T>::insert(&candidate, T::CommissionChangeTimer::now());
+
+        Pallet::<T>::deposit_event(Event::<T>::ChangedCommission {
+            candidate,
+            commission: request.commission,
+        });
+
+        Ok(().into())
+    }
+
+    pub fn cancel_commission_change(candidate: Candidate<T>) -> DispatchResultWithPostInfo {
+        ensure!(
+            PendingCommissionChanges::<T>::take(&candidate).is_some(),
+            Error::<T>::NoPendingCommissionChange
+        );
+
+        Pallet::<T>::deposit_event(Event::<T>::CancelledCommissionChange { candidate });
+
+        Ok(().into())
+    }
+
     /// Destory ManualReward or AutoCompounding shares while performing hold rebalancing if
     /// necessary.
     fn destroy_shares(
@@ -109,14 +109,8 @@ impl<T: Config> Candidates<T> {
         Ok(())
     }
 
-    pub fn update_total_stake(
-        candidate: &Candidate<T>,
-        new_stake: Stake<T::Balance>,
-    ) -> Result<(), Error<T>> {
-        let stake_before = Pools::<T>::get(candidate, &PoolsKey::CandidateTotalStake);
-        Pools::<T>::set(candidate, &PoolsKey::CandidateTotalStake, new_stake.0);
-
-        // Compute self delegation.
+    /// Stake the candidate holds in its own pools, across all pool kinds.
+    pub fn self_delegation(candidate: &Candidate<T>) -> Result<T::Balance, Error<T>> {
         let ac_self = if pools::AutoCompounding::<T>::shares_supply(candidate)
             .0
             .is_zero()
@@ -144,7 +138,17 @@ impl<T: Config> Candidates<T> {
             pools::Joining::shares_to_stake(candidate, shares)?.0
         };
 
-        let self_delegation = ac_self.err_add(&mr_self)?.err_add(&joining_self)?;
+        Ok(ac_self.err_add(&mr_self)?.err_add(&joining_self)?)
+    }
+
+    pub fn update_total_stake(
+        candidate: &Candidate<T>,
+        new_stake: Stake<T::Balance>,
+    ) -> Result<(), Error<T>> {
+        let stake_before = Pools::<T>::get(candidate, &PoolsKey::CandidateTotalStake);
+        Pools::<T>::set(candidate, &PoolsKey::CandidateTotalStake, new_stake.0);
+
+        let self_delegation = Self::self_delegation(candidate)?;
 
         let mut list = SortedEligibleCandidates::<T>::get();
 
@@ -66,7 +66,7 @@ pub mod pallet {
         frame_support::{
             pallet_prelude::*,
             storage::types::{StorageDoubleMap, StorageValue, ValueQuery},
-            traits::{fungible, tokens::Balance, IsType},
+            traits::{fungible, tokens::Balance, IsType, OnUnbalanced},
             Blake2_128Concat,
         },
         frame_system::pallet_prelude::*,
@@ -202,7 +202,17 @@ pub mod pallet {
     >;
 
     #[derive(
-        RuntimeDebug, PartialEq, Eq, Encode, Decode, Copy, Clone, TypeInfo, Serialize, Deserialize,
+        RuntimeDebug,
+        PartialEq,
+        Eq,
+        Encode,
+        Decode,
+        Copy,
+        Clone,
+        TypeInfo,
+        Serialize,
+        Deserialize,
+        MaxEncodedLen,
     )]
     pub enum TargetPool {
         AutoCompounding,
@@ -272,6 +282,58 @@ pub mod pallet {
     )]
     pub struct Stake<T>(pub T);
 
+    /// Commission change requested by a candidate. It can be executed once
+    /// `CommissionChangeTimer` is elapsed since `at`, which gives time to
+    /// delegators to react to the change.
+    #[derive(
+        RuntimeDebug,
+        PartialEq,
+        Eq,
+        Encode,
+        Decode,
+        Clone,
+        TypeInfo,
+        Serialize,
+        Deserialize,
+        MaxEncodedLen,
+    )]
+    pub struct CommissionChangeRequest<I> {
+        pub commission: Perbill,
+        pub at: I,
+    }
+
+    pub type CommissionChangeRequestOf<T> =
+        CommissionChangeRequest<<<T as Config>::CommissionChangeTimer as Timer>::Instant>;
+
+    /// Stake moved by `delegator` from a candidate to `target` using `redelegate`.
+    /// `stake` is still slashable for offences of the source candidate until
+    /// `RedelegationTimer` is elapsed since `at`.
+    #[derive(
+        RuntimeDebug,
+        PartialEq,
+        Eq,
+        Encode,
+        Decode,
+        Clone,
+        TypeInfo,
+        Serialize,
+        Deserialize,
+        MaxEncodedLen,
+    )]
+    pub struct Redelegation<A, B, I> {
+        pub delegator: A,
+        pub target: A,
+        pub pool: TargetPool,
+        pub stake: B,
+        pub at: I,
+    }
+
+    pub type RedelegationOf<T> = Redelegation<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::Balance,
+        <<T as Config>::RedelegationTimer as Timer>::Instant,
+    >;
+
     /// Pooled Staking pallet.
     #[pallet::pallet]
     pub struct Pallet<T>(PhantomData<T>);
@@ -313,8 +375,12 @@ pub mod pallet {
         #[pallet::constant]
         type MinimumSelfDelegation: Get<Self::Balance>;
         /// Part of the rewards that will be sent exclusively to the collator.
+        /// Used for candidates that didn't set their own commission.
         #[pallet::constant]
         type RewardsCollatorCommission: Get<Perbill>;
+        /// Maximum commission a candidate can set.
+        #[pallet::constant]
+        type MaxCommission: Get<Perbill>;
 
         /// The overarching runtime hold reason.
         type RuntimeHoldReason: From<HoldReason>;
@@ -323,6 +389,22 @@ pub mod pallet {
         type JoiningRequestTimer: Timer;
         /// Condition for when a leaving request can be executed.
         type LeavingRequestTimer: Timer;
+        /// Condition for when a commission change request can be executed.
+        /// A new request can only be made once it is also elapsed since the
+        /// last executed change, which limits how often the commission can change.
+        type CommissionChangeTimer: Timer;
+        /// Condition for when redelegated stake is no longer slashable for offences of
+        /// the candidate it was redelegated from.
+        type RedelegationTimer: Timer;
+        /// Maximum amount of redelegations from a single candidate for which the
+        /// `RedelegationTimer` is not elapsed.
+        #[pallet::constant]
+        type MaxRedelegationsPerCandidate: Get<u32>;
+        /// Minimum amount of stake that can be redelegated at once. As redelegations from a
+        /// candidate are bounded, it makes filling them to prevent other delegators from
+        /// redelegating costly.
+        #[pallet::constant]
+        type MinimumRedelegation: Get<Self::Balance>;
         /// All eligible candidates are stored in a sorted list that is modified each time
         /// delegations changes. It is safer to bound this list, in which case eligible candidate
         /// could fall out of this list if they have less stake than the top `EligibleCandidatesBufferSize`
@@ -332,6 +414,19 @@ pub mod pallet {
         type EligibleCandidatesBufferSize: Get<u32>;
         /// Additional filter for candidates to be eligible.
         type EligibleCandidatesFilter: IsCandidateEligible<Self::AccountId>;
+        /// Destination of the currency slashed from the pools of a misbehaving candidate.
+        type SlashedFundsHandler: OnUnbalanced<CreditOf<Self>>;
+        /// Maximum amount of pending operations checked in `on_idle` each block to execute
+        /// the ones that can be executed. Automatic execution is disabled if 0.
+        #[pallet::constant]
+        type AutoExecutionScanLimit: Get<u32>;
+        /// Bounty paid by a delegator for each of its pending operations executed in `on_idle`.
+        /// It is only paid if the delegator has enough free balance, and can be 0.
+        #[pallet::constant]
+        type AutoExecutionBounty: Get<Self::Balance>;
+        /// Account executing pending operations in `on_idle`, usually the block author, which
+        /// receives the automatic execution bounties. No bounty is paid if `None`.
+        type AutoExecutionKeeper: Get<Option<Self::AccountId>>;
 
         type WeightInfo: WeightInfo;
     }
@@ -374,6 +469,70 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Commission set by candidates. Candidates without an entry use
+    /// `RewardsCollatorCommission`.
+    #[pallet::storage]
+    pub type CandidateCommission<T: Config> =
+        StorageMap<_, Blake2_128Concat, Candidate<T>, Perbill, OptionQuery>;
+
+    /// Pending commission change requests.
+    #[pallet::storage]
+    pub type PendingCommissionChanges<T: Config> =
+        StorageMap<_, Blake2_128Concat, Candidate<T>, CommissionChangeRequestOf<T>, OptionQuery>;
+
+    /// When the last commission change of a candidate was executed.
+    #[pallet::storage]
+    pub type LastCommissionChange<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Candidate<T>,
+        <<T as Config>::CommissionChangeTimer as Timer>::Instant,
+        OptionQuery,
+    >;
+
+    /// Redelegations from a candidate that are still slashable for offences of that candidate.
+    #[pallet::storage]
+    pub type Redelegations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Candidate<T>,
+        BoundedVec<RedelegationOf<T>, T::MaxRedelegationsPerCandidate>,
+        ValueQuery,
+    >;
+
+    /// Instant of the last redelegation of a delegator towards a candidate. While the
+    /// `RedelegationTimer` is not elapsed the delegator cannot move stake out of that
+    /// candidate, which would allow to dodge slashes of the source candidate. Entries are
+    /// removed once elapsed, when the redelegations of the source candidate are pruned.
+    #[pallet::storage]
+    pub type LastRedelegationTo<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        Delegator<T>,
+        Blake2_128Concat,
+        Candidate<T>,
+        <T::RedelegationTimer as Timer>::Instant,
+        OptionQuery,
+    >;
+
+    /// Last pending operation checked in `on_idle`. Next block will continue from the
+    /// following operation, or from the start if `None`.
+    #[pallet::storage]
+    pub type AutoExecutionCursor<T: Config> =
+        StorageValue<_, (Delegator<T>, PendingOperationKeyOf<T>), OptionQuery>;
+
+    /// Delegators that opted in for the automatic execution of their pending operations,
+    /// and accept to pay `AutoExecutionBounty` for each of them.
+    #[pallet::storage]
+    pub type AutoExecutionOptIn<T: Config> =
+        StorageMap<_, Blake2_128Concat, Delegator<T>, (), OptionQuery>;
+
+    /// Amount of slashed currency that has not been sent to `SlashedFundsHandler` yet, as
+    /// the staking account couldn't advance it when the slash was applied. It is sent as
+    /// soon as rebalanced holds refill the staking account.
+    #[pallet::storage]
+    pub type PendingSlashedFunds<T: Config> = StorageValue<_, T::Balance, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -490,6 +649,64 @@ pub mod pallet {
             pending_leaving: T::Balance,
             released: T::Balance,
         },
+        /// A pool of the candidate has been slashed, which decreased the value of
+        /// each of its shares.
+        SlashedPool {
+            candidate: Candidate<T>,
+            pool: AllTargetPool,
+            slashed: T::Balance,
+        },
+        /// Slashed currency has been moved from the staking account to the slashed
+        /// funds handler. `pending` is the amount of slashed currency the staking
+        /// account still has to send.
+        TransferredSlashedFunds {
+            amount: T::Balance,
+            pending: T::Balance,
+        },
+        /// Delegator paid a bounty for the automatic execution of its pending operation.
+        PaidAutoExecutionBounty {
+            delegator: Delegator<T>,
+            keeper: T::AccountId,
+            amount: T::Balance,
+        },
+        /// Delegator opted in or out of the automatic execution of its pending operations.
+        SetAutoExecution {
+            delegator: Delegator<T>,
+            enabled: bool,
+        },
+        /// Candidate requested to change its commission.
+        RequestedCommissionChange {
+            candidate: Candidate<T>,
+            commission: Perbill,
+        },
+        /// Commission change request was executed.
+        ChangedCommission {
+            candidate: Candidate<T>,
+            commission: Perbill,
+        },
+        /// Candidate cancelled its pending commission change request.
+        CancelledCommissionChange { candidate: Candidate<T> },
+        /// Delegator moved stake from `source` to `target`. `source_stake` was removed
+        /// from the source pool, `target_stake` was staked in the same kind of pool of
+        /// the target, while the rounding has been `released`.
+        Redelegated {
+            source: Candidate<T>,
+            target: Candidate<T>,
+            delegator: Delegator<T>,
+            pool: TargetPool,
+            source_shares: T::Balance,
+            source_stake: T::Balance,
+            target_shares: T::Balance,
+            target_stake: T::Balance,
+            released: T::Balance,
+        },
+        /// Stake redelegated from `source` has been slashed in the pool of `target`.
+        SlashedRedelegation {
+            source: Candidate<T>,
+            target: Candidate<T>,
+            delegator: Delegator<T>,
+            slashed: T::Balance,
+        },
     }
 
     #[pallet::error]
@@ -508,6 +725,17 @@ pub mod pallet {
         CandidateTransferingOwnSharesForbidden,
         RequestCannotBeExecuted(u16),
         SwapResultsInZeroShares,
+        CommissionChangeAlreadyPending,
+        NoPendingCommissionChange,
+        CommissionChangeCannotBeExecuted,
+        CommissionTooHigh,
+        CommissionChangeTooSoon,
+        NotEnoughSelfDelegation,
+        RedelegateToSameCandidate,
+        RedelegationResultsInZeroShares,
+        TooManyRedelegations,
+        RedelegationBelowMinimum,
+        RedelegatedStakeInCooldown,
     }
 
     impl<T: Config> From<tp_maths::OverflowError> for Error<T> {
@@ -524,6 +752,10 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Calls::<T>::auto_execute_pending_operations(remaining_weight)
+        }
+
         #[cfg(feature = "try-runtime")]
         fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
             use sp_std::collections::btree_set::BTreeSet;
@@ -645,6 +877,100 @@ pub mod pallet {
 
             Calls::<T>::swap_pool(candidate, delegator, source_pool, amount)
         }
+
+        /// Request to change the commission of the caller, which will be applied
+        /// on rewards distribution once the request is executed. The caller must
+        /// be a candidate with at least `MinimumSelfDelegation`, and can't make
+        /// a new request until `CommissionChangeTimer` is elapsed since its last
+        /// executed change.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::request_commission_change())]
+        pub fn request_commission_change(
+            origin: OriginFor<T>,
+            commission: Perbill,
+        ) -> DispatchResultWithPostInfo {
+            let candidate = ensure_signed(origin)?;
+
+            Calls::<T>::request_commission_change(candidate, commission)
+        }
+
+        /// Apply the pending commission change of `candidate` once
+        /// `CommissionChangeTimer` is elapsed since it was requested.
+        /// Can be called by anyone.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::execute_commission_change())]
+        pub fn execute_commission_change(
+            origin: OriginFor<T>,
+            candidate: Candidate<T>,
+        ) -> DispatchResultWithPostInfo {
+            // We don't care about the sender.
+            let _ = ensure_signed(origin)?;
+
+            Calls::<T>::execute_commission_change(candidate)
+        }
+
+        /// Move stake from a pool of `source` to the same pool of `target`. The moved
+        /// stake stays slashable for offences of `source` until `RedelegationTimer` is elapsed,
+        /// during which the delegator cannot move stake out of `target`.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::redelegate(T::MaxRedelegationsPerCandidate::get()))]
+        pub fn redelegate(
+            origin: OriginFor<T>,
+            source: Candidate<T>,
+            target: Candidate<T>,
+            pool: TargetPool,
+            amount: SharesOrStake<T::Balance>,
+        ) -> DispatchResultWithPostInfo {
+            let delegator = ensure_signed(origin)?;
+
+            Calls::<T>::redelegate(source, target, delegator, pool, amount)
+        }
+
+        /// Slash all the pools of `candidate`, and the stake redelegated from it that is still
+        /// in cooldown, by `fraction`. Used by governance to punish a misbehaving collator.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::slash_candidate(T::MaxRedelegationsPerCandidate::get()))]
+        pub fn slash_candidate(
+            origin: OriginFor<T>,
+            candidate: Candidate<T>,
+            fraction: Perbill,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            pools::slash_candidate::<T>(&candidate, fraction)?;
+
+            Ok(().into())
+        }
+
+        /// Cancel the pending commission change request of the caller.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::cancel_commission_change())]
+        pub fn cancel_commission_change(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let candidate = ensure_signed(origin)?;
+
+            Calls::<T>::cancel_commission_change(candidate)
+        }
+
+        /// Opt in or out of the automatic execution of the pending operations of the caller
+        /// in `on_idle`, for which it pays `AutoExecutionBounty` to the keeper executing them.
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::set_auto_execution())]
+        pub fn set_auto_execution(
+            origin: OriginFor<T>,
+            enabled: bool,
+        ) -> DispatchResultWithPostInfo {
+            let delegator = ensure_signed(origin)?;
+
+            if enabled {
+                AutoExecutionOptIn::<T>::insert(&delegator, ());
+            } else {
+                AutoExecutionOptIn::<T>::remove(&delegator);
+            }
+
+            Self::deposit_event(Event::<T>::SetAutoExecution { delegator, enabled });
+
+            Ok(().into())
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -671,6 +997,110 @@ pub mod pallet {
             .ok()
             .map(|x| x.0)
         }
+
+        pub fn shares(
+            candidate: Candidate<T>,
+            delegator: Delegator<T>,
+            pool: AllTargetPool,
+        ) -> T::Balance {
+            use pools::Pool;
+            match pool {
+                AllTargetPool::Joining => pools::Joining::<T>::shares(&candidate, &delegator),
+                AllTargetPool::AutoCompounding => {
+                    pools::AutoCompounding::<T>::shares(&candidate, &delegator)
+                }
+                AllTargetPool::ManualRewards => {
+                    pools::ManualRewards::<T>::shares(&candidate, &delegator)
+                }
+                AllTargetPool::Leaving => pools::Leaving::<T>::shares(&candidate, &delegator),
+            }
+            .0
+        }
+
+        pub fn claimable_manual_rewards(
+            candidate: &Candidate<T>,
+            delegator: &Delegator<T>,
+        ) -> Option<T::Balance> {
+            pools::ManualRewards::<T>::pending_rewards(candidate, delegator)
+                .ok()
+                .map(|x| x.0)
+        }
+
+        /// Value of the shares of a pending operation.
+        pub fn pending_operation_stake(
+            operation: &PendingOperationKeyOf<T>,
+            shares: T::Balance,
+        ) -> Option<T::Balance> {
+            use pools::Pool;
+            match operation {
+                PendingOperationKey::JoiningAutoCompounding { candidate, .. }
+                | PendingOperationKey::JoiningManualRewards { candidate, .. } => {
+                    pools::Joining::<T>::shares_to_stake(candidate, Shares(shares))
+                }
+                PendingOperationKey::Leaving { candidate, .. } => {
+                    pools::Leaving::<T>::shares_to_stake(candidate, Shares(shares))
+                }
+            }
+            .ok()
+            .map(|x| x.0)
+        }
+
+        /// Candidates for which the delegator has shares in any pool.
+        /// Iterates over the pools of all candidates, and thus should only be
+        /// used outside of block execution.
+        pub fn delegator_candidates(delegator: &Delegator<T>) -> Vec<Candidate<T>> {
+            let mut candidates: Vec<_> = Pools::<T>::iter_keys()
+                .filter_map(|(candidate, key)| match key {
+                    PoolsKey::JoiningShares { delegator: d }
+                    | PoolsKey::AutoCompoundingShares { delegator: d }
+                    | PoolsKey::ManualRewardsShares { delegator: d }
+                    | PoolsKey::LeavingShares { delegator: d }
+                        if &d == delegator =>
+                    {
+                        Some(candidate)
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            candidates.sort();
+            candidates.dedup();
+            candidates
+        }
+
+        pub fn candidate_total_stake(candidate: &Candidate<T>) -> T::Balance {
+            candidate::Candidates::<T>::total_stake(candidate).0
+        }
+
+        /// Position of the candidate in `SortedEligibleCandidates`.
+        pub fn candidate_rank(candidate: &Candidate<T>) -> Option<u32> {
+            SortedEligibleCandidates::<T>::get()
+                .iter()
+                .position(|c| &c.candidate == candidate)
+                .map(|rank| rank as u32)
+        }
+
+        /// Commission applied to the rewards of the candidate, which is either
+        /// its own commission or `RewardsCollatorCommission`.
+        pub fn candidate_commission(candidate: &Candidate<T>) -> Perbill {
+            CandidateCommission::<T>::get(candidate)
+                .unwrap_or_else(T::RewardsCollatorCommission::get)
+        }
+    }
+
+    impl<T: Config> tp_traits::OnSlashApplied<Candidate<T>> for Pallet<T> {
+        fn on_slash_applied(offender: &Candidate<T>, fraction: Perbill) -> Weight {
+            if let Err(e) = pools::slash_candidate::<T>(offender, fraction) {
+                log::error!(
+                    target: "pooled_staking",
+                    "failed to slash pools of candidate {:?}: {:?}",
+                    offender,
+                    e
+                );
+            }
+
+            T::WeightInfo::slash_candidate(T::MaxRedelegationsPerCandidate::get())
+        }
     }
 
     impl<T: Config> tp_traits::DistributeRewards<Candidate<T>, CreditOf<T>> for Pallet<T> {
@@ -25,7 +25,10 @@ use {
     frame_support::{
         parameter_types,
         traits::{
-            tokens::fungible::{Inspect, InspectHold},
+            tokens::{
+                fungible::{Inspect, InspectHold},
+                imbalance::ResolveTo,
+            },
             Everything, OnFinalize, OnInitialize,
         },
     },
@@ -66,6 +69,8 @@ pub const ACCOUNT_CANDIDATE_1: u64 = 1;
 pub const ACCOUNT_CANDIDATE_2: u64 = 2;
 pub const ACCOUNT_DELEGATOR_1: u64 = 3;
 pub const ACCOUNT_DELEGATOR_2: u64 = 4;
+pub const ACCOUNT_SLASHED_FUNDS: u64 = 5;
+pub const ACCOUNT_KEEPER: u64 = 6;
 
 pub const KILO: u128 = 1000;
 pub const MEGA: u128 = 1000 * KILO;
@@ -156,8 +161,14 @@ parameter_types! {
     pub const InitialManualClaimShareValue: u128 = SHARE_INIT;
     pub const InitialAutoCompoundingShareValue: u128 = SHARE_INIT;
     pub const MinimumSelfDelegation: u128 = 10 * MEGA;
+    pub const MinimumRedelegation: u128 = 2 * SHARE_INIT;
     pub const RewardsCollatorCommission: Perbill = Perbill::from_percent(20);
+    pub const MaxCommission: Perbill = Perbill::from_percent(50);
     pub const BlocksToWait: u64 = BLOCKS_TO_WAIT;
+    pub const SlashedFundsAccount: u64 = ACCOUNT_SLASHED_FUNDS;
+    pub static AutoExecutionKeeper: Option<u64> = Some(ACCOUNT_KEEPER);
+    pub static AutoExecutionScanLimit: u32 = 3;
+    pub static AutoExecutionBounty: u128 = 0;
 }
 
 impl pallet_pooled_staking::Config for Runtime {
@@ -169,11 +180,21 @@ impl pallet_pooled_staking::Config for Runtime {
     type InitialAutoCompoundingShareValue = InitialAutoCompoundingShareValue;
     type MinimumSelfDelegation = MinimumSelfDelegation;
     type RewardsCollatorCommission = RewardsCollatorCommission;
+    type MaxCommission = MaxCommission;
     type JoiningRequestTimer = BlockNumberTimer<Self, BlocksToWait>;
     type LeavingRequestTimer = BlockNumberTimer<Self, BlocksToWait>;
+    type CommissionChangeTimer = BlockNumberTimer<Self, BlocksToWait>;
+    type RedelegationTimer = BlockNumberTimer<Self, BlocksToWait>;
+    // low values so we can test vec bounding, in practice it should be bigger
+    type MaxRedelegationsPerCandidate = ConstU32<2>;
+    type MinimumRedelegation = MinimumRedelegation;
     // low value so we can test vec bounding, in practice it should be bigger
     type EligibleCandidatesBufferSize = ConstU32<3>;
     type EligibleCandidatesFilter = ();
+    type SlashedFundsHandler = ResolveTo<SlashedFundsAccount, Balances>;
+    type AutoExecutionScanLimit = AutoExecutionScanLimit;
+    type AutoExecutionBounty = AutoExecutionBounty;
+    type AutoExecutionKeeper = AutoExecutionKeeper;
     type WeightInfo = ();
     type RuntimeHoldReason = RuntimeHoldReason;
 }
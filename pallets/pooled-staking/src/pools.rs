@@ -16,17 +16,25 @@
 
 use {
     crate::{
-        candidate::Candidates, weights::WeightInfo, Candidate, Config, CreditOf, Delegator, Error,
-        Event, Pallet, Pools, PoolsKey, Shares, Stake,
+        calls::Calls, candidate::Candidates, weights::WeightInfo, AllTargetPool, Candidate, Config,
+        CreditOf, Delegator, Error, Event, Pallet, PendingSlashedFunds, Pools, PoolsKey, Shares,
+        Stake,
     },
     core::marker::PhantomData,
     frame_support::{
         ensure,
         pallet_prelude::*,
-        traits::{fungible::Balanced, Imbalance},
+        traits::{
+            fungible::{Balanced, Inspect},
+            tokens::{Fortitude, Precision, Preservation},
+            Imbalance, OnUnbalanced,
+        },
     },
     sp_core::Get,
-    sp_runtime::traits::{CheckedAdd, CheckedDiv, Zero},
+    sp_runtime::{
+        traits::{CheckedAdd, CheckedDiv, One, Saturating, Zero},
+        Perbill,
+    },
     tp_maths::{ErrAdd, ErrMul, ErrSub, MulDiv},
 };
 
@@ -343,7 +351,6 @@ impl_pool!(
 );
 
 impl<T: Config> ManualRewards<T> {
-    #[allow(dead_code)]
     pub fn pending_rewards(
         candidate: &Candidate<T>,
         delegator: &Delegator<T>,
@@ -437,8 +444,9 @@ impl<T: Config> ManualRewards<T> {
 /// and it is the responsibility of the caller to transfer or mint the currency
 /// to the staking pallet account.
 ///
-/// Rewards are split using `RewardsCollatorCommission` between the candidate
-/// and all the delegators (including the candidate self-delegation). For each,
+/// Rewards are split using the candidate commission (or `RewardsCollatorCommission`
+/// if the candidate didn't set one) between the candidate and all the delegators
+/// (including the candidate self-delegation). For each,
 /// the rewards are then split according to the value of all the ManualRewards
 /// and AutoCompounding shares.
 ///
@@ -480,8 +488,8 @@ fn distribute_rewards_inner<T: Config>(
     candidate: &Candidate<T>,
     rewards: T::Balance,
 ) -> Result<T::Balance, Error<T>> {
-    // `RewardsCollatorCommission` is a `Perbill` so we're not worried about overflow.
-    let candidate_rewards = T::RewardsCollatorCommission::get() * rewards;
+    // Commission is a `Perbill` so we're not worried about overflow.
+    let candidate_rewards = Pallet::<T>::candidate_commission(candidate) * rewards;
     let delegators_rewards = rewards.err_sub(&candidate_rewards)?;
 
     let Stake(auto_total_stake) = AutoCompounding::<T>::total_staked(candidate);
@@ -562,3 +570,127 @@ fn distribute_rewards_inner<T: Config>(
 
     Ok(candidate_manual_rewards)
 }
+
+/// Slash all the pools of the provided candidate by `fraction`.
+///
+/// Slashing a pool consists of decreasing its total stake without destroying
+/// any share, which decreases the value of each share proportionally. The
+/// slashed amount is rounded down in favor of the delegators. A pool with
+/// shares always keeps at least one unit of stake, as shares without value
+/// would otherwise be diluted by the shares created afterwards at the initial
+/// share value.
+///
+/// The slashed amount is sent right away from the staking account to
+/// `SlashedFundsHandler`, see `settle_slashed_funds`.
+///
+/// Stake redelegated from this candidate that is still in cooldown is slashed too.
+///
+/// Returns the total amount slashed across all pools and redelegations.
+#[frame_support::transactional]
+pub fn slash_candidate<T: Config>(
+    candidate: &Candidate<T>,
+    fraction: Perbill,
+) -> Result<T::Balance, DispatchError> {
+    let pools_slash = slash_candidate_inner::<T>(candidate, fraction)?;
+    let redelegations_slash =
+        Calls::<T>::slash_redelegations(candidate, fraction).map_err(|e| e.error)?;
+
+    let total_slash = pools_slash
+        .err_add(&redelegations_slash)
+        .map_err(Error::<T>::from)?;
+    settle_slashed_funds::<T>(total_slash)?;
+
+    Ok(total_slash)
+}
+
+fn slash_candidate_inner<T: Config>(
+    candidate: &Candidate<T>,
+    fraction: Perbill,
+) -> Result<T::Balance, Error<T>> {
+    let joining = slash_pool::<T, Joining<T>>(candidate, fraction, AllTargetPool::Joining)?;
+    let auto =
+        slash_pool::<T, AutoCompounding<T>>(candidate, fraction, AllTargetPool::AutoCompounding)?;
+    let manual =
+        slash_pool::<T, ManualRewards<T>>(candidate, fraction, AllTargetPool::ManualRewards)?;
+    let leaving = slash_pool::<T, Leaving<T>>(candidate, fraction, AllTargetPool::Leaving)?;
+
+    // Leaving stake no longer contributes to the candidate total stake.
+    let candidate_slash = joining.err_add(&auto)?.err_add(&manual)?;
+    Candidates::<T>::sub_total_stake(candidate, Stake(candidate_slash))?;
+
+    check_candidate_consistency::<T>(candidate)?;
+
+    Ok(candidate_slash.err_add(&leaving)?)
+}
+
+fn slash_pool<T: Config, P: Pool<T>>(
+    candidate: &Candidate<T>,
+    fraction: Perbill,
+    pool: AllTargetPool,
+) -> Result<T::Balance, Error<T>> {
+    let Stake(total_staked) = P::total_staked(candidate);
+    if total_staked.is_zero() {
+        return Ok(Zero::zero());
+    }
+
+    let slash = fraction
+        .mul_floor(total_staked)
+        .min(total_staked.saturating_sub(One::one()));
+    if slash.is_zero() {
+        return Ok(Zero::zero());
+    }
+
+    P::slash_stake_among_holders(candidate, Stake(slash))?;
+
+    Pallet::<T>::deposit_event(Event::<T>::SlashedPool {
+        candidate: candidate.clone(),
+        pool,
+        slashed: slash,
+    });
+
+    Ok(slash)
+}
+
+/// Send `amount` of slashed currency, along with the slashed currency still pending,
+/// from the staking account to `SlashedFundsHandler`.
+///
+/// Slashed currency is still held in delegators accounts, and only goes to the staking
+/// account once their holds are rebalanced. The staking account thus advances it, so
+/// that slashes are settled without waiting for each delegator to be rebalanced. What
+/// the staking account can't pay is kept in `PendingSlashedFunds` and sent on the next
+/// call, which happens every time a rebalance refills the staking account.
+pub fn settle_slashed_funds<T: Config>(amount: T::Balance) -> DispatchResult {
+    let pending = PendingSlashedFunds::<T>::get()
+        .err_add(&amount)
+        .map_err(Error::<T>::from)?;
+    if pending.is_zero() {
+        return Ok(());
+    }
+
+    let staking_account = T::StakingAccount::get();
+    let available =
+        T::Currency::reducible_balance(&staking_account, Preservation::Preserve, Fortitude::Polite);
+    let settled = pending.min(available);
+    let pending = pending.err_sub(&settled).map_err(Error::<T>::from)?;
+    PendingSlashedFunds::<T>::put(pending);
+
+    if settled.is_zero() {
+        return Ok(());
+    }
+
+    let credit = T::Currency::withdraw(
+        &staking_account,
+        settled,
+        Precision::Exact,
+        Preservation::Preserve,
+        Fortitude::Polite,
+    )?;
+    T::SlashedFundsHandler::on_unbalanced(credit);
+
+    Pallet::<T>::deposit_event(Event::<T>::TransferredSlashedFunds {
+        amount: settled,
+        pending,
+    });
+
+    Ok(())
+}
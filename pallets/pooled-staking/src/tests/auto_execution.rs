@@ -0,0 +1,265 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    super::*,
+    crate::{assert_eq_last_events, AutoExecutionCursor},
+    frame_support::{traits::Hooks, weights::Weight},
+};
+
+fn request_delegate(candidate: AccountId, delegator: AccountId, pool: TargetPool) {
+    assert_ok!(Staking::set_auto_execution(
+        RuntimeOrigin::signed(delegator),
+        true
+    ));
+    assert_ok!(Staking::request_delegate(
+        RuntimeOrigin::signed(delegator),
+        candidate,
+        pool,
+        10 * SHARE_INIT,
+    ));
+}
+
+fn pending_operations_count() -> usize {
+    PendingOperations::<Runtime>::iter().count()
+}
+
+#[test]
+fn matured_operations_are_executed() {
+    ExtBuilder::default().build().execute_with(|| {
+        let block_number = block_number();
+        request_delegate(
+            ACCOUNT_CANDIDATE_1,
+            ACCOUNT_DELEGATOR_1,
+            TargetPool::AutoCompounding,
+        );
+
+        Staking::on_idle(block_number, Weight::MAX);
+        assert_eq!(pending_operations_count(), 1);
+
+        roll_to(block_number + BLOCKS_TO_WAIT);
+        Staking::on_idle(block_number + BLOCKS_TO_WAIT, Weight::MAX);
+
+        assert_eq!(pending_operations_count(), 0);
+        assert_eq!(AutoExecutionCursor::<Runtime>::get(), None);
+        assert_eq!(
+            Staking::computed_stake(
+                ACCOUNT_CANDIDATE_1,
+                ACCOUNT_DELEGATOR_1,
+                AllTargetPool::AutoCompounding
+            ),
+            Some(10 * SHARE_INIT)
+        );
+        assert_eq_last_events!(vec![Event::<Runtime>::ExecutedDelegate {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_1,
+            pool: TargetPool::AutoCompounding,
+            staked: 10 * SHARE_INIT,
+            released: 0,
+        }]);
+    })
+}
+
+#[test]
+fn cursor_continues_across_blocks() {
+    ExtBuilder::default().build().execute_with(|| {
+        let block_number = block_number();
+        for candidate in [ACCOUNT_CANDIDATE_1, ACCOUNT_CANDIDATE_2] {
+            for delegator in [ACCOUNT_DELEGATOR_1, ACCOUNT_DELEGATOR_2] {
+                request_delegate(candidate, delegator, TargetPool::AutoCompounding);
+            }
+        }
+        request_delegate(
+            ACCOUNT_CANDIDATE_1,
+            ACCOUNT_DELEGATOR_1,
+            TargetPool::ManualRewards,
+        );
+        assert_eq!(pending_operations_count(), 5);
+
+        roll_to(block_number + BLOCKS_TO_WAIT);
+
+        // Scan limit is 3.
+        Staking::on_idle(block_number + BLOCKS_TO_WAIT, Weight::MAX);
+        assert_eq!(pending_operations_count(), 2);
+        assert!(AutoExecutionCursor::<Runtime>::get().is_some());
+
+        Staking::on_idle(block_number + BLOCKS_TO_WAIT, Weight::MAX);
+        assert_eq!(pending_operations_count(), 0);
+        assert_eq!(AutoExecutionCursor::<Runtime>::get(), None);
+    })
+}
+
+#[test]
+fn not_matured_operations_are_skipped() {
+    ExtBuilder::default().build().execute_with(|| {
+        let block_number = block_number();
+        request_delegate(
+            ACCOUNT_CANDIDATE_1,
+            ACCOUNT_DELEGATOR_1,
+            TargetPool::AutoCompounding,
+        );
+
+        roll_to(block_number + BLOCKS_TO_WAIT);
+        request_delegate(
+            ACCOUNT_CANDIDATE_1,
+            ACCOUNT_DELEGATOR_2,
+            TargetPool::AutoCompounding,
+        );
+
+        Staking::on_idle(block_number + BLOCKS_TO_WAIT, Weight::MAX);
+
+        assert_eq!(pending_operations_count(), 1);
+        assert_eq!(
+            PendingOperations::<Runtime>::iter_keys().next(),
+            Some((
+                ACCOUNT_DELEGATOR_2,
+                PendingOperationKey::JoiningAutoCompounding {
+                    candidate: ACCOUNT_CANDIDATE_1,
+                    at: block_number + BLOCKS_TO_WAIT,
+                }
+            ))
+        );
+    })
+}
+
+#[test]
+fn auto_execution_can_be_disabled() {
+    ExtBuilder::default().build().execute_with(|| {
+        AutoExecutionScanLimit::set(0);
+
+        let block_number = block_number();
+        request_delegate(
+            ACCOUNT_CANDIDATE_1,
+            ACCOUNT_DELEGATOR_1,
+            TargetPool::AutoCompounding,
+        );
+        roll_to(block_number + BLOCKS_TO_WAIT);
+
+        assert_eq!(
+            Staking::on_idle(block_number + BLOCKS_TO_WAIT, Weight::MAX),
+            Weight::zero()
+        );
+        assert_eq!(pending_operations_count(), 1);
+    })
+}
+
+#[test]
+fn auto_execution_respects_remaining_weight() {
+    ExtBuilder::default().build().execute_with(|| {
+        let block_number = block_number();
+        request_delegate(
+            ACCOUNT_CANDIDATE_1,
+            ACCOUNT_DELEGATOR_1,
+            TargetPool::AutoCompounding,
+        );
+        roll_to(block_number + BLOCKS_TO_WAIT);
+
+        let limit = Weight::from_parts(1_000_000, 1_000);
+        assert!(Staking::on_idle(block_number + BLOCKS_TO_WAIT, limit).all_lte(limit));
+        assert_eq!(pending_operations_count(), 1);
+    })
+}
+
+#[test]
+fn bounty_is_paid_to_keeper() {
+    ExtBuilder::default().build().execute_with(|| {
+        AutoExecutionBounty::set(1_000);
+
+        let block_number = block_number();
+        request_delegate(
+            ACCOUNT_CANDIDATE_1,
+            ACCOUNT_DELEGATOR_1,
+            TargetPool::AutoCompounding,
+        );
+        roll_to(block_number + BLOCKS_TO_WAIT);
+
+        let delegator_balance = total_balance(&ACCOUNT_DELEGATOR_1);
+        Staking::on_idle(block_number + BLOCKS_TO_WAIT, Weight::MAX);
+
+        assert_eq!(
+            total_balance(&ACCOUNT_DELEGATOR_1),
+            delegator_balance - 1_000
+        );
+        assert_eq!(total_balance(&ACCOUNT_KEEPER), 1_000);
+        assert_eq_last_events!(vec![Event::<Runtime>::PaidAutoExecutionBounty {
+            delegator: ACCOUNT_DELEGATOR_1,
+            keeper: ACCOUNT_KEEPER,
+            amount: 1_000,
+        }]);
+    })
+}
+
+#[test]
+fn no_bounty_without_keeper() {
+    ExtBuilder::default().build().execute_with(|| {
+        AutoExecutionBounty::set(1_000);
+        AutoExecutionKeeper::set(None);
+
+        let block_number = block_number();
+        request_delegate(
+            ACCOUNT_CANDIDATE_1,
+            ACCOUNT_DELEGATOR_1,
+            TargetPool::AutoCompounding,
+        );
+        roll_to(block_number + BLOCKS_TO_WAIT);
+
+        let delegator_balance = total_balance(&ACCOUNT_DELEGATOR_1);
+        Staking::on_idle(block_number + BLOCKS_TO_WAIT, Weight::MAX);
+
+        assert_eq!(pending_operations_count(), 0);
+        assert_eq!(total_balance(&ACCOUNT_DELEGATOR_1), delegator_balance);
+    })
+}
+
+#[test]
+fn operations_of_delegators_not_opted_in_are_skipped() {
+    ExtBuilder::default().build().execute_with(|| {
+        let block_number = block_number();
+        request_delegate(
+            ACCOUNT_CANDIDATE_1,
+            ACCOUNT_DELEGATOR_1,
+            TargetPool::AutoCompounding,
+        );
+        request_delegate(
+            ACCOUNT_CANDIDATE_1,
+            ACCOUNT_DELEGATOR_2,
+            TargetPool::AutoCompounding,
+        );
+        assert_ok!(Staking::set_auto_execution(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            false
+        ));
+        assert_eq_last_events!(vec![Event::<Runtime>::SetAutoExecution {
+            delegator: ACCOUNT_DELEGATOR_1,
+            enabled: false,
+        }]);
+        roll_to(block_number + BLOCKS_TO_WAIT);
+
+        Staking::on_idle(block_number + BLOCKS_TO_WAIT, Weight::MAX);
+
+        assert_eq!(pending_operations_count(), 1);
+        assert_eq!(
+            PendingOperations::<Runtime>::iter_keys().next(),
+            Some((
+                ACCOUNT_DELEGATOR_1,
+                PendingOperationKey::JoiningAutoCompounding {
+                    candidate: ACCOUNT_CANDIDATE_1,
+                    at: block_number,
+                }
+            ))
+        );
+    })
+}
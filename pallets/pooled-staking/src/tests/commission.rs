@@ -0,0 +1,311 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    super::*,
+    crate::{
+        assert_eq_last_events, CandidateCommission, CommissionChangeRequest, Pallet,
+        PendingCommissionChanges,
+    },
+    sp_runtime::Perbill,
+};
+
+fn self_delegate(candidate: AccountId) {
+    FullDelegation {
+        candidate,
+        delegator: candidate,
+        request_amount: MinimumSelfDelegation::get(),
+        expected_increase: MinimumSelfDelegation::get(),
+        ..default()
+    }
+    .test::<pools::AutoCompounding<Runtime>>();
+}
+
+#[test]
+fn candidate_without_commission_uses_default() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(
+            Pallet::<Runtime>::candidate_commission(&ACCOUNT_CANDIDATE_1),
+            RewardsCollatorCommission::get()
+        );
+    })
+}
+
+#[test]
+fn commission_change_is_delayed() {
+    ExtBuilder::default().build().execute_with(|| {
+        self_delegate(ACCOUNT_CANDIDATE_1);
+        let block_number = System::block_number();
+
+        assert_ok!(Staking::request_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+            Perbill::from_percent(5),
+        ));
+
+        assert_eq_last_events!(vec![Event::<Runtime>::RequestedCommissionChange {
+            candidate: ACCOUNT_CANDIDATE_1,
+            commission: Perbill::from_percent(5),
+        }]);
+        assert_eq!(
+            PendingCommissionChanges::<Runtime>::get(ACCOUNT_CANDIDATE_1),
+            Some(CommissionChangeRequest {
+                commission: Perbill::from_percent(5),
+                at: block_number,
+            })
+        );
+
+        assert_noop!(
+            Staking::execute_commission_change(
+                RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+                ACCOUNT_CANDIDATE_1,
+            ),
+            Error::<Runtime>::CommissionChangeCannotBeExecuted
+        );
+        assert_eq!(
+            Pallet::<Runtime>::candidate_commission(&ACCOUNT_CANDIDATE_1),
+            RewardsCollatorCommission::get()
+        );
+
+        roll_to(block_number + BLOCKS_TO_WAIT);
+
+        // Anyone can execute the request.
+        assert_ok!(Staking::execute_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            ACCOUNT_CANDIDATE_1,
+        ));
+
+        assert_eq_last_events!(vec![Event::<Runtime>::ChangedCommission {
+            candidate: ACCOUNT_CANDIDATE_1,
+            commission: Perbill::from_percent(5),
+        }]);
+        assert_eq!(
+            PendingCommissionChanges::<Runtime>::get(ACCOUNT_CANDIDATE_1),
+            None
+        );
+        assert_eq!(
+            CandidateCommission::<Runtime>::get(ACCOUNT_CANDIDATE_1),
+            Some(Perbill::from_percent(5))
+        );
+        assert_eq!(
+            Pallet::<Runtime>::candidate_commission(&ACCOUNT_CANDIDATE_1),
+            Perbill::from_percent(5)
+        );
+    })
+}
+
+#[test]
+fn only_one_pending_commission_change() {
+    ExtBuilder::default().build().execute_with(|| {
+        self_delegate(ACCOUNT_CANDIDATE_1);
+        self_delegate(ACCOUNT_CANDIDATE_2);
+        let block_number = System::block_number();
+
+        assert_ok!(Staking::request_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+            Perbill::from_percent(5),
+        ));
+
+        assert_noop!(
+            Staking::request_commission_change(
+                RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+                Perbill::from_percent(50),
+            ),
+            Error::<Runtime>::CommissionChangeAlreadyPending
+        );
+
+        // Other candidates are not affected.
+        assert_ok!(Staking::request_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_CANDIDATE_2),
+            Perbill::from_percent(50),
+        ));
+
+        roll_to(block_number + BLOCKS_TO_WAIT);
+
+        assert_noop!(
+            Staking::request_commission_change(
+                RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+                Perbill::from_percent(50),
+            ),
+            Error::<Runtime>::CommissionChangeAlreadyPending
+        );
+
+        assert_ok!(Staking::execute_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+            ACCOUNT_CANDIDATE_1,
+        ));
+        roll_to(block_number + 2 * BLOCKS_TO_WAIT);
+
+        assert_ok!(Staking::request_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+            Perbill::from_percent(50),
+        ));
+    })
+}
+
+#[test]
+fn commission_change_requires_self_delegation() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Staking::request_commission_change(
+                RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+                Perbill::from_percent(5),
+            ),
+            Error::<Runtime>::NotEnoughSelfDelegation
+        );
+
+        // Delegating to another candidate doesn't count.
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_2,
+            delegator: ACCOUNT_CANDIDATE_1,
+            request_amount: MinimumSelfDelegation::get(),
+            expected_increase: MinimumSelfDelegation::get(),
+            ..default()
+        }
+        .test::<pools::AutoCompounding<Runtime>>();
+
+        assert_noop!(
+            Staking::request_commission_change(
+                RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+                Perbill::from_percent(5),
+            ),
+            Error::<Runtime>::NotEnoughSelfDelegation
+        );
+
+        self_delegate(ACCOUNT_CANDIDATE_1);
+
+        assert_ok!(Staking::request_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+            Perbill::from_percent(5),
+        ));
+    })
+}
+
+#[test]
+fn commission_cannot_exceed_max() {
+    ExtBuilder::default().build().execute_with(|| {
+        self_delegate(ACCOUNT_CANDIDATE_1);
+
+        assert_noop!(
+            Staking::request_commission_change(
+                RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+                Perbill::from_percent(51),
+            ),
+            Error::<Runtime>::CommissionTooHigh
+        );
+
+        assert_ok!(Staking::request_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+            MaxCommission::get(),
+        ));
+    })
+}
+
+#[test]
+fn commission_change_is_rate_limited() {
+    ExtBuilder::default().build().execute_with(|| {
+        self_delegate(ACCOUNT_CANDIDATE_1);
+        let block_number = System::block_number();
+
+        assert_ok!(Staking::request_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+            Perbill::from_percent(5),
+        ));
+        roll_to(block_number + BLOCKS_TO_WAIT);
+        assert_ok!(Staking::execute_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            ACCOUNT_CANDIDATE_1,
+        ));
+
+        assert_noop!(
+            Staking::request_commission_change(
+                RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+                Perbill::from_percent(10),
+            ),
+            Error::<Runtime>::CommissionChangeTooSoon
+        );
+
+        roll_to(block_number + 2 * BLOCKS_TO_WAIT);
+
+        assert_ok!(Staking::request_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+            Perbill::from_percent(10),
+        ));
+    })
+}
+
+#[test]
+fn cancel_commission_change() {
+    ExtBuilder::default().build().execute_with(|| {
+        self_delegate(ACCOUNT_CANDIDATE_1);
+        let block_number = System::block_number();
+
+        assert_noop!(
+            Staking::cancel_commission_change(RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1)),
+            Error::<Runtime>::NoPendingCommissionChange
+        );
+
+        assert_ok!(Staking::request_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+            Perbill::from_percent(5),
+        ));
+
+        // Only the candidate can cancel its own request.
+        assert_noop!(
+            Staking::cancel_commission_change(RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1)),
+            Error::<Runtime>::NoPendingCommissionChange
+        );
+
+        assert_ok!(Staking::cancel_commission_change(RuntimeOrigin::signed(
+            ACCOUNT_CANDIDATE_1
+        )));
+        assert_eq_last_events!(vec![Event::<Runtime>::CancelledCommissionChange {
+            candidate: ACCOUNT_CANDIDATE_1,
+        }]);
+        assert_eq!(
+            PendingCommissionChanges::<Runtime>::get(ACCOUNT_CANDIDATE_1),
+            None
+        );
+
+        roll_to(block_number + BLOCKS_TO_WAIT);
+
+        assert_noop!(
+            Staking::execute_commission_change(
+                RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+                ACCOUNT_CANDIDATE_1,
+            ),
+            Error::<Runtime>::NoPendingCommissionChange
+        );
+
+        // Cancelling doesn't count as a change, so a new request can be made right away.
+        assert_ok!(Staking::request_commission_change(
+            RuntimeOrigin::signed(ACCOUNT_CANDIDATE_1),
+            Perbill::from_percent(10),
+        ));
+    })
+}
+
+#[test]
+fn execute_commission_change_without_request() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Staking::execute_commission_change(
+                RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+                ACCOUNT_CANDIDATE_1,
+            ),
+            Error::<Runtime>::NoPendingCommissionChange
+        );
+    })
+}
@@ -0,0 +1,175 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    super::*,
+    crate::{traits::Timer, Pallet},
+};
+
+type AutoCompounding = pools::AutoCompounding<Runtime>;
+type ManualRewards = pools::ManualRewards<Runtime>;
+
+#[test]
+fn delegator_candidates_lists_all_pools() {
+    ExtBuilder::default().build().execute_with(|| {
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_1,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<AutoCompounding>();
+
+        assert_ok!(Staking::request_delegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            ACCOUNT_CANDIDATE_2,
+            TargetPool::ManualRewards,
+            1000,
+        ));
+
+        assert_eq!(
+            Pallet::<Runtime>::delegator_candidates(&ACCOUNT_DELEGATOR_1),
+            vec![ACCOUNT_CANDIDATE_1, ACCOUNT_CANDIDATE_2]
+        );
+        assert_eq!(
+            Pallet::<Runtime>::delegator_candidates(&ACCOUNT_DELEGATOR_2),
+            vec![]
+        );
+    })
+}
+
+#[test]
+fn shares_and_claimable_rewards() {
+    ExtBuilder::default().build().execute_with(|| {
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_1,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<ManualRewards>();
+
+        assert_eq!(
+            Pallet::<Runtime>::shares(
+                ACCOUNT_CANDIDATE_1,
+                ACCOUNT_DELEGATOR_1,
+                AllTargetPool::ManualRewards
+            ),
+            10
+        );
+        assert_eq!(
+            Pallet::<Runtime>::shares(
+                ACCOUNT_CANDIDATE_1,
+                ACCOUNT_DELEGATOR_1,
+                AllTargetPool::AutoCompounding
+            ),
+            0
+        );
+        assert_eq!(
+            Pallet::<Runtime>::claimable_manual_rewards(&ACCOUNT_CANDIDATE_1, &ACCOUNT_DELEGATOR_1),
+            Some(0)
+        );
+
+        assert_ok!(ManualRewards::increase_rewards(
+            &ACCOUNT_CANDIDATE_1,
+            Stake(1_000)
+        ));
+
+        assert_eq!(
+            Pallet::<Runtime>::claimable_manual_rewards(&ACCOUNT_CANDIDATE_1, &ACCOUNT_DELEGATOR_1),
+            Some(1_000)
+        );
+    })
+}
+
+#[test]
+fn pending_operation_stake_and_unlock() {
+    ExtBuilder::default().build().execute_with(|| {
+        let block_number = block_number();
+
+        assert_ok!(Staking::request_delegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            ACCOUNT_CANDIDATE_1,
+            TargetPool::AutoCompounding,
+            1001,
+        ));
+
+        let operation = PendingOperationKey::JoiningAutoCompounding {
+            candidate: ACCOUNT_CANDIDATE_1,
+            at: block_number,
+        };
+        let shares = PendingOperations::<Runtime>::get(ACCOUNT_DELEGATOR_1, &operation);
+
+        // Joining shares are worth 2, so 1 is not staked.
+        assert_eq!(shares, 500);
+        assert_eq!(
+            Pallet::<Runtime>::pending_operation_stake(&operation, shares),
+            Some(1000)
+        );
+
+        type JoiningTimer = <Runtime as crate::Config>::JoiningRequestTimer;
+        assert_eq!(
+            JoiningTimer::elapsed_at(&block_number),
+            Some(block_number + BLOCKS_TO_WAIT)
+        );
+        assert!(!JoiningTimer::is_elapsed(&block_number));
+
+        roll_to(block_number + BLOCKS_TO_WAIT);
+        assert!(JoiningTimer::is_elapsed(&block_number));
+    })
+}
+
+#[test]
+fn candidate_total_stake_and_rank() {
+    ExtBuilder::default().build().execute_with(|| {
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_CANDIDATE_1,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<AutoCompounding>();
+
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_2,
+            delegator: ACCOUNT_CANDIDATE_2,
+            request_amount: 20 * SHARE_INIT,
+            expected_increase: 20 * SHARE_INIT,
+            ..default()
+        }
+        .test::<AutoCompounding>();
+
+        assert_eq!(
+            Pallet::<Runtime>::candidate_total_stake(&ACCOUNT_CANDIDATE_1),
+            10 * SHARE_INIT
+        );
+        assert_eq!(
+            Pallet::<Runtime>::candidate_rank(&ACCOUNT_CANDIDATE_2),
+            Some(0)
+        );
+        assert_eq!(
+            Pallet::<Runtime>::candidate_rank(&ACCOUNT_CANDIDATE_1),
+            Some(1)
+        );
+        assert_eq!(
+            Pallet::<Runtime>::candidate_rank(&ACCOUNT_DELEGATOR_1),
+            None
+        );
+    })
+}
@@ -14,11 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
+mod auto_execution;
 mod candidates;
+mod commission;
 mod delegator_flow;
+mod getters;
 mod manual_rewards;
 mod rebalance;
+mod redelegate;
 mod rewards;
+mod slashing;
 
 use {
     crate::{
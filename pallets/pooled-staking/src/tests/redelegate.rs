@@ -0,0 +1,317 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    super::*,
+    crate::{assert_eq_last_events, LastRedelegationTo, PendingSlashedFunds, Redelegations},
+    sp_runtime::Perbill,
+};
+
+type AutoCompounding = pools::AutoCompounding<Runtime>;
+
+fn delegate_candidate_1() {
+    FullDelegation {
+        candidate: ACCOUNT_CANDIDATE_1,
+        delegator: ACCOUNT_DELEGATOR_1,
+        request_amount: 10 * SHARE_INIT,
+        expected_increase: 10 * SHARE_INIT,
+        ..default()
+    }
+    .test::<AutoCompounding>();
+}
+
+fn redelegate(stake: Balance) -> frame_support::dispatch::DispatchResultWithPostInfo {
+    Staking::redelegate(
+        RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+        ACCOUNT_CANDIDATE_1,
+        ACCOUNT_CANDIDATE_2,
+        TargetPool::AutoCompounding,
+        SharesOrStake::Stake(stake),
+    )
+}
+
+#[test]
+fn redelegate_moves_stake_between_candidates() {
+    ExtBuilder::default().build().execute_with(|| {
+        delegate_candidate_1();
+
+        assert_ok!(redelegate(4 * SHARE_INIT));
+
+        assert_eq!(
+            AutoCompounding::shares(&ACCOUNT_CANDIDATE_1, &ACCOUNT_DELEGATOR_1),
+            Shares(6)
+        );
+        assert_eq!(
+            AutoCompounding::shares(&ACCOUNT_CANDIDATE_2, &ACCOUNT_DELEGATOR_1),
+            Shares(4)
+        );
+        assert_eq!(
+            Candidates::<Runtime>::total_stake(&ACCOUNT_CANDIDATE_1),
+            Stake(6 * SHARE_INIT)
+        );
+        assert_eq!(
+            Candidates::<Runtime>::total_stake(&ACCOUNT_CANDIDATE_2),
+            Stake(4 * SHARE_INIT)
+        );
+        assert_eq!(balance_hold(&ACCOUNT_DELEGATOR_1), 10 * SHARE_INIT);
+
+        let redelegations = Redelegations::<Runtime>::get(ACCOUNT_CANDIDATE_1);
+        assert_eq!(redelegations.len(), 1);
+        assert_eq!(redelegations[0].target, ACCOUNT_CANDIDATE_2);
+        assert_eq!(redelegations[0].stake, 4 * SHARE_INIT);
+        assert_eq!(
+            LastRedelegationTo::<Runtime>::get(ACCOUNT_DELEGATOR_1, ACCOUNT_CANDIDATE_2),
+            Some(block_number())
+        );
+
+        assert_eq_last_events!(vec![Event::Redelegated {
+            source: ACCOUNT_CANDIDATE_1,
+            target: ACCOUNT_CANDIDATE_2,
+            delegator: ACCOUNT_DELEGATOR_1,
+            pool: TargetPool::AutoCompounding,
+            source_shares: 4,
+            source_stake: 4 * SHARE_INIT,
+            target_shares: 4,
+            target_stake: 4 * SHARE_INIT,
+            released: 0,
+        }]);
+    })
+}
+
+#[test]
+fn redelegate_to_same_candidate_fails() {
+    ExtBuilder::default().build().execute_with(|| {
+        delegate_candidate_1();
+
+        assert_noop!(
+            Staking::redelegate(
+                RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+                ACCOUNT_CANDIDATE_1,
+                ACCOUNT_CANDIDATE_1,
+                TargetPool::AutoCompounding,
+                SharesOrStake::Shares(1),
+            ),
+            Error::<Runtime>::RedelegateToSameCandidate
+        );
+    })
+}
+
+#[test]
+fn redelegated_stake_cannot_leave_target_during_cooldown() {
+    ExtBuilder::default().build().execute_with(|| {
+        delegate_candidate_1();
+        assert_ok!(redelegate(4 * SHARE_INIT));
+
+        assert_noop!(
+            Staking::request_undelegate(
+                RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+                ACCOUNT_CANDIDATE_2,
+                TargetPool::AutoCompounding,
+                SharesOrStake::Shares(1),
+            ),
+            Error::<Runtime>::RedelegatedStakeInCooldown
+        );
+        assert_noop!(
+            Staking::swap_pool(
+                RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+                ACCOUNT_CANDIDATE_2,
+                TargetPool::AutoCompounding,
+                SharesOrStake::Shares(1),
+            ),
+            Error::<Runtime>::RedelegatedStakeInCooldown
+        );
+        assert_noop!(
+            Staking::redelegate(
+                RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+                ACCOUNT_CANDIDATE_2,
+                ACCOUNT_CANDIDATE_1,
+                TargetPool::AutoCompounding,
+                SharesOrStake::Shares(1),
+            ),
+            Error::<Runtime>::RedelegatedStakeInCooldown
+        );
+
+        // Stake left at the source is not affected.
+        assert_ok!(Staking::request_undelegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            ACCOUNT_CANDIDATE_1,
+            TargetPool::AutoCompounding,
+            SharesOrStake::Shares(1),
+        ));
+
+        roll_to(block_number() + BLOCKS_TO_WAIT);
+
+        assert_ok!(Staking::request_undelegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            ACCOUNT_CANDIDATE_2,
+            TargetPool::AutoCompounding,
+            SharesOrStake::Shares(1),
+        ));
+        assert_eq!(
+            LastRedelegationTo::<Runtime>::get(ACCOUNT_DELEGATOR_1, ACCOUNT_CANDIDATE_2),
+            None
+        );
+    })
+}
+
+#[test]
+fn redelegations_per_candidate_are_bounded() {
+    ExtBuilder::default().build().execute_with(|| {
+        delegate_candidate_1();
+
+        assert_ok!(redelegate(2 * SHARE_INIT));
+        assert_ok!(redelegate(2 * SHARE_INIT));
+        assert_noop!(
+            redelegate(2 * SHARE_INIT),
+            Error::<Runtime>::TooManyRedelegations
+        );
+
+        // Elapsed redelegations are pruned, which frees space.
+        roll_to(block_number() + BLOCKS_TO_WAIT);
+
+        assert_ok!(redelegate(2 * SHARE_INIT));
+        assert_eq!(Redelegations::<Runtime>::get(ACCOUNT_CANDIDATE_1).len(), 1);
+    })
+}
+
+#[test]
+fn redelegation_below_minimum_fails() {
+    ExtBuilder::default().build().execute_with(|| {
+        delegate_candidate_1();
+
+        assert_noop!(
+            redelegate(MinimumRedelegation::get() - SHARE_INIT),
+            Error::<Runtime>::RedelegationBelowMinimum
+        );
+        assert_ok!(redelegate(MinimumRedelegation::get()));
+    })
+}
+
+#[test]
+fn elapsed_last_redelegation_is_pruned() {
+    ExtBuilder::default().build().execute_with(|| {
+        delegate_candidate_1();
+        assert_ok!(redelegate(4 * SHARE_INIT));
+
+        roll_to(block_number() + BLOCKS_TO_WAIT);
+
+        // Pruning the redelegations of the source also prunes the entry.
+        assert_eq!(
+            pools::slash_candidate::<Runtime>(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(0)),
+            Ok(0)
+        );
+        assert!(Redelegations::<Runtime>::get(ACCOUNT_CANDIDATE_1).is_empty());
+        assert_eq!(
+            LastRedelegationTo::<Runtime>::get(ACCOUNT_DELEGATOR_1, ACCOUNT_CANDIDATE_2),
+            None
+        );
+    })
+}
+
+#[test]
+fn last_redelegation_updated_by_another_source_is_kept() {
+    ExtBuilder::default().build().execute_with(|| {
+        delegate_candidate_1();
+        // Any account can be a candidate.
+        FullDelegation {
+            candidate: ACCOUNT_DELEGATOR_2,
+            delegator: ACCOUNT_DELEGATOR_1,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<AutoCompounding>();
+        assert_ok!(redelegate(4 * SHARE_INIT));
+
+        roll_to(block_number() + BLOCKS_TO_WAIT);
+
+        // Redelegation towards the same target from another source.
+        assert_ok!(Staking::redelegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            ACCOUNT_DELEGATOR_2,
+            ACCOUNT_CANDIDATE_2,
+            TargetPool::AutoCompounding,
+            SharesOrStake::Stake(2 * SHARE_INIT),
+        ));
+        let at = block_number();
+
+        assert_eq!(
+            pools::slash_candidate::<Runtime>(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(0)),
+            Ok(0)
+        );
+        assert_eq!(
+            LastRedelegationTo::<Runtime>::get(ACCOUNT_DELEGATOR_1, ACCOUNT_CANDIDATE_2),
+            Some(at)
+        );
+    })
+}
+
+#[test]
+fn slash_of_source_slashes_redelegated_stake() {
+    ExtBuilder::default().build().execute_with(|| {
+        delegate_candidate_1();
+        assert_ok!(redelegate(4 * SHARE_INIT));
+
+        assert_eq!(
+            pools::slash_candidate::<Runtime>(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(50)),
+            Ok(3 * SHARE_INIT + 2 * SHARE_INIT)
+        );
+
+        assert_eq!(
+            AutoCompounding::shares(&ACCOUNT_CANDIDATE_2, &ACCOUNT_DELEGATOR_1),
+            Shares(2)
+        );
+        assert_eq!(
+            Candidates::<Runtime>::total_stake(&ACCOUNT_CANDIDATE_2),
+            Stake(2 * SHARE_INIT)
+        );
+        // Slashed currency of both the pools and the redelegations is sent at once.
+        assert_eq!(total_balance(&ACCOUNT_SLASHED_FUNDS), 5 * SHARE_INIT);
+        assert_eq!(PendingSlashedFunds::<Runtime>::get(), 0);
+        assert_eq!(
+            Redelegations::<Runtime>::get(ACCOUNT_CANDIDATE_1)[0].stake,
+            2 * SHARE_INIT
+        );
+
+        assert_eq_last_events!(vec![Event::SlashedRedelegation {
+            source: ACCOUNT_CANDIDATE_1,
+            target: ACCOUNT_CANDIDATE_2,
+            delegator: ACCOUNT_DELEGATOR_1,
+            slashed: 2 * SHARE_INIT,
+        }]);
+    })
+}
+
+#[test]
+fn slash_of_source_ignores_redelegations_after_cooldown() {
+    ExtBuilder::default().build().execute_with(|| {
+        delegate_candidate_1();
+        assert_ok!(redelegate(4 * SHARE_INIT));
+
+        roll_to(block_number() + BLOCKS_TO_WAIT);
+
+        assert_eq!(
+            pools::slash_candidate::<Runtime>(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(50)),
+            Ok(3 * SHARE_INIT)
+        );
+
+        assert_eq!(
+            Candidates::<Runtime>::total_stake(&ACCOUNT_CANDIDATE_2),
+            Stake(4 * SHARE_INIT)
+        );
+        assert!(Redelegations::<Runtime>::get(ACCOUNT_CANDIDATE_1).is_empty());
+    })
+}
@@ -373,6 +373,63 @@ fn delegators_manual_only() {
     });
 }
 
+#[test]
+fn delegators_manual_only_candidate_commission() {
+    ExtBuilder::default().build().execute_with(|| {
+        crate::CandidateCommission::<Runtime>::insert(
+            ACCOUNT_CANDIDATE_1,
+            sp_runtime::Perbill::from_percent(50),
+        );
+
+        test_distribution(
+            &[
+                Delegation {
+                    candidate: ACCOUNT_CANDIDATE_1,
+                    delegator: ACCOUNT_CANDIDATE_1,
+                    pool: TargetPool::ManualRewards,
+                    stake: 1_000_000_000,
+                },
+                Delegation {
+                    candidate: ACCOUNT_CANDIDATE_1,
+                    delegator: ACCOUNT_DELEGATOR_1,
+                    pool: TargetPool::ManualRewards,
+                    stake: 250_000_000,
+                },
+            ],
+            RewardRequest {
+                collator: ACCOUNT_CANDIDATE_1,
+                rewards: 10_000_000,
+            },
+            &[
+                DelegatorState {
+                    candidate: ACCOUNT_CANDIDATE_1,
+                    delegator: ACCOUNT_CANDIDATE_1,
+                    auto_shares: 0,
+                    auto_stake: 0,
+                    manual_shares: 1_000,
+                    manual_stake: 1_000_000_000,
+                    pending_rewards: 4_000_000,
+                },
+                DelegatorState {
+                    candidate: ACCOUNT_CANDIDATE_1,
+                    delegator: ACCOUNT_DELEGATOR_1,
+                    auto_shares: 0,
+                    auto_stake: 0,
+                    manual_shares: 250,
+                    manual_stake: 250_000_000,
+                    pending_rewards: 1_000_000,
+                },
+            ],
+            Distribution {
+                collator_auto: 0,
+                collator_manual: 5_000_000,
+                delegators_auto: 0,
+                delegators_manual: 5_000_000,
+            },
+        )
+    });
+}
+
 #[test]
 fn delegators_auto_only() {
     ExtBuilder::default().build().execute_with(|| {
@@ -0,0 +1,397 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    super::*,
+    crate::{assert_eq_last_events, PendingSlashedFunds},
+    sp_runtime::{DispatchError, Perbill},
+    tp_traits::OnSlashApplied,
+};
+
+type AutoCompounding = pools::AutoCompounding<Runtime>;
+type ManualRewards = pools::ManualRewards<Runtime>;
+
+fn total_staked<P: Pool<Runtime>>() -> Balance {
+    P::total_staked(&ACCOUNT_CANDIDATE_1).0
+}
+
+#[test]
+fn slash_reduces_all_pools_proportionally() {
+    ExtBuilder::default().build().execute_with(|| {
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_1,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<AutoCompounding>();
+
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_2,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<ManualRewards>();
+
+        assert_ok!(Staking::request_delegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            ACCOUNT_CANDIDATE_1,
+            TargetPool::AutoCompounding,
+            1000,
+        ));
+        assert_ok!(Staking::request_undelegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_2),
+            ACCOUNT_CANDIDATE_1,
+            TargetPool::ManualRewards,
+            SharesOrStake::Stake(4 * SHARE_INIT),
+        ));
+
+        assert_eq!(total_staked::<Joining>(), 1000);
+        assert_eq!(total_staked::<AutoCompounding>(), 10 * SHARE_INIT);
+        assert_eq!(total_staked::<ManualRewards>(), 6 * SHARE_INIT);
+        // 1 is released as dust when converting to leaving shares.
+        assert_eq!(total_staked::<Leaving>(), 4 * SHARE_INIT - 1);
+        assert_eq!(
+            Candidates::<Runtime>::total_stake(&ACCOUNT_CANDIDATE_1),
+            Stake(16 * SHARE_INIT + 1000)
+        );
+
+        assert_eq!(
+            pools::slash_candidate::<Runtime>(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(10)),
+            Ok(1_600_100 + 399_999)
+        );
+
+        assert_eq!(total_staked::<Joining>(), 900);
+        assert_eq!(total_staked::<AutoCompounding>(), 9 * SHARE_INIT);
+        assert_eq!(total_staked::<ManualRewards>(), 5_400_000);
+        assert_eq!(total_staked::<Leaving>(), 3_600_000);
+        assert_eq!(
+            Candidates::<Runtime>::total_stake(&ACCOUNT_CANDIDATE_1),
+            Stake(14_400_900)
+        );
+        // Slashed funds are advanced by the staking account.
+        assert_eq!(total_balance(&ACCOUNT_SLASHED_FUNDS), 2_000_099);
+        assert_eq!(total_balance(&ACCOUNT_STAKING), DEFAULT_BALANCE - 2_000_099);
+        assert_eq!(PendingSlashedFunds::<Runtime>::get(), 0);
+
+        // Shares are untouched, only their value decreased.
+        assert_eq!(
+            AutoCompounding::shares(&ACCOUNT_CANDIDATE_1, &ACCOUNT_DELEGATOR_1),
+            Shares(10)
+        );
+        assert_eq!(
+            AutoCompounding::computed_stake(&ACCOUNT_CANDIDATE_1, &ACCOUNT_DELEGATOR_1)
+                .unwrap()
+                .0,
+            9 * SHARE_INIT
+        );
+
+        assert_eq_last_events!(vec![
+            Event::SlashedPool {
+                candidate: ACCOUNT_CANDIDATE_1,
+                pool: AllTargetPool::Joining,
+                slashed: 100,
+            },
+            Event::SlashedPool {
+                candidate: ACCOUNT_CANDIDATE_1,
+                pool: AllTargetPool::AutoCompounding,
+                slashed: SHARE_INIT,
+            },
+            Event::SlashedPool {
+                candidate: ACCOUNT_CANDIDATE_1,
+                pool: AllTargetPool::ManualRewards,
+                slashed: 600_000,
+            },
+            Event::SlashedPool {
+                candidate: ACCOUNT_CANDIDATE_1,
+                pool: AllTargetPool::Leaving,
+                slashed: 399_999,
+            },
+            Event::DecreasedStake {
+                candidate: ACCOUNT_CANDIDATE_1,
+                stake_diff: 1_600_100,
+            },
+            Event::UpdatedCandidatePosition {
+                candidate: ACCOUNT_CANDIDATE_1,
+                stake: 14_400_900,
+                self_delegation: 0,
+                before: None,
+                after: None,
+            },
+            Event::TransferredSlashedFunds {
+                amount: 2_000_099,
+                pending: 0,
+            },
+        ]);
+    })
+}
+
+#[test]
+fn slash_amount_is_rounded_down() {
+    ExtBuilder::default().build().execute_with(|| {
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_1,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<AutoCompounding>();
+
+        // 10_000_000 * 0.00000015 = 1.5
+        assert_eq!(
+            pools::slash_candidate::<Runtime>(&ACCOUNT_CANDIDATE_1, Perbill::from_parts(150)),
+            Ok(1)
+        );
+        assert_eq!(total_staked::<AutoCompounding>(), 10 * SHARE_INIT - 1);
+        assert_eq!(total_balance(&ACCOUNT_SLASHED_FUNDS), 1);
+    })
+}
+
+#[test]
+fn slash_rounding_to_zero_does_nothing() {
+    ExtBuilder::default().build().execute_with(|| {
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_1,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<AutoCompounding>();
+
+        let events_before = events().len();
+
+        // 10_000_000 * 0.000000001 = 0.01
+        assert_eq!(
+            pools::slash_candidate::<Runtime>(&ACCOUNT_CANDIDATE_1, Perbill::from_parts(1)),
+            Ok(0)
+        );
+        assert_eq!(total_staked::<AutoCompounding>(), 10 * SHARE_INIT);
+        assert_eq!(total_balance(&ACCOUNT_SLASHED_FUNDS), 0);
+        assert_eq!(events().len(), events_before);
+    })
+}
+
+#[test]
+fn full_slash_keeps_one_unit_per_pool() {
+    ExtBuilder::default().build().execute_with(|| {
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_1,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<AutoCompounding>();
+
+        assert_eq!(
+            pools::slash_candidate::<Runtime>(&ACCOUNT_CANDIDATE_1, Perbill::one()),
+            Ok(10 * SHARE_INIT - 1)
+        );
+        assert_eq!(total_staked::<AutoCompounding>(), 1);
+        assert_eq!(
+            AutoCompounding::shares_supply(&ACCOUNT_CANDIDATE_1),
+            Shares(10)
+        );
+
+        // New delegations are not diluted by the worthless shares.
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_2,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<AutoCompounding>();
+    })
+}
+
+#[test]
+fn slash_candidate_without_stake_does_nothing() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_eq!(
+            pools::slash_candidate::<Runtime>(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(50)),
+            Ok(0)
+        );
+        assert_eq!(total_balance(&ACCOUNT_SLASHED_FUNDS), 0);
+        assert_eq!(events(), vec![]);
+    })
+}
+
+#[test]
+fn on_slash_applied_hook_slashes_candidate() {
+    ExtBuilder::default().build().execute_with(|| {
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_1,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<ManualRewards>();
+
+        Staking::on_slash_applied(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(20));
+
+        assert_eq!(total_staked::<ManualRewards>(), 8 * SHARE_INIT);
+        assert_eq!(total_balance(&ACCOUNT_SLASHED_FUNDS), 2 * SHARE_INIT);
+    })
+}
+
+#[test]
+fn rebalance_of_slashed_delegators_refills_staking_account() {
+    ExtBuilder::default().build().execute_with(|| {
+        for delegator in [ACCOUNT_DELEGATOR_1, ACCOUNT_DELEGATOR_2] {
+            FullDelegation {
+                candidate: ACCOUNT_CANDIDATE_1,
+                delegator,
+                request_amount: 5 * SHARE_INIT,
+                expected_increase: 5 * SHARE_INIT,
+                ..default()
+            }
+            .test::<AutoCompounding>();
+        }
+
+        assert_ok!(pools::slash_candidate::<Runtime>(
+            &ACCOUNT_CANDIDATE_1,
+            Perbill::from_percent(10)
+        ));
+        assert_eq!(total_balance(&ACCOUNT_SLASHED_FUNDS), SHARE_INIT);
+        assert_eq!(total_balance(&ACCOUNT_STAKING), DEFAULT_BALANCE - SHARE_INIT);
+
+        for delegator in [ACCOUNT_DELEGATOR_1, ACCOUNT_DELEGATOR_2] {
+            assert_ok!(Staking::rebalance_hold(
+                RuntimeOrigin::signed(delegator),
+                ACCOUNT_CANDIDATE_1,
+                delegator,
+                AllTargetPool::AutoCompounding,
+            ));
+        }
+
+        // Each delegator only loses its own share of the slash, and keeps its free balance.
+        for delegator in [ACCOUNT_DELEGATOR_1, ACCOUNT_DELEGATOR_2] {
+            assert_eq!(balance_hold(&delegator), 4_500_000);
+            assert_eq!(
+                Balances::free_balance(delegator),
+                DEFAULT_BALANCE - 5 * SHARE_INIT
+            );
+            assert_eq!(total_balance(&delegator), DEFAULT_BALANCE - 500_000);
+        }
+
+        assert_eq!(total_balance(&ACCOUNT_STAKING), DEFAULT_BALANCE);
+        assert_eq!(total_balance(&ACCOUNT_SLASHED_FUNDS), SHARE_INIT);
+    })
+}
+
+#[test]
+fn slashed_funds_not_advanced_are_sent_when_rebalancing() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            (ACCOUNT_STAKING, 1),
+            (ACCOUNT_CANDIDATE_1, DEFAULT_BALANCE),
+            (ACCOUNT_DELEGATOR_1, DEFAULT_BALANCE),
+            (ACCOUNT_DELEGATOR_2, DEFAULT_BALANCE),
+        ])
+        .build()
+        .execute_with(|| {
+            for delegator in [ACCOUNT_DELEGATOR_1, ACCOUNT_DELEGATOR_2] {
+                FullDelegation {
+                    candidate: ACCOUNT_CANDIDATE_1,
+                    delegator,
+                    request_amount: 5 * SHARE_INIT,
+                    expected_increase: 5 * SHARE_INIT,
+                    ..default()
+                }
+                .test::<AutoCompounding>();
+            }
+
+            // The staking account can't go below the existential deposit.
+            assert_ok!(pools::slash_candidate::<Runtime>(
+                &ACCOUNT_CANDIDATE_1,
+                Perbill::from_percent(10)
+            ));
+            assert_eq!(total_balance(&ACCOUNT_SLASHED_FUNDS), 0);
+            assert_eq!(PendingSlashedFunds::<Runtime>::get(), SHARE_INIT);
+
+            assert_ok!(Staking::rebalance_hold(
+                RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+                ACCOUNT_CANDIDATE_1,
+                ACCOUNT_DELEGATOR_1,
+                AllTargetPool::AutoCompounding,
+            ));
+            assert_eq!(total_balance(&ACCOUNT_SLASHED_FUNDS), 500_000);
+            assert_eq!(PendingSlashedFunds::<Runtime>::get(), 500_000);
+            assert_eq_last_events!(vec![Event::TransferredSlashedFunds {
+                amount: 500_000,
+                pending: 500_000,
+            }]);
+
+            assert_ok!(Staking::rebalance_hold(
+                RuntimeOrigin::signed(ACCOUNT_DELEGATOR_2),
+                ACCOUNT_CANDIDATE_1,
+                ACCOUNT_DELEGATOR_2,
+                AllTargetPool::AutoCompounding,
+            ));
+            assert_eq!(total_balance(&ACCOUNT_SLASHED_FUNDS), SHARE_INIT);
+            assert_eq!(PendingSlashedFunds::<Runtime>::get(), 0);
+            assert_eq!(total_balance(&ACCOUNT_STAKING), 1);
+            assert_eq_last_events!(vec![Event::TransferredSlashedFunds {
+                amount: 500_000,
+                pending: 0,
+            }]);
+
+            for delegator in [ACCOUNT_DELEGATOR_1, ACCOUNT_DELEGATOR_2] {
+                assert_eq!(
+                    Balances::free_balance(delegator),
+                    DEFAULT_BALANCE - 5 * SHARE_INIT
+                );
+                assert_eq!(total_balance(&delegator), DEFAULT_BALANCE - 500_000);
+            }
+        })
+}
+
+#[test]
+fn slash_candidate_call_requires_root() {
+    ExtBuilder::default().build().execute_with(|| {
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_1,
+            request_amount: 10 * SHARE_INIT,
+            expected_increase: 10 * SHARE_INIT,
+            ..default()
+        }
+        .test::<AutoCompounding>();
+
+        assert_noop!(
+            Staking::slash_candidate(
+                RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+                ACCOUNT_CANDIDATE_1,
+                Perbill::from_percent(10),
+            ),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Staking::slash_candidate(
+            RuntimeOrigin::root(),
+            ACCOUNT_CANDIDATE_1,
+            Perbill::from_percent(10),
+        ));
+        assert_eq!(total_staked::<AutoCompounding>(), 9 * SHARE_INIT);
+    })
+}
@@ -34,6 +34,10 @@ pub trait Timer {
     /// Check if the timer started at `started` is elapsed.
     fn is_elapsed(start: &Self::Instant) -> bool;
 
+    /// Instant at which the timer started at `start` will be elapsed, or `None`
+    /// if it cannot be represented.
+    fn elapsed_at(start: &Self::Instant) -> Option<Self::Instant>;
+
     /// Returns an instant that will make `is_elapsed` true.
     #[cfg(feature = "runtime-benchmarks")]
     fn elapsed_instant() -> Self::Instant;
@@ -66,6 +70,10 @@ where
         end <= Self::now()
     }
 
+    fn elapsed_at(start: &Self::Instant) -> Option<Self::Instant> {
+        start.checked_add(&G::get())
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn elapsed_instant() -> Self::Instant {
         let delay = G::get();
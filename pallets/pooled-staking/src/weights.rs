@@ -60,6 +60,12 @@ pub trait WeightInfo {
 	fn update_candidate_position(b: u32, ) -> Weight;
 	fn swap_pool() -> Weight;
 	fn distribute_rewards() -> Weight;
+	fn slash_candidate(r: u32, ) -> Weight;
+	fn request_commission_change() -> Weight;
+	fn execute_commission_change() -> Weight;
+	fn cancel_commission_change() -> Weight;
+	fn set_auto_execution() -> Weight;
+	fn redelegate(r: u32, ) -> Weight;
 }
 
 /// Weights for pallet_pooled_staking using the Substrate node and recommended hardware.
@@ -81,10 +87,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: PooledStaking PendingOperations (max_values: None, max_size: None, mode: Measured)
 	fn request_delegate() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `1321`
-		//  Estimated: `29536`
+		//  Measured:  `1385`
+		//  Estimated: `33628`
 		// Minimum execution time: 127_339_000 picoseconds.
-		Weight::from_parts(133_146_000, 29536)
+		Weight::from_parts(133_146_000, 33628)
 			.saturating_add(T::DbWeight::get().reads(17_u64))
 			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
@@ -151,18 +157,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	}
 	/// Storage: PooledStaking Pools (r:4 w:1)
 	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
-	/// Storage: System Account (r:2 w:2)
+	/// Storage: System Account (r:3 w:3)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	/// Storage: Balances Holds (r:1 w:1)
 	/// Proof: Balances Holds (max_values: None, max_size: Some(66), added: 2541, mode: MaxEncodedLen)
+	/// Storage: PooledStaking PendingSlashedFunds (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingSlashedFunds (max_values: Some(1), max_size: None, mode: Measured)
 	fn rebalance_hold() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `980`
-		//  Estimated: `11870`
+		//  Measured:  `1044`
+		//  Estimated: `15962`
 		// Minimum execution time: 98_014_000 picoseconds.
-		Weight::from_parts(128_615_000, 11870)
-			.saturating_add(T::DbWeight::get().reads(7_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+		Weight::from_parts(128_615_000, 15962)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
 	/// Storage: PooledStaking Pools (r:600 w:100)
 	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
@@ -213,6 +221,107 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(13_u64))
 			.saturating_add(T::DbWeight::get().writes(8_u64))
 	}
+	/// Storage: PooledStaking Pools (r:16 w:6)
+	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking SortedEligibleCandidates (r:1 w:1)
+	/// Proof Skipped: PooledStaking SortedEligibleCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session NextKeys (r:1 w:0)
+	/// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking PendingSlashedFunds (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingSlashedFunds (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: PooledStaking Redelegations (r:1 w:1)
+	/// Proof: PooledStaking Redelegations (max_values: None, max_size: Some(8502), added: 10977, mode: MaxEncodedLen)
+	/// The range of component `r` is `[0, 100]`.
+	fn slash_candidate(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1398`
+		//  Estimated: `41088`
+		// Minimum execution time: 142_412_000 picoseconds.
+		Weight::from_parts(151_883_000, 41088)
+			// Standard Error: 41_205
+			.saturating_add(Weight::from_parts(38_913_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(21_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 7761).saturating_mul(r.into()))
+	}
+	/// Storage: PooledStaking Pools (r:6 w:0)
+	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking PendingCommissionChanges (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingCommissionChanges (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking LastCommissionChange (r:1 w:0)
+	/// Proof Skipped: PooledStaking LastCommissionChange (max_values: None, max_size: None, mode: Measured)
+	fn request_commission_change() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `142`
+		//  Estimated: `28502`
+		// Minimum execution time: 14_218_000 picoseconds.
+		Weight::from_parts(14_873_000, 28502)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: PooledStaking PendingCommissionChanges (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingCommissionChanges (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking CandidateCommission (r:0 w:1)
+	/// Proof Skipped: PooledStaking CandidateCommission (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking LastCommissionChange (r:0 w:1)
+	/// Proof Skipped: PooledStaking LastCommissionChange (max_values: None, max_size: None, mode: Measured)
+	fn execute_commission_change() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `262`
+		//  Estimated: `3727`
+		// Minimum execution time: 17_604_000 picoseconds.
+		Weight::from_parts(18_311_000, 3727)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: PooledStaking PendingCommissionChanges (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingCommissionChanges (max_values: None, max_size: None, mode: Measured)
+	fn cancel_commission_change() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `262`
+		//  Estimated: `3727`
+		// Minimum execution time: 12_904_000 picoseconds.
+		Weight::from_parts(13_402_000, 3727)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: PooledStaking AutoExecutionOptIn (r:0 w:1)
+	/// Proof Skipped: PooledStaking AutoExecutionOptIn (max_values: None, max_size: None, mode: Measured)
+	fn set_auto_execution() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_112_000 picoseconds.
+		Weight::from_parts(8_461_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: PooledStaking LastRedelegationTo (r:1 w:1)
+	/// Proof Skipped: PooledStaking LastRedelegationTo (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking Pools (r:8 w:8)
+	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking SortedEligibleCandidates (r:1 w:1)
+	/// Proof Skipped: PooledStaking SortedEligibleCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: PooledStaking Redelegations (r:1 w:1)
+	/// Proof: PooledStaking Redelegations (max_values: None, max_size: Some(8502), added: 10977, mode: MaxEncodedLen)
+	fn redelegate(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `10430`
+		//  Estimated: `31290`
+		// The range of component `r` is `[0, 100]`.
+		// Minimum execution time: 158_102_000 picoseconds.
+		Weight::from_parts(165_337_000, 31290)
+			// Standard Error: 2_417
+			.saturating_add(Weight::from_parts(3_312_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2530).saturating_mul(r.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -303,18 +412,20 @@ impl WeightInfo for () {
 	}
 	/// Storage: PooledStaking Pools (r:4 w:1)
 	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
-	/// Storage: System Account (r:2 w:2)
+	/// Storage: System Account (r:3 w:3)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	/// Storage: Balances Holds (r:1 w:1)
 	/// Proof: Balances Holds (max_values: None, max_size: Some(66), added: 2541, mode: MaxEncodedLen)
+	/// Storage: PooledStaking PendingSlashedFunds (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingSlashedFunds (max_values: Some(1), max_size: None, mode: Measured)
 	fn rebalance_hold() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `980`
-		//  Estimated: `11870`
+		//  Measured:  `1044`
+		//  Estimated: `15962`
 		// Minimum execution time: 98_014_000 picoseconds.
-		Weight::from_parts(128_615_000, 11870)
-			.saturating_add(RocksDbWeight::get().reads(7_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+		Weight::from_parts(128_615_000, 15962)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
 	/// Storage: PooledStaking Pools (r:600 w:100)
 	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
@@ -365,4 +476,105 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(13_u64))
 			.saturating_add(RocksDbWeight::get().writes(8_u64))
 	}
+	/// Storage: PooledStaking Pools (r:16 w:6)
+	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking SortedEligibleCandidates (r:1 w:1)
+	/// Proof Skipped: PooledStaking SortedEligibleCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session NextKeys (r:1 w:0)
+	/// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking PendingSlashedFunds (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingSlashedFunds (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: PooledStaking Redelegations (r:1 w:1)
+	/// Proof: PooledStaking Redelegations (max_values: None, max_size: Some(8502), added: 10977, mode: MaxEncodedLen)
+	/// The range of component `r` is `[0, 100]`.
+	fn slash_candidate(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1398`
+		//  Estimated: `41088`
+		// Minimum execution time: 142_412_000 picoseconds.
+		Weight::from_parts(151_883_000, 41088)
+			// Standard Error: 41_205
+			.saturating_add(Weight::from_parts(38_913_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(21_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 7761).saturating_mul(r.into()))
+	}
+	/// Storage: PooledStaking Pools (r:6 w:0)
+	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking PendingCommissionChanges (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingCommissionChanges (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking LastCommissionChange (r:1 w:0)
+	/// Proof Skipped: PooledStaking LastCommissionChange (max_values: None, max_size: None, mode: Measured)
+	fn request_commission_change() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `142`
+		//  Estimated: `28502`
+		// Minimum execution time: 14_218_000 picoseconds.
+		Weight::from_parts(14_873_000, 28502)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: PooledStaking PendingCommissionChanges (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingCommissionChanges (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking CandidateCommission (r:0 w:1)
+	/// Proof Skipped: PooledStaking CandidateCommission (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking LastCommissionChange (r:0 w:1)
+	/// Proof Skipped: PooledStaking LastCommissionChange (max_values: None, max_size: None, mode: Measured)
+	fn execute_commission_change() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `262`
+		//  Estimated: `3727`
+		// Minimum execution time: 17_604_000 picoseconds.
+		Weight::from_parts(18_311_000, 3727)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: PooledStaking PendingCommissionChanges (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingCommissionChanges (max_values: None, max_size: None, mode: Measured)
+	fn cancel_commission_change() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `262`
+		//  Estimated: `3727`
+		// Minimum execution time: 12_904_000 picoseconds.
+		Weight::from_parts(13_402_000, 3727)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: PooledStaking AutoExecutionOptIn (r:0 w:1)
+	/// Proof Skipped: PooledStaking AutoExecutionOptIn (max_values: None, max_size: None, mode: Measured)
+	fn set_auto_execution() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_112_000 picoseconds.
+		Weight::from_parts(8_461_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: PooledStaking LastRedelegationTo (r:1 w:1)
+	/// Proof Skipped: PooledStaking LastRedelegationTo (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking Pools (r:8 w:8)
+	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking SortedEligibleCandidates (r:1 w:1)
+	/// Proof Skipped: PooledStaking SortedEligibleCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: PooledStaking Redelegations (r:1 w:1)
+	/// Proof: PooledStaking Redelegations (max_values: None, max_size: Some(8502), added: 10977, mode: MaxEncodedLen)
+	fn redelegate(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `10430`
+		//  Estimated: `31290`
+		// The range of component `r` is `[0, 100]`.
+		// Minimum execution time: 158_102_000 picoseconds.
+		Weight::from_parts(165_337_000, 31290)
+			// Standard Error: 2_417
+			.saturating_add(Weight::from_parts(3_312_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2530).saturating_mul(r.into()))
+	}
 }
@@ -34,7 +34,9 @@ use {
     frame_system::RawOrigin,
     sp_core::Get,
     sp_std::{vec, vec::Vec},
-    tp_traits::{ParaId, RegistrarHandler, RelayStorageRootProvider, SlotFrequency},
+    tp_traits::{
+        CollatorCountRequest, ParaId, RegistrarHandler, RelayStorageRootProvider, SlotFrequency,
+    },
 };
 
 // !!! (Applicable for Dancelight only)
@@ -789,5 +791,47 @@ mod benchmarks {
         );
     }
 
+    #[benchmark]
+    fn set_collator_count_request() {
+        let para_id = 2010u32.into();
+
+        let origin = EnsureSignedByManager::<T>::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+        let request = CollatorCountRequest {
+            min: 1,
+            max: T::MaxRequestedCollators::get(),
+        };
+
+        // Before call: no request
+        assert!(crate::CollatorCountRequests::<T>::get(para_id).is_none());
+
+        #[extrinsic_call]
+        Pallet::<T>::set_collator_count_request(origin as T::RuntimeOrigin, para_id, Some(request));
+
+        // After call: request stored
+        assert_eq!(
+            crate::CollatorCountRequests::<T>::get(para_id),
+            Some(request)
+        );
+    }
+
+    #[benchmark]
+    fn set_core_count_request() {
+        let para_id = 2010u32.into();
+
+        let origin = EnsureSignedByManager::<T>::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+        let cores = T::MaxRequestedCores::get();
+
+        // Before call: no request
+        assert!(crate::CoreCountRequests::<T>::get(para_id).is_none());
+
+        #[extrinsic_call]
+        Pallet::<T>::set_core_count_request(origin as T::RuntimeOrigin, para_id, Some(cores));
+
+        // After call: request stored
+        assert_eq!(crate::CoreCountRequests::<T>::get(para_id), Some(cores));
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
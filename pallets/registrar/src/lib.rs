@@ -62,11 +62,14 @@ use {
         traits::{AtLeast32BitUnsigned, Verify},
         Saturating,
     },
-    sp_std::{collections::btree_set::BTreeSet, prelude::*},
+    sp_std::{
+        collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+        prelude::*,
+    },
     tp_traits::{
-        GetCurrentContainerChains, GetSessionContainerChains, GetSessionIndex, ParaId,
-        ParathreadParams as ParathreadParamsTy, RegistrarHandler, RelayStorageRootProvider,
-        SessionContainerChains, SlotFrequency,
+        CollatorCountRequest, GetCurrentContainerChains, GetSessionContainerChains,
+        GetSessionIndex, ParaId, ParathreadParams as ParathreadParamsTy, RegistrarHandler,
+        RelayStorageRootProvider, SessionContainerChains, SlotFrequency,
     },
 };
 
@@ -184,6 +187,14 @@ pub mod pallet {
         /// paras_registrar pallet.
         type InnerRegistrar: RegistrarHandler<Self::AccountId>;
 
+        /// Max number of collators that a para manager can request for a container chain
+        #[pallet::constant]
+        type MaxRequestedCollators: Get<u32>;
+
+        /// Max number of relay chain cores that a para manager can request for a parachain
+        #[pallet::constant]
+        type MaxRequestedCores: Get<u32>;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -244,6 +255,18 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Number of collators requested by the para manager. Para ids without an entry use the
+    /// default number of collators of the host configuration.
+    #[pallet::storage]
+    pub type CollatorCountRequests<T: Config> =
+        StorageMap<_, Blake2_128Concat, ParaId, CollatorCountRequest, OptionQuery>;
+
+    /// Number of relay chain cores requested by the para manager. Para ids without an entry use
+    /// 1 core.
+    #[pallet::storage]
+    pub type CoreCountRequests<T: Config> =
+        StorageMap<_, Blake2_128Concat, ParaId, u32, OptionQuery>;
+
     /// This storage aims to act as a 'buffer' for paraIds that must be deregistered at the
     /// end of the block execution by calling 'T::InnerRegistrar::deregister()' implementation.
     ///
@@ -307,6 +330,13 @@ pub mod pallet {
             para_id: ParaId,
             manager_address: T::AccountId,
         },
+        /// Requested number of collators changed. `None` means the default number of collators.
+        CollatorCountRequestChanged {
+            para_id: ParaId,
+            request: Option<CollatorCountRequest>,
+        },
+        /// Requested number of cores changed. `None` means 1 core.
+        CoreCountRequestChanged { para_id: ParaId, cores: Option<u32> },
     }
 
     #[pallet::error]
@@ -345,6 +375,13 @@ pub mod pallet {
         HeadDataNecessary,
         /// Tried to register a paraId in a relay context without specifying a wasm chain code.
         WasmCodeNecessary,
+        /// Attempted to execute an extrinsic meant only for the para manager
+        NotParaManager,
+        /// Requested collator count has a min of 0, a min greater than the max, or a max greater
+        /// than `MaxRequestedCollators`
+        InvalidCollatorCountRequest,
+        /// Requested core count is 0 or greater than `MaxRequestedCores`
+        InvalidCoreCountRequest,
     }
 
     #[pallet::composite_enum]
@@ -751,6 +788,86 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Request a custom number of collators for a container chain, or use the default number
+        /// of collators of the host configuration if `None`. Used from the next collator
+        /// assignment, extra collators are charged in pallet_services_payment.
+        /// Can only be called by the para manager or root.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::set_collator_count_request())]
+        pub fn set_collator_count_request(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            request: Option<CollatorCountRequest>,
+        ) -> DispatchResult {
+            // Allow root to force set collator count.
+            if let Some(origin) = ensure_signed_or_root(origin)? {
+                ensure!(
+                    Self::is_para_manager(&para_id, &origin),
+                    Error::<T>::NotParaManager
+                );
+            }
+
+            ensure!(
+                ParaGenesisData::<T>::contains_key(para_id),
+                Error::<T>::ParaIdNotRegistered
+            );
+
+            if let Some(request) = request {
+                ensure!(
+                    request.min > 0
+                        && request.min <= request.max
+                        && request.max <= T::MaxRequestedCollators::get(),
+                    Error::<T>::InvalidCollatorCountRequest
+                );
+                CollatorCountRequests::<T>::insert(para_id, request);
+            } else {
+                CollatorCountRequests::<T>::remove(para_id);
+            }
+
+            Self::deposit_event(Event::<T>::CollatorCountRequestChanged { para_id, request });
+
+            Ok(())
+        }
+
+        /// Request several relay chain cores for a parachain, so that it can include more than
+        /// one block per relay block (elastic scaling), or go back to 1 core if `None`. Used from
+        /// the next collator assignment, only if the relay chain has enough cores for parachains.
+        /// Ignored for parathreads. Can only be called by the para manager or root.
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::set_core_count_request())]
+        pub fn set_core_count_request(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            cores: Option<u32>,
+        ) -> DispatchResult {
+            // Allow root to force set core count.
+            if let Some(origin) = ensure_signed_or_root(origin)? {
+                ensure!(
+                    Self::is_para_manager(&para_id, &origin),
+                    Error::<T>::NotParaManager
+                );
+            }
+
+            ensure!(
+                ParaGenesisData::<T>::contains_key(para_id),
+                Error::<T>::ParaIdNotRegistered
+            );
+
+            if let Some(cores) = cores {
+                ensure!(
+                    cores > 0 && cores <= T::MaxRequestedCores::get(),
+                    Error::<T>::InvalidCoreCountRequest
+                );
+                CoreCountRequests::<T>::insert(para_id, cores);
+            } else {
+                CoreCountRequests::<T>::remove(para_id);
+            }
+
+            Self::deposit_event(Event::<T>::CoreCountRequestChanged { para_id, cores });
+
+            Ok(())
+        }
     }
 
     pub struct SessionChangeOutcome<T: Config> {
@@ -1308,6 +1425,8 @@ pub mod pallet {
         fn cleanup_deregistered_para_id(para_id: ParaId) {
             ParaGenesisData::<T>::remove(para_id);
             ParathreadParams::<T>::remove(para_id);
+            CollatorCountRequests::<T>::remove(para_id);
+            CoreCountRequests::<T>::remove(para_id);
             // Get asset creator and deposit amount
             // Deposit may not exist, for example if the para id was registered on genesis
             if let Some(asset_info) = RegistrarDeposit::<T>::take(para_id) {
@@ -1398,6 +1517,14 @@ pub mod pallet {
             PendingParathreadParams::<T>::get()
         }
 
+        pub fn collator_count_request(para_id: ParaId) -> Option<CollatorCountRequest> {
+            CollatorCountRequests::<T>::get(para_id)
+        }
+
+        pub fn core_count_request(para_id: ParaId) -> Option<u32> {
+            CoreCountRequests::<T>::get(para_id)
+        }
+
         pub fn registrar_deposit(para_id: ParaId) -> Option<DepositInfo<T>> {
             RegistrarDeposit::<T>::get(para_id)
         }
@@ -1432,6 +1559,8 @@ pub mod pallet {
 
             let mut parachains = vec![];
             let mut parathreads = vec![];
+            let mut collator_count_requests = BTreeMap::new();
+            let mut core_count_requests = BTreeMap::new();
 
             for para_id in paras {
                 // TODO: sweet O(n) db reads
@@ -1440,11 +1569,20 @@ pub mod pallet {
                 } else {
                     parachains.push(para_id);
                 }
+
+                if let Some(request) = CollatorCountRequests::<T>::get(para_id) {
+                    collator_count_requests.insert(para_id, request);
+                }
+                if let Some(cores) = CoreCountRequests::<T>::get(para_id) {
+                    core_count_requests.insert(para_id, cores);
+                }
             }
 
             SessionContainerChains {
                 parachains,
                 parathreads,
+                collator_count_requests,
+                core_count_requests,
             }
         }
 
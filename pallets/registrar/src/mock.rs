@@ -149,6 +149,8 @@ impl pallet_registrar::Config for Test {
     type RuntimeHoldReason = RuntimeHoldReason;
     type RegistrarHooks = Mock;
     type InnerRegistrar = Mock;
+    type MaxRequestedCollators = ConstU32<10>;
+    type MaxRequestedCores = ConstU32<4>;
     type WeightInfo = ();
 }
 
@@ -23,9 +23,9 @@ use {
         BoundedVec, Hashable,
     },
     parity_scale_codec::Encode,
-    sp_core::Pair,
+    sp_core::{Get, Pair},
     sp_runtime::DispatchError,
-    tp_traits::{ParaId, SlotFrequency},
+    tp_traits::{CollatorCountRequest, GetSessionContainerChains, ParaId, SlotFrequency},
 };
 
 #[test]
@@ -1441,6 +1441,234 @@ fn parathread_register_deregister_change_params() {
     });
 }
 
+#[test]
+fn para_manager_can_request_collator_count() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data(),
+            None
+        ));
+        assert_ok!(ParaRegistrar::mark_valid_for_collating(
+            RuntimeOrigin::root(),
+            42.into(),
+        ));
+
+        let request = CollatorCountRequest { min: 3, max: 5 };
+        assert_ok!(ParaRegistrar::set_collator_count_request(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            Some(request),
+        ));
+        assert_eq!(
+            ParaRegistrar::collator_count_request(ParaId::from(42)),
+            Some(request)
+        );
+        System::assert_last_event(
+            Event::CollatorCountRequestChanged {
+                para_id: 42.into(),
+                request: Some(request),
+            }
+            .into(),
+        );
+
+        // Request is used by collator assignment once the chain is registered
+        let container_chains = ParaRegistrar::session_container_chains(2);
+        assert_eq!(
+            container_chains
+                .collator_count_requests
+                .get(&ParaId::from(42)),
+            Some(&request)
+        );
+
+        // Going back to the default values
+        assert_ok!(ParaRegistrar::set_collator_count_request(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            None,
+        ));
+        assert_eq!(
+            ParaRegistrar::collator_count_request(ParaId::from(42)),
+            None
+        );
+    });
+}
+
+#[test]
+fn collator_count_request_only_by_para_manager_or_root() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data(),
+            None
+        ));
+
+        let request = CollatorCountRequest { min: 3, max: 5 };
+        assert_noop!(
+            ParaRegistrar::set_collator_count_request(
+                RuntimeOrigin::signed(BOB),
+                42.into(),
+                Some(request),
+            ),
+            Error::<Test>::NotParaManager
+        );
+        assert_ok!(ParaRegistrar::set_collator_count_request(
+            RuntimeOrigin::root(),
+            42.into(),
+            Some(request),
+        ));
+    });
+}
+
+#[test]
+fn collator_count_request_must_be_valid() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data(),
+            None
+        ));
+
+        for request in [
+            CollatorCountRequest { min: 0, max: 5 },
+            CollatorCountRequest { min: 4, max: 3 },
+            CollatorCountRequest {
+                min: 1,
+                max: <Test as crate::Config>::MaxRequestedCollators::get() + 1,
+            },
+        ] {
+            assert_noop!(
+                ParaRegistrar::set_collator_count_request(
+                    RuntimeOrigin::signed(ALICE),
+                    42.into(),
+                    Some(request),
+                ),
+                Error::<Test>::InvalidCollatorCountRequest
+            );
+        }
+
+        assert_noop!(
+            ParaRegistrar::set_collator_count_request(
+                RuntimeOrigin::root(),
+                43.into(),
+                Some(CollatorCountRequest { min: 1, max: 1 }),
+            ),
+            Error::<Test>::ParaIdNotRegistered
+        );
+    });
+}
+
+#[test]
+fn collator_count_request_removed_on_deregister() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data(),
+            None
+        ));
+        assert_ok!(ParaRegistrar::set_collator_count_request(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            Some(CollatorCountRequest { min: 1, max: 1 }),
+        ));
+
+        assert_ok!(ParaRegistrar::deregister(RuntimeOrigin::root(), 42.into()));
+        assert_eq!(
+            ParaRegistrar::collator_count_request(ParaId::from(42)),
+            None
+        );
+    });
+}
+
+#[test]
+fn para_manager_can_request_core_count() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data(),
+            None
+        ));
+        assert_ok!(ParaRegistrar::mark_valid_for_collating(
+            RuntimeOrigin::root(),
+            42.into(),
+        ));
+
+        assert_ok!(ParaRegistrar::set_core_count_request(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            Some(3),
+        ));
+        assert_eq!(ParaRegistrar::core_count_request(ParaId::from(42)), Some(3));
+        System::assert_last_event(
+            Event::CoreCountRequestChanged {
+                para_id: 42.into(),
+                cores: Some(3),
+            }
+            .into(),
+        );
+
+        let container_chains = ParaRegistrar::session_container_chains(2);
+        assert_eq!(
+            container_chains.core_count_requests.get(&ParaId::from(42)),
+            Some(&3)
+        );
+
+        // Going back to 1 core
+        assert_ok!(ParaRegistrar::set_core_count_request(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            None,
+        ));
+        assert_eq!(ParaRegistrar::core_count_request(ParaId::from(42)), None);
+    });
+}
+
+#[test]
+fn core_count_request_must_be_valid() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data(),
+            None
+        ));
+
+        assert_noop!(
+            ParaRegistrar::set_core_count_request(RuntimeOrigin::signed(BOB), 42.into(), Some(2),),
+            Error::<Test>::NotParaManager
+        );
+        for cores in [0, <Test as crate::Config>::MaxRequestedCores::get() + 1] {
+            assert_noop!(
+                ParaRegistrar::set_core_count_request(
+                    RuntimeOrigin::signed(ALICE),
+                    42.into(),
+                    Some(cores),
+                ),
+                Error::<Test>::InvalidCoreCountRequest
+            );
+        }
+
+        assert_ok!(ParaRegistrar::set_core_count_request(
+            RuntimeOrigin::root(),
+            42.into(),
+            Some(2),
+        ));
+        assert_ok!(ParaRegistrar::deregister(RuntimeOrigin::root(), 42.into()));
+        assert_eq!(ParaRegistrar::core_count_request(ParaId::from(42)), None);
+    });
+}
+
 mod register_with_relay_proof {
     use super::*;
 
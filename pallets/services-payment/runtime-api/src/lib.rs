@@ -18,13 +18,61 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use {
+    alloc::vec::Vec,
+    parity_scale_codec::{Decode, Encode},
+    serde::{Deserialize, Serialize},
+};
+
+/// Estimated number of blocks and sessions that a container chain can still pay for with its
+/// free credits and tank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo)]
+pub struct TankRunway {
+    /// Blocks that can be paid, if all the funds are spent on block production.
+    pub blocks: u32,
+    /// Sessions that can be paid, including their collator assignment and blocks.
+    pub sessions: u32,
+}
+
+/// Amounts spent by a container chain in a session.
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo, Serialize, Deserialize,
+)]
+pub struct SessionSpend<Balance> {
+    pub session: u32,
+    pub block_production: Balance,
+    pub collator_assignment: Balance,
+    pub tips: Balance,
+    /// Number of cores bought on the relay chain, their price is paid on the relay chain.
+    pub cores_purchased: u32,
+}
+
 sp_api::decl_runtime_apis! {
-    pub trait ServicesPaymentApi<Balance, ParaId>
+    #[api_version(4)]
+    pub trait ServicesPaymentApi<Balance, ParaId, AssetId>
     where
         Balance: parity_scale_codec::Codec,
         ParaId: parity_scale_codec::Codec,
+        AssetId: parity_scale_codec::Codec,
     {
         fn block_cost(para_id: ParaId) -> Balance;
         fn collator_assignment_cost(para_id: ParaId) -> Balance;
+        /// Cost of a block in the given asset.
+        /// Returns `None` if the asset cannot be used to pay for services.
+        #[api_version(2)]
+        fn block_cost_in_asset(para_id: ParaId, asset_id: AssetId) -> Option<Balance>;
+        /// Cost of a collator assignment in the given asset.
+        /// Returns `None` if the asset cannot be used to pay for services.
+        #[api_version(2)]
+        fn collator_assignment_cost_in_asset(para_id: ParaId, asset_id: AssetId) -> Option<Balance>;
+        /// Estimated blocks and sessions that the container chain can still pay for.
+        #[api_version(3)]
+        fn runway(para_id: ParaId) -> TankRunway;
+        /// Amounts spent by the container chain in the last sessions, oldest first.
+        /// Fees paid in a payment asset are reported at their price in the native currency.
+        #[api_version(4)]
+        fn spend_history(para_id: ParaId) -> Vec<SessionSpend<Balance>>;
     }
 }
@@ -19,16 +19,17 @@
 //! Benchmarking
 use {
     crate::{
-        AuthorNotingInfo, BalanceOf, BlockNumberFor, Call, Config, Pallet,
-        ProvideBlockProductionCost, ProvideCollatorAssignmentCost,
+        AuthorNotingInfo, AutoTopUpAuthorization, BalanceOf, BlockNumberFor, Call, Config, Pallet,
+        PaymentAssets, ProvideBlockProductionCost, ProvideCollatorAssignmentCost,
     },
+    cumulus_primitives_core::ParaId,
     frame_benchmarking::{account, v2::*},
     frame_support::{
         assert_ok,
         traits::{Currency, EnsureOriginWithArg, Get},
     },
     frame_system::RawOrigin,
-    sp_runtime::Saturating,
+    sp_runtime::{traits::Bounded, FixedU128, Perbill, Saturating},
     sp_std::prelude::*,
     tp_traits::{AuthorNotingHook, CollatorAssignmentHook},
 };
@@ -45,6 +46,20 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
 const SEED: u32 = 0;
 
+/// Worst case of a charge: the tank stays below its low balance threshold, so it is topped up
+fn set_worst_case_top_up<T: Config>(para_id: ParaId, payer: &T::AccountId) {
+    crate::LowBalanceThreshold::<T>::insert(para_id, BalanceOf::<T>::max_value());
+    crate::AutoTopUp::<T>::insert(
+        para_id,
+        AutoTopUpAuthorization {
+            payer: payer.clone(),
+            asset_id: crate::PaymentAsset::<T>::get(para_id),
+            amount: <T::Currency>::minimum_balance(),
+            remaining: BalanceOf::<T>::max_value(),
+        },
+    );
+}
+
 fn create_funded_user<T: Config>(
     string: &'static str,
     n: u32,
@@ -166,6 +181,7 @@ mod benchmarks {
     #[benchmark]
     fn on_container_authors_noted(n: Linear<1, 50>) {
         let mut infos = vec![];
+        let payer = create_funded_user::<T>("payer", 1, 1_000_000_000u32);
         for i in 0..n {
             let para_id = 1000u32 + i;
             let block_cost = T::ProvideBlockProductionCost::block_cost(&para_id.into()).0;
@@ -178,6 +194,7 @@ mod benchmarks {
                 para_id.into(),
                 balance_to_purchase + existential_deposit
             ));
+            set_worst_case_top_up::<T>(para_id.into(), &payer);
             infos.push(AuthorNotingInfo {
                 author: caller,
                 block_number: 1,
@@ -211,6 +228,8 @@ mod benchmarks {
             para_id.into(),
             Some(tip.into())
         ));
+        let payer = create_funded_user::<T>("payer", 1, 1_000_000_000u32);
+        set_worst_case_top_up::<T>(para_id.into(), &payer);
         #[block]
         {
             <Pallet<T> as CollatorAssignmentHook<BalanceOf<T>>>::on_collators_assigned(
@@ -234,5 +253,158 @@ mod benchmarks {
         assert!(crate::MaxTip::<T>::get(para_id).is_some());
     }
 
+    #[benchmark]
+    fn purchase_credits_in_asset() -> Result<(), BenchmarkError> {
+        let para_id = 1001u32.into();
+        let caller = create_funded_user::<T>("caller", 1, 1000);
+        let asset_id = T::PaymentAssets::bench_worst_case_asset_id();
+        let credit: BalanceOf<T> = 1_000_000u32.into();
+        T::PaymentAssets::bench_set_balance(&asset_id, &caller, credit.saturating_mul(2u32.into()));
+        // Runtimes without payment assets cannot run this benchmark
+        T::PaymentAssets::to_asset_balance(&asset_id, credit)
+            .map_err(|_| BenchmarkError::Weightless)?;
+        crate::PaymentAsset::<T>::insert(para_id, asset_id);
+
+        #[extrinsic_call]
+        Pallet::<T>::purchase_credits_in_asset(
+            RawOrigin::Signed(caller),
+            para_id,
+            asset_id,
+            credit,
+        );
+
+        assert_eq!(
+            T::PaymentAssets::balance(&asset_id, &Pallet::<T>::parachain_tank(para_id)),
+            credit
+        );
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_payment_asset() -> Result<(), BenchmarkError> {
+        let para_id = 1001u32.into();
+        let asset_id = T::PaymentAssets::bench_worst_case_asset_id();
+        let caller = create_funded_user::<T>("caller", 1, 1000);
+        T::PaymentAssets::bench_set_balance(&asset_id, &caller, 1_000_000u32.into());
+        // Runtimes without payment assets cannot run this benchmark
+        T::PaymentAssets::to_asset_balance(&asset_id, 1u32.into())
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let origin = T::ManagerOrigin::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+        // Worst case: the para already pays in an asset, so the tank balance is checked, and the
+        // top-up authorized in the native currency is removed
+        crate::PaymentAsset::<T>::insert(para_id, asset_id);
+        crate::AutoTopUp::<T>::insert(
+            para_id,
+            AutoTopUpAuthorization {
+                payer: caller,
+                asset_id: None,
+                amount: 1u32.into(),
+                remaining: 1u32.into(),
+            },
+        );
+
+        #[extrinsic_call]
+        Pallet::<T>::set_payment_asset(origin as T::RuntimeOrigin, para_id, Some(asset_id));
+
+        assert_eq!(crate::PaymentAsset::<T>::get(para_id), Some(asset_id));
+        assert!(crate::AutoTopUp::<T>::get(para_id).is_none());
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_low_balance_threshold() {
+        let para_id = 1001u32.into();
+        let origin = T::ManagerOrigin::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+
+        #[extrinsic_call]
+        Pallet::<T>::set_low_balance_threshold(
+            origin as T::RuntimeOrigin,
+            para_id,
+            Some(1_000_000u32.into()),
+        );
+
+        assert!(crate::LowBalanceThreshold::<T>::get(para_id).is_some());
+    }
+
+    #[benchmark]
+    fn set_auto_top_up() {
+        let para_id = 1001u32.into();
+        // Worst case: the manager replaces the account topping up the tank. Runtimes where the
+        // manager is not a signed account can only have it replaced by that account.
+        let previous_payer = create_funded_user::<T>("caller", 2, 1000);
+        let origin = T::ManagerOrigin::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+        let caller = frame_system::ensure_signed(origin).unwrap_or_else(|_| previous_payer.clone());
+        crate::AutoTopUp::<T>::insert(
+            para_id,
+            AutoTopUpAuthorization {
+                payer: previous_payer,
+                asset_id: None,
+                amount: 1u32.into(),
+                remaining: 1u32.into(),
+            },
+        );
+
+        #[extrinsic_call]
+        Pallet::<T>::set_auto_top_up(
+            RawOrigin::Signed(caller.clone()),
+            para_id,
+            Some(1_000_000u32.into()),
+            10_000_000u32.into(),
+        );
+
+        assert_eq!(
+            crate::AutoTopUp::<T>::get(para_id).map(|top_up| top_up.payer),
+            Some(caller)
+        );
+    }
+
+    #[benchmark]
+    fn set_price_multiplier_bounds() {
+        let bounds = (FixedU128::from_rational(1, 2), FixedU128::from_u32(2));
+
+        #[extrinsic_call]
+        Pallet::<T>::set_price_multiplier_bounds(RawOrigin::Root, Some(bounds));
+
+        assert_eq!(crate::PriceMultiplierBounds::<T>::get(), Some(bounds));
+    }
+
+    #[benchmark]
+    fn update_price_multiplier() {
+        crate::PriceMultiplierBounds::<T>::put((FixedU128::from_u32(1), FixedU128::from_u32(2)));
+
+        #[block]
+        {
+            Pallet::<T>::initializer_on_new_session(Perbill::one(), Perbill::from_percent(80));
+        }
+
+        assert!(crate::PriceMultiplier::<T>::get() > FixedU128::from_u32(1));
+    }
+
+    #[benchmark]
+    fn withdraw_from_tank() {
+        let para_id = 1001u32.into();
+        let origin = T::ManagerOrigin::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+        let refund_address = create_funded_user::<T>("refund", 1, 1000);
+        crate::RefundAddress::<T>::insert(para_id, refund_address.clone());
+        let tank_balance = T::Currency::minimum_balance().saturating_mul(1_000_000u32.into());
+        T::Currency::make_free_balance_be(&Pallet::<T>::parachain_tank(para_id), tank_balance);
+        let amount = T::Currency::minimum_balance();
+        let refund_balance = T::Currency::free_balance(&refund_address);
+
+        #[extrinsic_call]
+        Pallet::<T>::withdraw_from_tank(origin as T::RuntimeOrigin, para_id, amount);
+
+        assert_eq!(
+            T::Currency::free_balance(&refund_address),
+            refund_balance.saturating_add(amount)
+        );
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::benchmarks::new_test_ext(), crate::mock::Test);
 }
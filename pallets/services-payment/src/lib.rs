@@ -18,6 +18,27 @@
 //!
 //! This pallet allows for block creation services to be paid for by a
 //! containerChain.
+//!
+//! Services are paid from the parachain tank in the native currency by default. A para can instead
+//! choose to pay in any asset that has a conversion rate to the native currency, see
+//! [`PaymentAssets`]. Costs are always computed in the native currency and converted to the
+//! payment asset when charged. Collator assignment tips are always paid in the native currency.
+//!
+//! Managers can set a low balance threshold for the tank of their para. An event is emitted when a
+//! charge makes the tank drop below it, and the tank is topped up from a pre-authorized account if
+//! one was set with `set_auto_top_up`, up to the total amount authorized by that account. Changing
+//! the payment asset of the para removes the authorization.
+//!
+//! What each para spends is recorded per session for the last `SpendHistoryLength` sessions.
+//! Amounts are in the native currency, fees paid in the payment asset are recorded at their
+//! native price.
+//!
+//! Once governance sets bounds for it, a price multiplier is updated every session: it rises
+//! while the share of assigned collators is above the target container chain fullness and falls
+//! while it is below. Runtimes apply it to the costs they provide.
+//!
+//! Paras that are paused or have no collators assigned can withdraw from their tank to their
+//! refund address with `withdraw_from_tank`, keeping a reserve for the charges still in flight.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -27,18 +48,21 @@ use {
     cumulus_primitives_core::ParaId,
     frame_support::{
         pallet_prelude::*,
-        sp_runtime::{traits::Zero, Saturating},
+        sp_runtime::{traits::Zero, SaturatedConversion, Saturating},
+        storage::with_storage_layer,
         traits::{
-            tokens::ExistenceRequirement, Currency, EnsureOriginWithArg, OnUnbalanced,
-            WithdrawReasons,
+            tokens::{ExistenceRequirement, Preservation},
+            Contains, Currency, EnsureOriginWithArg, OnUnbalanced, WithdrawReasons,
         },
     },
     frame_system::pallet_prelude::*,
     scale_info::prelude::vec::Vec,
     serde::{Deserialize, Serialize},
     sp_io::hashing::blake2_256,
-    sp_runtime::{traits::TrailingZeroInput, DispatchError},
-    tp_traits::{AuthorNotingHook, CollatorAssignmentHook, CollatorAssignmentTip},
+    sp_runtime::{
+        traits::TrailingZeroInput, DispatchError, DispatchResult, FixedU128, Perbill, TokenError,
+    },
+    tp_traits::{AuthorNotingHook, CollatorAssignmentHook, CollatorAssignmentTip, GetSessionIndex},
 };
 
 #[cfg(any(test, feature = "runtime-benchmarks"))]
@@ -84,6 +108,28 @@ pub mod pallet {
         /// Owner of the container chain, can call some only-owner methods
         type ManagerOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, ParaId>;
 
+        /// Identifier of the assets that can be used to pay for services instead of `Currency`
+        type AssetId: Member + Parameter + MaxEncodedLen + Copy;
+        /// Assets that can be used to pay for services and their rates to `Currency`
+        type PaymentAssets: PaymentAssets<Self::AccountId, Self::AssetId, BalanceOf<Self>>;
+
+        /// Current session index, used to group the spend history
+        type CurrentSessionIndex: GetSessionIndex<u32>;
+        /// Number of sessions kept in the spend history of each para
+        #[pallet::constant]
+        type SpendHistoryLength: Get<u32>;
+        /// Max change of the price multiplier in a session, reached when all the collators are
+        /// assigned or all of them are idle
+        #[pallet::constant]
+        type PriceAdjustmentRate: Get<Perbill>;
+
+        /// Paras that are paused or have no collators assigned, and can withdraw from their tank
+        type InactiveParas: Contains<ParaId>;
+        /// Number of blocks that `withdraw_from_tank` keeps paid for in the tank, on top of a
+        /// collator assignment, to cover the charges still in flight
+        #[pallet::constant]
+        type TankReserveBlocks: Get<u32>;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -92,6 +138,23 @@ pub mod pallet {
         InsufficientFundsToPurchaseCredits,
         InsufficientCredits,
         CreditPriceTooExpensive,
+        /// The asset has no conversion rate to the native currency
+        UnsupportedPaymentAsset,
+        /// The auto top-up of this para is paid by another account, and the caller is not the
+        /// manager of the para
+        NotAutoTopUpPayer,
+        /// The min price multiplier is above the max
+        InvalidPriceMultiplierBounds,
+        /// The para is producing blocks or has collators assigned
+        ParaIsActive,
+        /// The para has no refund address to withdraw to
+        RefundAddressNotSet,
+        /// The tank does not have enough balance above its reserve
+        InsufficientTankBalance,
+        /// The asset is not the payment asset of the para
+        NotPaymentAsset,
+        /// The tank still holds the current payment asset of the para
+        PaymentAssetInTank,
     }
 
     #[pallet::pallet]
@@ -134,6 +197,59 @@ pub mod pallet {
             para_id: ParaId,
             credits: u32,
         },
+        CreditsPurchasedInAsset {
+            para_id: ParaId,
+            payer: T::AccountId,
+            asset_id: T::AssetId,
+            credit: BalanceOf<T>,
+        },
+        PaymentAssetUpdated {
+            para_id: ParaId,
+            asset_id: Option<T::AssetId>,
+        },
+        LowBalanceThresholdUpdated {
+            para_id: ParaId,
+            threshold: Option<BalanceOf<T>>,
+        },
+        AutoTopUpUpdated {
+            para_id: ParaId,
+            payer: T::AccountId,
+            amount: Option<BalanceOf<T>>,
+        },
+        /// A charge made the tank balance drop below the low balance threshold of the para.
+        /// Balances are in the currency the para pays in.
+        TankBalanceLow {
+            para_id: ParaId,
+            balance: BalanceOf<T>,
+            threshold: BalanceOf<T>,
+        },
+        TankToppedUp {
+            para_id: ParaId,
+            payer: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Burning a free credit made the value of the remaining credits of a service drop below
+        /// the low balance threshold of the para. Values are in the currency the para pays in.
+        FreeCreditsLow {
+            para_id: ParaId,
+            service: PaidService,
+            value: BalanceOf<T>,
+            threshold: BalanceOf<T>,
+        },
+        PriceMultiplierBoundsUpdated {
+            bounds: Option<(FixedU128, FixedU128)>,
+        },
+        /// Price multiplier applied to services in the new session.
+        PriceMultiplierUpdated {
+            multiplier: FixedU128,
+            collator_fullness: Perbill,
+        },
+        /// Funds withdrawn from the tank of an inactive para, in the currency the para pays in.
+        TankWithdrawn {
+            para_id: ParaId,
+            refund_address: T::AccountId,
+            amount: BalanceOf<T>,
+        },
     }
 
     #[pallet::storage]
@@ -153,6 +269,47 @@ pub mod pallet {
     pub type RefundAddress<T: Config> =
         StorageMap<_, Blake2_128Concat, ParaId, T::AccountId, OptionQuery>;
 
+    /// Tank balance, or value of the remaining free credits of a service, below which a low balance
+    /// event is emitted, in the currency the para pays in
+    #[pallet::storage]
+    pub type LowBalanceThreshold<T: Config> =
+        StorageMap<_, Blake2_128Concat, ParaId, BalanceOf<T>, OptionQuery>;
+
+    /// Account and amounts used to top up the tank when it drops below the low balance threshold
+    #[pallet::storage]
+    pub type AutoTopUp<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        ParaId,
+        AutoTopUpAuthorization<T::AccountId, T::AssetId, BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// Multiplier applied by the runtime to the cost of services, adjusted every session
+    #[pallet::storage]
+    pub type PriceMultiplier<T: Config> = StorageValue<_, FixedU128, ValueQuery, OneMultiplier>;
+
+    #[pallet::type_value]
+    pub fn OneMultiplier() -> FixedU128 {
+        FixedU128::from_u32(1)
+    }
+
+    /// Min and max price multiplier. The price multiplier is only adjusted if set
+    #[pallet::storage]
+    pub type PriceMultiplierBounds<T: Config> =
+        StorageValue<_, (FixedU128, FixedU128), OptionQuery>;
+
+    /// Amounts spent by each para in the sessions of the last `SpendHistoryLength` in which it
+    /// spent anything, oldest first
+    #[pallet::storage]
+    pub type SpendHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        ParaId,
+        BoundedVec<(u32, SpendRecord<BalanceOf<T>>), T::SpendHistoryLength>,
+        ValueQuery,
+    >;
+
     /// Max core price for parathread in relay chain currency
     #[pallet::storage]
     pub type MaxCorePrice<T: Config> = StorageMap<_, Blake2_128Concat, ParaId, u128, OptionQuery>;
@@ -161,6 +318,11 @@ pub mod pallet {
     #[pallet::storage]
     pub type MaxTip<T: Config> = StorageMap<_, Blake2_128Concat, ParaId, BalanceOf<T>, OptionQuery>;
 
+    /// Asset used to pay for services instead of the native currency
+    #[pallet::storage]
+    pub type PaymentAsset<T: Config> =
+        StorageMap<_, Blake2_128Concat, ParaId, T::AssetId, OptionQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T>
     where
@@ -310,6 +472,234 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Purchase credits for this para_id in an asset other than the native currency. The
+        /// asset must be the payment asset of the para, so that the tank never holds an asset
+        /// that is not charged or refunded.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::purchase_credits_in_asset())]
+        pub fn purchase_credits_in_asset(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            asset_id: T::AssetId,
+            credit: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let account = ensure_signed(origin)?;
+            ensure!(
+                PaymentAsset::<T>::get(para_id).as_ref() == Some(&asset_id),
+                Error::<T>::NotPaymentAsset
+            );
+            Self::ensure_supported_payment_asset(&asset_id)?;
+
+            let parachain_tank = Self::parachain_tank(para_id);
+            T::PaymentAssets::transfer(
+                &asset_id,
+                &account,
+                &parachain_tank,
+                credit,
+                Preservation::Preserve,
+            )?;
+
+            Self::deposit_event(Event::<T>::CreditsPurchasedInAsset {
+                para_id,
+                payer: account,
+                asset_id,
+                credit,
+            });
+
+            Ok(().into())
+        }
+
+        /// Set the asset used to pay for the services of this para_id, or `None` to pay in the
+        /// native currency. The tank must not hold any of the current payment asset, as it
+        /// could no longer be charged or refunded. Can only be called by container chain manager.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::set_payment_asset())]
+        pub fn set_payment_asset(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            asset_id: Option<T::AssetId>,
+        ) -> DispatchResultWithPostInfo {
+            T::ManagerOrigin::ensure_origin(origin, &para_id)?;
+
+            if let Some(current_asset_id) = PaymentAsset::<T>::get(para_id) {
+                ensure!(
+                    T::PaymentAssets::balance(&current_asset_id, &Self::parachain_tank(para_id))
+                        .is_zero()
+                        || asset_id.as_ref() == Some(&current_asset_id),
+                    Error::<T>::PaymentAssetInTank
+                );
+            }
+
+            if let Some(asset_id) = asset_id {
+                Self::ensure_supported_payment_asset(&asset_id)?;
+                PaymentAsset::<T>::insert(para_id, asset_id);
+            } else {
+                PaymentAsset::<T>::remove(para_id);
+            }
+
+            // Top-ups were authorized in the previous payment asset
+            if let Some(top_up) = AutoTopUp::<T>::get(para_id) {
+                if top_up.asset_id != asset_id {
+                    AutoTopUp::<T>::remove(para_id);
+                    Self::deposit_event(Event::<T>::AutoTopUpUpdated {
+                        para_id,
+                        payer: top_up.payer,
+                        amount: None,
+                    });
+                }
+            }
+
+            Self::deposit_event(Event::<T>::PaymentAssetUpdated { para_id, asset_id });
+
+            Ok(().into())
+        }
+
+        /// Set the tank balance below which a low balance event is emitted, in the currency the
+        /// para pays in. Can only be called by container chain manager.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::set_low_balance_threshold())]
+        pub fn set_low_balance_threshold(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            threshold: Option<BalanceOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            T::ManagerOrigin::ensure_origin(origin, &para_id)?;
+
+            if let Some(threshold) = threshold {
+                LowBalanceThreshold::<T>::insert(para_id, threshold);
+            } else {
+                LowBalanceThreshold::<T>::remove(para_id);
+            }
+
+            Self::deposit_event(Event::<T>::LowBalanceThresholdUpdated { para_id, threshold });
+
+            Ok(().into())
+        }
+
+        /// Authorize topping up the tank of this para_id with `amount` from the caller whenever
+        /// it is below the low balance threshold, or `None` to stop it. The caller pays at most
+        /// `max_total` in total, in the current payment asset of the para. Changing the payment
+        /// asset stops the top-ups. Only the account topping up the tank or the manager of the
+        /// para can replace or stop an existing top-up.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::set_auto_top_up())]
+        pub fn set_auto_top_up(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            amount: Option<BalanceOf<T>>,
+            max_total: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let account = ensure_signed(origin.clone())?;
+
+            if let Some(top_up) = AutoTopUp::<T>::get(para_id) {
+                ensure!(
+                    top_up.payer == account
+                        || T::ManagerOrigin::ensure_origin(origin, &para_id).is_ok(),
+                    Error::<T>::NotAutoTopUpPayer
+                );
+            }
+
+            if let Some(amount) = amount {
+                AutoTopUp::<T>::insert(
+                    para_id,
+                    AutoTopUpAuthorization {
+                        payer: account.clone(),
+                        asset_id: PaymentAsset::<T>::get(para_id),
+                        amount,
+                        remaining: max_total,
+                    },
+                );
+            } else {
+                AutoTopUp::<T>::remove(para_id);
+            }
+
+            Self::deposit_event(Event::<T>::AutoTopUpUpdated {
+                para_id,
+                payer: account,
+                amount,
+            });
+
+            Ok(().into())
+        }
+
+        /// Set the bounds of the price multiplier, enabling congestion pricing.
+        /// Removing them resets the price multiplier to one.
+        /// Can only be called by root.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::set_price_multiplier_bounds())]
+        pub fn set_price_multiplier_bounds(
+            origin: OriginFor<T>,
+            bounds: Option<(FixedU128, FixedU128)>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            if let Some((min, max)) = bounds {
+                ensure!(min <= max, Error::<T>::InvalidPriceMultiplierBounds);
+                PriceMultiplierBounds::<T>::put((min, max));
+                PriceMultiplier::<T>::mutate(|multiplier| {
+                    *multiplier = (*multiplier).clamp(min, max)
+                });
+            } else {
+                PriceMultiplierBounds::<T>::kill();
+                PriceMultiplier::<T>::kill();
+            }
+
+            Self::deposit_event(Event::<T>::PriceMultiplierBoundsUpdated { bounds });
+
+            Ok(().into())
+        }
+
+        /// Withdraw `amount` from the tank of a para that is paused or has no collators assigned
+        /// to its refund address, in the currency the para pays in. The tank keeps the reserve
+        /// returned by `tank_reserve`.
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::withdraw_from_tank())]
+        pub fn withdraw_from_tank(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            T::ManagerOrigin::ensure_origin(origin, &para_id)?;
+
+            ensure!(
+                T::InactiveParas::contains(&para_id),
+                Error::<T>::ParaIsActive
+            );
+            let refund_address =
+                RefundAddress::<T>::get(para_id).ok_or(Error::<T>::RefundAddressNotSet)?;
+            let reserve = Self::tank_reserve(&para_id)?;
+            ensure!(
+                amount.saturating_add(reserve) <= Self::tank_balance(&para_id),
+                Error::<T>::InsufficientTankBalance
+            );
+
+            let parachain_tank = Self::parachain_tank(para_id);
+            if let Some(asset_id) = PaymentAsset::<T>::get(para_id) {
+                T::PaymentAssets::transfer(
+                    &asset_id,
+                    &parachain_tank,
+                    &refund_address,
+                    amount,
+                    Preservation::Preserve,
+                )?;
+            } else {
+                T::Currency::transfer(
+                    &parachain_tank,
+                    &refund_address,
+                    amount,
+                    ExistenceRequirement::KeepAlive,
+                )?;
+            }
+
+            Self::deposit_event(Event::<T>::TankWithdrawn {
+                para_id,
+                refund_address,
+                amount,
+            });
+
+            Ok(().into())
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -332,6 +722,11 @@ pub mod pallet {
                 para_id: *para_id,
                 credits_remaining: updated_credits,
             });
+            Self::check_low_credits(
+                para_id,
+                PaidService::BlockProduction,
+                updated_credits.saturated_into(),
+            );
 
             Ok(().into())
         }
@@ -351,6 +746,11 @@ pub mod pallet {
                 para_id: *para_id,
                 credits_remaining: updated_credits,
             });
+            Self::check_low_credits(
+                para_id,
+                PaidService::CollatorAssignment,
+                updated_credits.into(),
+            );
 
             Ok(().into())
         }
@@ -425,27 +825,389 @@ pub mod pallet {
             });
         }
 
+        /// Charge the clearing price of the tip auction to a para assigned collators on congestion.
+        /// Only the clearing price is withdrawn from the parachain tank, so a para never pays more
+        /// than the lowest winning tip.
         pub fn charge_tip(para_id: &ParaId, tip: &BalanceOf<T>) -> Result<(), DispatchError> {
             // Only charge the tip to the paras that had a max tip set
             // (aka were willing to tip for being assigned a collator)
-            if MaxTip::<T>::get(para_id).is_some() {
+            if let Some(max_tip) = MaxTip::<T>::get(para_id) {
+                let parachain_tank = Self::parachain_tank(*para_id);
+                let balance_before = Self::tank_balance(para_id);
+                let clearing_price = (*tip).min(max_tip);
                 let tip_imbalance = T::Currency::withdraw(
-                    &Self::parachain_tank(*para_id),
-                    *tip,
+                    &parachain_tank,
+                    clearing_price,
                     WithdrawReasons::TIP,
                     ExistenceRequirement::KeepAlive,
                 )?;
 
+                Self::record_spend(para_id, |spend| {
+                    spend.tips = spend.tips.saturating_add(clearing_price)
+                });
+
                 Self::deposit_event(Event::<T>::CollatorAssignmentTipCollected {
                     para_id: *para_id,
-                    payer: Self::parachain_tank(*para_id),
-                    tip: *tip,
+                    payer: parachain_tank,
+                    tip: clearing_price,
                 });
                 T::OnChargeForCollatorAssignmentTip::on_unbalanced(tip_imbalance);
+                // Tips are paid in the native currency, the payment asset balance does not change
+                if !PaymentAsset::<T>::contains_key(para_id) {
+                    Self::check_low_balance(para_id, balance_before);
+                }
             }
             Ok(())
         }
 
+        /// Charge a service fee of `amount` native currency to the tank of `para_id`.
+        /// Paras with a payment asset pay the converted amount in that asset, and `None` is
+        /// returned. Otherwise the withdrawn native currency is returned to be handled by the
+        /// caller.
+        pub fn charge_fee(
+            para_id: &ParaId,
+            amount: BalanceOf<T>,
+            service: PaidService,
+        ) -> Result<Option<NegativeImbalanceOf<T>>, DispatchError> {
+            let parachain_tank = Self::parachain_tank(*para_id);
+            let balance_before = Self::tank_balance(para_id);
+
+            let maybe_imbalance = if let Some(asset_id) = PaymentAsset::<T>::get(para_id) {
+                let asset_amount = T::PaymentAssets::to_asset_balance(&asset_id, amount)?;
+                T::PaymentAssets::withdraw_fee(&asset_id, &parachain_tank, asset_amount)?;
+
+                None
+            } else {
+                Some(T::Currency::withdraw(
+                    &parachain_tank,
+                    amount,
+                    WithdrawReasons::FEE,
+                    ExistenceRequirement::KeepAlive,
+                )?)
+            };
+
+            Self::record_spend(para_id, |spend| match service {
+                PaidService::BlockProduction => {
+                    spend.block_production = spend.block_production.saturating_add(amount)
+                }
+                PaidService::CollatorAssignment => {
+                    spend.collator_assignment = spend.collator_assignment.saturating_add(amount)
+                }
+            });
+            Self::check_low_balance(para_id, balance_before);
+
+            Ok(maybe_imbalance)
+        }
+
+        /// Record a core bought on the relay chain for `para_id` in its spend history.
+        pub fn record_core_purchase(para_id: &ParaId) {
+            Self::record_spend(para_id, |spend| {
+                spend.cores_purchased = spend.cores_purchased.saturating_add(1)
+            });
+        }
+
+        /// Add a spend of `para_id` to the current session of its history, forgetting the sessions
+        /// that are too old.
+        fn record_spend(para_id: &ParaId, record: impl FnOnce(&mut SpendRecord<BalanceOf<T>>)) {
+            let session_index = T::CurrentSessionIndex::session_index();
+            let history_length = T::SpendHistoryLength::get();
+
+            SpendHistory::<T>::mutate(para_id, |history| {
+                history
+                    .retain(|(session, _)| session.saturating_add(history_length) > session_index);
+
+                match history.last_mut() {
+                    Some((session, spend)) if *session == session_index => record(spend),
+                    _ => {
+                        let mut spend = SpendRecord::default();
+                        record(&mut spend);
+                        // Only fails if the history is disabled, older sessions were removed above
+                        let _ = history.try_push((session_index, spend));
+                    }
+                }
+            });
+        }
+
+        /// Adjust the price multiplier to the share of collators assigned in the last session.
+        /// Must be called before the collator assignment of the new session charges the paras.
+        ///
+        /// The multiplier changes by `PriceAdjustmentRate` scaled by how far `collator_fullness` is
+        /// from `target_fullness`, relative to the distance between the target and a full or idle
+        /// network, and stays within `PriceMultiplierBounds`.
+        pub fn initializer_on_new_session(collator_fullness: Perbill, target_fullness: Perbill) {
+            let Some((min, max)) = PriceMultiplierBounds::<T>::get() else {
+                frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                    T::DbWeight::get().reads(1),
+                    DispatchClass::Mandatory,
+                );
+                return;
+            };
+            frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                T::WeightInfo::update_price_multiplier(),
+                DispatchClass::Mandatory,
+            );
+
+            let rate = T::PriceAdjustmentRate::get();
+            let multiplier = PriceMultiplier::<T>::get();
+            let new_multiplier = if collator_fullness > target_fullness {
+                let congestion = Perbill::from_rational(
+                    collator_fullness
+                        .saturating_sub(target_fullness)
+                        .deconstruct(),
+                    Perbill::one().saturating_sub(target_fullness).deconstruct(),
+                );
+                multiplier.saturating_add(multiplier.saturating_mul((rate * congestion).into()))
+            } else if collator_fullness < target_fullness {
+                let idleness = Perbill::from_rational(
+                    target_fullness
+                        .saturating_sub(collator_fullness)
+                        .deconstruct(),
+                    target_fullness.deconstruct(),
+                );
+                multiplier.saturating_sub(multiplier.saturating_mul((rate * idleness).into()))
+            } else {
+                multiplier
+            }
+            .clamp(min, max);
+
+            PriceMultiplier::<T>::put(new_multiplier);
+
+            Self::deposit_event(Event::<T>::PriceMultiplierUpdated {
+                multiplier: new_multiplier,
+                collator_fullness,
+            });
+        }
+
+        pub fn price_multiplier() -> FixedU128 {
+            PriceMultiplier::<T>::get()
+        }
+
+        /// Balance of the tank of `para_id` that can be spent on services, in the currency the
+        /// para pays in.
+        pub fn tank_balance(para_id: &ParaId) -> BalanceOf<T> {
+            let parachain_tank = Self::parachain_tank(*para_id);
+
+            if let Some(asset_id) = PaymentAsset::<T>::get(para_id) {
+                T::PaymentAssets::reducible_balance(&asset_id, &parachain_tank)
+            } else {
+                T::Currency::free_balance(&parachain_tank)
+                    .saturating_sub(T::Currency::minimum_balance())
+            }
+        }
+
+        /// Balance that `withdraw_from_tank` keeps in the tank of `para_id`, enough to pay for a
+        /// collator assignment and `TankReserveBlocks` blocks, in the currency the para pays in.
+        pub fn tank_reserve(para_id: &ParaId) -> Result<BalanceOf<T>, DispatchError> {
+            let (block_cost, _weight) = T::ProvideBlockProductionCost::block_cost(para_id);
+            let (assignment_cost, _weight) =
+                T::ProvideCollatorAssignmentCost::collator_assignment_cost(para_id);
+            let reserve = block_cost
+                .saturating_mul(T::TankReserveBlocks::get().into())
+                .saturating_add(assignment_cost);
+
+            match PaymentAsset::<T>::get(para_id) {
+                Some(asset_id) => T::PaymentAssets::to_asset_balance(&asset_id, reserve),
+                None => Ok(reserve),
+            }
+        }
+
+        /// Emit a low balance event if the tank of `para_id` dropped below its threshold since
+        /// it had `balance_before`, and top it up if it is below the threshold and an account
+        /// authorized it.
+        fn check_low_balance(para_id: &ParaId, balance_before: BalanceOf<T>) {
+            let Some(threshold) = LowBalanceThreshold::<T>::get(para_id) else {
+                return;
+            };
+            let balance = Self::tank_balance(para_id);
+            if balance >= threshold {
+                return;
+            }
+
+            if balance_before >= threshold {
+                Self::deposit_event(Event::<T>::TankBalanceLow {
+                    para_id: *para_id,
+                    balance,
+                    threshold,
+                });
+            }
+
+            let Some(mut top_up) = AutoTopUp::<T>::get(para_id) else {
+                return;
+            };
+            // The payment asset cannot change without removing the top-up, this is a sanity check
+            if top_up.asset_id != PaymentAsset::<T>::get(para_id) {
+                return;
+            }
+            let amount = top_up.amount.min(top_up.remaining);
+            if amount.is_zero() {
+                return;
+            }
+            let payer = top_up.payer.clone();
+            let parachain_tank = Self::parachain_tank(*para_id);
+            let result = if let Some(asset_id) = top_up.asset_id {
+                T::PaymentAssets::transfer(
+                    &asset_id,
+                    &payer,
+                    &parachain_tank,
+                    amount,
+                    Preservation::Preserve,
+                )
+            } else {
+                T::Currency::transfer(
+                    &payer,
+                    &parachain_tank,
+                    amount,
+                    ExistenceRequirement::KeepAlive,
+                )
+            };
+
+            match result {
+                Ok(()) => {
+                    top_up.remaining = top_up.remaining.saturating_sub(amount);
+                    AutoTopUp::<T>::insert(para_id, top_up);
+                    Self::deposit_event(Event::<T>::TankToppedUp {
+                        para_id: *para_id,
+                        payer,
+                        amount,
+                    });
+                }
+                Err(e) => log::warn!(
+                    "Failed to top up the tank of container chain {}: {:?}",
+                    u32::from(*para_id),
+                    e
+                ),
+            }
+        }
+
+        /// Emit a low credits event if burning a free credit of `service` made the value of the
+        /// `credits_remaining` of `para_id` drop below its threshold.
+        fn check_low_credits(para_id: &ParaId, service: PaidService, credits_remaining: u128) {
+            let Some(threshold) = LowBalanceThreshold::<T>::get(para_id) else {
+                return;
+            };
+            let (cost, _weight) = match service {
+                PaidService::BlockProduction => T::ProvideBlockProductionCost::block_cost(para_id),
+                PaidService::CollatorAssignment => {
+                    T::ProvideCollatorAssignmentCost::collator_assignment_cost(para_id)
+                }
+            };
+            let cost = match PaymentAsset::<T>::get(para_id) {
+                Some(asset_id) => match T::PaymentAssets::to_asset_balance(&asset_id, cost) {
+                    Ok(cost) => cost,
+                    Err(_) => return,
+                },
+                None => cost,
+            };
+
+            let value = cost.saturating_mul(credits_remaining.saturated_into());
+            // The credit that was just burned was worth `cost`
+            if value >= threshold || value.saturating_add(cost) < threshold {
+                return;
+            }
+
+            Self::deposit_event(Event::<T>::FreeCreditsLow {
+                para_id: *para_id,
+                service,
+                value,
+                threshold,
+            });
+        }
+
+        /// Estimate how many blocks and sessions of `para_id` can still be paid with its free
+        /// credits and tank, if every session has `blocks_per_session` blocks.
+        pub fn runway(para_id: &ParaId, blocks_per_session: u32) -> Runway<BlockNumberFor<T>> {
+            let free_blocks: u128 = BlockProductionCredits::<T>::get(para_id)
+                .unwrap_or_default()
+                .saturated_into();
+            let free_sessions: u128 = CollatorAssignmentCredits::<T>::get(para_id)
+                .unwrap_or_default()
+                .into();
+            let (block_cost, _weight) = T::ProvideBlockProductionCost::block_cost(para_id);
+            let (assignment_cost, _weight) =
+                T::ProvideCollatorAssignmentCost::collator_assignment_cost(para_id);
+
+            // Costs in the currency the para pays in, the tank is useless if they cannot be converted
+            let (block_cost, assignment_cost, funds) = match PaymentAsset::<T>::get(para_id) {
+                Some(asset_id) => match (
+                    T::PaymentAssets::to_asset_balance(&asset_id, block_cost),
+                    T::PaymentAssets::to_asset_balance(&asset_id, assignment_cost),
+                ) {
+                    (Ok(block_cost), Ok(assignment_cost)) => {
+                        (block_cost, assignment_cost, Self::tank_balance(para_id))
+                    }
+                    _ => (block_cost, assignment_cost, Zero::zero()),
+                },
+                None => (block_cost, assignment_cost, Self::tank_balance(para_id)),
+            };
+            let block_cost: u128 = block_cost.saturated_into();
+            let assignment_cost: u128 = assignment_cost.saturated_into();
+            let funds: u128 = funds.saturated_into();
+
+            let blocks =
+                free_blocks.saturating_add(funds.checked_div(block_cost).unwrap_or(u128::MAX));
+            let session_cost = assignment_cost
+                .saturating_add(block_cost.saturating_mul(blocks_per_session.into()));
+            let total_funds = funds
+                .saturating_add(free_blocks.saturating_mul(block_cost))
+                .saturating_add(free_sessions.saturating_mul(assignment_cost));
+            let sessions = if session_cost.is_zero() {
+                u128::MAX
+            } else {
+                total_funds / session_cost
+            };
+
+            Runway {
+                blocks: blocks.saturated_into(),
+                sessions: sessions.saturated_into(),
+            }
+        }
+
+        /// Ensure `who` holds enough of `asset_id` to pay for `amount` native currency, keeping
+        /// the account alive.
+        pub fn ensure_can_pay_in_asset(
+            asset_id: &T::AssetId,
+            who: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let asset_amount = T::PaymentAssets::to_asset_balance(asset_id, amount)?;
+            ensure!(
+                T::PaymentAssets::reducible_balance(asset_id, who) >= asset_amount,
+                TokenError::FundsUnavailable
+            );
+
+            Ok(())
+        }
+
+        /// Cost of a block of `para_id` in `asset_id`, or `None` if the asset cannot be used to
+        /// pay for services.
+        pub fn block_cost_in_asset(
+            para_id: &ParaId,
+            asset_id: &T::AssetId,
+        ) -> Option<BalanceOf<T>> {
+            let (block_cost, _weight) = T::ProvideBlockProductionCost::block_cost(para_id);
+
+            T::PaymentAssets::to_asset_balance(asset_id, block_cost).ok()
+        }
+
+        /// Cost of a collator assignment of `para_id` in `asset_id`, or `None` if the asset
+        /// cannot be used to pay for services.
+        pub fn collator_assignment_cost_in_asset(
+            para_id: &ParaId,
+            asset_id: &T::AssetId,
+        ) -> Option<BalanceOf<T>> {
+            let (collator_assignment_cost, _weight) =
+                T::ProvideCollatorAssignmentCost::collator_assignment_cost(para_id);
+
+            T::PaymentAssets::to_asset_balance(asset_id, collator_assignment_cost).ok()
+        }
+
+        fn ensure_supported_payment_asset(asset_id: &T::AssetId) -> DispatchResult {
+            T::PaymentAssets::to_asset_balance(asset_id, 1u32.into())
+                .map_err(|_| Error::<T>::UnsupportedPaymentAsset)?;
+
+            Ok(())
+        }
+
         pub fn free_block_production_credits(para_id: ParaId) -> Option<BlockNumberFor<T>> {
             BlockProductionCredits::<T>::get(para_id)
         }
@@ -465,6 +1227,18 @@ pub mod pallet {
         pub fn max_tip(para_id: ParaId) -> Option<BalanceOf<T>> {
             MaxTip::<T>::get(para_id)
         }
+
+        pub fn payment_asset(para_id: ParaId) -> Option<T::AssetId> {
+            PaymentAsset::<T>::get(para_id)
+        }
+
+        pub fn low_balance_threshold(para_id: ParaId) -> Option<BalanceOf<T>> {
+            LowBalanceThreshold::<T>::get(para_id)
+        }
+
+        pub fn spend_history(para_id: ParaId) -> Vec<(u32, SpendRecord<BalanceOf<T>>)> {
+            SpendHistory::<T>::get(para_id).into_inner()
+        }
     }
 
     #[pallet::genesis_config]
@@ -497,6 +1271,46 @@ pub mod pallet {
     }
 }
 
+/// Authorization to top up the tank of a para from the account of `payer`
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AutoTopUpAuthorization<AccountId, AssetId, Balance> {
+    pub payer: AccountId,
+    /// Payment asset of the para when the top-up was authorized, `None` for the native currency
+    pub asset_id: Option<AssetId>,
+    /// Amount of each top-up
+    pub amount: Balance,
+    /// Amount that can still be topped up in total
+    pub remaining: Balance,
+}
+
+/// Service paid from the tank of a para
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum PaidService {
+    BlockProduction,
+    CollatorAssignment,
+}
+
+/// Amounts spent by a para in a session, in the native currency
+#[derive(
+    Default, Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct SpendRecord<Balance> {
+    pub block_production: Balance,
+    pub collator_assignment: Balance,
+    pub tips: Balance,
+    /// Cores are paid on the relay chain, so only the number of cores bought is known
+    pub cores_purchased: u32,
+}
+
+/// Estimated number of blocks and sessions that a para can still pay for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Runway<BlockNumber> {
+    /// Blocks that can be paid, if all the funds are spent on block production
+    pub blocks: BlockNumber,
+    /// Sessions that can be paid, including their collator assignment and blocks
+    pub sessions: u32,
+}
+
 // Params to be set in genesis
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
 pub struct FreeCreditGenesisParams<BlockProductCredits> {
@@ -539,6 +1353,77 @@ pub trait ProvideCollatorAssignmentCost<T: Config> {
     fn collator_assignment_cost(para_id: &ParaId) -> (BalanceOf<T>, Weight);
 }
 
+/// Assets other than the native currency that can be used to pay for services.
+pub trait PaymentAssets<AccountId, AssetId, Balance> {
+    /// Convert an amount of native currency into the equivalent amount of `asset_id`.
+    /// Errors if the asset has no conversion rate.
+    fn to_asset_balance(asset_id: &AssetId, amount: Balance) -> Result<Balance, DispatchError>;
+
+    /// Transfer `amount` of `asset_id` from `from` to `to`.
+    fn transfer(
+        asset_id: &AssetId,
+        from: &AccountId,
+        to: &AccountId,
+        amount: Balance,
+        preservation: Preservation,
+    ) -> DispatchResult;
+
+    /// Withdraw `amount` of `asset_id` from `who` as a fee for services, keeping the account
+    /// alive. What happens to the fee is up to the implementation.
+    fn withdraw_fee(asset_id: &AssetId, who: &AccountId, amount: Balance) -> DispatchResult;
+
+    /// Amount of `asset_id` that `who` can spend while keeping the account alive.
+    fn reducible_balance(asset_id: &AssetId, who: &AccountId) -> Balance;
+
+    /// Amount of `asset_id` held by `who`.
+    fn balance(asset_id: &AssetId, who: &AccountId) -> Balance;
+
+    /// Return an asset that can be used to pay for services, for benchmarking.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_worst_case_asset_id() -> AssetId;
+
+    /// Set the balance of `account` in `asset_id`, creating the asset and its rate if needed.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_set_balance(asset_id: &AssetId, account: &AccountId, amount: Balance);
+}
+
+/// No asset can be used to pay for services, only the native currency.
+impl<AccountId, AssetId: Default, Balance: Zero> PaymentAssets<AccountId, AssetId, Balance> for () {
+    fn to_asset_balance(_asset_id: &AssetId, _amount: Balance) -> Result<Balance, DispatchError> {
+        Err(TokenError::UnknownAsset.into())
+    }
+
+    fn transfer(
+        _asset_id: &AssetId,
+        _from: &AccountId,
+        _to: &AccountId,
+        _amount: Balance,
+        _preservation: Preservation,
+    ) -> DispatchResult {
+        Err(TokenError::UnknownAsset.into())
+    }
+
+    fn withdraw_fee(_asset_id: &AssetId, _who: &AccountId, _amount: Balance) -> DispatchResult {
+        Err(TokenError::UnknownAsset.into())
+    }
+
+    fn reducible_balance(_asset_id: &AssetId, _who: &AccountId) -> Balance {
+        Zero::zero()
+    }
+
+    fn balance(_asset_id: &AssetId, _who: &AccountId) -> Balance {
+        Zero::zero()
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_worst_case_asset_id() -> AssetId {
+        Default::default()
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_set_balance(_asset_id: &AssetId, _account: &AccountId, _amount: Balance) {}
+}
+
 impl<T: Config> AuthorNotingHook<T::AccountId> for Pallet<T> {
     // This hook is called when pallet_author_noting sees that the block number of a container chain has increased.
     // Currently we always charge 1 credit, even if a container chain produced more that 1 block in between tanssi
@@ -550,20 +1435,17 @@ impl<T: Config> AuthorNotingHook<T::AccountId> for Pallet<T> {
                 let (amount_to_charge, _weight) =
                     T::ProvideBlockProductionCost::block_cost(&para_id);
 
-                match T::Currency::withdraw(
-                    &Self::parachain_tank(para_id),
-                    amount_to_charge,
-                    WithdrawReasons::FEE,
-                    ExistenceRequirement::KeepAlive,
-                ) {
+                match Self::charge_fee(&para_id, amount_to_charge, PaidService::BlockProduction) {
                     Err(e) => log::warn!(
                         "Failed to withdraw block production payment for container chain {}: {:?}",
                         u32::from(para_id),
                         e
                     ),
-                    Ok(imbalance) => {
+                    Ok(Some(imbalance)) => {
                         T::OnChargeForBlock::on_unbalanced(imbalance);
                     }
+                    // Paid in the payment asset of the para
+                    Ok(None) => {}
                 }
             }
         }
@@ -593,58 +1475,37 @@ impl<T: Config> CollatorAssignmentHook<BalanceOf<T>> for Pallet<T> {
         maybe_tip: Option<&BalanceOf<T>>,
         _is_parathread: bool,
     ) -> Result<Weight, DispatchError> {
-        // Withdraw assignment fee
-        let maybe_assignment_imbalance =
-            if Pallet::<T>::burn_collator_assignment_free_credit_for_para(&para_id).is_err() {
-                let (amount_to_charge, _weight) =
-                    T::ProvideCollatorAssignmentCost::collator_assignment_cost(&para_id);
-                Some(T::Currency::withdraw(
-                    &Self::parachain_tank(para_id),
-                    amount_to_charge,
-                    WithdrawReasons::FEE,
-                    ExistenceRequirement::KeepAlive,
-                )?)
-            } else {
-                None
-            };
-
-        if let Some(&tip) = maybe_tip {
-            // Only charge the tip to the paras that had a max tip set
-            // (aka were willing to tip for being assigned a collator)
-            if MaxTip::<T>::get(para_id).is_some() {
-                match T::Currency::withdraw(
-                    &Self::parachain_tank(para_id),
-                    tip,
-                    WithdrawReasons::TIP,
-                    ExistenceRequirement::KeepAlive,
-                ) {
-                    Err(e) => {
-                        // Return assignment imbalance to tank on error
-                        if let Some(assignment_imbalance) = maybe_assignment_imbalance {
-                            T::Currency::resolve_creating(
-                                &Self::parachain_tank(para_id),
-                                assignment_imbalance,
-                            );
-                        }
-                        return Err(e);
-                    }
-                    Ok(tip_imbalance) => {
-                        Self::deposit_event(Event::<T>::CollatorAssignmentTipCollected {
-                            para_id,
-                            payer: Self::parachain_tank(para_id),
-                            tip,
-                        });
-                        T::OnChargeForCollatorAssignmentTip::on_unbalanced(tip_imbalance);
+        // Fees paid in the payment asset are rolled back if the tip cannot be charged
+        with_storage_layer(|| {
+            // Withdraw assignment fee
+            let maybe_assignment_imbalance =
+                if Pallet::<T>::burn_collator_assignment_free_credit_for_para(&para_id).is_err() {
+                    let (amount_to_charge, _weight) =
+                        T::ProvideCollatorAssignmentCost::collator_assignment_cost(&para_id);
+                    Self::charge_fee(&para_id, amount_to_charge, PaidService::CollatorAssignment)?
+                } else {
+                    None
+                };
+
+            if let Some(tip) = maybe_tip {
+                if let Err(e) = Self::charge_tip(&para_id, tip) {
+                    // Return assignment imbalance to tank on error
+                    if let Some(assignment_imbalance) = maybe_assignment_imbalance {
+                        T::Currency::resolve_creating(
+                            &Self::parachain_tank(para_id),
+                            assignment_imbalance,
+                        );
                     }
+                    return Err(e);
                 }
             }
-        }
 
-        if let Some(assignment_imbalance) = maybe_assignment_imbalance {
-            T::OnChargeForCollatorAssignment::on_unbalanced(assignment_imbalance);
-        }
+            if let Some(assignment_imbalance) = maybe_assignment_imbalance {
+                T::OnChargeForCollatorAssignment::on_unbalanced(assignment_imbalance);
+            }
 
-        Ok(T::WeightInfo::on_collators_assigned())
+            Ok(T::WeightInfo::on_collators_assigned())
+        })
     }
 }
 
@@ -682,13 +1543,41 @@ impl<T: Config> Pallet<T> {
             }
         }
 
+        // Refund the payment asset too, it is kept in the tank if there is no refund address
+        if let (Some(asset_id), Some(address)) = (
+            PaymentAsset::<T>::get(para_id),
+            RefundAddress::<T>::get(para_id),
+        ) {
+            let parachain_tank = Self::parachain_tank(para_id);
+            let asset_balance = T::PaymentAssets::balance(&asset_id, &parachain_tank);
+            if !asset_balance.is_zero() {
+                if let Err(e) = T::PaymentAssets::transfer(
+                    &asset_id,
+                    &parachain_tank,
+                    &address,
+                    asset_balance,
+                    Preservation::Expendable,
+                ) {
+                    log::warn!(
+                        "Failed to refund payment asset of container chain {}: {:?}",
+                        u32::from(para_id),
+                        e
+                    );
+                }
+            }
+        }
+
         // Clean refund addres
         RefundAddress::<T>::remove(para_id);
+        LowBalanceThreshold::<T>::remove(para_id);
+        AutoTopUp::<T>::remove(para_id);
+        SpendHistory::<T>::remove(para_id);
 
         // Clean credits
         BlockProductionCredits::<T>::remove(para_id);
         CollatorAssignmentCredits::<T>::remove(para_id);
         MaxTip::<T>::remove(para_id);
         MaxCorePrice::<T>::remove(para_id);
+        PaymentAsset::<T>::remove(para_id);
     }
 }
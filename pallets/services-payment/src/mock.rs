@@ -16,20 +16,23 @@
 
 use {
     crate::{
-        self as pallet_services_payment, ProvideBlockProductionCost, ProvideCollatorAssignmentCost,
+        self as pallet_services_payment, PaymentAssets, ProvideBlockProductionCost,
+        ProvideCollatorAssignmentCost,
     },
     cumulus_primitives_core::ParaId,
     frame_support::{
         pallet_prelude::*,
         parameter_types,
-        traits::{ConstU32, ConstU64, Everything},
+        storage::unhashed,
+        traits::{tokens::Preservation, ConstU32, ConstU64, Contains, EitherOfDiverse, Everything},
     },
-    frame_system::EnsureRoot,
+    frame_system::{EnsureRoot, EnsureSignedBy},
     sp_core::H256,
     sp_runtime::{
         traits::{BlakeTwo256, IdentityLookup},
-        BuildStorage,
+        BuildStorage, DispatchError, DispatchResult, Perbill, TokenError,
     },
+    tp_traits::GetSessionIndex,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -100,6 +103,28 @@ impl pallet_balances::Config for Test {
 parameter_types! {
     pub const FreeBlockProductionCredits: u64 = 5;
     pub const FreeCollatorAssignmentCredits: u32 = 5;
+    pub static MockSessionIndex: u32 = 0;
+    pub const PriceAdjustmentRate: Perbill = Perbill::from_percent(10);
+    pub static ActiveParas: Vec<ParaId> = vec![];
+}
+
+/// Paras are inactive unless added to `ActiveParas`
+pub struct InactiveParas;
+impl Contains<ParaId> for InactiveParas {
+    fn contains(para_id: &ParaId) -> bool {
+        !ActiveParas::get().contains(para_id)
+    }
+}
+
+pub struct CurrentSessionIndex;
+impl GetSessionIndex<u32> for CurrentSessionIndex {
+    fn session_index() -> u32 {
+        MockSessionIndex::get()
+    }
+}
+
+frame_support::ord_parameter_types! {
+    pub const ParaManager: AccountId = 3;
 }
 
 impl pallet_services_payment::Config for Test {
@@ -112,10 +137,94 @@ impl pallet_services_payment::Config for Test {
     type ProvideCollatorAssignmentCost = CollatorAssignmentProductionCost<Test>;
     type FreeBlockProductionCredits = FreeBlockProductionCredits;
     type FreeCollatorAssignmentCredits = FreeCollatorAssignmentCredits;
-    type ManagerOrigin = EnsureRoot<AccountId>;
+    type ManagerOrigin =
+        EitherOfDiverse<EnsureRoot<AccountId>, EnsureSignedBy<ParaManager, AccountId>>;
+    type AssetId = u16;
+    type PaymentAssets = MockPaymentAssets;
+    type CurrentSessionIndex = CurrentSessionIndex;
+    type SpendHistoryLength = ConstU32<3>;
+    type PriceAdjustmentRate = PriceAdjustmentRate;
+    type InactiveParas = InactiveParas;
+    type TankReserveBlocks = ConstU32<2>;
     type WeightInfo = ();
 }
 
+/// Asset with a rate of 2 units per unit of native currency
+pub(crate) const PAYMENT_ASSET: u16 = 1;
+/// Asset without a rate
+pub(crate) const UNSUPPORTED_ASSET: u16 = 2;
+pub(crate) const PAYMENT_ASSET_RATE: u128 = 2;
+pub(crate) const PAYMENT_ASSET_MIN_BALANCE: u128 = 1;
+
+/// Asset balances kept in storage, so they are rolled back like the rest of the state
+pub struct MockPaymentAssets;
+impl MockPaymentAssets {
+    fn key(asset_id: &u16, who: &AccountId) -> Vec<u8> {
+        (b"MockPaymentAssets", asset_id, who).encode()
+    }
+
+    pub fn set_balance(asset_id: &u16, who: &AccountId, amount: Balance) {
+        unhashed::put(&Self::key(asset_id, who), &amount);
+    }
+}
+
+impl PaymentAssets<AccountId, u16, Balance> for MockPaymentAssets {
+    fn to_asset_balance(asset_id: &u16, amount: Balance) -> Result<Balance, DispatchError> {
+        match *asset_id {
+            PAYMENT_ASSET => Ok(amount * PAYMENT_ASSET_RATE),
+            _ => Err(TokenError::UnknownAsset.into()),
+        }
+    }
+
+    fn transfer(
+        asset_id: &u16,
+        from: &AccountId,
+        to: &AccountId,
+        amount: Balance,
+        preservation: Preservation,
+    ) -> DispatchResult {
+        let from_balance = Self::balance(asset_id, from);
+        let spendable = if preservation == Preservation::Expendable {
+            from_balance
+        } else {
+            Self::reducible_balance(asset_id, from)
+        };
+        ensure!(spendable >= amount, TokenError::FundsUnavailable);
+        Self::set_balance(asset_id, from, from_balance - amount);
+        Self::set_balance(asset_id, to, Self::balance(asset_id, to) + amount);
+
+        Ok(())
+    }
+
+    fn withdraw_fee(asset_id: &u16, who: &AccountId, amount: Balance) -> DispatchResult {
+        ensure!(
+            Self::reducible_balance(asset_id, who) >= amount,
+            TokenError::FundsUnavailable
+        );
+        Self::set_balance(asset_id, who, Self::balance(asset_id, who) - amount);
+
+        Ok(())
+    }
+
+    fn reducible_balance(asset_id: &u16, who: &AccountId) -> Balance {
+        Self::balance(asset_id, who).saturating_sub(PAYMENT_ASSET_MIN_BALANCE)
+    }
+
+    fn balance(asset_id: &u16, who: &AccountId) -> Balance {
+        unhashed::get_or_default(&Self::key(asset_id, who))
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_worst_case_asset_id() -> u16 {
+        PAYMENT_ASSET
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn bench_set_balance(asset_id: &u16, account: &AccountId, amount: Balance) {
+        Self::set_balance(asset_id, account, amount);
+    }
+}
+
 pub(crate) const FIXED_BLOCK_PRODUCTION_COST: u128 = 100;
 pub(crate) const FIXED_COLLATOR_ASSIGNMENT_COST: u128 = 200;
 
@@ -17,17 +17,20 @@
 use tp_traits::AuthorNotingInfo;
 use {
     crate::{
-        mock::*, pallet as pallet_services_payment, BlockProductionCredits,
-        CollatorAssignmentCredits, ProvideBlockProductionCost, ProvideCollatorAssignmentCost,
-        RefundAddress,
+        mock::*, pallet as pallet_services_payment, AutoTopUp, AutoTopUpAuthorization,
+        BlockProductionCredits, CollatorAssignmentCredits, PaymentAsset, PaymentAssets,
+        PriceMultiplier, ProvideBlockProductionCost, ProvideCollatorAssignmentCost, RefundAddress,
+        Runway, SpendHistory, SpendRecord,
     },
     cumulus_primitives_core::ParaId,
     frame_support::{assert_err, assert_noop, assert_ok, traits::fungible::Inspect},
-    sp_runtime::DispatchError,
+    sp_runtime::{DispatchError, FixedU128, Perbill},
     tp_traits::{AuthorNotingHook, CollatorAssignmentHook},
 };
 
 const ALICE: u64 = 1;
+const BOB: u64 = 2;
+const MANAGER: u64 = 3;
 
 #[test]
 fn purchase_credits_works() {
@@ -489,6 +492,57 @@ fn tip_should_be_charged_on_collators_assignment() {
         });
 }
 
+#[test]
+fn only_clearing_price_of_tip_is_charged() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 2_000_000)].into())
+        .build()
+        .execute_with(|| {
+            let para_id = 1;
+            let max_tip = 10u128;
+            let clearing_price = 4u128;
+            let balance = 5000u128;
+            System::set_block_number(1);
+
+            assert_ok!(PaymentServices::purchase_credits(
+                RuntimeOrigin::signed(ALICE),
+                para_id.into(),
+                balance,
+            ));
+            assert_ok!(PaymentServices::set_max_tip(
+                RuntimeOrigin::root(),
+                para_id.into(),
+                Some(max_tip),
+            ));
+
+            assert_ok!(PaymentServices::on_collators_assigned(
+                para_id.into(),
+                Some(&clearing_price),
+                false
+            ));
+
+            let (assignment_cost, _weight) =
+                <Test as crate::Config>::ProvideCollatorAssignmentCost::collator_assignment_cost(
+                    &para_id.into(),
+                );
+            let parachain_tank = crate::Pallet::<Test>::parachain_tank(para_id.into());
+            assert_eq!(
+                Balances::balance(&parachain_tank),
+                balance - assignment_cost - clearing_price,
+            );
+            assert_eq!(
+                events().last(),
+                Some(
+                    &pallet_services_payment::Event::CollatorAssignmentTipCollected {
+                        para_id: para_id.into(),
+                        payer: parachain_tank,
+                        tip: clearing_price,
+                    }
+                )
+            );
+        });
+}
+
 #[test]
 fn insufficient_balance_for_tip_reimburses_fee_imbalance() {
     ExtBuilder::default()
@@ -524,3 +578,824 @@ fn insufficient_balance_for_tip_reimburses_fee_imbalance() {
             );
         });
 }
+
+#[test]
+fn purchase_credits_in_asset_works() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            MockPaymentAssets::set_balance(&PAYMENT_ASSET, &ALICE, 1_000);
+
+            // Credits can only be purchased in the payment asset of the para
+            assert_noop!(
+                PaymentServices::purchase_credits_in_asset(
+                    RuntimeOrigin::signed(ALICE),
+                    1.into(),
+                    PAYMENT_ASSET,
+                    100u128,
+                ),
+                pallet_services_payment::Error::<Test>::NotPaymentAsset,
+            );
+
+            assert_ok!(PaymentServices::set_payment_asset(
+                RuntimeOrigin::root(),
+                1.into(),
+                Some(PAYMENT_ASSET),
+            ));
+            assert_ok!(PaymentServices::purchase_credits_in_asset(
+                RuntimeOrigin::signed(ALICE),
+                1.into(),
+                PAYMENT_ASSET,
+                100u128,
+            ));
+
+            assert_eq!(
+                events(),
+                vec![
+                    pallet_services_payment::Event::PaymentAssetUpdated {
+                        para_id: 1.into(),
+                        asset_id: Some(PAYMENT_ASSET),
+                    },
+                    pallet_services_payment::Event::CreditsPurchasedInAsset {
+                        para_id: 1.into(),
+                        payer: ALICE,
+                        asset_id: PAYMENT_ASSET,
+                        credit: 100u128
+                    }
+                ]
+            );
+            assert_eq!(
+                MockPaymentAssets::balance(
+                    &PAYMENT_ASSET,
+                    &crate::Pallet::<Test>::parachain_tank(1.into())
+                ),
+                100u128
+            );
+
+            MockPaymentAssets::set_balance(&UNSUPPORTED_ASSET, &ALICE, 1_000);
+            assert_noop!(
+                PaymentServices::purchase_credits_in_asset(
+                    RuntimeOrigin::signed(ALICE),
+                    1.into(),
+                    UNSUPPORTED_ASSET,
+                    100u128,
+                ),
+                pallet_services_payment::Error::<Test>::NotPaymentAsset,
+            );
+            assert_noop!(
+                PaymentServices::set_payment_asset(
+                    RuntimeOrigin::root(),
+                    2.into(),
+                    Some(UNSUPPORTED_ASSET),
+                ),
+                pallet_services_payment::Error::<Test>::UnsupportedPaymentAsset,
+            );
+            // The tank still holds the payment asset, so it can't be switched
+            assert_noop!(
+                PaymentServices::set_payment_asset(RuntimeOrigin::root(), 1.into(), None),
+                pallet_services_payment::Error::<Test>::PaymentAssetInTank,
+            );
+        });
+}
+
+#[test]
+fn services_are_charged_in_payment_asset() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            let para_id: ParaId = 1.into();
+            let balance = 5_000u128;
+            let parachain_tank = crate::Pallet::<Test>::parachain_tank(para_id);
+            MockPaymentAssets::set_balance(&PAYMENT_ASSET, &ALICE, 10_000);
+
+            assert_ok!(PaymentServices::set_payment_asset(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(PAYMENT_ASSET),
+            ));
+            assert_ok!(PaymentServices::purchase_credits_in_asset(
+                RuntimeOrigin::signed(ALICE),
+                para_id,
+                PAYMENT_ASSET,
+                balance,
+            ));
+            assert_eq!(PaymentAsset::<Test>::get(para_id), Some(PAYMENT_ASSET));
+
+            assert_ok!(PaymentServices::on_collators_assigned(para_id, None, false));
+            PaymentServices::on_container_authors_noted(&[AuthorNotingInfo {
+                author: 1,
+                block_number: 1,
+                para_id,
+            }]);
+
+            assert_eq!(
+                MockPaymentAssets::balance(&PAYMENT_ASSET, &parachain_tank),
+                balance
+                    - (FIXED_COLLATOR_ASSIGNMENT_COST + FIXED_BLOCK_PRODUCTION_COST)
+                        * PAYMENT_ASSET_RATE,
+            );
+            assert_eq!(Balances::balance(&parachain_tank), 0);
+            assert_eq!(
+                PaymentServices::block_cost_in_asset(&para_id, &PAYMENT_ASSET),
+                Some(FIXED_BLOCK_PRODUCTION_COST * PAYMENT_ASSET_RATE)
+            );
+            assert_eq!(
+                PaymentServices::collator_assignment_cost_in_asset(&para_id, &UNSUPPORTED_ASSET),
+                None
+            );
+        });
+}
+
+#[test]
+fn insufficient_balance_for_tip_reimburses_fee_in_payment_asset() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            let para_id: ParaId = 1.into();
+            let tip = 10u128;
+            let balance = 5_000u128;
+            let parachain_tank = crate::Pallet::<Test>::parachain_tank(para_id);
+            MockPaymentAssets::set_balance(&PAYMENT_ASSET, &ALICE, 10_000);
+
+            assert_ok!(PaymentServices::set_payment_asset(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(PAYMENT_ASSET),
+            ));
+            assert_ok!(PaymentServices::purchase_credits_in_asset(
+                RuntimeOrigin::signed(ALICE),
+                para_id,
+                PAYMENT_ASSET,
+                balance,
+            ));
+            assert_ok!(PaymentServices::set_max_tip(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(tip),
+            ));
+
+            // Tips are paid in the native currency, and the tank has none
+            assert!(PaymentServices::on_collators_assigned(para_id, Some(&tip), false).is_err());
+
+            assert_eq!(
+                MockPaymentAssets::balance(&PAYMENT_ASSET, &parachain_tank),
+                balance
+            );
+        });
+}
+
+#[test]
+fn low_tank_balance_emits_event_and_tops_up_tank() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 2_000), (BOB, 2_000)].into())
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            let para_id: ParaId = 1.into();
+            let parachain_tank = crate::Pallet::<Test>::parachain_tank(para_id);
+
+            assert_ok!(PaymentServices::purchase_credits(
+                RuntimeOrigin::signed(ALICE),
+                para_id,
+                1_000u128,
+            ));
+            assert_ok!(PaymentServices::set_low_balance_threshold(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(900u128),
+            ));
+            assert_ok!(PaymentServices::set_auto_top_up(
+                RuntimeOrigin::signed(BOB),
+                para_id,
+                Some(500u128),
+                1_000u128,
+            ));
+
+            // The assignment makes the tank drop below the threshold
+            assert_ok!(PaymentServices::on_collators_assigned(para_id, None, false));
+
+            let events = events();
+            assert!(
+                events.contains(&pallet_services_payment::Event::TankBalanceLow {
+                    para_id,
+                    balance: 1_000 - FIXED_COLLATOR_ASSIGNMENT_COST - 1,
+                    threshold: 900,
+                })
+            );
+            assert_eq!(
+                events.last(),
+                Some(&pallet_services_payment::Event::TankToppedUp {
+                    para_id,
+                    payer: BOB,
+                    amount: 500,
+                })
+            );
+            assert_eq!(
+                Balances::balance(&parachain_tank),
+                1_000 - FIXED_COLLATOR_ASSIGNMENT_COST + 500
+            );
+
+            // Only drops below the threshold are reported
+            System::reset_events();
+            PaymentServices::on_container_authors_noted(&[AuthorNotingInfo {
+                author: 1,
+                block_number: 1,
+                para_id,
+            }]);
+            assert!(!events().iter().any(|event| matches!(
+                event,
+                pallet_services_payment::Event::TankBalanceLow { .. }
+            )));
+        });
+}
+
+#[test]
+fn tank_below_threshold_is_topped_up_on_every_charge() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 2_000), (BOB, 2_000)].into())
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            let para_id: ParaId = 1.into();
+            let parachain_tank = crate::Pallet::<Test>::parachain_tank(para_id);
+
+            assert_ok!(PaymentServices::purchase_credits(
+                RuntimeOrigin::signed(ALICE),
+                para_id,
+                500u128,
+            ));
+            // The tank is already below the threshold when it is set
+            assert_ok!(PaymentServices::set_low_balance_threshold(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(900u128),
+            ));
+            assert_ok!(PaymentServices::set_auto_top_up(
+                RuntimeOrigin::signed(BOB),
+                para_id,
+                Some(200u128),
+                1_000u128,
+            ));
+
+            PaymentServices::on_container_authors_noted(&[AuthorNotingInfo {
+                author: 1,
+                block_number: 1,
+                para_id,
+            }]);
+            assert_eq!(
+                Balances::balance(&parachain_tank),
+                500 - FIXED_BLOCK_PRODUCTION_COST + 200
+            );
+
+            // Still below the threshold, so the next charge tops it up again
+            PaymentServices::on_container_authors_noted(&[AuthorNotingInfo {
+                author: 1,
+                block_number: 2,
+                para_id,
+            }]);
+            assert_eq!(
+                Balances::balance(&parachain_tank),
+                500 - 2 * FIXED_BLOCK_PRODUCTION_COST + 400
+            );
+            // The tank never crossed the threshold
+            assert!(!events().iter().any(|event| matches!(
+                event,
+                pallet_services_payment::Event::TankBalanceLow { .. }
+            )));
+        });
+}
+
+#[test]
+fn auto_top_up_can_only_be_replaced_by_payer_or_manager() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 2_000), (BOB, 2_000)].into())
+        .build()
+        .execute_with(|| {
+            let para_id: ParaId = 1.into();
+
+            assert_ok!(PaymentServices::set_auto_top_up(
+                RuntimeOrigin::signed(BOB),
+                para_id,
+                Some(500u128),
+                1_000u128,
+            ));
+            assert_noop!(
+                PaymentServices::set_auto_top_up(
+                    RuntimeOrigin::signed(ALICE),
+                    para_id,
+                    None,
+                    0u128
+                ),
+                pallet_services_payment::Error::<Test>::NotAutoTopUpPayer,
+            );
+            assert_noop!(
+                PaymentServices::set_auto_top_up(
+                    RuntimeOrigin::signed(ALICE),
+                    para_id,
+                    Some(1u128),
+                    1u128
+                ),
+                pallet_services_payment::Error::<Test>::NotAutoTopUpPayer,
+            );
+
+            // The payer can replace its own top-up
+            assert_ok!(PaymentServices::set_auto_top_up(
+                RuntimeOrigin::signed(BOB),
+                para_id,
+                Some(300u128),
+                600u128,
+            ));
+            assert_eq!(
+                AutoTopUp::<Test>::get(para_id),
+                Some(AutoTopUpAuthorization {
+                    payer: BOB,
+                    asset_id: None,
+                    amount: 300,
+                    remaining: 600,
+                })
+            );
+
+            // The manager can replace it too
+            assert_ok!(PaymentServices::set_auto_top_up(
+                RuntimeOrigin::signed(MANAGER),
+                para_id,
+                Some(100u128),
+                100u128,
+            ));
+            assert_eq!(
+                AutoTopUp::<Test>::get(para_id).map(|top_up| top_up.payer),
+                Some(MANAGER)
+            );
+
+            assert_ok!(PaymentServices::set_auto_top_up(
+                RuntimeOrigin::signed(MANAGER),
+                para_id,
+                None,
+                0u128,
+            ));
+            assert_eq!(AutoTopUp::<Test>::get(para_id), None);
+        });
+}
+
+#[test]
+fn auto_top_up_stops_at_max_total() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 2_000), (BOB, 2_000)].into())
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            let para_id: ParaId = 1.into();
+            let parachain_tank = crate::Pallet::<Test>::parachain_tank(para_id);
+
+            assert_ok!(PaymentServices::purchase_credits(
+                RuntimeOrigin::signed(ALICE),
+                para_id,
+                500u128,
+            ));
+            assert_ok!(PaymentServices::set_low_balance_threshold(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(900u128),
+            ));
+            // Only 300 can be topped up in total
+            assert_ok!(PaymentServices::set_auto_top_up(
+                RuntimeOrigin::signed(BOB),
+                para_id,
+                Some(200u128),
+                300u128,
+            ));
+
+            for block_number in 1..=3 {
+                PaymentServices::on_container_authors_noted(&[AuthorNotingInfo {
+                    author: 1,
+                    block_number,
+                    para_id,
+                }]);
+            }
+
+            assert_eq!(
+                Balances::balance(&parachain_tank),
+                500 - 3 * FIXED_BLOCK_PRODUCTION_COST + 300
+            );
+            assert_eq!(Balances::balance(&BOB), 2_000 - 300);
+            assert_eq!(
+                AutoTopUp::<Test>::get(para_id).map(|top_up| top_up.remaining),
+                Some(0)
+            );
+        });
+}
+
+#[test]
+fn auto_top_up_is_removed_when_payment_asset_changes() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 2_000), (BOB, 2_000)].into())
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            let para_id: ParaId = 1.into();
+
+            // Authorized in the native currency
+            assert_ok!(PaymentServices::set_auto_top_up(
+                RuntimeOrigin::signed(BOB),
+                para_id,
+                Some(500u128),
+                1_000u128,
+            ));
+            assert_ok!(PaymentServices::set_payment_asset(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(PAYMENT_ASSET),
+            ));
+
+            assert_eq!(AutoTopUp::<Test>::get(para_id), None);
+            assert!(
+                events().contains(&pallet_services_payment::Event::AutoTopUpUpdated {
+                    para_id,
+                    payer: BOB,
+                    amount: None,
+                })
+            );
+
+            // Top-ups authorized in the payment asset are kept while it doesn't change
+            assert_ok!(PaymentServices::set_auto_top_up(
+                RuntimeOrigin::signed(BOB),
+                para_id,
+                Some(500u128),
+                1_000u128,
+            ));
+            assert_ok!(PaymentServices::set_payment_asset(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(PAYMENT_ASSET),
+            ));
+            assert_eq!(
+                AutoTopUp::<Test>::get(para_id).and_then(|top_up| top_up.asset_id),
+                Some(PAYMENT_ASSET)
+            );
+        });
+}
+
+#[test]
+fn low_free_credits_emit_event() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 2_000)].into())
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            let para_id: ParaId = 1.into();
+
+            BlockProductionCredits::<Test>::insert(para_id, 3);
+            assert_ok!(PaymentServices::set_low_balance_threshold(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(2 * FIXED_BLOCK_PRODUCTION_COST),
+            ));
+
+            // 2 credits left are still worth the threshold
+            assert_ok!(PaymentServices::burn_block_production_free_credit_for_para(
+                &para_id
+            ));
+            assert!(!events().iter().any(|event| matches!(
+                event,
+                pallet_services_payment::Event::FreeCreditsLow { .. }
+            )));
+
+            assert_ok!(PaymentServices::burn_block_production_free_credit_for_para(
+                &para_id
+            ));
+            assert!(
+                events().contains(&pallet_services_payment::Event::FreeCreditsLow {
+                    para_id,
+                    service: crate::PaidService::BlockProduction,
+                    value: FIXED_BLOCK_PRODUCTION_COST,
+                    threshold: 2 * FIXED_BLOCK_PRODUCTION_COST,
+                })
+            );
+
+            // Only drops below the threshold are reported
+            System::reset_events();
+            assert_ok!(PaymentServices::burn_block_production_free_credit_for_para(
+                &para_id
+            ));
+            assert!(!events().iter().any(|event| matches!(
+                event,
+                pallet_services_payment::Event::FreeCreditsLow { .. }
+            )));
+        });
+}
+
+#[test]
+fn runway_counts_free_credits_and_tank() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 2_000)].into())
+        .build()
+        .execute_with(|| {
+            let para_id: ParaId = 1.into();
+
+            // 1 is kept in the tank as existential deposit
+            assert_ok!(PaymentServices::purchase_credits(
+                RuntimeOrigin::signed(ALICE),
+                para_id,
+                1_001u128,
+            ));
+            PaymentServices::set_free_block_production_credits(&para_id, 10);
+            PaymentServices::set_free_collator_assignment_credits(&para_id, 1);
+
+            // 10 free blocks and 1000 / 100 paid blocks. Each session costs 200 + 5 * 100, and
+            // the credits and tank are worth 10 * 100 + 200 + 1000.
+            assert_eq!(
+                PaymentServices::runway(&para_id, 5),
+                Runway {
+                    blocks: 20,
+                    sessions: 3,
+                }
+            );
+        });
+}
+
+#[test]
+fn spend_is_recorded_per_session() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 2_000_000)].into())
+        .build()
+        .execute_with(|| {
+            let para_id: ParaId = 1.into();
+            let tip = 10u128;
+
+            assert_ok!(PaymentServices::purchase_credits(
+                RuntimeOrigin::signed(ALICE),
+                para_id,
+                5_000u128,
+            ));
+            assert_ok!(PaymentServices::set_max_tip(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(tip),
+            ));
+
+            assert_ok!(PaymentServices::on_collators_assigned(
+                para_id,
+                Some(&tip),
+                false
+            ));
+            for block_number in 1..=2 {
+                PaymentServices::on_container_authors_noted(&[AuthorNotingInfo {
+                    author: 1,
+                    block_number,
+                    para_id,
+                }]);
+            }
+
+            MockSessionIndex::set(1);
+            PaymentServices::record_core_purchase(&para_id);
+
+            assert_eq!(
+                PaymentServices::spend_history(para_id),
+                vec![
+                    (
+                        0,
+                        SpendRecord {
+                            block_production: 2 * FIXED_BLOCK_PRODUCTION_COST,
+                            collator_assignment: FIXED_COLLATOR_ASSIGNMENT_COST,
+                            tips: tip,
+                            cores_purchased: 0,
+                        }
+                    ),
+                    (
+                        1,
+                        SpendRecord {
+                            cores_purchased: 1,
+                            ..Default::default()
+                        }
+                    ),
+                ]
+            );
+        });
+}
+
+#[test]
+fn spend_history_forgets_old_sessions() {
+    ExtBuilder::default().build().execute_with(|| {
+        let para_id: ParaId = 1.into();
+
+        for session_index in [0, 1, 2, 4] {
+            MockSessionIndex::set(session_index);
+            PaymentServices::record_core_purchase(&para_id);
+        }
+
+        // Only the last 3 sessions are kept
+        let sessions: Vec<u32> = PaymentServices::spend_history(para_id)
+            .into_iter()
+            .map(|(session, _)| session)
+            .collect();
+        assert_eq!(sessions, vec![2, 4]);
+
+        PaymentServices::para_deregistered(para_id);
+        assert!(!SpendHistory::<Test>::contains_key(para_id));
+    });
+}
+
+#[test]
+fn price_multiplier_is_not_adjusted_without_bounds() {
+    ExtBuilder::default().build().execute_with(|| {
+        PaymentServices::initializer_on_new_session(Perbill::one(), Perbill::from_percent(80));
+
+        assert_eq!(PaymentServices::price_multiplier(), FixedU128::from_u32(1));
+    });
+}
+
+#[test]
+fn price_multiplier_follows_collator_fullness_within_bounds() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+        let target = Perbill::from_percent(80);
+        assert_ok!(PaymentServices::set_price_multiplier_bounds(
+            RuntimeOrigin::root(),
+            Some((
+                FixedU128::from_rational(95, 100),
+                FixedU128::from_rational(115, 100)
+            )),
+        ));
+
+        // Halfway between the target and a full network raises the price by half the rate
+        PaymentServices::initializer_on_new_session(Perbill::from_percent(90), target);
+        assert_eq!(
+            PaymentServices::price_multiplier(),
+            FixedU128::from_rational(105, 100)
+        );
+        System::assert_last_event(
+            crate::Event::PriceMultiplierUpdated {
+                multiplier: FixedU128::from_rational(105, 100),
+                collator_fullness: Perbill::from_percent(90),
+            }
+            .into(),
+        );
+
+        // A full network raises it by the whole rate, up to the max
+        PaymentServices::initializer_on_new_session(Perbill::one(), target);
+        assert_eq!(
+            PaymentServices::price_multiplier(),
+            FixedU128::from_rational(115, 100)
+        );
+
+        // Reaching the target keeps the price
+        PaymentServices::initializer_on_new_session(target, target);
+        assert_eq!(
+            PaymentServices::price_multiplier(),
+            FixedU128::from_rational(115, 100)
+        );
+
+        // An idle network lowers it, down to the min
+        for _ in 0..3 {
+            PaymentServices::initializer_on_new_session(Perbill::zero(), target);
+        }
+        assert_eq!(
+            PaymentServices::price_multiplier(),
+            FixedU128::from_rational(95, 100)
+        );
+    });
+}
+
+#[test]
+fn set_price_multiplier_bounds_works() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            PaymentServices::set_price_multiplier_bounds(
+                RuntimeOrigin::signed(ALICE),
+                Some((FixedU128::from_u32(1), FixedU128::from_u32(2))),
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PaymentServices::set_price_multiplier_bounds(
+                RuntimeOrigin::root(),
+                Some((FixedU128::from_u32(2), FixedU128::from_u32(1))),
+            ),
+            crate::Error::<Test>::InvalidPriceMultiplierBounds
+        );
+
+        // The current multiplier is moved into the new bounds
+        assert_ok!(PaymentServices::set_price_multiplier_bounds(
+            RuntimeOrigin::root(),
+            Some((FixedU128::from_u32(2), FixedU128::from_u32(3))),
+        ));
+        assert_eq!(PaymentServices::price_multiplier(), FixedU128::from_u32(2));
+
+        // Removing the bounds resets it
+        assert_ok!(PaymentServices::set_price_multiplier_bounds(
+            RuntimeOrigin::root(),
+            None,
+        ));
+        assert!(!PriceMultiplier::<Test>::exists());
+        assert_eq!(PaymentServices::price_multiplier(), FixedU128::from_u32(1));
+    });
+}
+
+#[test]
+fn withdraw_from_tank_keeps_reserve() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 2_000)].into())
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            let para_id: ParaId = 1.into();
+            let refund_address = 10u64;
+
+            // 1 is kept in the tank as existential deposit
+            assert_ok!(PaymentServices::purchase_credits(
+                RuntimeOrigin::signed(ALICE),
+                para_id,
+                1_001u128,
+            ));
+
+            ActiveParas::set(vec![para_id]);
+            assert_noop!(
+                PaymentServices::withdraw_from_tank(RuntimeOrigin::root(), para_id, 100u128),
+                crate::Error::<Test>::ParaIsActive
+            );
+
+            ActiveParas::set(vec![]);
+            assert_noop!(
+                PaymentServices::withdraw_from_tank(RuntimeOrigin::root(), para_id, 100u128),
+                crate::Error::<Test>::RefundAddressNotSet
+            );
+
+            assert_ok!(PaymentServices::set_refund_address(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(refund_address),
+            ));
+
+            // The reserve pays for a collator assignment and 2 blocks
+            let reserve = FIXED_COLLATOR_ASSIGNMENT_COST + 2 * FIXED_BLOCK_PRODUCTION_COST;
+            assert_eq!(PaymentServices::tank_reserve(&para_id), Ok(reserve));
+            assert_noop!(
+                PaymentServices::withdraw_from_tank(
+                    RuntimeOrigin::root(),
+                    para_id,
+                    1_000 - reserve + 1,
+                ),
+                crate::Error::<Test>::InsufficientTankBalance
+            );
+
+            assert_ok!(PaymentServices::withdraw_from_tank(
+                RuntimeOrigin::root(),
+                para_id,
+                1_000 - reserve,
+            ));
+            System::assert_last_event(
+                crate::Event::TankWithdrawn {
+                    para_id,
+                    refund_address,
+                    amount: 1_000 - reserve,
+                }
+                .into(),
+            );
+            assert_eq!(Balances::balance(&refund_address), 1_000 - reserve);
+            assert_eq!(PaymentServices::tank_balance(&para_id), reserve);
+        });
+}
+
+#[test]
+fn withdraw_from_tank_in_payment_asset() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            let para_id: ParaId = 1.into();
+            let refund_address = 10u64;
+            let tank = crate::Pallet::<Test>::parachain_tank(para_id);
+
+            assert_ok!(PaymentServices::set_payment_asset(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(PAYMENT_ASSET),
+            ));
+            assert_ok!(PaymentServices::set_refund_address(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(refund_address),
+            ));
+            MockPaymentAssets::set_balance(&PAYMENT_ASSET, &tank, 2_000);
+
+            // The reserve is converted to the payment asset
+            let reserve = (FIXED_COLLATOR_ASSIGNMENT_COST + 2 * FIXED_BLOCK_PRODUCTION_COST)
+                * PAYMENT_ASSET_RATE;
+            let amount = PaymentServices::tank_balance(&para_id) - reserve;
+            assert_ok!(PaymentServices::withdraw_from_tank(
+                RuntimeOrigin::root(),
+                para_id,
+                amount,
+            ));
+
+            assert_eq!(
+                MockPaymentAssets::balance(&PAYMENT_ASSET, &refund_address),
+                amount
+            );
+            assert_eq!(PaymentServices::tank_balance(&para_id), reserve);
+        });
+}
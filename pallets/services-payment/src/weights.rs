@@ -61,6 +61,13 @@ pub trait WeightInfo {
 	fn on_container_authors_noted(n: u32, ) -> Weight;
 	fn on_collators_assigned() -> Weight;
 	fn set_max_tip() -> Weight;
+	fn purchase_credits_in_asset() -> Weight;
+	fn set_payment_asset() -> Weight;
+	fn set_low_balance_threshold() -> Weight;
+	fn set_auto_top_up() -> Weight;
+	fn set_price_multiplier_bounds() -> Weight;
+	fn update_price_multiplier() -> Weight;
+	fn withdraw_from_tank() -> Weight;
 }
 
 /// Weights for pallet_services_payment using the Substrate node and recommended hardware.
@@ -125,35 +132,61 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	}
 	/// Storage: `ServicesPayment::BlockProductionCredits` (r:50 w:0)
 	/// Proof: `ServicesPayment::BlockProductionCredits` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:50 w:50)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:51 w:51)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:50 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `Session::CurrentIndex` (r:1 w:0)
+	/// Proof: `Session::CurrentIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ServicesPayment::SpendHistory` (r:50 w:50)
+	/// Proof: `ServicesPayment::SpendHistory` (`max_values`: None, `max_size`: Some(1701), added: 4176, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::LowBalanceThreshold` (r:50 w:0)
+	/// Proof: `ServicesPayment::LowBalanceThreshold` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::AutoTopUp` (r:50 w:50)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
 	/// The range of component `n` is `[1, 50]`.
 	fn on_container_authors_noted(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `300 + n * (132 ±0)`
-		//  Estimated: `990 + n * (2603 ±0)`
-		// Minimum execution time: 20_800_000 picoseconds.
-		Weight::from_parts(8_063_256, 990)
+		//  Measured:  `712 + n * (263 ±0)`
+		//  Estimated: `3593 + n * (4176 ±0)`
+		// Minimum execution time: 52_004_000 picoseconds.
+		Weight::from_parts(26_005_374, 3593)
 			// Standard Error: 289_417
-			.saturating_add(Weight::from_parts(14_388_705, 0).saturating_mul(n.into()))
-			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
-			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(33_705_107, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 4176).saturating_mul(n.into()))
 	}
 	/// Storage: `ServicesPayment::CollatorAssignmentCredits` (r:1 w:0)
 	/// Proof: `ServicesPayment::CollatorAssignmentCredits` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Session::CurrentIndex` (r:1 w:0)
+	/// Proof: `Session::CurrentIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ServicesPayment::SpendHistory` (r:1 w:1)
+	/// Proof: `ServicesPayment::SpendHistory` (`max_values`: None, `max_size`: Some(1701), added: 4176, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::LowBalanceThreshold` (r:1 w:0)
+	/// Proof: `ServicesPayment::LowBalanceThreshold` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::AutoTopUp` (r:1 w:1)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
 	/// Storage: `ServicesPayment::MaxTip` (r:1 w:0)
 	/// Proof: `ServicesPayment::MaxTip` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
 	fn on_collators_assigned() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `315`
-		//  Estimated: `3593`
-		// Minimum execution time: 36_282_000 picoseconds.
-		Weight::from_parts(36_282_000, 3593)
-			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+		//  Measured:  `801`
+		//  Estimated: `6196`
+		// Minimum execution time: 94_699_000 picoseconds.
+		Weight::from_parts(94_699_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: `ServicesPayment::MaxTip` (r:0 w:1)
 	/// Proof: `ServicesPayment::MaxTip` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
@@ -165,6 +198,110 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(5_832_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRate::ConversionRateToNative` (r:1 w:0)
+	/// Proof: `AssetRate::ConversionRateToNative` (`max_values`: None, `max_size`: Some(34), added: 2509, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(208), added: 2683, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:2 w:2)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn purchase_credits_in_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `560`
+		//  Estimated: `6208`
+		// Minimum execution time: 64_102_000 picoseconds.
+		Weight::from_parts(65_487_000, 6208)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:1)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:1 w:0)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRate::ConversionRateToNative` (r:1 w:0)
+	/// Proof: `AssetRate::ConversionRateToNative` (`max_values`: None, `max_size`: Some(34), added: 2509, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::AutoTopUp` (r:1 w:1)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
+	fn set_payment_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3599`
+		// Minimum execution time: 19_203_000 picoseconds.
+		Weight::from_parts(19_841_000, 3599)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `ServicesPayment::LowBalanceThreshold` (r:0 w:1)
+	/// Proof: `ServicesPayment::LowBalanceThreshold` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	fn set_low_balance_threshold() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 5_914_000 picoseconds.
+		Weight::from_parts(6_102_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ServicesPayment::AutoTopUp` (r:1 w:1)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::ParaManager` (r:1 w:0)
+	/// Proof: `Registrar::ParaManager` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	fn set_auto_top_up() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402`
+		//  Estimated: `3867`
+		// Minimum execution time: 15_118_000 picoseconds.
+		Weight::from_parts(15_760_000, 3867)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:1)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PriceMultiplierBounds` (r:0 w:1)
+	/// Proof: `ServicesPayment::PriceMultiplierBounds` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	fn set_price_multiplier_bounds() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `1501`
+		// Minimum execution time: 7_012_000 picoseconds.
+		Weight::from_parts(7_341_000, 1501)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `ServicesPayment::PriceMultiplierBounds` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplierBounds` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:1)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn update_price_multiplier() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `48`
+		//  Estimated: `1517`
+		// Minimum execution time: 6_528_000 picoseconds.
+		Weight::from_parts(6_703_000, 1517)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ServicesPayment::RefundAddress` (r:1 w:0)
+	/// Proof: `ServicesPayment::RefundAddress` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn withdraw_from_tank() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `310`
+		//  Estimated: `6196`
+		// Minimum execution time: 58_211_000 picoseconds.
+		Weight::from_parts(59_847_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -228,35 +365,61 @@ impl WeightInfo for () {
 	}
 	/// Storage: `ServicesPayment::BlockProductionCredits` (r:50 w:0)
 	/// Proof: `ServicesPayment::BlockProductionCredits` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:50 w:50)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:51 w:51)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:50 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `Session::CurrentIndex` (r:1 w:0)
+	/// Proof: `Session::CurrentIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ServicesPayment::SpendHistory` (r:50 w:50)
+	/// Proof: `ServicesPayment::SpendHistory` (`max_values`: None, `max_size`: Some(1701), added: 4176, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::LowBalanceThreshold` (r:50 w:0)
+	/// Proof: `ServicesPayment::LowBalanceThreshold` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::AutoTopUp` (r:50 w:50)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
 	/// The range of component `n` is `[1, 50]`.
 	fn on_container_authors_noted(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `300 + n * (132 ±0)`
-		//  Estimated: `990 + n * (2603 ±0)`
-		// Minimum execution time: 20_800_000 picoseconds.
-		Weight::from_parts(8_063_256, 990)
+		//  Measured:  `712 + n * (263 ±0)`
+		//  Estimated: `3593 + n * (4176 ±0)`
+		// Minimum execution time: 52_004_000 picoseconds.
+		Weight::from_parts(26_005_374, 3593)
 			// Standard Error: 289_417
-			.saturating_add(Weight::from_parts(14_388_705, 0).saturating_mul(n.into()))
-			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
-			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
-			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(33_705_107, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 4176).saturating_mul(n.into()))
 	}
 	/// Storage: `ServicesPayment::CollatorAssignmentCredits` (r:1 w:0)
 	/// Proof: `ServicesPayment::CollatorAssignmentCredits` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Session::CurrentIndex` (r:1 w:0)
+	/// Proof: `Session::CurrentIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `ServicesPayment::SpendHistory` (r:1 w:1)
+	/// Proof: `ServicesPayment::SpendHistory` (`max_values`: None, `max_size`: Some(1701), added: 4176, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::LowBalanceThreshold` (r:1 w:0)
+	/// Proof: `ServicesPayment::LowBalanceThreshold` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::AutoTopUp` (r:1 w:1)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
 	/// Storage: `ServicesPayment::MaxTip` (r:1 w:0)
 	/// Proof: `ServicesPayment::MaxTip` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
 	fn on_collators_assigned() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `315`
-		//  Estimated: `3593`
-		// Minimum execution time: 36_282_000 picoseconds.
-		Weight::from_parts(36_282_000, 3593)
-			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+		//  Measured:  `801`
+		//  Estimated: `6196`
+		// Minimum execution time: 94_699_000 picoseconds.
+		Weight::from_parts(94_699_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: `ServicesPayment::MaxTip` (r:0 w:1)
 	/// Proof: `ServicesPayment::MaxTip` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
@@ -268,4 +431,108 @@ impl WeightInfo for () {
 		Weight::from_parts(5_832_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRate::ConversionRateToNative` (r:1 w:0)
+	/// Proof: `AssetRate::ConversionRateToNative` (`max_values`: None, `max_size`: Some(34), added: 2509, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Asset` (r:1 w:1)
+	/// Proof: `ForeignAssets::Asset` (`max_values`: None, `max_size`: Some(208), added: 2683, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:2 w:2)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn purchase_credits_in_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `560`
+		//  Estimated: `6208`
+		// Minimum execution time: 64_102_000 picoseconds.
+		Weight::from_parts(65_487_000, 6208)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:1)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	/// Storage: `ForeignAssets::Account` (r:1 w:0)
+	/// Proof: `ForeignAssets::Account` (`max_values`: None, `max_size`: Some(134), added: 2609, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRate::ConversionRateToNative` (r:1 w:0)
+	/// Proof: `AssetRate::ConversionRateToNative` (`max_values`: None, `max_size`: Some(34), added: 2509, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::AutoTopUp` (r:1 w:1)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
+	fn set_payment_asset() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3599`
+		// Minimum execution time: 19_203_000 picoseconds.
+		Weight::from_parts(19_841_000, 3599)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `ServicesPayment::LowBalanceThreshold` (r:0 w:1)
+	/// Proof: `ServicesPayment::LowBalanceThreshold` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	fn set_low_balance_threshold() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 5_914_000 picoseconds.
+		Weight::from_parts(6_102_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ServicesPayment::AutoTopUp` (r:1 w:1)
+	/// Proof: `ServicesPayment::AutoTopUp` (`max_values`: None, `max_size`: Some(87), added: 2562, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::ParaManager` (r:1 w:0)
+	/// Proof: `Registrar::ParaManager` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(22), added: 2497, mode: `MaxEncodedLen`)
+	fn set_auto_top_up() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402`
+		//  Estimated: `3867`
+		// Minimum execution time: 15_118_000 picoseconds.
+		Weight::from_parts(15_760_000, 3867)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:1)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PriceMultiplierBounds` (r:0 w:1)
+	/// Proof: `ServicesPayment::PriceMultiplierBounds` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	fn set_price_multiplier_bounds() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `1501`
+		// Minimum execution time: 7_012_000 picoseconds.
+		Weight::from_parts(7_341_000, 1501)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `ServicesPayment::PriceMultiplierBounds` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplierBounds` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:1)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn update_price_multiplier() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `48`
+		//  Estimated: `1517`
+		// Minimum execution time: 6_528_000 picoseconds.
+		Weight::from_parts(6_703_000, 1517)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ServicesPayment::RefundAddress` (r:1 w:0)
+	/// Proof: `ServicesPayment::RefundAddress` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PaymentAsset` (r:1 w:0)
+	/// Proof: `ServicesPayment::PaymentAsset` (`max_values`: None, `max_size`: Some(42), added: 2517, mode: `MaxEncodedLen`)
+	/// Storage: `ServicesPayment::PriceMultiplier` (r:1 w:0)
+	/// Proof: `ServicesPayment::PriceMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn withdraw_from_tank() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `310`
+		//  Estimated: `6196`
+		// Minimum execution time: 58_211_000 picoseconds.
+		Weight::from_parts(59_847_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
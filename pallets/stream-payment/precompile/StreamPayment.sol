@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The StreamPayment contract's address.
+address constant STREAM_PAYMENT_ADDRESS = 0x0000000000000000000000000000000000000806;
+
+/// @dev The StreamPayment contract's instance.
+StreamPayment constant STREAM_PAYMENT_CONTRACT = StreamPayment(STREAM_PAYMENT_ADDRESS);
+
+/// @author Moondance Labs
+/// @title Pallet Stream Payment Interface
+/// @title The interface through which solidity contracts will interact with the Stream Payment pallet
+/// @custom:address 0x0000000000000000000000000000000000000806
+interface StreamPayment {
+    /// @dev Unit in which the time of a stream is measured.
+    /// The values are represented as `uint8`.
+    enum TimeUnit {
+        BlockNumber,
+        Timestamp
+    }
+
+    /// @dev How the deposit of a stream should change.
+    /// The values are represented as `uint8`.
+    enum DepositChangeKind {
+        None,
+        Increase,
+        Decrease,
+        Absolute
+    }
+
+    /// @dev Open a payment stream from the caller to the target.
+    /// Assets are identified by the address of their ERC20 precompile.
+    /// @custom:selector 9d35771b
+    /// @param target The account receiving the payments
+    /// @param timeUnit The unit in which time is measured
+    /// @param asset The address of the ERC20 precompile of the asset used for payment
+    /// @param rate The amount of asset paid per time unit
+    /// @param initialDeposit The initial amount deposited to fund the stream
+    /// @return streamId The id of the opened stream
+    function openStream(
+        address target,
+        TimeUnit timeUnit,
+        address asset,
+        uint256 rate,
+        uint256 initialDeposit
+    ) external returns (uint64 streamId);
+
+    /// @dev Close a stream in which the caller is involved, performing the pending payment first.
+    /// @custom:selector 5d260ffa
+    /// @param streamId The id of the stream
+    function closeStream(uint64 streamId) external;
+
+    /// @dev Perform the pending payment of a stream. Anyone can call this.
+    /// @custom:selector c75f1504
+    /// @param streamId The id of the stream
+    function performPayment(uint64 streamId) external;
+
+    /// @dev Request a change to the config or deposit of a stream.
+    /// Only the source of the stream can provide a deposit change.
+    /// @custom:selector cff50869
+    /// @param streamId The id of the stream
+    /// @param timeUnit The new time unit
+    /// @param asset The address of the ERC20 precompile of the new asset
+    /// @param rate The new rate
+    /// @param mandatory Whether the other party must accept the change or close the stream
+    /// @param deadline If mandatory, time after which the stream will be stalled
+    /// @param depositChangeKind How the deposit should change, if at all
+    /// @param depositChangeAmount The amount used by the deposit change
+    function requestChange(
+        uint64 streamId,
+        TimeUnit timeUnit,
+        address asset,
+        uint256 rate,
+        bool mandatory,
+        uint256 deadline,
+        DepositChangeKind depositChangeKind,
+        uint256 depositChangeAmount
+    ) external;
+
+    /// @dev Accept a change requested by the other party of the stream.
+    /// @custom:selector c36fcaf7
+    /// @param streamId The id of the stream
+    /// @param requestNonce The nonce of the accepted request, to prevent frontrunning
+    /// @param depositChangeKind How the deposit should change, if at all
+    /// @param depositChangeAmount The amount used by the deposit change
+    function acceptRequestedChange(
+        uint64 streamId,
+        uint32 requestNonce,
+        DepositChangeKind depositChangeKind,
+        uint256 depositChangeAmount
+    ) external;
+
+    /// @dev Cancel the change requested by the caller.
+    /// @custom:selector d818da2b
+    /// @param streamId The id of the stream
+    function cancelChangeRequest(uint64 streamId) external;
+
+    /// @dev Immediately change the deposit of a stream the caller is the source of.
+    /// @custom:selector 12eaa52a
+    /// @param streamId The id of the stream
+    /// @param asset The address of the ERC20 precompile of the asset currently used by the stream
+    /// @param depositChangeKind How the deposit should change, cannot be `None`
+    /// @param depositChangeAmount The amount used by the deposit change
+    function changeDeposit(
+        uint64 streamId,
+        address asset,
+        DepositChangeKind depositChangeKind,
+        uint256 depositChangeAmount
+    ) external;
+}
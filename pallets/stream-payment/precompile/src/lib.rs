@@ -0,0 +1,265 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Precompile to interact with pallet-stream-payment from Solidity.
+//! See `StreamPayment.sol` for the Solidity interface.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use {
+    fp_evm::PrecompileHandle,
+    frame_support::{
+        dispatch::{GetDispatchInfo, PostDispatchInfo},
+        pallet_prelude::MaxEncodedLen,
+    },
+    pallet_evm::AddressMapping,
+    pallet_stream_payment::{ChangeKind, DepositChange, StreamConfig},
+    precompile_utils::prelude::*,
+    sp_core::{H160, U256},
+    sp_runtime::traits::Dispatchable,
+};
+
+/// Solidity value for the absence of a deposit change.
+pub const DEPOSIT_CHANGE_NONE: u8 = 0;
+/// Solidity value for `DepositChange::Increase`.
+pub const DEPOSIT_CHANGE_INCREASE: u8 = 1;
+/// Solidity value for `DepositChange::Decrease`.
+pub const DEPOSIT_CHANGE_DECREASE: u8 = 2;
+/// Solidity value for `DepositChange::Absolute`.
+pub const DEPOSIT_CHANGE_ABSOLUTE: u8 = 3;
+
+/// Conversions from the values used in the Solidity interface to the stream
+/// payment types of the runtime.
+pub trait StreamPaymentTypesConverter<TimeUnit, AssetId> {
+    /// Time unit represented by `unit`.
+    fn time_unit(unit: u8) -> Option<TimeUnit>;
+
+    /// Asset represented by the address of its ERC20 precompile.
+    fn asset_id(asset: H160) -> Option<AssetId>;
+}
+
+type StreamIdOf<Runtime> = <Runtime as pallet_stream_payment::Config>::StreamId;
+type AssetIdOf<Runtime> = <Runtime as pallet_stream_payment::Config>::AssetId;
+type BalanceOf<Runtime> = <Runtime as pallet_stream_payment::Config>::Balance;
+type StreamConfigOf<Runtime> = StreamConfig<
+    <Runtime as pallet_stream_payment::Config>::TimeUnit,
+    AssetIdOf<Runtime>,
+    BalanceOf<Runtime>,
+>;
+
+pub struct StreamPaymentPrecompile<Runtime, Converter>(
+    core::marker::PhantomData<(Runtime, Converter)>,
+);
+
+#[precompile_utils::precompile]
+impl<Runtime, Converter> StreamPaymentPrecompile<Runtime, Converter>
+where
+    Runtime: pallet_stream_payment::Config + pallet_evm::Config + frame_system::Config,
+    Runtime::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+        + GetDispatchInfo
+        + From<pallet_stream_payment::Call<Runtime>>,
+    <Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+    Runtime::AddressMapping: AddressMapping<Runtime::AccountId>,
+    StreamIdOf<Runtime>: From<u64> + Into<u64> + MaxEncodedLen,
+    BalanceOf<Runtime>: TryFrom<U256>,
+    Converter: StreamPaymentTypesConverter<
+        <Runtime as pallet_stream_payment::Config>::TimeUnit,
+        AssetIdOf<Runtime>,
+    >,
+{
+    #[precompile::public("openStream(address,uint8,address,uint256,uint256)")]
+    fn open_stream(
+        handle: &mut impl PrecompileHandle,
+        target: Address,
+        time_unit: u8,
+        asset: Address,
+        rate: U256,
+        initial_deposit: U256,
+    ) -> EvmResult<u64> {
+        let config = Self::stream_config(time_unit, asset, rate)?;
+        let initial_deposit = Self::balance(initial_deposit, "initialDeposit")?;
+        let target = Runtime::AddressMapping::into_account_id(target.into());
+
+        // The stream will be created with the next available id.
+        // NextStreamId: StreamId
+        handle.record_db_read::<Runtime>(<StreamIdOf<Runtime>>::max_encoded_len())?;
+        let stream_id = pallet_stream_payment::NextStreamId::<Runtime>::get();
+
+        let call = pallet_stream_payment::Call::<Runtime>::open_stream {
+            target,
+            config,
+            initial_deposit,
+        };
+        Self::dispatch(handle, call)?;
+
+        Ok(stream_id.into())
+    }
+
+    #[precompile::public("closeStream(uint64)")]
+    fn close_stream(handle: &mut impl PrecompileHandle, stream_id: u64) -> EvmResult {
+        let call = pallet_stream_payment::Call::<Runtime>::close_stream {
+            stream_id: stream_id.into(),
+        };
+        Self::dispatch(handle, call)
+    }
+
+    #[precompile::public("performPayment(uint64)")]
+    fn perform_payment(handle: &mut impl PrecompileHandle, stream_id: u64) -> EvmResult {
+        let call = pallet_stream_payment::Call::<Runtime>::perform_payment {
+            stream_id: stream_id.into(),
+        };
+        Self::dispatch(handle, call)
+    }
+
+    #[precompile::public("requestChange(uint64,uint8,address,uint256,bool,uint256,uint8,uint256)")]
+    fn request_change(
+        handle: &mut impl PrecompileHandle,
+        stream_id: u64,
+        time_unit: u8,
+        asset: Address,
+        rate: U256,
+        mandatory: bool,
+        deadline: U256,
+        deposit_change_kind: u8,
+        deposit_change_amount: U256,
+    ) -> EvmResult {
+        let new_config = Self::stream_config(time_unit, asset, rate)?;
+        let kind = if mandatory {
+            ChangeKind::Mandatory {
+                deadline: Self::balance(deadline, "deadline")?,
+            }
+        } else {
+            ChangeKind::Suggestion
+        };
+        let deposit_change = Self::deposit_change(deposit_change_kind, deposit_change_amount)?;
+
+        let call = pallet_stream_payment::Call::<Runtime>::request_change {
+            stream_id: stream_id.into(),
+            kind,
+            new_config,
+            deposit_change,
+        };
+        Self::dispatch(handle, call)
+    }
+
+    #[precompile::public("acceptRequestedChange(uint64,uint32,uint8,uint256)")]
+    fn accept_requested_change(
+        handle: &mut impl PrecompileHandle,
+        stream_id: u64,
+        request_nonce: u32,
+        deposit_change_kind: u8,
+        deposit_change_amount: U256,
+    ) -> EvmResult {
+        let deposit_change = Self::deposit_change(deposit_change_kind, deposit_change_amount)?;
+
+        let call = pallet_stream_payment::Call::<Runtime>::accept_requested_change {
+            stream_id: stream_id.into(),
+            request_nonce,
+            deposit_change,
+        };
+        Self::dispatch(handle, call)
+    }
+
+    #[precompile::public("cancelChangeRequest(uint64)")]
+    fn cancel_change_request(handle: &mut impl PrecompileHandle, stream_id: u64) -> EvmResult {
+        let call = pallet_stream_payment::Call::<Runtime>::cancel_change_request {
+            stream_id: stream_id.into(),
+        };
+        Self::dispatch(handle, call)
+    }
+
+    #[precompile::public("changeDeposit(uint64,address,uint8,uint256)")]
+    fn change_deposit(
+        handle: &mut impl PrecompileHandle,
+        stream_id: u64,
+        asset: Address,
+        deposit_change_kind: u8,
+        deposit_change_amount: U256,
+    ) -> EvmResult {
+        let asset_id = Self::asset_id(asset)?;
+        let change =
+            Self::deposit_change(deposit_change_kind, deposit_change_amount)?.ok_or_else(|| {
+                RevertReason::custom("Deposit change is required").in_field("depositChangeKind")
+            })?;
+
+        let call = pallet_stream_payment::Call::<Runtime>::immediately_change_deposit {
+            stream_id: stream_id.into(),
+            asset_id,
+            change,
+        };
+        Self::dispatch(handle, call)
+    }
+
+    fn dispatch(
+        handle: &mut impl PrecompileHandle,
+        call: pallet_stream_payment::Call<Runtime>,
+    ) -> EvmResult {
+        let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+        RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+        Ok(())
+    }
+
+    fn stream_config(
+        time_unit: u8,
+        asset: Address,
+        rate: U256,
+    ) -> EvmResult<StreamConfigOf<Runtime>> {
+        let time_unit = Converter::time_unit(time_unit)
+            .ok_or_else(|| RevertReason::custom("Unknown time unit").in_field("timeUnit"))?;
+
+        Ok(StreamConfig {
+            time_unit,
+            asset_id: Self::asset_id(asset)?,
+            rate: Self::balance(rate, "rate")?,
+        })
+    }
+
+    fn asset_id(asset: Address) -> EvmResult<AssetIdOf<Runtime>> {
+        Converter::asset_id(asset.into()).ok_or_else(|| {
+            RevertReason::custom("Unknown asset")
+                .in_field("asset")
+                .into()
+        })
+    }
+
+    fn balance(value: U256, field: &'static str) -> EvmResult<BalanceOf<Runtime>> {
+        value.try_into().map_err(|_| {
+            RevertReason::value_is_too_large("balance type")
+                .in_field(field)
+                .into()
+        })
+    }
+
+    fn deposit_change(
+        kind: u8,
+        amount: U256,
+    ) -> EvmResult<Option<DepositChange<BalanceOf<Runtime>>>> {
+        let change = match kind {
+            DEPOSIT_CHANGE_NONE => return Ok(None),
+            DEPOSIT_CHANGE_INCREASE => DepositChange::Increase,
+            DEPOSIT_CHANGE_DECREASE => DepositChange::Decrease,
+            DEPOSIT_CHANGE_ABSOLUTE => DepositChange::Absolute,
+            _ => {
+                return Err(RevertReason::custom("Unknown deposit change kind")
+                    .in_field("depositChangeKind")
+                    .into())
+            }
+        };
+
+        Ok(Some(change(Self::balance(amount, "depositChangeAmount")?)))
+    }
+}
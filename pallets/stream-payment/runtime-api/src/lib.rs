@@ -21,7 +21,7 @@
 extern crate alloc;
 
 use {
-    alloc::string::String,
+    alloc::{string::String, vec::Vec},
     parity_scale_codec::{Decode, Encode},
     serde::{Deserialize, Serialize},
 };
@@ -32,9 +32,95 @@ use {
 pub struct StreamPaymentApiStatus<Balance> {
     pub payment: Balance,
     pub deposit_left: Balance,
-    /// Whenever the stream is stalled, which can occur either when no funds are left or
-    /// if the time is past a mandatory request deadline.
+    /// Whenever the stream is stalled, which can occur either when no funds are left,
+    /// if the time is past a mandatory request deadline or if the stream has ended.
     pub stalled: bool,
+    /// Whenever the stream start time has been reached.
+    pub started: bool,
+    /// Whenever the stream end time has been reached.
+    pub ended: bool,
+}
+
+/// Status returned by `stream_payment_status` before version 2 of the API.
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo, Serialize, Deserialize,
+)]
+pub struct StreamPaymentApiStatusV1<Balance> {
+    pub payment: Balance,
+    pub deposit_left: Balance,
+    pub stalled: bool,
+}
+
+impl<Balance> From<StreamPaymentApiStatusV1<Balance>> for StreamPaymentApiStatus<Balance> {
+    /// Streams could not have a start or end time before version 2, so they are always
+    /// started and never ended.
+    fn from(status: StreamPaymentApiStatusV1<Balance>) -> Self {
+        Self {
+            payment: status.payment,
+            deposit_left: status.deposit_left,
+            stalled: status.stalled,
+            started: true,
+            ended: false,
+        }
+    }
+}
+
+/// Party of a stream. Additional targets of a stream are considered targets.
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo, Serialize, Deserialize,
+)]
+pub enum StreamPaymentApiParty {
+    Source,
+    Target,
+}
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo, Serialize, Deserialize,
+)]
+pub enum StreamPaymentApiDepositChange<Balance> {
+    Increase(Balance),
+    Decrease(Balance),
+    Absolute(Balance),
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo, Serialize, Deserialize,
+)]
+pub enum StreamPaymentApiEventKind<AccountId, Balance> {
+    Opened,
+    Closed {
+        refunded: Balance,
+    },
+    /// `amount` excludes what was paid to additional targets in `PaymentSplit`.
+    Payment {
+        source: AccountId,
+        target: AccountId,
+        amount: Balance,
+        stalled: bool,
+    },
+    PaymentSplit {
+        target: AccountId,
+        amount: Balance,
+    },
+    ChangeRequested {
+        request_nonce: u32,
+        requester: StreamPaymentApiParty,
+    },
+    ConfigChanged {
+        deposit_change: Option<StreamPaymentApiDepositChange<Balance>>,
+    },
+}
+
+/// Event emitted by the stream payment pallet.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo, Serialize, Deserialize,
+)]
+pub struct StreamPaymentApiEvent<AccountId, StreamId, Balance> {
+    pub stream_id: StreamId,
+    /// Source, target and additional targets of the stream at the end of the block.
+    /// Empty if the stream no longer exists.
+    pub accounts: Vec<AccountId>,
+    pub kind: StreamPaymentApiEventKind<AccountId, Balance>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo)]
@@ -47,21 +133,44 @@ pub enum StreamPaymentApiError {
 }
 
 sp_api::decl_runtime_apis! {
-    pub trait StreamPaymentApi<StreamId, Instant, Balance>
+    #[api_version(2)]
+    pub trait StreamPaymentApi<StreamId, Instant, Balance, AccountId>
     where
         StreamId: parity_scale_codec::Codec,
         Instant: parity_scale_codec::Codec,
         Balance: parity_scale_codec::Codec,
+        AccountId: parity_scale_codec::Codec,
     {
+        #[changed_in(2)]
+        fn stream_payment_status(
+            stream_id: StreamId,
+            now: Option<Instant>,
+        ) -> Result<StreamPaymentApiStatusV1<Balance>, StreamPaymentApiError>;
+
         /// Get the stream payment current status, telling how much payment is
-        /// pending, how much deposit will be left and whenever the stream is stalled.
-        /// The stream is considered stalled if no funds are left or if the provided
-        /// time is past a mandatory request deadline. If the provided `now` is `None`
-        /// then the current time will be fetched. Being able to provide a custom `now`
-        /// allows to check the status in the future.
+        /// pending, how much deposit will be left and whenever the stream is stalled,
+        /// started or ended. The stream is considered stalled if no funds are left or if
+        /// the provided time is past a mandatory request deadline. If the provided `now`
+        /// is `None` then the current time will be fetched. Being able to provide a custom
+        /// `now` allows to check the status in the future.
         fn stream_payment_status(
             stream_id: StreamId,
             now: Option<Instant>,
         ) -> Result<StreamPaymentApiStatus<Balance>, StreamPaymentApiError>;
+
+        /// Streams in which the account is the given party, at most `limit` of them.
+        /// Streams are returned in storage order, starting after `start_after` if provided,
+        /// which allows to paginate by providing the last stream id of the previous page.
+        #[api_version(2)]
+        fn account_streams(
+            account: AccountId,
+            party: StreamPaymentApiParty,
+            start_after: Option<StreamId>,
+            limit: u32,
+        ) -> Vec<StreamId>;
+
+        /// Events emitted by the stream payment pallet in the block.
+        #[api_version(2)]
+        fn stream_events() -> Vec<StreamPaymentApiEvent<AccountId, StreamId, Balance>>;
     }
 }
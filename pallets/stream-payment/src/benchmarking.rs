@@ -17,13 +17,32 @@
 use {
     crate::{
         Assets, Call, ChangeKind, Config, DepositChange, Event, Pallet, Party, StreamConfig,
-        Streams, TimeProvider,
+        StreamSchedule, StreamSplit, StreamSplits, Streams, TimeProvider,
     },
     frame_benchmarking::{account, v2::*, BenchmarkError},
-    frame_support::{assert_ok, dispatch::RawOrigin},
+    frame_support::{assert_ok, dispatch::RawOrigin, traits::Get, BoundedVec},
     frame_system::EventRecord,
+    sp_runtime::Perbill,
+    sp_std::vec::Vec,
 };
 
+/// Create `s` funded additional targets sharing equally half of each payment.
+fn create_splits<T: Config>(
+    s: u32,
+    asset_id: &T::AssetId,
+) -> BoundedVec<StreamSplit<T::AccountId>, T::MaxStreamSplits> {
+    let ratio = Perbill::from_rational(1, 2 * s.max(1));
+
+    (0..s)
+        .map(|i| StreamSplit {
+            account: create_funded_user::<T>("split", i, asset_id),
+            ratio,
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .expect("s is bounded by MaxStreamSplits")
+}
+
 /// Create a funded user.
 fn create_funded_user<T: Config>(
     string: &'static str,
@@ -85,7 +104,6 @@ mod benchmarks {
 
     #[benchmark]
     fn close_stream() -> Result<(), BenchmarkError> {
-        use frame_support::traits::Get;
         // Worst case is closing a stream with a pending payment.
         let time_unit = T::TimeProvider::bench_worst_case_time_unit();
         let asset_id = T::Assets::bench_worst_case_asset_id();
@@ -429,6 +447,85 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn open_scheduled_stream(
+        s: Linear<0, { T::MaxStreamSplits::get() }>,
+    ) -> Result<(), BenchmarkError> {
+        let asset_id = T::Assets::bench_worst_case_asset_id();
+        let time_unit = T::TimeProvider::bench_worst_case_time_unit();
+
+        let source = create_funded_user::<T>("source", 1, &asset_id);
+        let target = create_funded_user::<T>("target", 2, &asset_id);
+        let splits = create_splits::<T>(s, &asset_id);
+
+        let now = T::TimeProvider::now(&time_unit).expect("can fetch time");
+        let schedule = StreamSchedule {
+            start: Some(now + 10u32.into()),
+            end: Some(now + 100u32.into()),
+        };
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(source.clone()),
+            target,
+            StreamConfig {
+                time_unit,
+                asset_id,
+                rate: 100u32.into(),
+            },
+            1_000_000u32.into(),
+            schedule,
+            splits,
+        );
+
+        assert_last_event::<T>(
+            Event::StreamOpened {
+                stream_id: 0u32.into(),
+            }
+            .into(),
+        );
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn pay_splits(s: Linear<0, { T::MaxStreamSplits::get() }>) -> Result<(), BenchmarkError> {
+        let time_unit = T::TimeProvider::bench_worst_case_time_unit();
+        let asset_id = T::Assets::bench_worst_case_asset_id();
+
+        let source = create_funded_user::<T>("source", 1, &asset_id);
+        let target = create_funded_user::<T>("target", 2, &asset_id);
+        let splits = create_splits::<T>(s, &asset_id);
+
+        let initial_deposit = 1_000_000u32.into();
+
+        assert_ok!(Pallet::<T>::open_scheduled_stream(
+            RawOrigin::Signed(source.clone()).into(),
+            target,
+            StreamConfig {
+                time_unit,
+                asset_id,
+                rate: 100u32.into(),
+            },
+            initial_deposit,
+            Default::default(),
+            splits,
+        ));
+
+        let stream_id: T::StreamId = 0u32.into();
+        let stream = Streams::<T>::get(stream_id).expect("to be a stream");
+        let payment: T::Balance = 1_000u32.into();
+
+        #[block]
+        {
+            assert_ok!(Pallet::<T>::pay_splits(stream_id, &stream, payment));
+        }
+
+        assert_eq!(StreamSplits::<T>::get(stream_id).len() as u32, s);
+
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(
         Pallet,
         crate::mock::ExtBuilder::default().build(),
@@ -30,12 +30,15 @@ pub mod weights;
 pub use weights::WeightInfo;
 
 use {
-    core::cmp::min,
+    core::cmp::{max, min},
     frame_support::{
         dispatch::DispatchErrorWithPostInfo,
         pallet,
         pallet_prelude::*,
-        storage::types::{StorageDoubleMap, StorageMap},
+        storage::{
+            types::{StorageDoubleMap, StorageMap},
+            with_storage_layer,
+        },
         traits::{
             fungible::{Inspect, MutateHold},
             tokens::{Balance, Precision},
@@ -43,14 +46,18 @@ use {
         Blake2_128Concat,
     },
     frame_system::pallet_prelude::*,
+    pallet_stream_payment_runtime_api::{
+        StreamPaymentApiDepositChange, StreamPaymentApiEvent, StreamPaymentApiEventKind,
+        StreamPaymentApiParty,
+    },
     parity_scale_codec::{FullCodec, MaxEncodedLen},
     scale_info::TypeInfo,
     serde::{Deserialize, Serialize},
     sp_runtime::{
         traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, One, Saturating, Zero},
-        ArithmeticError,
+        ArithmeticError, Perbill,
     },
-    sp_std::{fmt::Debug, marker::PhantomData},
+    sp_std::{collections::btree_set::BTreeSet, fmt::Debug, marker::PhantomData, vec, vec::Vec},
 };
 
 pub use pallet::*;
@@ -155,6 +162,11 @@ pub mod pallet {
         #[pallet::constant]
         type OpenStreamHoldAmount: Get<Self::Balance>;
 
+        /// Additional opening balance hold for each additional target of a stream, for the
+        /// storage used by its split and its target lookup entry.
+        #[pallet::constant]
+        type OpenStreamSplitHoldAmount: Get<Self::Balance>;
+
         /// Represents which units of time can be used. Designed to be an enum
         /// with a variant for each kind of time source/scale supported.
         type TimeUnit: Debug + Clone + FullCodec + TypeInfo + MaxEncodedLen + Eq;
@@ -162,6 +174,15 @@ pub mod pallet {
         /// Provide the current time in given unit.
         type TimeProvider: TimeProvider<Self::TimeUnit, Self::Balance>;
 
+        /// Maximum amount of additional targets a stream payment can be split with.
+        #[pallet::constant]
+        type MaxStreamSplits: Get<u32>;
+
+        /// Maximum amount of streams checked in `on_idle` each block to settle them and close the
+        /// ones that can no longer pay. Automatic settlement is disabled if 0.
+        #[pallet::constant]
+        type AutoSettlementScanLimit: Get<u32>;
+
         type WeightInfo: weights::WeightInfo;
     }
 
@@ -204,6 +225,8 @@ pub mod pallet {
         pub pending_request: Option<ChangeRequest<Unit, AssetId, Balance>>,
         /// One-time opening deposit. Will be released on close.
         pub opening_deposit: Balance,
+        /// Time bounds of the stream, in `config.time_unit`.
+        pub schedule: StreamSchedule<Balance>,
     }
 
     impl<AccountId: PartialEq, Unit, AssetId, Balance> Stream<AccountId, Unit, AssetId, Balance> {
@@ -239,6 +262,55 @@ pub mod pallet {
         pub rate: Balance,
     }
 
+    /// Optional time bounds of a stream, expressed in the stream time unit.
+    /// Nothing is paid for the time before `start` or after `end`.
+    #[derive(
+        RuntimeDebug,
+        PartialEq,
+        Eq,
+        Encode,
+        Decode,
+        Copy,
+        Clone,
+        Default,
+        TypeInfo,
+        Serialize,
+        Deserialize,
+        MaxEncodedLen,
+    )]
+    pub struct StreamSchedule<Time> {
+        /// Time at which the stream starts paying. Starts immediately if `None`.
+        pub start: Option<Time>,
+        /// Time at which the stream stops paying. Never ends if `None`.
+        pub end: Option<Time>,
+    }
+
+    impl<Time> StreamSchedule<Time> {
+        pub fn is_unbounded(&self) -> bool {
+            self.start.is_none() && self.end.is_none()
+        }
+    }
+
+    /// Additional target of a stream, receiving `ratio` of each payment.
+    /// What is not sent to additional targets is paid to the stream target.
+    #[derive(
+        RuntimeDebug,
+        PartialEq,
+        Eq,
+        Encode,
+        Decode,
+        Copy,
+        Clone,
+        TypeInfo,
+        Serialize,
+        Deserialize,
+        MaxEncodedLen,
+    )]
+    pub struct StreamSplit<AccountId> {
+        pub account: AccountId,
+        pub ratio: Perbill,
+    }
+
     /// Origin of a change request.
     #[derive(
         RuntimeDebug,
@@ -343,19 +415,32 @@ pub mod pallet {
     pub type ChangeRequestOf<T> =
         ChangeRequest<<T as Config>::TimeUnit, AssetIdOf<T>, <T as Config>::Balance>;
 
+    pub type StreamSplitsOf<T> =
+        BoundedVec<StreamSplit<AccountIdOf<T>>, <T as Config>::MaxStreamSplits>;
+
     #[derive(Debug, Copy, Clone, PartialEq, Eq)]
     pub struct StreamPaymentStatus<Balance> {
         pub payment: Balance,
         pub deposit_left: Balance,
-        /// Whenever the stream is stalled, which can occur either when no funds are left or
-        /// if the time is past a mandatory request deadline.
+        /// Whenever the stream is stalled, which can occur either when no funds are left,
+        /// if the time is past a mandatory request deadline or if the stream has ended.
         pub stalled: bool,
+        /// Whenever the stream start time has been reached.
+        pub started: bool,
+        /// Whenever the stream end time has been reached.
+        pub ended: bool,
     }
 
     /// Store the next available stream id.
     #[pallet::storage]
     pub type NextStreamId<T: Config> = StorageValue<Value = T::StreamId, QueryKind = ValueQuery>;
 
+    /// Last stream checked in `on_idle`. Next block will continue from the following
+    /// stream, or from the start if `None`.
+    #[pallet::storage]
+    pub type AutoSettlementCursor<T: Config> =
+        StorageValue<Value = T::StreamId, QueryKind = OptionQuery>;
+
     /// Store each stream indexed by an Id.
     #[pallet::storage]
     pub type Streams<T: Config> = StorageMap<
@@ -365,6 +450,15 @@ pub mod pallet {
         QueryKind = OptionQuery,
     >;
 
+    /// Additional targets of a stream, if any.
+    #[pallet::storage]
+    pub type StreamSplits<T: Config> = StorageMap<
+        Hasher = Blake2_128Concat,
+        Key = T::StreamId,
+        Value = StreamSplitsOf<T>,
+        QueryKind = ValueQuery,
+    >;
+
     /// Lookup for all streams with given source.
     /// To avoid maintaining a growing list of stream ids, they are stored in
     /// the form of an entry (AccountId, StreamId). If such entry exists then
@@ -383,7 +477,7 @@ pub mod pallet {
     /// Lookup for all streams with given target.
     /// To avoid maintaining a growing list of stream ids, they are stored in
     /// the form of an entry (AccountId, StreamId). If such entry exists then
-    /// this AccountId is a target in StreamId. One can iterate over all storage
+    /// this AccountId is a target or an additional target in StreamId. One can iterate over all storage
     /// keys starting with the AccountId to find all StreamIds.
     #[pallet::storage]
     pub type LookupStreamsWithTarget<T: Config> = StorageDoubleMap<
@@ -415,6 +509,10 @@ pub mod pallet {
         ImmediateDepositChangeRequiresSameAssetId,
         DeadlineCantBeInPast,
         CantFetchStatusBeforeLastTimeUpdated,
+        InvalidSchedule,
+        InvalidSplitAccount,
+        SplitRatiosExceedOne,
+        ScheduledStreamCantChangeTimeUnit,
     }
 
     #[pallet::event]
@@ -427,6 +525,8 @@ pub mod pallet {
             stream_id: T::StreamId,
             refunded: T::Balance,
         },
+        /// Stream paid `amount` to its target, which excludes what was paid to its
+        /// additional targets in `StreamPaymentSplit`.
         StreamPayment {
             stream_id: T::StreamId,
             source: AccountIdOf<T>,
@@ -434,6 +534,11 @@ pub mod pallet {
             amount: T::Balance,
             stalled: bool,
         },
+        StreamPaymentSplit {
+            stream_id: T::StreamId,
+            target: AccountIdOf<T>,
+            amount: T::Balance,
+        },
         StreamConfigChangeRequested {
             stream_id: T::StreamId,
             request_nonce: RequestNonce,
@@ -462,6 +567,13 @@ pub mod pallet {
         StreamOpened,
     }
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::auto_settle_streams(remaining_weight)
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Create a payment stream from the origin to the target with provided config
@@ -484,7 +596,10 @@ pub mod pallet {
         /// Close a given stream in which the origin is involved. It performs the pending payment
         /// before closing the stream.
         #[pallet::call_index(1)]
-        #[pallet::weight(T::WeightInfo::close_stream())]
+        #[pallet::weight(
+            T::WeightInfo::close_stream()
+            .saturating_add(T::WeightInfo::pay_splits(T::MaxStreamSplits::get()))
+        )]
         pub fn close_stream(
             origin: OriginFor<T>,
             stream_id: T::StreamId,
@@ -501,36 +616,17 @@ pub mod pallet {
             // Update stream before closing it to ensure fair payment.
             Self::perform_stream_payment(stream_id, &mut stream)?;
 
-            // Unfreeze funds left in the stream.
-            T::Assets::decrease_deposit(&stream.config.asset_id, &stream.source, stream.deposit)?;
-
-            // Release opening deposit
-            if stream.opening_deposit > 0u32.into() {
-                T::Currency::release(
-                    &HoldReason::StreamOpened.into(),
-                    &stream.source,
-                    stream.opening_deposit,
-                    Precision::Exact,
-                )?;
-            }
-
-            // Remove stream from storage.
-            Streams::<T>::remove(stream_id);
-            LookupStreamsWithSource::<T>::remove(stream.source, stream_id);
-            LookupStreamsWithTarget::<T>::remove(stream.target, stream_id);
-
-            // Emit event.
-            Pallet::<T>::deposit_event(Event::<T>::StreamClosed {
-                stream_id,
-                refunded: stream.deposit.saturating_add(stream.opening_deposit),
-            });
+            Self::close_settled_stream(stream_id, stream)?;
 
             Ok(().into())
         }
 
         /// Perform the pending payment of a stream. Anyone can call this.
         #[pallet::call_index(2)]
-        #[pallet::weight(T::WeightInfo::perform_payment())]
+        #[pallet::weight(
+            T::WeightInfo::perform_payment()
+            .saturating_add(T::WeightInfo::pay_splits(T::MaxStreamSplits::get()))
+        )]
         pub fn perform_payment(
             origin: OriginFor<T>,
             stream_id: T::StreamId,
@@ -557,6 +653,7 @@ pub mod pallet {
         #[pallet::call_index(3)]
         #[pallet::weight(
             T::WeightInfo::request_change_immediate()
+            .saturating_add(T::WeightInfo::pay_splits(T::MaxStreamSplits::get()))
             .max(T::WeightInfo::request_change_delayed())
         )]
         pub fn request_change(
@@ -582,6 +679,12 @@ pub mod pallet {
                 return Ok(().into());
             }
 
+            // Schedule is expressed in the stream time unit, which thus can't be changed.
+            ensure!(
+                stream.schedule.is_unbounded() || new_config.time_unit == stream.config.time_unit,
+                Error::<T>::ScheduledStreamCantChangeTimeUnit
+            );
+
             if let ChangeKind::Mandatory { deadline } = kind {
                 let now = T::TimeProvider::now(&stream.config.time_unit)
                     .ok_or(Error::<T>::CantFetchCurrentTime)?;
@@ -651,7 +754,10 @@ pub mod pallet {
         /// frontrunning attacks. If the target made a request, the source is able to change their
         /// deposit.
         #[pallet::call_index(4)]
-        #[pallet::weight(T::WeightInfo::accept_requested_change())]
+        #[pallet::weight(
+            T::WeightInfo::accept_requested_change()
+            .saturating_add(T::WeightInfo::pay_splits(T::MaxStreamSplits::get()))
+        )]
         pub fn accept_requested_change(
             origin: OriginFor<T>,
             stream_id: T::StreamId,
@@ -778,7 +884,10 @@ pub mod pallet {
         /// the call is included in a block, in which case the unit is no longer the same and quantities
         /// will not have the same scale/value.
         #[pallet::call_index(6)]
-        #[pallet::weight(T::WeightInfo::immediately_change_deposit())]
+        #[pallet::weight(
+            T::WeightInfo::immediately_change_deposit()
+            .saturating_add(T::WeightInfo::pay_splits(T::MaxStreamSplits::get()))
+        )]
         pub fn immediately_change_deposit(
             origin: OriginFor<T>,
             stream_id: T::StreamId,
@@ -813,6 +922,34 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Create a payment stream from the origin to the target with provided config
+        /// and initial deposit (in the asset defined in the config), which only pays for the time
+        /// inside the provided schedule. Each payment is split between the target and the
+        /// additional targets according to their ratios.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::open_scheduled_stream(splits.len() as u32))]
+        pub fn open_scheduled_stream(
+            origin: OriginFor<T>,
+            target: AccountIdOf<T>,
+            config: StreamConfigOf<T>,
+            initial_deposit: T::Balance,
+            schedule: StreamSchedule<T::Balance>,
+            splits: StreamSplitsOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let origin = ensure_signed(origin)?;
+
+            let _stream_id = Self::open_scheduled_stream_returns_id(
+                origin,
+                target,
+                config,
+                initial_deposit,
+                schedule,
+                splits,
+            )?;
+
+            Ok(().into())
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -824,8 +961,30 @@ pub mod pallet {
             target: AccountIdOf<T>,
             config: StreamConfigOf<T>,
             initial_deposit: T::Balance,
+        ) -> Result<T::StreamId, DispatchErrorWithPostInfo> {
+            Self::open_scheduled_stream_returns_id(
+                origin,
+                target,
+                config,
+                initial_deposit,
+                Default::default(),
+                Default::default(),
+            )
+        }
+
+        /// Try to open a scheduled stream and returns its id.
+        /// Prefers calling this function from other pallets instead of `open_scheduled_stream`
+        /// as the latter can't return the id.
+        pub fn open_scheduled_stream_returns_id(
+            origin: AccountIdOf<T>,
+            target: AccountIdOf<T>,
+            config: StreamConfigOf<T>,
+            initial_deposit: T::Balance,
+            schedule: StreamSchedule<T::Balance>,
+            splits: StreamSplitsOf<T>,
         ) -> Result<T::StreamId, DispatchErrorWithPostInfo> {
             ensure!(origin != target, Error::<T>::CantBeBothSourceAndTarget);
+            Self::ensure_valid_splits(&origin, &target, &splits)?;
 
             // Generate a new stream id.
             let stream_id = NextStreamId::<T>::get();
@@ -834,8 +993,10 @@ pub mod pallet {
                 .ok_or(Error::<T>::StreamIdOverflow)?;
             NextStreamId::<T>::set(next_stream_id);
 
-            // Hold opening deposit for the storage used by Stream
-            let opening_deposit = T::OpenStreamHoldAmount::get();
+            // Hold opening deposit for the storage used by Stream and its splits
+            let opening_deposit = T::OpenStreamSplitHoldAmount::get()
+                .saturating_mul((splits.len() as u32).into())
+                .saturating_add(T::OpenStreamHoldAmount::get());
             if opening_deposit > 0u32.into() {
                 T::Currency::hold(&HoldReason::StreamOpened.into(), &origin, opening_deposit)?;
             }
@@ -846,6 +1007,14 @@ pub mod pallet {
             // Create stream data.
             let now =
                 T::TimeProvider::now(&config.time_unit).ok_or(Error::<T>::CantFetchCurrentTime)?;
+
+            if let Some(end) = schedule.end {
+                ensure!(
+                    end > now && schedule.start.map_or(true, |start| start < end),
+                    Error::<T>::InvalidSchedule
+                );
+            }
+
             let stream = Stream {
                 source: origin.clone(),
                 target: target.clone(),
@@ -855,12 +1024,19 @@ pub mod pallet {
                 request_nonce: 0,
                 pending_request: None,
                 opening_deposit,
+                schedule,
             };
 
             // Insert stream in storage.
             Streams::<T>::insert(stream_id, stream);
             LookupStreamsWithSource::<T>::insert(origin, stream_id, ());
             LookupStreamsWithTarget::<T>::insert(target, stream_id, ());
+            for split in &splits {
+                LookupStreamsWithTarget::<T>::insert(&split.account, stream_id, ());
+            }
+            if !splits.is_empty() {
+                StreamSplits::<T>::insert(stream_id, splits);
+            }
 
             // Emit event.
             Pallet::<T>::deposit_event(Event::<T>::StreamOpened { stream_id });
@@ -870,11 +1046,11 @@ pub mod pallet {
 
         /// Get the stream payment current status, telling how much payment is
         /// pending, how much deposit will be left and whenever the stream is stalled.
-        /// The stream is considered stalled if no funds are left or if the provided
-        /// time is past a mandatory request deadline. If the provided `now` is `None`
-        /// then the current time will be fetched. Being able to provide a custom `now`
-        /// allows to check the status in the future. It is invalid to provide a `now` that is
-        /// before `last_time_updated`.
+        /// The stream is considered stalled if no funds are left, if the provided
+        /// time is past a mandatory request deadline or past the end of the stream.
+        /// If the provided `now` is `None` then the current time will be fetched. Being able
+        /// to provide a custom `now` allows to check the status in the future. It is invalid to
+        /// provide a `now` that is before `last_time_updated`.
         pub fn stream_payment_status(
             stream_id: T::StreamId,
             now: Option<T::Balance>,
@@ -896,12 +1072,166 @@ pub mod pallet {
             Self::stream_payment_status_by_ref(&stream, last_time_updated, now)
         }
 
+        /// Streams in which `account` is the given party, at most `limit` of them. Streams are
+        /// returned in storage order, starting after `start_after` if provided. Additional
+        /// targets of a stream are considered targets.
+        pub fn account_streams(
+            account: &AccountIdOf<T>,
+            party: Party,
+            start_after: Option<T::StreamId>,
+            limit: u32,
+        ) -> Vec<T::StreamId> {
+            let limit = limit as usize;
+
+            match (party, start_after) {
+                (Party::Source, None) => LookupStreamsWithSource::<T>::iter_key_prefix(account)
+                    .take(limit)
+                    .collect(),
+                (Party::Source, Some(stream_id)) => {
+                    LookupStreamsWithSource::<T>::iter_key_prefix_from(
+                        account,
+                        LookupStreamsWithSource::<T>::hashed_key_for(account, stream_id),
+                    )
+                    .take(limit)
+                    .collect()
+                }
+                (Party::Target, None) => LookupStreamsWithTarget::<T>::iter_key_prefix(account)
+                    .take(limit)
+                    .collect(),
+                (Party::Target, Some(stream_id)) => {
+                    LookupStreamsWithTarget::<T>::iter_key_prefix_from(
+                        account,
+                        LookupStreamsWithTarget::<T>::hashed_key_for(account, stream_id),
+                    )
+                    .take(limit)
+                    .collect()
+                }
+            }
+        }
+
+        /// Source, target and additional targets of a stream. Empty if the stream doesn't exist.
+        pub fn stream_accounts(stream_id: T::StreamId) -> Vec<AccountIdOf<T>> {
+            let Some(stream) = Streams::<T>::get(stream_id) else {
+                return Vec::new();
+            };
+
+            let mut accounts = vec![stream.source, stream.target];
+            accounts.extend(
+                StreamSplits::<T>::get(stream_id)
+                    .into_iter()
+                    .map(|split| split.account),
+            );
+            accounts
+        }
+
+        /// Events emitted by this pallet in the current block. Should only be used outside of
+        /// block execution, such as in runtime APIs.
+        pub fn block_events() -> Vec<Event<T>>
+        where
+            <T as Config>::RuntimeEvent: TryInto<Event<T>>,
+        {
+            frame_system::Pallet::<T>::read_events_no_consensus()
+                .filter_map(|record| {
+                    <T as Config>::RuntimeEvent::from(record.event)
+                        .try_into()
+                        .ok()
+                })
+                .collect()
+        }
+
+        /// Events emitted by this pallet in the current block, converted for the runtime API.
+        /// Events that don't concern a stream are skipped.
+        pub fn api_events() -> Vec<StreamPaymentApiEvent<AccountIdOf<T>, T::StreamId, T::Balance>>
+        where
+            <T as Config>::RuntimeEvent: TryInto<Event<T>>,
+        {
+            use StreamPaymentApiEventKind as Kind;
+
+            Self::block_events()
+                .into_iter()
+                .filter_map(|event| {
+                    let (stream_id, kind) = match event {
+                        Event::StreamOpened { stream_id } => (stream_id, Kind::Opened),
+                        Event::StreamClosed {
+                            stream_id,
+                            refunded,
+                        } => (stream_id, Kind::Closed { refunded }),
+                        Event::StreamPayment {
+                            stream_id,
+                            source,
+                            target,
+                            amount,
+                            stalled,
+                        } => (
+                            stream_id,
+                            Kind::Payment {
+                                source,
+                                target,
+                                amount,
+                                stalled,
+                            },
+                        ),
+                        Event::StreamPaymentSplit {
+                            stream_id,
+                            target,
+                            amount,
+                        } => (stream_id, Kind::PaymentSplit { target, amount }),
+                        Event::StreamConfigChangeRequested {
+                            stream_id,
+                            request_nonce,
+                            requester,
+                            ..
+                        } => {
+                            let requester = match requester {
+                                Party::Source => StreamPaymentApiParty::Source,
+                                Party::Target => StreamPaymentApiParty::Target,
+                            };
+                            (
+                                stream_id,
+                                Kind::ChangeRequested {
+                                    request_nonce,
+                                    requester,
+                                },
+                            )
+                        }
+                        Event::StreamConfigChanged {
+                            stream_id,
+                            deposit_change,
+                            ..
+                        } => {
+                            let deposit_change = deposit_change.map(|change| match change {
+                                DepositChange::Increase(x) => {
+                                    StreamPaymentApiDepositChange::Increase(x)
+                                }
+                                DepositChange::Decrease(x) => {
+                                    StreamPaymentApiDepositChange::Decrease(x)
+                                }
+                                DepositChange::Absolute(x) => {
+                                    StreamPaymentApiDepositChange::Absolute(x)
+                                }
+                            });
+                            (stream_id, Kind::ConfigChanged { deposit_change })
+                        }
+                        _ => return None,
+                    };
+
+                    Some(StreamPaymentApiEvent {
+                        stream_id,
+                        accounts: Self::stream_accounts(stream_id),
+                        kind,
+                    })
+                })
+                .collect()
+        }
+
         fn stream_payment_status_by_ref(
             stream: &StreamOf<T>,
             last_time_updated: T::Balance,
             mut now: T::Balance,
         ) -> Result<StreamPaymentStatus<T::Balance>, Error<T>> {
             let mut stalled_by_deadline = false;
+            let started = stream.schedule.start.map_or(true, |start| now >= start);
+            let ended = stream.schedule.end.map_or(false, |end| now >= end);
 
             // Take into account mandatory change request deadline. Note that
             // while it'll perform payment up to deadline,
@@ -919,22 +1249,38 @@ pub mod pallet {
                 }
             }
 
+            // Nothing is paid past the end of the stream.
+            if let Some(end) = stream.schedule.end {
+                now = min(now, end);
+            }
+
             // If deposit is zero the stream is fully drained and there is nothing to transfer.
             if stream.deposit.is_zero() {
                 return Ok(StreamPaymentStatus {
                     payment: 0u32.into(),
                     deposit_left: stream.deposit,
                     stalled: true,
+                    started,
+                    ended,
                 });
             }
 
+            // Nothing is paid before the start of the stream.
+            let last_time_updated = match stream.schedule.start {
+                Some(start) => max(last_time_updated, start),
+                None => last_time_updated,
+            };
+
             // Dont perform payment if now is before or equal to `last_time_updated`.
-            // It can be before due to the deadline adjustment.
+            // It can be before due to the deadline adjustment, the stream end or
+            // the stream not being started yet.
             let Some(delta) = now.checked_sub(&last_time_updated) else {
                 return Ok(StreamPaymentStatus {
                     payment: 0u32.into(),
                     deposit_left: stream.deposit,
-                    stalled: true,
+                    stalled: stalled_by_deadline || ended,
+                    started,
+                    ended,
                 });
             };
 
@@ -950,7 +1296,7 @@ pub mod pallet {
             // we pay all that is left.
             let (deposit_left, stalled) = match stream.deposit.checked_sub(&payment) {
                 Some(v) if v.is_zero() => (v, true),
-                Some(v) => (v, stalled_by_deadline),
+                Some(v) => (v, stalled_by_deadline || ended),
                 None => {
                     payment = stream.deposit;
                     (Zero::zero(), true)
@@ -961,6 +1307,8 @@ pub mod pallet {
                 payment,
                 deposit_left,
                 stalled,
+                started,
+                ended,
             })
         }
 
@@ -990,19 +1338,23 @@ pub mod pallet {
                 payment,
                 deposit_left,
                 stalled,
+                ..
             } = Self::stream_payment_status_by_ref(stream, last_time_updated, now)?;
 
             if payment.is_zero() {
                 return Ok(0u32.into());
             }
 
-            // Transfer from the source to target.
-            T::Assets::transfer_deposit(
-                &stream.config.asset_id,
-                &stream.source,
-                &stream.target,
-                payment,
-            )?;
+            // Transfer shares of the payment to additional targets, then the rest to the target.
+            let target_payment = Self::pay_splits(stream_id, stream, payment)?;
+            if !target_payment.is_zero() {
+                T::Assets::transfer_deposit(
+                    &stream.config.asset_id,
+                    &stream.source,
+                    &stream.target,
+                    target_payment,
+                )?;
+            }
 
             // Update stream info.
             stream.deposit = deposit_left;
@@ -1012,13 +1364,206 @@ pub mod pallet {
                 stream_id,
                 source: stream.source.clone(),
                 target: stream.target.clone(),
-                amount: payment,
+                amount: target_payment,
                 stalled,
             });
 
             Ok(payment)
         }
 
+        /// Refund the deposit left in an already settled stream, release its opening deposit and
+        /// remove it from storage.
+        fn close_settled_stream(stream_id: T::StreamId, stream: StreamOf<T>) -> DispatchResult {
+            // Unfreeze funds left in the stream.
+            T::Assets::decrease_deposit(&stream.config.asset_id, &stream.source, stream.deposit)?;
+
+            // Release opening deposit
+            if stream.opening_deposit > 0u32.into() {
+                T::Currency::release(
+                    &HoldReason::StreamOpened.into(),
+                    &stream.source,
+                    stream.opening_deposit,
+                    Precision::Exact,
+                )?;
+            }
+
+            // Remove stream from storage.
+            Streams::<T>::remove(stream_id);
+            LookupStreamsWithSource::<T>::remove(stream.source, stream_id);
+            LookupStreamsWithTarget::<T>::remove(stream.target, stream_id);
+            for split in StreamSplits::<T>::take(stream_id) {
+                LookupStreamsWithTarget::<T>::remove(split.account, stream_id);
+            }
+
+            // Emit event.
+            Pallet::<T>::deposit_event(Event::<T>::StreamClosed {
+                stream_id,
+                refunded: stream.deposit.saturating_add(stream.opening_deposit),
+            });
+
+            Ok(())
+        }
+
+        /// Settles streams and closes the ones that can no longer pay, starting after the cursor
+        /// left by the previous block. At most `AutoSettlementScanLimit` streams are checked, and
+        /// no stream is settled if it could exceed `remaining_weight`.
+        pub fn auto_settle_streams(remaining_weight: Weight) -> Weight {
+            let scan_limit = T::AutoSettlementScanLimit::get();
+            // Read and write of the cursor.
+            let mut used_weight = T::DbWeight::get().reads_writes(1, 1);
+
+            if scan_limit == 0 || used_weight.any_gt(remaining_weight) {
+                return Weight::zero();
+            }
+
+            // Every scanned stream is settled, while closing is only charged for the streams
+            // that are actually closed.
+            let settlement_weight = T::WeightInfo::perform_payment()
+                .saturating_add(T::WeightInfo::pay_splits(T::MaxStreamSplits::get()));
+            let closing_weight = T::WeightInfo::close_stream();
+            let scan_weight = T::DbWeight::get().reads(1);
+
+            let mut cursor = AutoSettlementCursor::<T>::get();
+            let mut iter = match cursor {
+                Some(stream_id) => Streams::<T>::iter_from(Streams::<T>::hashed_key_for(stream_id)),
+                None => Streams::<T>::iter(),
+            };
+            let mut reached_end = false;
+
+            for _ in 0..scan_limit {
+                if used_weight
+                    .saturating_add(scan_weight)
+                    .saturating_add(settlement_weight)
+                    .saturating_add(closing_weight)
+                    .any_gt(remaining_weight)
+                {
+                    break;
+                }
+
+                let Some((stream_id, mut stream)) = iter.next() else {
+                    reached_end = true;
+                    break;
+                };
+
+                used_weight.saturating_accrue(scan_weight.saturating_add(settlement_weight));
+                cursor = Some(stream_id);
+
+                let res = with_storage_layer(|| -> Result<bool, DispatchError> {
+                    Self::perform_stream_payment(stream_id, &mut stream).map_err(|e| e.error)?;
+
+                    if Self::can_no_longer_pay(&stream) {
+                        Self::close_settled_stream(stream_id, stream)?;
+                        Ok(true)
+                    } else {
+                        Streams::<T>::insert(stream_id, stream);
+                        Ok(false)
+                    }
+                });
+
+                match res {
+                    Ok(true) => used_weight.saturating_accrue(closing_weight),
+                    Ok(false) => (),
+                    Err(e) => log::warn!(
+                        target: "stream_payment",
+                        "failed to automatically settle stream {:?}: {:?}",
+                        stream_id,
+                        e
+                    ),
+                }
+            }
+
+            if reached_end {
+                AutoSettlementCursor::<T>::kill();
+            } else {
+                AutoSettlementCursor::<T>::set(cursor);
+            }
+
+            used_weight
+        }
+
+        /// A settled stream can no longer pay if its deposit is drained, if the deadline of a
+        /// mandatory change request is reached or if its end time is reached.
+        fn can_no_longer_pay(stream: &StreamOf<T>) -> bool {
+            if stream.deposit.is_zero() {
+                return true;
+            }
+
+            let Some(now) = T::TimeProvider::now(&stream.config.time_unit) else {
+                return false;
+            };
+
+            let deadline_reached = matches!(
+                &stream.pending_request,
+                Some(ChangeRequest {
+                    kind: ChangeKind::Mandatory { deadline },
+                    ..
+                }) if now >= *deadline
+            );
+
+            deadline_reached || stream.schedule.end.map_or(false, |end| now >= end)
+        }
+
+        /// Transfer to each additional target of the stream its share of `payment`.
+        /// Returns what is left to be paid to the stream target.
+        pub(crate) fn pay_splits(
+            stream_id: T::StreamId,
+            stream: &StreamOf<T>,
+            payment: T::Balance,
+        ) -> Result<T::Balance, DispatchErrorWithPostInfo> {
+            let mut left = payment;
+
+            for split in StreamSplits::<T>::get(stream_id) {
+                let amount = split.ratio.mul_floor(payment);
+
+                if amount.is_zero() {
+                    continue;
+                }
+
+                T::Assets::transfer_deposit(
+                    &stream.config.asset_id,
+                    &stream.source,
+                    &split.account,
+                    amount,
+                )?;
+                left = left.saturating_sub(amount);
+
+                Pallet::<T>::deposit_event(Event::<T>::StreamPaymentSplit {
+                    stream_id,
+                    target: split.account,
+                    amount,
+                });
+            }
+
+            Ok(left)
+        }
+
+        fn ensure_valid_splits(
+            source: &AccountIdOf<T>,
+            target: &AccountIdOf<T>,
+            splits: &StreamSplitsOf<T>,
+        ) -> DispatchResult {
+            let mut accounts = BTreeSet::new();
+            let mut total: u64 = 0;
+
+            for split in splits {
+                ensure!(
+                    &split.account != source
+                        && &split.account != target
+                        && accounts.insert(&split.account),
+                    Error::<T>::InvalidSplitAccount
+                );
+
+                total = total.saturating_add(split.ratio.deconstruct().into());
+            }
+
+            ensure!(
+                total <= Perbill::one().deconstruct().into(),
+                Error::<T>::SplitRatiosExceedOne
+            );
+
+            Ok(())
+        }
+
         fn apply_deposit_change(
             stream: &mut StreamOf<T>,
             change: DepositChange<T::Balance>,
@@ -241,6 +241,9 @@ impl pallet_stream_payment::TimeProvider<TimeUnit, Balance> for TimeProvider {
 
 parameter_types! {
     pub const OpenStreamHoldAmount: Balance = 100;
+    pub const OpenStreamSplitHoldAmount: Balance = 10;
+    pub const MaxStreamSplits: u32 = 4;
+    pub static AutoSettlementScanLimit: u32 = 3;
 }
 
 impl pallet_stream_payment::Config for Runtime {
@@ -252,8 +255,11 @@ impl pallet_stream_payment::Config for Runtime {
     type Assets = StreamPaymentAssets;
     type Currency = Balances;
     type OpenStreamHoldAmount = OpenStreamHoldAmount;
+    type OpenStreamSplitHoldAmount = OpenStreamSplitHoldAmount;
     type RuntimeHoldReason = RuntimeHoldReason;
     type TimeProvider = TimeProvider;
+    type MaxStreamSplits = MaxStreamSplits;
+    type AutoSettlementScanLimit = AutoSettlementScanLimit;
     type WeightInfo = ();
 }
 
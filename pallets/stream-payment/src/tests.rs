@@ -18,16 +18,17 @@ use {
     crate::{
         assert_event_emitted, assert_event_not_emitted,
         mock::{
-            roll_to, AccountId, Balance, Balances, ExtBuilder, OpenStreamHoldAmount, Runtime,
-            RuntimeOrigin, StreamPayment, StreamPaymentAssetId, StreamPaymentAssets, TimeUnit,
-            ALICE, BOB, CHARLIE, DEFAULT_BALANCE, MEGA,
+            roll_to, AccountId, Balance, Balances, ExtBuilder, OpenStreamHoldAmount,
+            OpenStreamSplitHoldAmount, Runtime, RuntimeOrigin, StreamPayment, StreamPaymentAssetId,
+            StreamPaymentAssets, TimeUnit, ALICE, BOB, CHARLIE, DEFAULT_BALANCE, MEGA,
         },
         ArithmeticError, Assets, ChangeKind, DepositChange, DispatchResultWithPostInfo, Event,
         LookupStreamsWithSource, LookupStreamsWithTarget, NextStreamId, Party, Stream,
-        StreamConfig, StreamConfigOf, StreamOf, Streams,
+        StreamConfig, StreamConfigOf, StreamOf, StreamPaymentStatus, StreamSchedule, StreamSplit,
+        StreamSplits, StreamSplitsOf, Streams,
     },
     frame_support::{assert_err, assert_ok},
-    sp_runtime::TokenError,
+    sp_runtime::{Perbill, TokenError},
     tap::tap::Tap,
 };
 
@@ -62,6 +63,7 @@ fn default_stream() -> StreamOf<Runtime> {
         last_time_updated: 0u32.into(),
         request_nonce: 0,
         pending_request: None,
+        schedule: default(),
     }
 }
 
@@ -1839,3 +1841,586 @@ mod immediately_change_deposit {
         })
     }
 }
+
+mod scheduled_stream {
+    use super::*;
+
+    const DAVE: AccountId = 3;
+
+    fn splits(splits: &[(AccountId, u32)]) -> StreamSplitsOf<Runtime> {
+        splits
+            .iter()
+            .map(|&(account, percent)| StreamSplit {
+                account,
+                ratio: Perbill::from_percent(percent),
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .expect("not too many splits")
+    }
+
+    fn open_scheduled_stream(
+        schedule: StreamSchedule<Balance>,
+        splits: StreamSplitsOf<Runtime>,
+    ) -> DispatchResultWithPostInfo {
+        let open_stream = OpenStream::default();
+        StreamPayment::open_scheduled_stream(
+            RuntimeOrigin::signed(open_stream.from),
+            open_stream.to,
+            open_stream.config,
+            open_stream.deposit,
+            schedule,
+            splits,
+        )
+    }
+
+    #[test]
+    fn end_must_be_in_future() {
+        ExtBuilder::default().build().execute_with(|| {
+            roll_to(10);
+
+            assert_err!(
+                open_scheduled_stream(
+                    StreamSchedule {
+                        start: None,
+                        end: Some(10),
+                    },
+                    default()
+                ),
+                Error::InvalidSchedule
+            );
+        })
+    }
+
+    #[test]
+    fn start_must_be_before_end() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_err!(
+                open_scheduled_stream(
+                    StreamSchedule {
+                        start: Some(20),
+                        end: Some(20),
+                    },
+                    default()
+                ),
+                Error::InvalidSchedule
+            );
+        })
+    }
+
+    #[test]
+    fn split_ratios_cant_exceed_one() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_err!(
+                open_scheduled_stream(default(), splits(&[(CHARLIE, 60), (DAVE, 41)])),
+                Error::SplitRatiosExceedOne
+            );
+        })
+    }
+
+    #[test]
+    fn split_account_cant_be_source_target_or_duplicate() {
+        ExtBuilder::default().build().execute_with(|| {
+            for invalid in [
+                splits(&[(ALICE, 10)]),
+                splits(&[(BOB, 10)]),
+                splits(&[(CHARLIE, 10), (CHARLIE, 10)]),
+            ] {
+                assert_err!(
+                    open_scheduled_stream(default(), invalid),
+                    Error::InvalidSplitAccount
+                );
+            }
+        })
+    }
+
+    #[test]
+    fn nothing_is_paid_before_start() {
+        ExtBuilder::default().build().execute_with(|| {
+            let open_stream = OpenStream::default();
+            assert_ok!(open_scheduled_stream(
+                StreamSchedule {
+                    start: Some(10),
+                    end: None,
+                },
+                default()
+            ));
+
+            roll_to(5);
+            assert_eq!(
+                StreamPayment::stream_payment_status(0, None),
+                Ok(StreamPaymentStatus {
+                    payment: 0,
+                    deposit_left: open_stream.deposit,
+                    stalled: false,
+                    started: false,
+                    ended: false,
+                })
+            );
+            assert_ok!(StreamPayment::perform_payment(
+                RuntimeOrigin::signed(CHARLIE),
+                0
+            ));
+            assert_balance_change!(+, BOB, 0);
+
+            roll_to(15);
+            let payment = 5 * open_stream.config.rate;
+            assert_eq!(
+                StreamPayment::stream_payment_status(0, None),
+                Ok(StreamPaymentStatus {
+                    payment,
+                    deposit_left: open_stream.deposit - payment,
+                    stalled: false,
+                    started: true,
+                    ended: false,
+                })
+            );
+            assert_ok!(StreamPayment::perform_payment(
+                RuntimeOrigin::signed(CHARLIE),
+                0
+            ));
+            assert_event_emitted!(PaymentEvent {
+                amount: payment,
+                ..default()
+            });
+            assert_balance_change!(+, BOB, payment);
+        })
+    }
+
+    #[test]
+    fn nothing_is_paid_after_end() {
+        ExtBuilder::default().build().execute_with(|| {
+            let open_stream = OpenStream::default();
+            assert_ok!(open_scheduled_stream(
+                StreamSchedule {
+                    start: None,
+                    end: Some(10),
+                },
+                default()
+            ));
+
+            roll_to(20);
+            let payment = 9 * open_stream.config.rate;
+            assert_ok!(StreamPayment::perform_payment(
+                RuntimeOrigin::signed(CHARLIE),
+                0
+            ));
+            assert_event_emitted!(PaymentEvent {
+                amount: payment,
+                stalled: true,
+                ..default()
+            });
+            assert_balance_change!(+, BOB, payment);
+
+            roll_to(30);
+            assert_eq!(
+                StreamPayment::stream_payment_status(0, None),
+                Ok(StreamPaymentStatus {
+                    payment: 0,
+                    deposit_left: open_stream.deposit - payment,
+                    stalled: true,
+                    started: true,
+                    ended: true,
+                })
+            );
+            assert_ok!(StreamPayment::perform_payment(
+                RuntimeOrigin::signed(CHARLIE),
+                0
+            ));
+            assert_balance_change!(+, BOB, payment);
+        })
+    }
+
+    #[test]
+    fn payment_is_split_between_targets() {
+        ExtBuilder::default().build().execute_with(|| {
+            let open_stream = OpenStream::default();
+            assert_ok!(open_scheduled_stream(
+                default(),
+                splits(&[(CHARLIE, 20), (DAVE, 30)])
+            ));
+
+            assert!(LookupStreamsWithTarget::<Runtime>::get(CHARLIE, 0).is_some());
+            assert!(LookupStreamsWithTarget::<Runtime>::get(DAVE, 0).is_some());
+            assert_eq!(
+                Streams::<Runtime>::get(0).unwrap().opening_deposit,
+                OpenStreamHoldAmount::get() + 2 * OpenStreamSplitHoldAmount::get()
+            );
+
+            let delta = u128::from(roll_to(11));
+            let payment = delta * open_stream.config.rate;
+            assert_ok!(StreamPayment::perform_payment(
+                RuntimeOrigin::signed(CHARLIE),
+                0
+            ));
+
+            // Only what is left after the splits is paid to the target.
+            assert_event_emitted!(PaymentEvent {
+                amount: payment / 2,
+                ..default()
+            });
+            assert_event_emitted!(Event::<Runtime>::StreamPaymentSplit {
+                stream_id: 0,
+                target: CHARLIE,
+                amount: payment / 5,
+            });
+            assert_event_emitted!(Event::<Runtime>::StreamPaymentSplit {
+                stream_id: 0,
+                target: DAVE,
+                amount: payment * 3 / 10,
+            });
+
+            assert_balance_change!(+, CHARLIE, payment / 5);
+            assert_eq!(Balances::free_balance(DAVE), payment * 3 / 10);
+            assert_balance_change!(+, BOB, payment / 2);
+            assert_eq!(get_deposit(ALICE), open_stream.deposit - payment);
+
+            assert_ok!(StreamPayment::close_stream(RuntimeOrigin::signed(ALICE), 0));
+            assert!(StreamSplits::<Runtime>::get(0).is_empty());
+            assert!(LookupStreamsWithTarget::<Runtime>::get(CHARLIE, 0).is_none());
+            assert!(LookupStreamsWithTarget::<Runtime>::get(DAVE, 0).is_none());
+        })
+    }
+
+    #[test]
+    fn scheduled_stream_cant_change_time_unit() {
+        ExtBuilder::default().build().execute_with(|| {
+            let open_stream = OpenStream::default();
+            assert_ok!(open_scheduled_stream(
+                StreamSchedule {
+                    start: Some(10),
+                    end: None,
+                },
+                default()
+            ));
+
+            assert_err!(
+                StreamPayment::request_change(
+                    RuntimeOrigin::signed(ALICE),
+                    0,
+                    ChangeKind::Suggestion,
+                    StreamConfig {
+                        time_unit: TimeUnit::Timestamp,
+                        ..open_stream.config
+                    },
+                    None,
+                ),
+                Error::ScheduledStreamCantChangeTimeUnit
+            );
+        })
+    }
+}
+
+mod auto_settlement {
+    use {
+        super::*,
+        crate::{
+            mock::{AutoSettlementScanLimit, MaxStreamSplits},
+            AutoSettlementCursor, WeightInfo,
+        },
+        frame_support::{traits::Hooks, weights::Weight},
+    };
+
+    fn settlement_weight() -> Weight {
+        <() as WeightInfo>::perform_payment()
+            .saturating_add(<() as WeightInfo>::pay_splits(MaxStreamSplits::get()))
+    }
+
+    fn settled_streams_count(now: Balance) -> usize {
+        Streams::<Runtime>::iter_values()
+            .filter(|stream| stream.last_time_updated == now)
+            .count()
+    }
+
+    #[test]
+    fn running_stream_is_settled() {
+        ExtBuilder::default().build().execute_with(|| {
+            let open_stream = OpenStream::default();
+            assert_ok!(open_stream.call());
+
+            let delta = u128::from(roll_to(10));
+            let payment = delta * open_stream.config.rate;
+
+            // Closing is not charged for streams that are still running.
+            assert_eq!(StreamPayment::on_idle(10, Weight::MAX), settlement_weight());
+
+            assert_event_emitted!(PaymentEvent {
+                amount: payment,
+                ..default()
+            });
+            assert_eq!(
+                Streams::<Runtime>::get(0),
+                Some(Stream {
+                    deposit: open_stream.deposit - payment,
+                    last_time_updated: 10,
+                    ..default_stream()
+                })
+            );
+            assert_eq!(AutoSettlementCursor::<Runtime>::get(), None);
+            assert_balance_change!(+, BOB, payment);
+        })
+    }
+
+    #[test]
+    fn drained_stream_is_closed() {
+        ExtBuilder::default().build().execute_with(|| {
+            let opening_deposit = OpenStreamHoldAmount::get();
+            let open_stream = OpenStream {
+                deposit: 500,
+                ..default()
+            };
+            assert_ok!(open_stream.call());
+
+            roll_to(10);
+            assert_eq!(
+                StreamPayment::on_idle(10, Weight::MAX),
+                settlement_weight().saturating_add(<() as WeightInfo>::close_stream())
+            );
+
+            assert_event_emitted!(PaymentEvent {
+                amount: open_stream.deposit,
+                stalled: true,
+                ..default()
+            });
+            assert_event_emitted!(Event::<Runtime>::StreamClosed {
+                stream_id: 0,
+                refunded: opening_deposit,
+            });
+            assert_eq!(Streams::<Runtime>::get(0), None);
+            assert_eq!(LookupStreamsWithSource::<Runtime>::get(ALICE, 0), None);
+            assert_eq!(LookupStreamsWithTarget::<Runtime>::get(BOB, 0), None);
+
+            assert_eq!(get_deposit(ALICE), 0);
+            assert_balance_change!(-, ALICE, open_stream.deposit);
+            assert_balance_change!(+, BOB, open_stream.deposit);
+        })
+    }
+
+    #[test]
+    fn stream_past_mandatory_deadline_is_closed() {
+        ExtBuilder::default().build().execute_with(|| {
+            let opening_deposit = OpenStreamHoldAmount::get();
+            let open_stream = OpenStream::default();
+            assert_ok!(open_stream.call());
+
+            assert_ok!(StreamPayment::request_change(
+                RuntimeOrigin::signed(BOB),
+                0,
+                ChangeKind::Mandatory { deadline: 5 },
+                StreamConfig {
+                    rate: 200,
+                    ..open_stream.config
+                },
+                None,
+            ));
+
+            roll_to(4);
+            StreamPayment::on_idle(4, Weight::MAX);
+            assert!(Streams::<Runtime>::get(0).is_some());
+
+            roll_to(10);
+            StreamPayment::on_idle(10, Weight::MAX);
+
+            // Payment stopped at the deadline.
+            let payment = 4 * open_stream.config.rate;
+            assert_event_emitted!(Event::<Runtime>::StreamClosed {
+                stream_id: 0,
+                refunded: open_stream.deposit - payment + opening_deposit,
+            });
+            assert_eq!(Streams::<Runtime>::get(0), None);
+            assert_eq!(LookupStreamsWithSource::<Runtime>::get(ALICE, 0), None);
+            assert_eq!(LookupStreamsWithTarget::<Runtime>::get(BOB, 0), None);
+
+            assert_eq!(get_deposit(ALICE), 0);
+            assert_balance_change!(-, ALICE, payment);
+            assert_balance_change!(+, BOB, payment);
+        })
+    }
+
+    #[test]
+    fn cursor_continues_across_blocks() {
+        ExtBuilder::default().build().execute_with(|| {
+            for _ in 0..5 {
+                assert_ok!(OpenStream::default().call());
+            }
+
+            roll_to(10);
+
+            StreamPayment::on_idle(10, Weight::MAX);
+            assert_eq!(settled_streams_count(10), 3);
+            assert!(AutoSettlementCursor::<Runtime>::get().is_some());
+
+            StreamPayment::on_idle(10, Weight::MAX);
+            assert_eq!(settled_streams_count(10), 5);
+            assert_eq!(AutoSettlementCursor::<Runtime>::get(), None);
+        })
+    }
+
+    #[test]
+    fn nothing_is_done_without_enough_weight() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(OpenStream::default().call());
+
+            roll_to(10);
+
+            assert_eq!(StreamPayment::on_idle(10, Weight::zero()), Weight::zero());
+            assert_eq!(settled_streams_count(10), 0);
+        })
+    }
+
+    #[test]
+    fn nothing_is_done_when_disabled() {
+        ExtBuilder::default().build().execute_with(|| {
+            AutoSettlementScanLimit::set(0);
+            assert_ok!(OpenStream::default().call());
+
+            roll_to(10);
+
+            assert_eq!(StreamPayment::on_idle(10, Weight::MAX), Weight::zero());
+            assert_eq!(settled_streams_count(10), 0);
+        })
+    }
+}
+
+mod queries {
+    use super::*;
+
+    #[test]
+    fn account_streams_can_be_paginated() {
+        ExtBuilder::default().build().execute_with(|| {
+            for _ in 0..5 {
+                assert_ok!(OpenStream::default().call());
+            }
+            assert_ok!(OpenStream {
+                from: BOB,
+                to: ALICE,
+                ..default()
+            }
+            .call());
+
+            let all = StreamPayment::account_streams(&ALICE, Party::Source, None, 10);
+            assert_eq!(all.len(), 5);
+
+            let first_page = StreamPayment::account_streams(&ALICE, Party::Source, None, 3);
+            let second_page = StreamPayment::account_streams(
+                &ALICE,
+                Party::Source,
+                first_page.last().copied(),
+                3,
+            );
+            assert_eq!(first_page.len(), 3);
+            assert_eq!(second_page.len(), 2);
+            assert_eq!([first_page, second_page].concat(), all);
+
+            assert_eq!(
+                StreamPayment::account_streams(&ALICE, Party::Target, None, 10),
+                vec![5]
+            );
+            assert!(StreamPayment::account_streams(&CHARLIE, Party::Source, None, 10).is_empty());
+        })
+    }
+
+    #[test]
+    fn stream_accounts_include_splits() {
+        ExtBuilder::default().build().execute_with(|| {
+            let open_stream = OpenStream::default();
+            assert_ok!(StreamPayment::open_scheduled_stream(
+                RuntimeOrigin::signed(open_stream.from),
+                open_stream.to,
+                open_stream.config,
+                open_stream.deposit,
+                default(),
+                vec![StreamSplit {
+                    account: CHARLIE,
+                    ratio: Perbill::from_percent(10),
+                }]
+                .try_into()
+                .unwrap(),
+            ));
+
+            assert_eq!(StreamPayment::stream_accounts(0), vec![ALICE, BOB, CHARLIE]);
+            assert_eq!(
+                StreamPayment::account_streams(&CHARLIE, Party::Target, None, 10),
+                vec![0]
+            );
+
+            assert_ok!(StreamPayment::close_stream(RuntimeOrigin::signed(ALICE), 0));
+            assert!(StreamPayment::stream_accounts(0).is_empty());
+        })
+    }
+
+    #[test]
+    fn block_events_only_contains_pallet_events() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(OpenStream::default().call());
+            assert_ok!(StreamPayment::close_stream(RuntimeOrigin::signed(ALICE), 0));
+
+            assert_eq!(
+                StreamPayment::block_events(),
+                vec![
+                    Event::<Runtime>::StreamOpened { stream_id: 0 },
+                    Event::<Runtime>::StreamClosed {
+                        stream_id: 0,
+                        refunded: OpenStream::default().deposit + OpenStreamHoldAmount::get(),
+                    },
+                ]
+            );
+        })
+    }
+
+    #[test]
+    fn api_events_include_stream_accounts() {
+        use pallet_stream_payment_runtime_api::{
+            StreamPaymentApiEvent, StreamPaymentApiEventKind as Kind,
+        };
+
+        ExtBuilder::default().build().execute_with(|| {
+            let open_stream = OpenStream::default();
+            assert_ok!(StreamPayment::open_scheduled_stream(
+                RuntimeOrigin::signed(open_stream.from),
+                open_stream.to,
+                open_stream.config,
+                open_stream.deposit,
+                default(),
+                vec![StreamSplit {
+                    account: CHARLIE,
+                    ratio: Perbill::from_percent(50),
+                }]
+                .try_into()
+                .unwrap(),
+            ));
+
+            let delta = u128::from(roll_to(11));
+            let payment = delta * open_stream.config.rate;
+            frame_system::Pallet::<Runtime>::reset_events();
+            assert_ok!(StreamPayment::perform_payment(
+                RuntimeOrigin::signed(ALICE),
+                0
+            ));
+
+            assert_eq!(
+                StreamPayment::api_events(),
+                vec![
+                    StreamPaymentApiEvent {
+                        stream_id: 0,
+                        accounts: vec![ALICE, BOB, CHARLIE],
+                        kind: Kind::PaymentSplit {
+                            target: CHARLIE,
+                            amount: payment / 2,
+                        },
+                    },
+                    StreamPaymentApiEvent {
+                        stream_id: 0,
+                        accounts: vec![ALICE, BOB, CHARLIE],
+                        kind: Kind::Payment {
+                            source: ALICE,
+                            target: BOB,
+                            amount: payment / 2,
+                            stalled: false,
+                        },
+                    },
+                ]
+            );
+        })
+    }
+}
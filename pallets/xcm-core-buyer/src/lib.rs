@@ -172,6 +172,8 @@ pub mod pallet {
         /// If `None`, defaults to `u128::MAX`, the parathread will pay the market price with no
         /// upper bound.
         type GetParathreadMaxCorePrice: GetParathreadMaxCorePrice;
+        /// Notified when the relay chain confirms that a core was bought for a parathread.
+        type OnCorePurchased: OnCorePurchased;
         /// Orchestartor chain `ParaId`. Used in `absolute_multilocation` to convert the
         /// `interior_multilocation` into what the relay chain needs to allow to `DepositAsset`.
         type SelfParaId: Get<ParaId>;
@@ -449,6 +451,7 @@ pub mod pallet {
                     let now = <frame_system::Pallet<T>>::block_number();
                     let ttl = T::PendingBlocksTtl::get();
                     PendingBlocks::<T>::insert(para_id, now + ttl);
+                    T::OnCorePurchased::on_core_purchased(para_id);
                 }
                 Response::DispatchResult(_) => {
                     // We do not add paraid to pending block on failure
@@ -596,6 +599,7 @@ pub mod pallet {
                     Slot::from(current_slot as u64),
                     T::BuyCoreSlotDrift::get(),
                     latest_author_info.latest_slot_number,
+                    T::CheckCollatorValidity::num_collators(para_id),
                 ) {
                     // TODO: Take max slots to produce a block from config
                     return Err(BuyingError::NotAllowedToProduceBlockRightNow {
@@ -787,6 +791,9 @@ pub trait GetPurchaseCoreCall<RelayChain> {
 pub trait CheckCollatorValidity<AccountId, PublicKey> {
     fn is_valid_collator(para_id: ParaId, public_key: PublicKey) -> bool;
 
+    /// Number of collators assigned to `para_id`, which take turns to author its blocks.
+    fn num_collators(para_id: ParaId) -> u32;
+
     #[cfg(feature = "runtime-benchmarks")]
     fn set_valid_collator(para_id: ParaId, account_id: AccountId, public_key: PublicKey);
 }
@@ -801,6 +808,14 @@ impl GetParathreadMaxCorePrice for () {
     }
 }
 
+pub trait OnCorePurchased {
+    fn on_core_purchased(para_id: ParaId);
+}
+
+impl OnCorePurchased for () {
+    fn on_core_purchased(_para_id: ParaId) {}
+}
+
 pub trait GetParathreadParams {
     fn get_parathread_params(para_id: ParaId) -> Option<ParathreadParams>;
 
@@ -221,6 +221,7 @@ impl pallet_xcm_core_buyer::Config for Test {
     type GetPurchaseCoreCall = EncodedCallToBuyCore;
     type GetParathreadAccountId = ParaIdIntoAccountTruncating;
     type GetParathreadMaxCorePrice = ();
+    type OnCorePurchased = ();
     type SelfParaId = ParachainId;
     type RelayChain = ();
     type GetParathreadParams = GetParathreadParamsImpl;
@@ -290,6 +291,13 @@ impl CheckCollatorValidity<AccountId, NimbusId> for CheckCollatorValidityImpl {
             .is_some_and(|collators| collators.contains(&public_key))
     }
 
+    fn num_collators(para_id: ParaId) -> u32 {
+        MockData::mock()
+            .container_chain_collators
+            .get(&para_id)
+            .map_or(0, |collators| collators.len() as u32)
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn set_valid_collator(para_id: ParaId, _account_id: AccountId, public_key: NimbusId) {
         let mock_data = MockData::mock();
@@ -395,6 +395,51 @@ fn slot_frequency_is_taken_into_account() {
         })
 }
 
+#[test]
+fn core_buying_follows_the_next_author_slot_of_parathread() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            run_to_block(1);
+
+            let para_id = 3333.into();
+            let another_collator = NimbusId::generate_pair(None);
+            MockData::mutate(|stored_mock_data| {
+                stored_mock_data
+                    .container_chain_collators
+                    .get_mut(&para_id)
+                    .expect("Collator data for test paraid must exists")
+                    .push(another_collator.clone());
+                stored_mock_data.parathread_params.insert(
+                    para_id,
+                    ParathreadParams {
+                        slot_frequency: SlotFrequency { min: 10, max: 10 },
+                    },
+                );
+            });
+
+            // The last block was authored in slot 0 by the first collator, and slot 10 also
+            // belongs to it, so the next block is authored by the second collator in slot 11.
+            // With a drift of 2 slots, the core can only be bought from slot 9.
+            let proof = BuyCoreCollatorProof::new(0, para_id, another_collator)
+                .expect("creating collator proof must succeed");
+
+            run_to_block(8);
+            assert_noop!(
+                XcmCoreBuyer::buy_core(RuntimeOrigin::none(), para_id, proof.clone()),
+                Error::<Test>::NotAllowedToProduceBlockRightNow
+            );
+
+            run_to_block(9);
+            assert_ok!(XcmCoreBuyer::buy_core(
+                RuntimeOrigin::none(),
+                para_id,
+                proof
+            ));
+        })
+}
+
 #[test]
 fn root_origin_can_force_buy_xcm() {
     ExtBuilder::default()
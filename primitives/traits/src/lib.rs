@@ -35,7 +35,8 @@ use {
     frame_support::{
         dispatch::DispatchErrorWithPostInfo,
         pallet_prelude::{Decode, DispatchResultWithPostInfo, Encode, Get, MaxEncodedLen, Weight},
-        BoundedVec,
+        BoundedBTreeSet, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound,
+        RuntimeDebugNoBound,
     },
     scale_info::TypeInfo,
     serde::{Deserialize, Serialize},
@@ -131,6 +132,23 @@ impl<AccountId, Imbalance> DistributeRewards<AccountId, Imbalance> for () {
     }
 }
 
+/// Hook to react to a slash being applied to an offender.
+pub trait OnSlashApplied<AccountId> {
+    /// This hook is called when a slash of `fraction` is applied to `offender`.
+    ///
+    /// The hook should never panic and is required to return the weight consumed.
+    fn on_slash_applied(offender: &AccountId, fraction: Perbill) -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<AccountId> OnSlashApplied<AccountId> for Tuple {
+    fn on_slash_applied(o: &AccountId, f: Perbill) -> Weight {
+        let mut weight: Weight = Default::default();
+        for_tuples!( #( weight.saturating_accrue(Tuple::on_slash_applied(o, f)); )* );
+        weight
+    }
+}
+
 /// Get the current list of container chains parachain ids.
 pub trait GetCurrentContainerChains {
     type MaxContainerChains: Get<u32>;
@@ -168,24 +186,40 @@ pub struct SlotFrequency {
 }
 
 impl SlotFrequency {
+    /// Whether a core can be bought in `current_slot` for the next block of a parathread with
+    /// `num_authors` collators, allowing the purchase to start up to
+    /// `max_slot_required_to_complete_purchase` slots before `next_parathread_block_slot`.
     pub fn should_parathread_buy_core(
         &self,
         current_slot: Slot,
         max_slot_required_to_complete_purchase: Slot,
         last_block_slot: Slot,
+        num_authors: u32,
     ) -> bool {
-        current_slot
-            >= last_block_slot
-                .saturating_add(Slot::from(u64::from(self.min)))
-                .saturating_sub(max_slot_required_to_complete_purchase)
+        current_slot.saturating_add(max_slot_required_to_complete_purchase)
+            >= self.next_parathread_block_slot(last_block_slot, num_authors)
     }
 
-    pub fn should_parathread_author_block(
-        &self,
-        current_slot: Slot,
-        last_block_slot: Slot,
-    ) -> bool {
-        current_slot >= last_block_slot.saturating_add(Slot::from(u64::from(self.min)))
+    /// First slot in which the next block of a parathread with `num_authors` collators should be
+    /// authored, given the slot of its last block.
+    ///
+    /// Collators take turns to author blocks (the author of a slot is `slot % num_authors`), but
+    /// if `min` is a multiple of `num_authors`, the first slot allowed by `min` always belongs to
+    /// the author of the last block. So the next block is assigned to the collator after the
+    /// author of the last block, in its first slot allowed by `min`. Collators can author blocks
+    /// in any of their slots after that one, in case the assigned collator misses it.
+    pub fn next_parathread_block_slot(&self, last_block_slot: Slot, num_authors: u32) -> Slot {
+        let min_slot = last_block_slot.saturating_add(Slot::from(u64::from(self.min)));
+        if num_authors <= 1 {
+            return min_slot;
+        }
+
+        let num_authors = u64::from(num_authors);
+        let next_author = u64::from(last_block_slot).saturating_add(1) % num_authors;
+        let min_slot_author = u64::from(min_slot) % num_authors;
+        let offset = (next_author + num_authors - min_slot_author) % num_authors;
+
+        min_slot.saturating_add(Slot::from(offset))
     }
 }
 
@@ -211,10 +245,75 @@ pub struct ParathreadParams {
     pub slot_frequency: SlotFrequency,
 }
 
+/// Number of collators requested by the para manager for a container chain, overriding the
+/// `collators_per_container` or `collators_per_parathread` of the host configuration.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Encode,
+    Decode,
+    scale_info::TypeInfo,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    MaxEncodedLen,
+)]
+pub struct CollatorCountRequest {
+    /// The chain will not be assigned collators unless it can get at least `min` of them.
+    pub min: u32,
+    /// Extra collators up to `max` are only assigned once all the chains have reached their min.
+    pub max: u32,
+}
+
+/// Para ids accepted by a data preserver profile or a collator, with at most `MaxLen` para ids
+/// in the list.
+#[derive(
+    RuntimeDebugNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    CloneNoBound,
+    Encode,
+    Decode,
+    scale_info::TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxLen))]
+pub enum ParaIdsFilter<MaxLen: Get<u32>> {
+    AnyParaId,
+    Whitelist(BoundedBTreeSet<ParaId, MaxLen>),
+    Blacklist(BoundedBTreeSet<ParaId, MaxLen>),
+}
+
+impl<MaxLen: Get<u32>> ParaIdsFilter<MaxLen> {
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::AnyParaId => 0,
+            Self::Whitelist(list) | Self::Blacklist(list) => list.len(),
+        }
+    }
+
+    pub fn can_assign(&self, para_id: &ParaId) -> bool {
+        match self {
+            ParaIdsFilter::AnyParaId => true,
+            ParaIdsFilter::Whitelist(list) => list.contains(para_id),
+            ParaIdsFilter::Blacklist(list) => !list.contains(para_id),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Encode, Decode, scale_info::TypeInfo, PartialEq, Eq)]
 pub struct SessionContainerChains {
     pub parachains: Vec<ParaId>,
     pub parathreads: Vec<(ParaId, ParathreadParams)>,
+    /// Custom collator counts of the para ids above. Para ids not in this map use the default
+    /// values of the host configuration.
+    pub collator_count_requests: BTreeMap<ParaId, CollatorCountRequest>,
+    /// Number of relay chain cores requested by the para manager of the parachains above.
+    /// Parachains not in this map use 1 core.
+    pub core_count_requests: BTreeMap<ParaId, u32>,
 }
 
 /// Get the list of container chains parachain ids at given
@@ -263,6 +362,15 @@ impl<SessionIndex> ShouldRotateAllCollators<SessionIndex> for () {
     }
 }
 
+/// Helper trait for pallet_collator_assignment to only allow collators with registered session
+/// keys to register tags
+pub trait IsCollatorRegistered<AccountId> {
+    fn is_collator_registered(account: &AccountId) -> bool;
+    /// Register the session keys of the collator, for benchmarking.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn register_collator(_account: &AccountId) {}
+}
+
 /// Helper trait for pallet_collator_assignment to be able to give priority to invulnerables
 pub trait RemoveInvulnerables<AccountId> {
     /// Remove the first n invulnerables from the list of collators. The order should be respected.
@@ -288,9 +396,12 @@ pub trait ParaIdAssignmentHooks<B, AC> {
     /// Remove para ids with not enough credits. The resulting order will affect priority: the first para id in the list
     /// will be the first one to get collators.
     fn pre_assignment(para_ids: &mut Vec<ParaId>, old_assigned: &BTreeSet<ParaId>);
+    /// Remove para ids that cannot pay for the collators in `new_assigned`. Para ids missing from
+    /// `parachain_cores` use 1 core.
     fn post_assignment(
         current_assigned: &BTreeSet<ParaId>,
         new_assigned: &mut BTreeMap<ParaId, Vec<AC>>,
+        parachain_cores: &BTreeMap<ParaId, u32>,
         maybe_tip: &Option<B>,
     ) -> Weight;
 
@@ -305,6 +416,7 @@ impl<B, AC> ParaIdAssignmentHooks<B, AC> for () {
     fn post_assignment(
         _current_assigned: &BTreeSet<ParaId>,
         _new_assigned: &mut BTreeMap<ParaId, Vec<AC>>,
+        _parachain_cores: &BTreeMap<ParaId, u32>,
         _maybe_tip: &Option<B>,
     ) -> Weight {
         Default::default()
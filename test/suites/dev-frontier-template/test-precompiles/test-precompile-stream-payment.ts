@@ -0,0 +1,96 @@
+import { describeSuite, expect } from "@moonwall/cli";
+import { ALITH_ADDRESS, BALTATHAR_ADDRESS } from "@moonwall/util";
+import { parseEther } from "viem";
+import { expectEVMResult } from "helpers";
+
+const PRECOMPILE_NATIVE_ERC20_ADDRESS = "0x0000000000000000000000000000000000000800";
+const PRECOMPILE_STREAM_PAYMENT_ADDRESS = "0x0000000000000000000000000000000000000806";
+const TIME_UNIT_BLOCK_NUMBER = 0;
+
+describeSuite({
+    id: "DF1116",
+    title: "Precompile - Stream payment",
+    foundationMethods: "dev",
+    testCases: ({ it, context }) => {
+        let streamId: number;
+
+        it({
+            id: "T01",
+            title: "should open a stream paid in native token",
+            test: async () => {
+                const rawTxn = await context.writeContract!({
+                    contractAddress: PRECOMPILE_STREAM_PAYMENT_ADDRESS,
+                    contractName: "StreamPayment",
+                    functionName: "openStream",
+                    args: [
+                        BALTATHAR_ADDRESS,
+                        TIME_UNIT_BLOCK_NUMBER,
+                        PRECOMPILE_NATIVE_ERC20_ADDRESS,
+                        parseEther("0.001"),
+                        parseEther("1"),
+                    ],
+                    rawTxOnly: true,
+                });
+                const { result } = await context.createBlock(rawTxn);
+                expectEVMResult(result!.events, "Succeed");
+
+                const opened = result!.events.find((e) =>
+                    context.polkadotJs().events.streamPayment.StreamOpened.is(e.event)
+                );
+                expect(opened).to.not.be.undefined;
+                streamId = opened!.event.data[0].toNumber();
+
+                const stream = (await context.polkadotJs().query.streamPayment.streams(streamId)).unwrap();
+                expect(stream.source.toString()).to.equal(ALITH_ADDRESS);
+                expect(stream.target.toString()).to.equal(BALTATHAR_ADDRESS);
+                expect(stream.config.assetId.toString()).to.equal("Native");
+                expect(stream.deposit.toBigInt()).to.equal(parseEther("1"));
+            },
+        });
+
+        it({
+            id: "T02",
+            title: "should revert with an unknown asset",
+            test: async () => {
+                const rawTxn = await context.writeContract!({
+                    contractAddress: PRECOMPILE_STREAM_PAYMENT_ADDRESS,
+                    contractName: "StreamPayment",
+                    functionName: "openStream",
+                    args: [
+                        BALTATHAR_ADDRESS,
+                        TIME_UNIT_BLOCK_NUMBER,
+                        "0x1111111111111111111111111111111111111111",
+                        parseEther("0.001"),
+                        parseEther("1"),
+                    ],
+                    rawTxOnly: true,
+                    gas: 1_000_000n,
+                });
+                const { result } = await context.createBlock(rawTxn);
+                expectEVMResult(result!.events, "Revert");
+            },
+        });
+
+        it({
+            id: "T03",
+            title: "should close the stream and refund the deposit",
+            test: async () => {
+                const rawTxn = await context.writeContract!({
+                    contractAddress: PRECOMPILE_STREAM_PAYMENT_ADDRESS,
+                    contractName: "StreamPayment",
+                    functionName: "closeStream",
+                    args: [streamId],
+                    rawTxOnly: true,
+                });
+                const { result } = await context.createBlock(rawTxn);
+                expectEVMResult(result!.events, "Succeed");
+
+                const closed = result!.events.find((e) =>
+                    context.polkadotJs().events.streamPayment.StreamClosed.is(e.event)
+                );
+                expect(closed).to.not.be.undefined;
+                expect((await context.polkadotJs().query.streamPayment.streams(streamId)).isNone).to.be.true;
+            },
+        });
+    },
+});